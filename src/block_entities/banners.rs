@@ -1,7 +1,9 @@
 use crate::{
     components::banner_patterns::BannerPattern,
-    traits::FromCompoundNbt,
-    util::{get_optional_name, get_t_compound_vec},
+    traits::{FromCompoundNbt, ToCompoundNbt, ToVersionedCompoundNbt},
+    util::{
+        get_optional_name, get_t_compound_vec, set_tag, write_compound_list, write_optional_name,
+    },
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -34,3 +36,20 @@ impl FromCompoundNbt for Banner {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for Banner {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        write_optional_name(&mut nbt, self.custom_name.as_ref(), data_version);
+        if !self.patterns.is_empty() {
+            set_tag(
+                &mut nbt,
+                "patterns",
+                write_compound_list(&self.patterns, BannerPattern::to_compound_nbt),
+            );
+        }
+
+        nbt
+    }
+}
@@ -1,6 +1,10 @@
 use crate::{
-    traits::FromCompoundNbt,
-    util::{get_optional_lock, get_optional_name, get_owned_optional_string},
+    components::lock::Lock,
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+    util::{
+        get_optional_lock, get_optional_name, get_owned_optional_string, set_optional_tag,
+        write_optional_lock, write_optional_name,
+    },
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -11,10 +15,10 @@ pub struct Beacon {
     /// `CustomName`
     pub custom_name: Option<String>,
 
-    /// Optional. When not blank, prevents the container from being opened unless the opener is holding an item whose name matches this string.
+    /// Optional. Prevents the container from being opened unless the opener is holding an item whose name matches, or from 1.21.2 an item matching the predicate.
     ///
-    /// `Lock`
-    pub lock: Option<String>,
+    /// `Lock`, `lock` from 1.21.2
+    pub lock: Option<Lock>,
 
     /// Optional. The primary effect selected, see Potion effects for resource locations. Cannot be set to an effect that beacons do not normally use. Although Regeneration cannot normally be chosen as the primary effect, setting this value to minecraft:regeneration works and even allows Regeneration II to be chosen as the secondary via the normal beacon GUI.
     pub primary_effect: Option<String>,
@@ -31,7 +35,7 @@ impl FromCompoundNbt for Beacon {
         Self: Sized,
     {
         let custom_name = get_optional_name(nbt);
-        let lock = get_optional_lock(nbt);
+        let lock = get_optional_lock(nbt)?;
 
         let primary_effect = get_owned_optional_string(nbt, "primary_effect");
         let secondary_effect = get_owned_optional_string(nbt, "secondary_effect");
@@ -44,3 +48,20 @@ impl FromCompoundNbt for Beacon {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for Beacon {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        write_optional_name(&mut nbt, self.custom_name.as_ref(), data_version);
+        write_optional_lock(&mut nbt, self.lock.as_ref());
+        set_optional_tag(&mut nbt, "primary_effect", self.primary_effect.as_deref());
+        set_optional_tag(
+            &mut nbt,
+            "secondary_effect",
+            self.secondary_effect.as_deref(),
+        );
+
+        nbt
+    }
+}
@@ -1,17 +1,17 @@
 use crate::{
     components::bees::Bee,
-    traits::FromCompoundNbt,
-    util::{get_int_array, get_t_compound_vec},
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+    util::{get_int_array, get_t_compound_vec, set_tag, write_compound_list},
 };
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Beehive {
     /// Entities currently in the hive.
-    bees: Vec<Bee>,
+    pub bees: Vec<Bee>,
 
     /// Stores the flower block location, as 3 integers, so other bees can go to it.
-    flower_pos: Vec<i32>,
+    pub flower_pos: Vec<i32>,
 }
 
 impl FromCompoundNbt for Beehive {
@@ -27,3 +27,26 @@ impl FromCompoundNbt for Beehive {
         Ok(Beehive { bees, flower_pos })
     }
 }
+
+impl ToVersionedCompoundNbt for Beehive {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_tag(
+            &mut nbt,
+            "bees",
+            write_compound_list(&self.bees, |bee| {
+                bee.to_compound_nbt_with_version(data_version)
+            }),
+        );
+        if !self.flower_pos.is_empty() {
+            set_tag(
+                &mut nbt,
+                "flower_pos",
+                simdnbt::owned::NbtTag::IntArray(self.flower_pos.clone()),
+            );
+        }
+
+        nbt
+    }
+}
@@ -1,8 +1,12 @@
 use crate::{
+    components::lock::Lock,
     error::SculkParseError,
     item::Item,
-    traits::FromCompoundNbt,
-    util::{get_optional_lock, get_optional_name, get_t_compound_vec},
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+    util::{
+        get_optional_lock, get_optional_name, get_t_compound_vec, set_tag, write_items,
+        write_optional_lock, write_optional_name,
+    },
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -34,10 +38,10 @@ pub struct BrewingStand {
     /// `Items`
     pub items: Vec<Item>,
 
-    /// Optional. When not blank, prevents the container from being opened unless the opener is holding an item whose name matches this string.
+    /// Optional. Prevents the container from being opened unless the opener is holding an item whose name matches, or from 1.21.2 an item matching the predicate.
     ///
-    /// `Lock`
-    pub lock: Option<String>,
+    /// `Lock`, `lock` from 1.21.2
+    pub lock: Option<Lock>,
 }
 
 impl FromCompoundNbt for BrewingStand {
@@ -55,7 +59,7 @@ impl FromCompoundNbt for BrewingStand {
             .byte("Fuel")
             .ok_or(SculkParseError::MissingField("Fuel".into()))?;
         let items = get_t_compound_vec(nbt, "Items", Item::from_compound_nbt)?;
-        let lock = get_optional_lock(nbt);
+        let lock = get_optional_lock(nbt)?;
 
         Ok(BrewingStand {
             brew_time,
@@ -66,3 +70,17 @@ impl FromCompoundNbt for BrewingStand {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for BrewingStand {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_tag(&mut nbt, "BrewTime", self.brew_time);
+        write_optional_name(&mut nbt, self.custom_name.as_ref(), data_version);
+        set_tag(&mut nbt, "Fuel", self.fuel);
+        write_items(&mut nbt, &self.items, data_version);
+        write_optional_lock(&mut nbt, self.lock.as_ref());
+
+        nbt
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     error::SculkParseError,
-    traits::FromCompoundNbt,
-    util::{get_doubles_array, get_owned_string},
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::{get_doubles_array, get_owned_string, set_optional_tag, set_tag},
     uuid::Uuid,
 };
 
@@ -162,3 +162,67 @@ impl FromCompoundNbt for Event {
         })
     }
 }
+
+impl ToCompoundNbt for CalibratedSculkSensor {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("last_vibration_frequency", self.last_vibration_frequency);
+        nbt.insert("listener", self.listener.to_compound_nbt());
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for Listener {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_optional_tag(
+            &mut nbt,
+            "event",
+            self.event.as_ref().map(Event::to_compound_nbt),
+        );
+        set_tag(&mut nbt, "event_delay", self.event_delay);
+        set_tag(&mut nbt, "selector", self.selector.to_compound_nbt());
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for Selector {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_tag(&mut nbt, "tick", self.tick);
+        set_optional_tag(
+            &mut nbt,
+            "event",
+            self.event.as_ref().map(Event::to_compound_nbt),
+        );
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for Event {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_tag(&mut nbt, "distance", self.distance);
+        set_tag(&mut nbt, "game_event", self.game_event.as_str());
+        set_tag(
+            &mut nbt,
+            "pos",
+            simdnbt::owned::NbtList::Double(self.pos.to_vec()),
+        );
+        set_optional_tag(
+            &mut nbt,
+            "projectile_owner",
+            self.projectile_owner.as_ref().map(Uuid::to_tag),
+        );
+        set_optional_tag(&mut nbt, "source", self.source.as_ref().map(Uuid::to_tag));
+
+        nbt
+    }
+}
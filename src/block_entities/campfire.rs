@@ -1,7 +1,7 @@
 use crate::{
     item::Item,
-    traits::FromCompoundNbt,
-    util::{get_int_array, get_t_compound_vec},
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+    util::{get_int_array, get_t_compound_vec, set_tag, write_items},
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -42,3 +42,23 @@ impl FromCompoundNbt for Campfire {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for Campfire {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_tag(
+            &mut nbt,
+            "CookingTimes",
+            simdnbt::owned::NbtTag::IntArray(self.cooking_times.clone()),
+        );
+        set_tag(
+            &mut nbt,
+            "CookingTotalTimes",
+            simdnbt::owned::NbtTag::IntArray(self.cooking_total_times.clone()),
+        );
+        write_items(&mut nbt, &self.items, data_version);
+
+        nbt
+    }
+}
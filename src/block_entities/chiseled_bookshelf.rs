@@ -1,5 +1,8 @@
 use crate::{
-    error::SculkParseError, item::Item, traits::FromCompoundNbt, util::get_t_compound_vec,
+    error::SculkParseError,
+    item::Item,
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+    util::{get_t_compound_vec, set_tag, write_items},
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -34,3 +37,14 @@ impl FromCompoundNbt for ChiseledBookshelf {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for ChiseledBookshelf {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        write_items(&mut nbt, &self.items, data_version);
+        set_tag(&mut nbt, "last_interacted_slot", self.last_interacted_slot);
+
+        nbt
+    }
+}
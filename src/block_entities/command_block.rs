@@ -1,7 +1,7 @@
 use crate::{
     error::SculkParseError,
-    traits::FromCompoundNbt,
-    util::{get_bool, get_owned_optional_string, get_owned_string},
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+    util::{get_bool, get_optional_name, get_owned_string, set_tag, write_optional_name},
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -64,7 +64,7 @@ impl FromCompoundNbt for CommandBlock {
         let auto = get_bool(nbt, "auto");
         let command = get_owned_string(nbt, "Command")?;
         let condition_met = get_bool(nbt, "conditionMet");
-        let custom_name = get_owned_optional_string(nbt, "CustomName");
+        let custom_name = get_optional_name(nbt);
         let last_execution = nbt
             .long("LastExecution")
             .ok_or(SculkParseError::MissingField("LastExecution".into()))?;
@@ -90,3 +90,22 @@ impl FromCompoundNbt for CommandBlock {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for CommandBlock {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_tag(&mut nbt, "auto", self.auto);
+        set_tag(&mut nbt, "Command", self.command.as_str());
+        set_tag(&mut nbt, "conditionMet", self.condition_met);
+        write_optional_name(&mut nbt, self.custom_name.as_ref(), data_version);
+        set_tag(&mut nbt, "LastExecution", self.last_execution);
+        set_tag(&mut nbt, "LastOutput", self.last_output.as_str());
+        set_tag(&mut nbt, "powered", self.powered);
+        set_tag(&mut nbt, "SuccessCount", self.success_count);
+        set_tag(&mut nbt, "TrackOutput", self.track_output);
+        set_tag(&mut nbt, "UpdateLastExecution", self.update_last_execution);
+
+        nbt
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{error::SculkParseError, traits::FromCompoundNbt};
+use crate::{
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+};
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -23,3 +26,13 @@ impl FromCompoundNbt for Comparator {
         })
     }
 }
+
+impl ToCompoundNbt for Comparator {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("OutputSignal", self.output_signal);
+
+        nbt
+    }
+}
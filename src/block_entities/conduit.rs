@@ -1,4 +1,9 @@
-use crate::{error::SculkParseError, traits::FromCompoundNbt, uuid::Uuid};
+use crate::{
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::set_optional_tag,
+    uuid::Uuid,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -17,3 +22,13 @@ impl FromCompoundNbt for Conduit {
         })
     }
 }
+
+impl ToCompoundNbt for Conduit {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_optional_tag(&mut nbt, "target", self.target.as_ref().map(Uuid::to_tag));
+
+        nbt
+    }
+}
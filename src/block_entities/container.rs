@@ -0,0 +1,499 @@
+//! A common interface for block entities that hold items, reachable through [`BlockEntityKind::as_container`].
+
+use super::{
+    barrel::Barrel, brewing_stand::BrewingStand, campfire::Campfire, chest::Chest,
+    chiseled_bookshelf::ChiseledBookshelf, crafter::Crafter, decorated_pot::DecoratedPot,
+    dispenser::Dispenser, dropper::Dropper, furnace::Furnace, hopper::Hopper, jukebox::Jukebox,
+    shulker_box::ShulkerBox, BlockEntityKind,
+};
+use crate::{
+    components::lock::Lock,
+    item::{Item, ItemWithNoSlot},
+    resource_location::ResourceLocation,
+    util::LootTableData,
+};
+
+/// A block entity that holds items.
+pub trait Container {
+    /// The items in the container.
+    fn items(&self) -> ContainerItems<'_>;
+
+    /// The items in the container, mutably.
+    fn items_mut(&mut self) -> ContainerItemsMut<'_>;
+
+    /// How many slots the container has.
+    fn slot_count(&self) -> usize;
+
+    /// The loot table that fills the container when it's opened, `None` if it isn't set or the container can't have one.
+    fn loot_table(&self) -> Option<LootTableData> {
+        None
+    }
+
+    /// The custom name JSON text component, `None` if it isn't set or the container can't have one.
+    fn custom_name(&self) -> Option<&str> {
+        None
+    }
+
+    /// The key item needed to open the container, `None` if it isn't set or the container can't have one.
+    fn lock(&self) -> Option<&Lock> {
+        None
+    }
+}
+
+/// The items of a [`Container`], which either has slots or holds a single item.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ContainerItems<'a> {
+    /// Items in numbered slots, like a chest.
+    Slots(&'a [Item]),
+
+    /// A single item without a slot, like the record in a jukebox.
+    Single(Option<&'a ItemWithNoSlot>),
+}
+
+/// The items of a [`Container`], mutably.
+#[derive(Debug, PartialEq)]
+pub enum ContainerItemsMut<'a> {
+    /// Items in numbered slots, like a chest.
+    Slots(&'a mut Vec<Item>),
+
+    /// A single item without a slot, like the record in a jukebox.
+    Single(&'a mut Option<ItemWithNoSlot>),
+}
+
+impl<'a> ContainerItems<'a> {
+    /// Amount of filled slots.
+    pub fn len(&self) -> usize {
+        match self {
+            ContainerItems::Slots(items) => items.len(),
+            ContainerItems::Single(item) => item.is_some() as usize,
+        }
+    }
+
+    /// Whether the container is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The id and count of every item, in slot order.
+    pub fn stacks(&self) -> Vec<(&'a ResourceLocation, i32)> {
+        match *self {
+            ContainerItems::Slots(items) => {
+                items.iter().map(|item| (&item.id, item.count)).collect()
+            }
+            ContainerItems::Single(item) => {
+                item.iter().map(|item| (&item.id, item.count)).collect()
+            }
+        }
+    }
+}
+
+impl BlockEntityKind {
+    /// The block entity as a [`Container`], `None` if it doesn't hold items.
+    pub fn as_container(&self) -> Option<&dyn Container> {
+        let container: &dyn Container = match self {
+            BlockEntityKind::Barrel(barrel) => barrel,
+            BlockEntityKind::BlastFurnace(furnace)
+            | BlockEntityKind::Furnace(furnace)
+            | BlockEntityKind::Smoker(furnace) => furnace,
+            BlockEntityKind::BrewingStand(brewing_stand) => brewing_stand,
+            BlockEntityKind::Campfire(campfire) | BlockEntityKind::SoulCampfire(campfire) => {
+                campfire
+            }
+            BlockEntityKind::ChiseledBookshelf(bookshelf) => bookshelf,
+            BlockEntityKind::Chest(chest) | BlockEntityKind::TrappedChest(chest) => chest,
+            BlockEntityKind::Crafter(crafter) => crafter,
+            BlockEntityKind::DecoratedPot(pot) => pot,
+            BlockEntityKind::Dispenser(dispenser) => dispenser,
+            BlockEntityKind::Dropper(dropper) => dropper,
+            BlockEntityKind::Hopper(hopper) => hopper,
+            BlockEntityKind::Jukebox(jukebox) => jukebox,
+            BlockEntityKind::ShulkerBox(shulker_box) => shulker_box,
+            _ => return None,
+        };
+
+        Some(container)
+    }
+
+    /// The block entity as a mutable [`Container`], `None` if it doesn't hold items.
+    pub fn as_container_mut(&mut self) -> Option<&mut dyn Container> {
+        let container: &mut dyn Container = match self {
+            BlockEntityKind::Barrel(barrel) => barrel,
+            BlockEntityKind::BlastFurnace(furnace)
+            | BlockEntityKind::Furnace(furnace)
+            | BlockEntityKind::Smoker(furnace) => furnace,
+            BlockEntityKind::BrewingStand(brewing_stand) => brewing_stand,
+            BlockEntityKind::Campfire(campfire) | BlockEntityKind::SoulCampfire(campfire) => {
+                campfire
+            }
+            BlockEntityKind::ChiseledBookshelf(bookshelf) => bookshelf,
+            BlockEntityKind::Chest(chest) | BlockEntityKind::TrappedChest(chest) => chest,
+            BlockEntityKind::Crafter(crafter) => crafter,
+            BlockEntityKind::DecoratedPot(pot) => pot,
+            BlockEntityKind::Dispenser(dispenser) => dispenser,
+            BlockEntityKind::Dropper(dropper) => dropper,
+            BlockEntityKind::Hopper(hopper) => hopper,
+            BlockEntityKind::Jukebox(jukebox) => jukebox,
+            BlockEntityKind::ShulkerBox(shulker_box) => shulker_box,
+            _ => return None,
+        };
+
+        Some(container)
+    }
+}
+
+/// `LootTable` and `LootTableSeed`, `None` if there's no loot table.
+fn loot_table_data(
+    loot_table: &Option<ResourceLocation>,
+    seed: Option<i64>,
+) -> Option<LootTableData> {
+    loot_table.as_ref().map(|loot_table| LootTableData {
+        loot_table: Some(loot_table.clone()),
+        loot_table_seed: seed,
+    })
+}
+
+impl Container for Chest {
+    fn items(&self) -> ContainerItems<'_> {
+        ContainerItems::Slots(&self.items)
+    }
+
+    fn items_mut(&mut self) -> ContainerItemsMut<'_> {
+        ContainerItemsMut::Slots(&mut self.items)
+    }
+
+    fn slot_count(&self) -> usize {
+        27
+    }
+
+    fn loot_table(&self) -> Option<LootTableData> {
+        loot_table_data(&self.loot_table, self.loot_table_seed)
+    }
+
+    fn custom_name(&self) -> Option<&str> {
+        self.custom_name.as_deref()
+    }
+
+    fn lock(&self) -> Option<&Lock> {
+        self.lock.as_ref()
+    }
+}
+
+impl Container for Barrel {
+    fn items(&self) -> ContainerItems<'_> {
+        ContainerItems::Slots(&self.items)
+    }
+
+    fn items_mut(&mut self) -> ContainerItemsMut<'_> {
+        ContainerItemsMut::Slots(&mut self.items)
+    }
+
+    fn slot_count(&self) -> usize {
+        27
+    }
+
+    fn loot_table(&self) -> Option<LootTableData> {
+        loot_table_data(&self.loot_table, self.loot_table_seed)
+    }
+
+    fn custom_name(&self) -> Option<&str> {
+        self.custom_name.as_deref()
+    }
+
+    fn lock(&self) -> Option<&Lock> {
+        self.lock.as_ref()
+    }
+}
+
+impl Container for Dispenser {
+    fn items(&self) -> ContainerItems<'_> {
+        ContainerItems::Slots(&self.items)
+    }
+
+    fn items_mut(&mut self) -> ContainerItemsMut<'_> {
+        ContainerItemsMut::Slots(&mut self.items)
+    }
+
+    fn slot_count(&self) -> usize {
+        9
+    }
+
+    fn loot_table(&self) -> Option<LootTableData> {
+        loot_table_data(&self.loot_table, self.loot_table_seed)
+    }
+
+    fn custom_name(&self) -> Option<&str> {
+        self.custom_name.as_deref()
+    }
+
+    fn lock(&self) -> Option<&Lock> {
+        self.lock.as_ref()
+    }
+}
+
+impl Container for Dropper {
+    fn items(&self) -> ContainerItems<'_> {
+        ContainerItems::Slots(&self.items)
+    }
+
+    fn items_mut(&mut self) -> ContainerItemsMut<'_> {
+        ContainerItemsMut::Slots(&mut self.items)
+    }
+
+    fn slot_count(&self) -> usize {
+        9
+    }
+
+    fn loot_table(&self) -> Option<LootTableData> {
+        loot_table_data(&self.loot_table, self.loot_table_seed)
+    }
+
+    fn custom_name(&self) -> Option<&str> {
+        self.custom_name.as_deref()
+    }
+
+    fn lock(&self) -> Option<&Lock> {
+        self.lock.as_ref()
+    }
+}
+
+impl Container for Hopper {
+    fn items(&self) -> ContainerItems<'_> {
+        ContainerItems::Slots(&self.items)
+    }
+
+    fn items_mut(&mut self) -> ContainerItemsMut<'_> {
+        ContainerItemsMut::Slots(&mut self.items)
+    }
+
+    fn slot_count(&self) -> usize {
+        5
+    }
+
+    fn loot_table(&self) -> Option<LootTableData> {
+        loot_table_data(&self.loot_table, self.loot_table_seed)
+    }
+
+    fn custom_name(&self) -> Option<&str> {
+        self.custom_name.as_deref()
+    }
+
+    fn lock(&self) -> Option<&Lock> {
+        self.lock.as_ref()
+    }
+}
+
+impl Container for ShulkerBox {
+    fn items(&self) -> ContainerItems<'_> {
+        ContainerItems::Slots(&self.items)
+    }
+
+    fn items_mut(&mut self) -> ContainerItemsMut<'_> {
+        ContainerItemsMut::Slots(&mut self.items)
+    }
+
+    fn slot_count(&self) -> usize {
+        27
+    }
+
+    fn loot_table(&self) -> Option<LootTableData> {
+        loot_table_data(&self.loot_table, self.loot_table_seed)
+    }
+
+    fn custom_name(&self) -> Option<&str> {
+        self.custom_name.as_deref()
+    }
+
+    fn lock(&self) -> Option<&Lock> {
+        self.lock.as_ref()
+    }
+}
+
+impl Container for BrewingStand {
+    fn items(&self) -> ContainerItems<'_> {
+        ContainerItems::Slots(&self.items)
+    }
+
+    fn items_mut(&mut self) -> ContainerItemsMut<'_> {
+        ContainerItemsMut::Slots(&mut self.items)
+    }
+
+    /// Three bottles, the ingredient and the blaze powder fuel.
+    fn slot_count(&self) -> usize {
+        5
+    }
+
+    fn custom_name(&self) -> Option<&str> {
+        self.custom_name.as_deref()
+    }
+
+    fn lock(&self) -> Option<&Lock> {
+        self.lock.as_ref()
+    }
+}
+
+impl Container for Furnace {
+    fn items(&self) -> ContainerItems<'_> {
+        ContainerItems::Slots(&self.items)
+    }
+
+    fn items_mut(&mut self) -> ContainerItemsMut<'_> {
+        ContainerItemsMut::Slots(&mut self.items)
+    }
+
+    /// The input, the fuel and the result.
+    fn slot_count(&self) -> usize {
+        3
+    }
+
+    fn custom_name(&self) -> Option<&str> {
+        self.custom_name.as_deref()
+    }
+
+    fn lock(&self) -> Option<&Lock> {
+        self.lock.as_ref()
+    }
+}
+
+impl Container for Crafter {
+    fn items(&self) -> ContainerItems<'_> {
+        ContainerItems::Slots(&self.items)
+    }
+
+    fn items_mut(&mut self) -> ContainerItemsMut<'_> {
+        ContainerItemsMut::Slots(&mut self.items)
+    }
+
+    fn slot_count(&self) -> usize {
+        9
+    }
+
+    fn loot_table(&self) -> Option<LootTableData> {
+        loot_table_data(&self.loot_table, self.loot_table_seed)
+    }
+
+    fn lock(&self) -> Option<&Lock> {
+        self.lock.as_ref()
+    }
+}
+
+impl Container for Campfire {
+    fn items(&self) -> ContainerItems<'_> {
+        ContainerItems::Slots(&self.items)
+    }
+
+    fn items_mut(&mut self) -> ContainerItemsMut<'_> {
+        ContainerItemsMut::Slots(&mut self.items)
+    }
+
+    fn slot_count(&self) -> usize {
+        4
+    }
+}
+
+impl Container for ChiseledBookshelf {
+    fn items(&self) -> ContainerItems<'_> {
+        ContainerItems::Slots(&self.items)
+    }
+
+    fn items_mut(&mut self) -> ContainerItemsMut<'_> {
+        ContainerItemsMut::Slots(&mut self.items)
+    }
+
+    fn slot_count(&self) -> usize {
+        6
+    }
+}
+
+impl Container for DecoratedPot {
+    fn items(&self) -> ContainerItems<'_> {
+        ContainerItems::Single(self.item.as_ref())
+    }
+
+    fn items_mut(&mut self) -> ContainerItemsMut<'_> {
+        ContainerItemsMut::Single(&mut self.item)
+    }
+
+    fn slot_count(&self) -> usize {
+        1
+    }
+
+    fn loot_table(&self) -> Option<LootTableData> {
+        loot_table_data(&self.loot_table, self.loot_table_seed)
+    }
+}
+
+impl Container for Jukebox {
+    fn items(&self) -> ContainerItems<'_> {
+        ContainerItems::Single(self.record_item.as_ref())
+    }
+
+    fn items_mut(&mut self) -> ContainerItemsMut<'_> {
+        ContainerItemsMut::Single(&mut self.record_item)
+    }
+
+    fn slot_count(&self) -> usize {
+        1
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn as_container() {
+    let mut kind = BlockEntityKind::Jukebox(Jukebox {
+        record_item: None,
+        ticks_since_song_started: None,
+    });
+    assert!(kind.as_container().unwrap().items().is_empty());
+
+    if let Some(ContainerItemsMut::Single(record)) =
+        kind.as_container_mut().map(|jukebox| jukebox.items_mut())
+    {
+        *record = Some(ItemWithNoSlot {
+            id: "minecraft:music_disc_cat".parse().unwrap(),
+            count: 1,
+            components: None,
+            extra: simdnbt::owned::NbtCompound::new(),
+        });
+    }
+    let jukebox = kind.as_container().unwrap();
+    let stacks = jukebox.items().stacks();
+    assert_eq!(stacks.len(), 1);
+    assert_eq!(*stacks[0].0, "minecraft:music_disc_cat");
+    assert_eq!(jukebox.slot_count(), 1);
+    assert_eq!(jukebox.loot_table(), None);
+
+    assert!(BlockEntityKind::Bed.as_container().is_none());
+}
+
+#[cfg(test)]
+#[test]
+fn lock_predicate() {
+    use crate::{
+        block_entity::BlockEntity, error::ParseMode, traits::ToCompoundNbt,
+        util::from_owned_compound,
+    };
+    use simdnbt::owned::{NbtCompound, NbtTag};
+
+    let mut components = NbtCompound::new();
+    components.insert("minecraft:custom_name", NbtTag::String("key".into()));
+    let mut predicate = NbtCompound::new();
+    predicate.insert("components", NbtTag::Compound(components));
+
+    let mut nbt = NbtCompound::new();
+    nbt.insert("id", NbtTag::String("minecraft:chest".into()));
+    nbt.insert("x", NbtTag::Int(0));
+    nbt.insert("y", NbtTag::Int(64));
+    nbt.insert("z", NbtTag::Int(0));
+    nbt.insert("Items", NbtTag::List(simdnbt::owned::NbtList::Empty));
+    nbt.insert("lock", NbtTag::Compound(predicate.clone()));
+
+    let chest = from_owned_compound(&nbt, |nbt| {
+        BlockEntity::from_compound_nbt_with_mode(nbt, ParseMode::Strict, &mut Vec::new())
+    })
+    .unwrap();
+    assert_eq!(
+        chest.kind.as_container().unwrap().lock(),
+        Some(&Lock::Predicate(predicate.clone()))
+    );
+    assert_eq!(chest.to_compound_nbt().compound("lock"), Some(&predicate));
+}
@@ -1,8 +1,13 @@
 use crate::{
+    components::lock::Lock,
     error::SculkParseError,
     item::Item,
-    traits::FromCompoundNbt,
-    util::{get_bool, get_int_array, get_loot_table_data, get_optional_lock, get_t_compound_vec},
+    resource_location::ResourceLocation,
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+    util::{
+        get_bool, get_int_array, get_loot_table_data, get_optional_lock, get_t_compound_vec,
+        set_tag, write_items, write_loot_table_data, write_optional_lock,
+    },
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -24,15 +29,15 @@ pub struct Crafter {
     /// `Items`
     pub items: Vec<Item>,
 
-    /// Optional. When not blank, prevents the container from being opened unless the opener is holding an item whose name matches this string.
+    /// Optional. Prevents the container from being opened unless the opener is holding an item whose name matches, or from 1.21.2 an item matching the predicate.
     ///
-    /// `Lock`
-    pub lock: Option<String>,
+    /// `Lock`, `lock` from 1.21.2
+    pub lock: Option<Lock>,
 
     /// Optional. Name of the loot table to use. If this is used in a chest-like container, the loot table generates its content when it is opened. Generating the items in the container removes both loot table tags ( LootTable and  LootTableSeed).
     ///
     /// `LootTable`
-    pub loot_table: Option<String>,
+    pub loot_table: Option<ResourceLocation>,
 
     /// Optional. Seed for generating the loot table. The default value works similarly to the seeds for worlds, where value of 0 or an omitted value causes the game to use a random seed.
     ///
@@ -56,8 +61,8 @@ impl FromCompoundNbt for Crafter {
         let disabled_slots = get_int_array(nbt, "disabled_slots")?;
 
         let items = get_t_compound_vec(nbt, "Items", Item::from_compound_nbt)?;
-        let lock = get_optional_lock(nbt);
-        let loot_table = get_loot_table_data(nbt);
+        let lock = get_optional_lock(nbt)?;
+        let loot_table = get_loot_table_data(nbt)?;
 
         Ok(Crafter {
             crafting_ticks_remaining,
@@ -70,3 +75,28 @@ impl FromCompoundNbt for Crafter {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for Crafter {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_tag(
+            &mut nbt,
+            "crafting_ticks_remaining",
+            self.crafting_ticks_remaining,
+        );
+        set_tag(&mut nbt, "triggered", self.triggered);
+        set_tag(
+            &mut nbt,
+            "disabled_slots",
+            simdnbt::owned::NbtTag::IntArray(self.disabled_slots.clone()),
+        );
+        if self.loot_table.is_none() {
+            write_items(&mut nbt, &self.items, data_version);
+        }
+        write_optional_lock(&mut nbt, self.lock.as_ref());
+        write_loot_table_data(&mut nbt, self.loot_table.as_ref(), self.loot_table_seed);
+
+        nbt
+    }
+}
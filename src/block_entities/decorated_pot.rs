@@ -1,4 +1,10 @@
-use crate::{item::ItemWithNoSlot, traits::FromCompoundNbt, util::get_loot_table_data};
+use crate::{
+    error::SculkParseError,
+    item::ItemWithNoSlot,
+    resource_location::ResourceLocation,
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+    util::{get_loot_table_data, set_optional_tag, set_tag, write_loot_table_data},
+};
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -14,7 +20,7 @@ pub struct DecoratedPot {
     /// Optional. Name of the loot table to use. If this is used in a chest-like container, the loot table generates its content when it is opened. Generating the items in the container removes both loot table tags ( LootTable and  LootTableSeed).
     ///
     /// `LootTable`
-    pub loot_table: Option<String>,
+    pub loot_table: Option<ResourceLocation>,
 
     /// Optional. Seed for generating the loot table. The default value works similarly to the seeds for worlds, where value of 0 or an omitted value causes the game to use a random seed.
     ///
@@ -29,17 +35,14 @@ impl FromCompoundNbt for DecoratedPot {
     where
         Self: Sized,
     {
-        let sherds = if let Some(sherds_list) = nbt.list("sherds") {
-            let mut sherds: Vec<String> = vec![];
-            for sherd in sherds_list.strings().into_iter() {
-                // maybe???
-                let str = (*sherd.first().unwrap()).to_string();
-                sherds.push(str);
-            }
-
-            sherds
-        } else {
-            vec![]
+        let sherds = match nbt.list("sherds") {
+            Some(sherds) => sherds
+                .strings()
+                .ok_or(SculkParseError::InvalidField("sherds".into()))?
+                .iter()
+                .map(|sherd| sherd.to_string())
+                .collect(),
+            None => vec![],
         };
 
         let item = if let Some(item) = nbt.compound("item") {
@@ -48,7 +51,7 @@ impl FromCompoundNbt for DecoratedPot {
             None
         };
 
-        let loot_table = get_loot_table_data(nbt);
+        let loot_table = get_loot_table_data(nbt)?;
 
         Ok(DecoratedPot {
             sherds,
@@ -58,3 +61,23 @@ impl FromCompoundNbt for DecoratedPot {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for DecoratedPot {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        if !self.sherds.is_empty() {
+            set_tag(&mut nbt, "sherds", self.sherds.clone());
+        }
+        set_optional_tag(
+            &mut nbt,
+            "item",
+            self.item
+                .as_ref()
+                .map(|item| item.to_compound_nbt_with_version(data_version)),
+        );
+        write_loot_table_data(&mut nbt, self.loot_table.as_ref(), self.loot_table_seed);
+
+        nbt
+    }
+}
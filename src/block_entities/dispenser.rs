@@ -1,7 +1,12 @@
 use crate::{
+    components::lock::Lock,
     item::Item,
-    traits::FromCompoundNbt,
-    util::{get_loot_table_data, get_optional_lock, get_optional_name, get_t_compound_vec},
+    resource_location::ResourceLocation,
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+    util::{
+        get_loot_table_data, get_optional_lock, get_optional_name, get_t_compound_vec, write_items,
+        write_loot_table_data, write_optional_lock, write_optional_name,
+    },
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -19,15 +24,15 @@ pub struct Dispenser {
     /// `Items`
     pub items: Vec<Item>,
 
-    /// Optional. When not blank, prevents the container from being opened unless the opener is holding an item whose name matches this string.
+    /// Optional. Prevents the container from being opened unless the opener is holding an item whose name matches, or from 1.21.2 an item matching the predicate.
     ///
-    /// `Lock`
-    pub lock: Option<String>,
+    /// `Lock`, `lock` from 1.21.2
+    pub lock: Option<Lock>,
 
     /// Optional. Name of the loot table to use. If this is used in a chest-like container, the loot table generates its content when it is opened. Generating the items in the container removes both loot table tags ( LootTable and  LootTableSeed).
     ///
     /// `LootTable`
-    pub loot_table: Option<String>,
+    pub loot_table: Option<ResourceLocation>,
 
     /// Optional. Seed for generating the loot table. The default value works similarly to the seeds for worlds, where value of 0 or an omitted value causes the game to use a random seed.
     ///
@@ -45,8 +50,8 @@ impl FromCompoundNbt for Dispenser {
         let items = get_t_compound_vec(nbt, "Items", Item::from_compound_nbt)?;
 
         let custom_name = get_optional_name(nbt);
-        let lock = get_optional_lock(nbt);
-        let loot_table = get_loot_table_data(nbt);
+        let lock = get_optional_lock(nbt)?;
+        let loot_table = get_loot_table_data(nbt)?;
 
         Ok(Dispenser {
             custom_name,
@@ -57,3 +62,18 @@ impl FromCompoundNbt for Dispenser {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for Dispenser {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        write_optional_name(&mut nbt, self.custom_name.as_ref(), data_version);
+        if self.loot_table.is_none() {
+            write_items(&mut nbt, &self.items, data_version);
+        }
+        write_optional_lock(&mut nbt, self.lock.as_ref());
+        write_loot_table_data(&mut nbt, self.loot_table.as_ref(), self.loot_table_seed);
+
+        nbt
+    }
+}
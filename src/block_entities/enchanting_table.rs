@@ -1,4 +1,7 @@
-use crate::{traits::FromCompoundNbt, util::get_optional_name};
+use crate::{
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+    util::{get_optional_name, write_optional_name},
+};
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -21,3 +24,13 @@ impl FromCompoundNbt for EnchantingTable {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for EnchantingTable {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        write_optional_name(&mut nbt, self.custom_name.as_ref(), data_version);
+
+        nbt
+    }
+}
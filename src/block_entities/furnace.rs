@@ -1,8 +1,12 @@
 use crate::{
+    components::lock::Lock,
     error::SculkParseError,
     item::Item,
-    traits::FromCompoundNbt,
-    util::{get_optional_lock, get_optional_name, get_t_compound_vec},
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+    util::{
+        get_optional_lock, get_optional_name, get_t_compound_vec, set_tag, write_items,
+        write_optional_lock, write_optional_name,
+    },
 };
 use std::collections::HashMap;
 
@@ -38,10 +42,10 @@ pub struct Furnace {
     /// `Items`
     pub items: Vec<Item>,
 
-    /// Optional. When not blank, prevents the container from being opened unless the opener is holding an item whose name matches this string.
+    /// Optional. Prevents the container from being opened unless the opener is holding an item whose name matches, or from 1.21.2 an item matching the predicate.
     ///
-    /// `Lock`
-    pub lock: Option<String>,
+    /// `Lock`, `lock` from 1.21.2
+    pub lock: Option<Lock>,
 
     /// Recipes that have been used since the last time a recipe result item was manually removed from the GUI. Used to calculate experience given to the player when taking out the resulting item.
     ///
@@ -70,7 +74,7 @@ impl FromCompoundNbt for Furnace {
 
         let custom_name = get_optional_name(nbt);
         let items = get_t_compound_vec(nbt, "Items", Item::from_compound_nbt)?;
-        let lock = get_optional_lock(nbt);
+        let lock = get_optional_lock(nbt)?;
 
         let recipes_used = nbt
             .compound("RecipesUsed")
@@ -96,3 +100,26 @@ impl FromCompoundNbt for Furnace {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for Furnace {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_tag(&mut nbt, "BurnTime", self.burn_time);
+        set_tag(&mut nbt, "CookTime", self.cook_time);
+        set_tag(&mut nbt, "CookTimeTotal", self.cook_time_total);
+        write_optional_name(&mut nbt, self.custom_name.as_ref(), data_version);
+        write_items(&mut nbt, &self.items, data_version);
+        write_optional_lock(&mut nbt, self.lock.as_ref());
+
+        let mut recipes_used: Vec<_> = self.recipes_used.iter().collect();
+        recipes_used.sort();
+        let mut recipes = simdnbt::owned::NbtCompound::new();
+        for (recipe, count) in recipes_used {
+            recipes.insert(recipe.as_str(), *count);
+        }
+        set_tag(&mut nbt, "RecipesUsed", recipes);
+
+        nbt
+    }
+}
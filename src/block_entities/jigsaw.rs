@@ -1,4 +1,8 @@
-use crate::{error::SculkParseError, traits::FromCompoundNbt, util::get_owned_string};
+use crate::{
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::get_owned_string,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -86,3 +90,19 @@ impl FromCompoundNbt for Jigsaw {
         })
     }
 }
+
+impl ToCompoundNbt for Jigsaw {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("final_state", self.final_state.as_str());
+        nbt.insert("joint", <&str>::from(self.joint.clone()));
+        nbt.insert("name", self.name.as_str());
+        nbt.insert("pool", self.pool.as_str());
+        nbt.insert("target", self.target.as_str());
+        nbt.insert("selection_priority", self.selection_priority);
+        nbt.insert("placement_priority", self.placement_priority);
+
+        nbt
+    }
+}
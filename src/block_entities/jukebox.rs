@@ -1,4 +1,8 @@
-use crate::{item::ItemWithNoSlot, traits::FromCompoundNbt};
+use crate::{
+    item::ItemWithNoSlot,
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+    util::set_optional_tag,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -30,3 +34,24 @@ impl FromCompoundNbt for Jukebox {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for Jukebox {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_optional_tag(
+            &mut nbt,
+            "RecordItem",
+            self.record_item
+                .as_ref()
+                .map(|item| item.to_compound_nbt_with_version(data_version)),
+        );
+        set_optional_tag(
+            &mut nbt,
+            "ticks_since_song_started",
+            self.ticks_since_song_started,
+        );
+
+        nbt
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{item::ItemWithNoSlot, traits::FromCompoundNbt};
+use crate::{
+    item::ItemWithNoSlot,
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+    util::set_optional_tag,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -30,3 +34,20 @@ impl FromCompoundNbt for Lectern {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for Lectern {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_optional_tag(
+            &mut nbt,
+            "Book",
+            self.book
+                .as_ref()
+                .map(|book| book.to_compound_nbt_with_version(data_version)),
+        );
+        set_optional_tag(&mut nbt, "Page", self.page);
+
+        nbt
+    }
+}
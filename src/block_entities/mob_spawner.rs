@@ -1,5 +1,8 @@
 use crate::{
-    entity::MaybeEntity, error::SculkParseError, traits::FromCompoundNbt, util::get_owned_string,
+    entity::MaybeEntity,
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToCompoundNbt, ToVersionedCompoundNbt},
+    util::{get_owned_string, set_optional_tag, set_tag, write_compound_list},
 };
 use simdnbt::borrow::NbtCompound;
 
@@ -311,3 +314,124 @@ impl FromCompoundNbt for DropChances {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for MobSpawner {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_tag(&mut nbt, "Delay", self.delay);
+        set_tag(&mut nbt, "MaxNearbyEntities", self.max_nearby_entities);
+        set_tag(&mut nbt, "MaxSpawnDelay", self.max_spawn_delay);
+        set_tag(&mut nbt, "MinSpawnDelay", self.min_spawn_delay);
+        set_tag(&mut nbt, "RequiredPlayerRange", self.required_player_range);
+        set_tag(&mut nbt, "SpawnCount", self.spawn_count);
+        set_tag(
+            &mut nbt,
+            "SpawnData",
+            self.spawn_data.to_compound_nbt_with_version(data_version),
+        );
+        if let Some(spawn_potentials) = &self.spawn_potentials {
+            set_tag(
+                &mut nbt,
+                "SpawnPotentials",
+                write_compound_list(spawn_potentials, |potential| {
+                    potential.to_compound_nbt_with_version(data_version)
+                }),
+            );
+        }
+        set_tag(&mut nbt, "SpawnRange", self.spawn_range);
+
+        nbt
+    }
+}
+
+impl ToVersionedCompoundNbt for PotentialSpawn {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("weight", self.weight);
+        nbt.insert("data", self.data.to_compound_nbt_with_version(data_version));
+
+        nbt
+    }
+}
+
+impl ToVersionedCompoundNbt for SpawnData {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_tag(
+            &mut nbt,
+            "entity",
+            self.entity.to_compound_nbt_with_version(data_version),
+        );
+        set_optional_tag(
+            &mut nbt,
+            "custom_spawn_rules",
+            self.custom_spawn_rules
+                .as_ref()
+                .map(SpawnRules::to_compound_nbt),
+        );
+        set_optional_tag(
+            &mut nbt,
+            "equipment",
+            self.equipment.as_ref().map(Equipment::to_compound_nbt),
+        );
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for SpawnRules {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("block_light_limit", self.block_light_limit);
+        nbt.insert("sky_light_limit", self.sky_light_limit);
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for Equipment {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_tag(&mut nbt, "loot_table", self.loot_table.as_str());
+        set_optional_tag(
+            &mut nbt,
+            "slot_drop_chances",
+            self.slot_drop_chances.as_ref().map(DropChanceType::to_tag),
+        );
+
+        nbt
+    }
+}
+
+impl DropChanceType {
+    /// Writes the chance for every slot as a float, or the chances per slot as a compound.
+    pub fn to_tag(&self) -> simdnbt::owned::NbtTag {
+        match self {
+            DropChanceType::All(chance) => simdnbt::owned::NbtTag::Float(*chance),
+            DropChanceType::Indiviual(chances) => {
+                simdnbt::owned::NbtTag::Compound(chances.to_compound_nbt())
+            }
+        }
+    }
+}
+
+impl ToCompoundNbt for DropChances {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_optional_tag(&mut nbt, "feet", self.feet);
+        set_optional_tag(&mut nbt, "legs", self.legs);
+        set_optional_tag(&mut nbt, "chest", self.chest);
+        set_optional_tag(&mut nbt, "head", self.head);
+        set_optional_tag(&mut nbt, "body", self.body);
+        set_optional_tag(&mut nbt, "mainhand", self.mainhand);
+        set_optional_tag(&mut nbt, "offhand", self.offhand);
+
+        nbt
+    }
+}
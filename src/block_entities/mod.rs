@@ -1,4 +1,8 @@
-use crate::{error::SculkParseError, traits::FromCompoundNbt, util::get_owned_string};
+use crate::{
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToCompoundNbt, ToVersionedCompoundNbt},
+    util::get_owned_string,
+};
 
 pub mod variant;
 
@@ -14,6 +18,7 @@ pub mod chiseled_bookshelf;
 pub mod command_block;
 pub mod comparator;
 pub mod conduit;
+pub mod container;
 pub mod crafter;
 pub mod decorated_pot;
 pub mod dispenser;
@@ -186,6 +191,11 @@ pub enum BlockEntityKind {
 
     /// `DUMMY`
     Dummy,
+
+    /// Raw nbt of a block entity that failed to parse.  
+    /// Used with [`crate::error::ParseMode::Lenient`], and for partial `minecraft:block_entity_data` of items.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Unknown(simdnbt::owned::NbtCompound),
 }
 
 impl FromCompoundNbt for BlockEntityKind {
@@ -465,3 +475,272 @@ impl FromCompoundNbt for BlockEntityKind {
         Ok(kind)
     }
 }
+
+impl ToVersionedCompoundNbt for BlockEntityKind {
+    /// Writes the fields of the kind, only a raw [`BlockEntityKind::Unknown`] also has the base fields.
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        match self {
+            BlockEntityKind::Banners(banner) => banner.to_compound_nbt_with_version(data_version),
+            BlockEntityKind::Barrel(barrel) => barrel.to_compound_nbt_with_version(data_version),
+            BlockEntityKind::Beacon(beacon) => beacon.to_compound_nbt_with_version(data_version),
+            BlockEntityKind::Beehive(beehive) => beehive.to_compound_nbt_with_version(data_version),
+            BlockEntityKind::BlastFurnace(furnace)
+            | BlockEntityKind::Furnace(furnace)
+            | BlockEntityKind::Smoker(furnace) => {
+                furnace.to_compound_nbt_with_version(data_version)
+            }
+            BlockEntityKind::BrewingStand(brewing_stand) => {
+                brewing_stand.to_compound_nbt_with_version(data_version)
+            }
+            BlockEntityKind::BrushableBlock(block)
+            | BlockEntityKind::SuspiciousGravel(block)
+            | BlockEntityKind::SuspiciousSand(block) => {
+                block.to_compound_nbt_with_version(data_version)
+            }
+            BlockEntityKind::CalibratedSculkSensor(sensor) => sensor.to_compound_nbt(),
+            BlockEntityKind::Campfire(campfire) | BlockEntityKind::SoulCampfire(campfire) => {
+                campfire.to_compound_nbt_with_version(data_version)
+            }
+            BlockEntityKind::ChiseledBookshelf(bookshelf) => {
+                bookshelf.to_compound_nbt_with_version(data_version)
+            }
+            BlockEntityKind::Chest(chest) | BlockEntityKind::TrappedChest(chest) => {
+                chest.to_compound_nbt_with_version(data_version)
+            }
+            BlockEntityKind::Comparator(comparator) => comparator.to_compound_nbt(),
+            BlockEntityKind::CommandBlock(command_block) => {
+                command_block.to_compound_nbt_with_version(data_version)
+            }
+            BlockEntityKind::Conduit(conduit) => conduit.to_compound_nbt(),
+            BlockEntityKind::Crafter(crafter) => crafter.to_compound_nbt_with_version(data_version),
+            BlockEntityKind::DecoratedPot(pot) => pot.to_compound_nbt_with_version(data_version),
+            BlockEntityKind::Dispenser(dispenser) => {
+                dispenser.to_compound_nbt_with_version(data_version)
+            }
+            BlockEntityKind::Dropper(dropper) => dropper.to_compound_nbt_with_version(data_version),
+            BlockEntityKind::EnchantingTable(table) => {
+                table.to_compound_nbt_with_version(data_version)
+            }
+            BlockEntityKind::EndGateway(gateway) => gateway.to_compound_nbt(),
+            BlockEntityKind::HangingSign(sign) | BlockEntityKind::Sign(sign) => {
+                sign.to_compound_nbt_with_version(data_version)
+            }
+            BlockEntityKind::Hopper(hopper) => hopper.to_compound_nbt_with_version(data_version),
+            BlockEntityKind::Jigsaw(jigsaw) => jigsaw.to_compound_nbt(),
+            BlockEntityKind::Jukebox(jukebox) => jukebox.to_compound_nbt_with_version(data_version),
+            BlockEntityKind::Lectern(lectern) => lectern.to_compound_nbt_with_version(data_version),
+            BlockEntityKind::MobSpawner(spawner) => {
+                spawner.to_compound_nbt_with_version(data_version)
+            }
+            BlockEntityKind::Piston(piston) => piston.to_compound_nbt(),
+            BlockEntityKind::SculkCatalyst(catalyst) => catalyst.to_compound_nbt(),
+            BlockEntityKind::SculkSensor(sensor) => sensor.to_compound_nbt(),
+            BlockEntityKind::SculkShrieker(shrieker) => shrieker.to_compound_nbt(),
+            BlockEntityKind::ShulkerBox(shulker_box) => {
+                shulker_box.to_compound_nbt_with_version(data_version)
+            }
+            BlockEntityKind::Skull(skull) => skull.to_compound_nbt_with_version(data_version),
+            BlockEntityKind::StructureBlock(structure_block) => structure_block.to_compound_nbt(),
+            BlockEntityKind::TrialSpawner(spawner) => {
+                spawner.to_compound_nbt_with_version(data_version)
+            }
+            BlockEntityKind::Vault(vault) => vault.to_compound_nbt_with_version(data_version),
+            BlockEntityKind::Bed
+            | BlockEntityKind::Bell
+            | BlockEntityKind::DaylightDetector
+            | BlockEntityKind::EnderChest
+            | BlockEntityKind::EndPortal
+            | BlockEntityKind::Dummy => simdnbt::owned::NbtCompound::new(),
+            BlockEntityKind::Unknown(raw) => raw.clone(),
+        }
+    }
+}
+
+/// Fields read by every kind that stores items like a chest.
+const CONTAINER_FIELDS: &[&str] = &[
+    "CustomName",
+    "Items",
+    "Lock",
+    "lock",
+    "LootTable",
+    "LootTableSeed",
+];
+
+/// Fields read by the furnace kinds.
+const FURNACE_FIELDS: &[&str] = &[
+    "BurnTime",
+    "CookTime",
+    "CookTimeTotal",
+    "CustomName",
+    "Items",
+    "Lock",
+    "lock",
+    "RecipesUsed",
+];
+
+/// Fields read by the campfire kinds.
+const CAMPFIRE_FIELDS: &[&str] = &["CookingTimes", "CookingTotalTimes", "Items"];
+
+/// Fields read by the sculk sensor kinds.
+const SCULK_SENSOR_FIELDS: &[&str] = &["last_vibration_frequency", "listener"];
+
+/// Fields read by the suspicious block kinds.
+const BRUSHABLE_FIELDS: &[&str] = &["item", "LootTable", "LootTableSeed"];
+
+impl BlockEntityKind {
+    /// The top level fields the kind is parsed from, besides the base fields.  
+    /// [`BlockEntityKind::Unknown`] keeps the whole compound, so it has none.
+    pub(crate) fn fields(&self) -> &'static [&'static str] {
+        match self {
+            BlockEntityKind::Banners(_) => &["CustomName", "patterns"],
+            BlockEntityKind::Barrel(_)
+            | BlockEntityKind::Chest(_)
+            | BlockEntityKind::TrappedChest(_)
+            | BlockEntityKind::Dispenser(_)
+            | BlockEntityKind::Dropper(_)
+            | BlockEntityKind::ShulkerBox(_) => CONTAINER_FIELDS,
+            BlockEntityKind::Beacon(_) => &[
+                "CustomName",
+                "Lock",
+                "lock",
+                "primary_effect",
+                "secondary_effect",
+            ],
+            BlockEntityKind::Beehive(_) => &["bees", "flower_pos"],
+            BlockEntityKind::BlastFurnace(_)
+            | BlockEntityKind::Furnace(_)
+            | BlockEntityKind::Smoker(_) => FURNACE_FIELDS,
+            BlockEntityKind::BrewingStand(_) => {
+                &["BrewTime", "CustomName", "Fuel", "Items", "Lock", "lock"]
+            }
+            BlockEntityKind::BrushableBlock(_)
+            | BlockEntityKind::SuspiciousGravel(_)
+            | BlockEntityKind::SuspiciousSand(_) => BRUSHABLE_FIELDS,
+            BlockEntityKind::CalibratedSculkSensor(_) | BlockEntityKind::SculkSensor(_) => {
+                SCULK_SENSOR_FIELDS
+            }
+            BlockEntityKind::Campfire(_) | BlockEntityKind::SoulCampfire(_) => CAMPFIRE_FIELDS,
+            BlockEntityKind::ChiseledBookshelf(_) => &["Items", "last_interacted_slot"],
+            BlockEntityKind::CommandBlock(_) => &[
+                "auto",
+                "Command",
+                "conditionMet",
+                "CustomName",
+                "LastExecution",
+                "LastOutput",
+                "powered",
+                "SuccessCount",
+                "TrackOutput",
+                "UpdateLastExecution",
+            ],
+            BlockEntityKind::Comparator(_) => &["OutputSignal"],
+            BlockEntityKind::Conduit(_) => &["target"],
+            BlockEntityKind::Crafter(_) => &[
+                "crafting_ticks_remaining",
+                "disabled_slots",
+                "Items",
+                "Lock",
+                "lock",
+                "LootTable",
+                "LootTableSeed",
+                "triggered",
+            ],
+            BlockEntityKind::DecoratedPot(_) => &["item", "LootTable", "LootTableSeed", "sherds"],
+            BlockEntityKind::EnchantingTable(_) => &["CustomName"],
+            BlockEntityKind::EndGateway(_) => &["Age", "ExactTeleport", "ExitPortal"],
+            BlockEntityKind::Hopper(_) => &[
+                "CustomName",
+                "Items",
+                "Lock",
+                "lock",
+                "LootTable",
+                "LootTableSeed",
+                "TransferCooldown",
+            ],
+            BlockEntityKind::Jigsaw(_) => &[
+                "final_state",
+                "joint",
+                "name",
+                "placement_priority",
+                "pool",
+                "selection_priority",
+                "target",
+            ],
+            BlockEntityKind::Jukebox(_) => &["RecordItem", "ticks_since_song_started"],
+            BlockEntityKind::Lectern(_) => &["Book", "Page"],
+            BlockEntityKind::MobSpawner(_) => &[
+                "Delay",
+                "MaxNearbyEntities",
+                "MaxSpawnDelay",
+                "MinSpawnDelay",
+                "RequiredPlayerRange",
+                "SpawnCount",
+                "SpawnData",
+                "SpawnPotentials",
+                "SpawnRange",
+            ],
+            BlockEntityKind::Piston(_) => {
+                &["blockState", "extending", "facing", "progress", "source"]
+            }
+            BlockEntityKind::SculkCatalyst(_) => &["cursors"],
+            BlockEntityKind::SculkShrieker(_) => &["listener"],
+            BlockEntityKind::Sign(_) | BlockEntityKind::HangingSign(_) => {
+                &["back_text", "front_text", "is_waxed"]
+            }
+            BlockEntityKind::Skull(_) => &["custom_name", "note_block_sound", "profile"],
+            BlockEntityKind::StructureBlock(_) => &[
+                "author",
+                "ignoreEntities",
+                "integrity",
+                "metadata",
+                "mirror",
+                "mode",
+                "name",
+                "posX",
+                "posY",
+                "posZ",
+                "powered",
+                "rotation",
+                "seed",
+                "showboundingbox",
+                "sizeX",
+                "sizeY",
+                "sizeZ",
+            ],
+            BlockEntityKind::TrialSpawner(_) => &[
+                "cooldown_ends_at",
+                "current_mobs",
+                "ejecting_loot_table",
+                "next_mob_spawns_at",
+                "normal_config",
+                "ominous_config",
+                "registered_players",
+                "required_player_range",
+                "spawn_data",
+                "target_cooldown_length",
+                "total_mobs_spawned",
+            ],
+            BlockEntityKind::Vault(_) => &["config", "server_data", "shared_data"],
+            BlockEntityKind::Bed
+            | BlockEntityKind::Bell
+            | BlockEntityKind::DaylightDetector
+            | BlockEntityKind::EnderChest
+            | BlockEntityKind::EndPortal
+            | BlockEntityKind::Dummy
+            | BlockEntityKind::Unknown(_) => &[],
+        }
+    }
+
+    /// Vanilla fields of the kind that aren't decoded, but that [`crate::error::ParseMode::Strict`] still accepts.
+    pub(crate) fn undecoded_fields(&self) -> &'static [&'static str] {
+        match self {
+            BlockEntityKind::Beacon(_) => &["Levels"],
+            BlockEntityKind::BrushableBlock(_)
+            | BlockEntityKind::SuspiciousGravel(_)
+            | BlockEntityKind::SuspiciousSand(_) => &["hit_direction"],
+            BlockEntityKind::Conduit(_) => &["Target"],
+            BlockEntityKind::SculkShrieker(_) => &["warning_level"],
+            _ => &[],
+        }
+    }
+}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use crate::{
     error::SculkParseError,
-    traits::FromCompoundNbt,
+    traits::{FromCompoundNbt, ToCompoundNbt},
     util::{get_bool, get_owned_string},
 };
 
@@ -142,3 +142,37 @@ impl FromCompoundNbt for BlockState {
         Ok(BlockState { name, properties })
     }
 }
+
+impl ToCompoundNbt for Piston {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("blockState", self.block_state.to_compound_nbt());
+        nbt.insert("extending", self.extending);
+        nbt.insert("facing", i32::from(self.facing.clone()));
+        nbt.insert("progress", self.progress);
+        nbt.insert("source", self.source);
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for BlockState {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("name", self.name.as_str());
+        if let Some(properties) = &self.properties {
+            let mut properties: Vec<_> = properties.iter().collect();
+            properties.sort();
+
+            let mut properties_nbt = simdnbt::owned::NbtCompound::new();
+            for (key, value) in properties {
+                properties_nbt.insert(key.as_str(), value.as_str());
+            }
+            nbt.insert("properties", properties_nbt);
+        }
+
+        nbt
+    }
+}
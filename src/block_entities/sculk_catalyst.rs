@@ -1,10 +1,14 @@
-use crate::{error::SculkParseError, traits::FromCompoundNbt, util::get_int_array};
+use crate::{
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::{get_int_array, write_compound_list},
+};
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SculkCatalyst {
     /// List of sculk charge clusters associated with the sculk catalyst.
-    cursors: Vec<Cursor>,
+    pub cursors: Vec<Cursor>,
 }
 
 /// A sculk charge cluster. Each cluster is stored within a single sculk block.
@@ -100,3 +104,35 @@ impl FromCompoundNbt for Cursor {
         })
     }
 }
+
+impl ToCompoundNbt for SculkCatalyst {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert(
+            "cursors",
+            write_compound_list(&self.cursors, Cursor::to_compound_nbt),
+        );
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for Cursor {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("charge", self.charge);
+        nbt.insert("pos", simdnbt::owned::NbtTag::IntArray(self.pos.to_vec()));
+        nbt.insert("decay_delay", self.decay_delay);
+        nbt.insert("update_delay", self.update_delay);
+        if !self.facings.is_empty() {
+            nbt.insert(
+                "facings",
+                simdnbt::owned::NbtList::Compound(self.facings.clone()),
+            );
+        }
+
+        nbt
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{error::SculkParseError, traits::FromCompoundNbt};
+use crate::{
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+};
 
 use super::calibrated_sculk_sensor::Listener;
 
@@ -34,3 +37,14 @@ impl FromCompoundNbt for SculkSensor {
         })
     }
 }
+
+impl ToCompoundNbt for SculkSensor {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("last_vibration_frequency", self.last_vibration_frequency);
+        nbt.insert("listener", self.listener.to_compound_nbt());
+
+        nbt
+    }
+}
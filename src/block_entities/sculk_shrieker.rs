@@ -1,4 +1,7 @@
-use crate::{error::SculkParseError, traits::FromCompoundNbt};
+use crate::{
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+};
 
 use super::calibrated_sculk_sensor::Listener;
 
@@ -23,3 +26,13 @@ impl FromCompoundNbt for SculkShrieker {
         Ok(SculkShrieker { listener })
     }
 }
+
+impl ToCompoundNbt for SculkShrieker {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("listener", self.listener.to_compound_nbt());
+
+        nbt
+    }
+}
@@ -1,4 +1,10 @@
-use crate::{color::Color, error::SculkParseError, traits::FromCompoundNbt, util::get_bool};
+use crate::{
+    color::Color,
+    components::text::{get_plain_or_text_components, plain_or_text_components_tag},
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+    util::get_bool,
+};
 use std::str::FromStr;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -69,28 +75,19 @@ impl FromCompoundNbt for SignText {
             .map(|s| Color::from_str(s.to_str().as_ref()).map_err(SculkParseError::InvalidField))
             .ok_or(SculkParseError::MissingField("color".into()))??;
 
-        let filtered_messages = if let Some(list) = nbt.list("filtered_messages") {
-            let mut filtered_messages: Vec<String> = vec![];
+        let filtered_messages = nbt
+            .get("filtered_messages")
+            .map(|messages| {
+                get_plain_or_text_components(&messages)
+                    .ok_or(SculkParseError::InvalidField("filtered_messages".into()))
+            })
+            .transpose()?;
 
-            for message in list.strings().into_iter() {
-                let str = (*message.first().unwrap()).to_string();
-                filtered_messages.push(str);
-            }
-
-            Some(filtered_messages)
-        } else {
-            None
-        };
-
-        let messages_list = nbt
-            .list("messages")
+        let messages = nbt
+            .get("messages")
             .ok_or(SculkParseError::MissingField("messages".into()))?;
-        let mut messages: Vec<String> = vec![];
-
-        for message in messages_list.strings().into_iter() {
-            let str = (*message.first().unwrap()).to_string();
-            messages.push(str);
-        }
+        let messages = get_plain_or_text_components(&messages)
+            .ok_or(SculkParseError::InvalidField("messages".into()))?;
 
         Ok(SignText {
             has_glowing_text,
@@ -100,3 +97,42 @@ impl FromCompoundNbt for SignText {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for Sign {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("is_waxed", self.is_waxed);
+        nbt.insert(
+            "front_text",
+            self.front_text.to_compound_nbt_with_version(data_version),
+        );
+        nbt.insert(
+            "back_text",
+            self.back_text.to_compound_nbt_with_version(data_version),
+        );
+
+        nbt
+    }
+}
+
+impl ToVersionedCompoundNbt for SignText {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("has_glowing_text", self.has_glowing_text);
+        nbt.insert("color", self.color.to_str());
+        if let Some(filtered_messages) = &self.filtered_messages {
+            nbt.insert(
+                "filtered_messages",
+                plain_or_text_components_tag(filtered_messages, data_version),
+            );
+        }
+        nbt.insert(
+            "messages",
+            plain_or_text_components_tag(&self.messages, data_version),
+        );
+
+        nbt
+    }
+}
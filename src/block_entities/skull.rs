@@ -1,7 +1,10 @@
 use crate::{
     error::SculkParseError,
-    traits::FromCompoundNbt,
-    util::{get_owned_optional_string, get_owned_string},
+    traits::{FromCompoundNbt, ToCompoundNbt, ToVersionedCompoundNbt},
+    util::{
+        get_optional_text, get_owned_optional_string, get_owned_string, set_optional_tag, set_tag,
+        write_compound_list, write_optional_text,
+    },
     uuid::Uuid,
 };
 
@@ -58,12 +61,12 @@ impl FromCompoundNbt for Skull {
     where
         Self: Sized,
     {
-        let custom_name = get_owned_optional_string(nbt, "custom_name");
+        let custom_name = get_optional_text(nbt, "custom_name");
         let note_block_sound = get_owned_optional_string(nbt, "note_block_sound");
 
         let profile = match SkullProfile::from_compound_nbt(nbt) {
             Ok(profile) => Some(profile),
-            Err(SculkParseError::InvalidField(_)) => None,
+            Err(e) if matches!(e.root_cause(), SculkParseError::InvalidField(_)) => None,
             Err(e) => return Err(e),
         };
 
@@ -120,20 +123,23 @@ impl FromCompoundNbt for Profile {
 
         let id = nbt.int_array("id").map(Uuid::from);
 
-        let properties = if let Some(props) = nbt.list("properties") {
-            let mut properties = vec![];
-
-            for prop in props
-                .compounds()
-                .ok_or(SculkParseError::InvalidField("properties".into()))?
-            {
-                let prop_item = Property::from_compound_nbt(&prop)?;
-                properties.push(prop_item);
+        let properties = match nbt.list("properties") {
+            // Profiles without properties are written with an empty list.
+            Some(props) if props.empty() => Some(vec![]),
+            Some(props) => {
+                let mut properties = vec![];
+
+                for prop in props
+                    .compounds()
+                    .ok_or(SculkParseError::InvalidField("properties".into()))?
+                {
+                    let prop_item = Property::from_compound_nbt(&prop)?;
+                    properties.push(prop_item);
+                }
+
+                Some(properties)
             }
-
-            Some(properties)
-        } else {
-            None
+            None => None,
         };
 
         Ok(Profile {
@@ -162,3 +168,70 @@ impl FromCompoundNbt for Property {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for Skull {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        write_optional_text(
+            &mut nbt,
+            "custom_name",
+            self.custom_name.as_ref(),
+            data_version,
+        );
+        set_optional_tag(
+            &mut nbt,
+            "note_block_sound",
+            self.note_block_sound.as_deref(),
+        );
+        set_optional_tag(
+            &mut nbt,
+            "profile",
+            self.profile.as_ref().map(SkullProfile::to_tag),
+        );
+
+        nbt
+    }
+}
+
+impl SkullProfile {
+    /// Writes the profile as a name or a compound, the inverse of [`SkullProfile::from_compound_nbt`].
+    pub fn to_tag(&self) -> simdnbt::owned::NbtTag {
+        match self {
+            SkullProfile::Name(name) => simdnbt::owned::NbtTag::String(name.as_str().into()),
+            SkullProfile::Profile(profile) => {
+                simdnbt::owned::NbtTag::Compound(profile.to_compound_nbt())
+            }
+        }
+    }
+}
+
+impl ToCompoundNbt for Profile {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_optional_tag(&mut nbt, "name", self.name.as_deref());
+        set_optional_tag(&mut nbt, "id", self.id.as_ref().map(Uuid::to_tag));
+        if let Some(properties) = &self.properties {
+            set_tag(
+                &mut nbt,
+                "properties",
+                write_compound_list(properties, Property::to_compound_nbt),
+            );
+        }
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for Property {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_tag(&mut nbt, "name", self.name.as_str());
+        set_tag(&mut nbt, "value", self.value.as_str());
+        set_optional_tag(&mut nbt, "signature", self.signature.as_deref());
+
+        nbt
+    }
+}
@@ -1,4 +1,9 @@
-use crate::{item::Item, traits::FromCompoundNbt, util::get_loot_table_data};
+use crate::{
+    item::Item,
+    resource_location::ResourceLocation,
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+    util::{get_loot_table_data, set_optional_tag, write_loot_table_data},
+};
 
 /// Both loot table tags are removed once the items have been generated.
 #[derive(Debug, Clone, PartialEq)]
@@ -7,7 +12,7 @@ pub struct SuspiciousBlock {
     /// Optional. Name of the loot table to use. If this is used in a chest-like container, the loot table generates its content when it is opened. Generating the items in the container removes both loot table tags ( LootTable and  LootTableSeed).
     ///
     /// `LootTable`
-    pub loot_table: Option<String>,
+    pub loot_table: Option<ResourceLocation>,
 
     /// Optional. Seed for generating the loot table. The default value works similarly to the seeds for worlds, where value of 0 or an omitted value causes the game to use a random seed.
     ///
@@ -25,7 +30,7 @@ impl FromCompoundNbt for SuspiciousBlock {
     where
         Self: Sized,
     {
-        let loot_table = get_loot_table_data(nbt);
+        let loot_table = get_loot_table_data(nbt)?;
 
         let item = if let Some(item) = nbt.compound("item") {
             Some(Item::from_compound_nbt(&item)?)
@@ -40,3 +45,20 @@ impl FromCompoundNbt for SuspiciousBlock {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for SuspiciousBlock {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        write_loot_table_data(&mut nbt, self.loot_table.as_ref(), self.loot_table_seed);
+        set_optional_tag(
+            &mut nbt,
+            "item",
+            self.item
+                .as_ref()
+                .map(|item| item.to_compound_nbt_with_version(data_version)),
+        );
+
+        nbt
+    }
+}
@@ -1,7 +1,9 @@
 use crate::{
     error::SculkParseError,
-    traits::FromCompoundNbt,
-    util::{get_owned_optional_string, get_owned_string},
+    traits::{FromCompoundNbt, ToCompoundNbt, ToVersionedCompoundNbt},
+    util::{
+        get_owned_optional_string, get_owned_string, set_optional_tag, set_tag, write_compound_list,
+    },
     uuid::Uuid,
 };
 
@@ -212,3 +214,116 @@ impl FromCompoundNbt for LootTable {
         Ok(LootTable { weight, data })
     }
 }
+
+impl ToVersionedCompoundNbt for TrailSpawner {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_tag(
+            &mut nbt,
+            "required_player_range",
+            self.required_player_range,
+        );
+        set_tag(
+            &mut nbt,
+            "target_cooldown_length",
+            self.target_cooldown_length,
+        );
+        set_optional_tag(
+            &mut nbt,
+            "normal_config",
+            self.normal_config
+                .as_ref()
+                .map(|config| config.to_compound_nbt_with_version(data_version)),
+        );
+        set_optional_tag(
+            &mut nbt,
+            "ominous_config",
+            self.ominous_config
+                .as_ref()
+                .map(|config| config.to_compound_nbt_with_version(data_version)),
+        );
+        set_tag(
+            &mut nbt,
+            "registered_players",
+            Uuid::to_nbt_list(&self.registered_players),
+        );
+        set_tag(
+            &mut nbt,
+            "current_mobs",
+            Uuid::to_nbt_list(&self.current_mobs),
+        );
+        set_tag(&mut nbt, "cooldown_ends_at", self.cooldown_ends_at);
+        set_tag(&mut nbt, "next_mob_spawns_at", self.next_mob_spawns_at);
+        set_tag(&mut nbt, "total_mobs_spawned", self.total_mobs_spawned);
+        set_optional_tag(
+            &mut nbt,
+            "spawn_data",
+            self.spawn_data
+                .as_ref()
+                .map(|spawn_data| spawn_data.to_compound_nbt_with_version(data_version)),
+        );
+        set_optional_tag(
+            &mut nbt,
+            "ejecting_loot_table",
+            self.ejecting_loot_table.as_deref(),
+        );
+
+        nbt
+    }
+}
+
+impl ToVersionedCompoundNbt for TrailSpawnerConfig {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_optional_tag(&mut nbt, "spawn_range", self.spawn_range);
+        set_optional_tag(&mut nbt, "total_mobs", self.total_mobs);
+        set_optional_tag(&mut nbt, "simultaneous_mobs", self.simultaneous_mobs);
+        set_optional_tag(
+            &mut nbt,
+            "total_mobs_added_per_player",
+            self.total_mobs_added_per_player,
+        );
+        set_optional_tag(
+            &mut nbt,
+            "simultaneous_mobs_added_per_player",
+            self.simultaneous_mobs_added_per_player,
+        );
+        set_optional_tag(&mut nbt, "ticks_between_spawn", self.ticks_between_spawn);
+        if let Some(spawn_potentials) = &self.spawn_potentials {
+            set_tag(
+                &mut nbt,
+                "spawn_potentials",
+                write_compound_list(spawn_potentials, |potential| {
+                    potential.to_compound_nbt_with_version(data_version)
+                }),
+            );
+        }
+        if let Some(loot_tables) = &self.loot_tables_to_eject {
+            set_tag(
+                &mut nbt,
+                "loot_tables_to_eject",
+                write_compound_list(loot_tables, LootTable::to_compound_nbt),
+            );
+        }
+        set_optional_tag(
+            &mut nbt,
+            "items_to_drop_when_ominous",
+            self.items_to_drop_when_ominous.as_deref(),
+        );
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for LootTable {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("weight", self.weight);
+        nbt.insert("data", self.data.as_str());
+
+        nbt
+    }
+}
@@ -51,6 +51,7 @@ pub enum BlockEntityVariant {
     TrialSpawner,
     Vault,
     Dummy,
+    Unknown,
 }
 
 impl BlockEntityKind {
@@ -104,6 +105,7 @@ impl BlockEntityKind {
             BlockEntityKind::TrialSpawner(_) => BlockEntityVariant::TrialSpawner,
             BlockEntityKind::Vault(_) => BlockEntityVariant::Vault,
             BlockEntityKind::Dummy => BlockEntityVariant::Dummy,
+            BlockEntityKind::Unknown(_) => BlockEntityVariant::Unknown,
         }
     }
 }
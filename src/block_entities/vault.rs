@@ -1,8 +1,11 @@
 use crate::{
     error::SculkParseError,
     item::{Item, ItemWithNoSlot},
-    traits::FromCompoundNbt,
-    util::{get_owned_optional_string, get_t_compound_vec},
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+    util::{
+        get_owned_optional_string, get_t_compound_vec, set_optional_tag, set_tag,
+        write_compound_list,
+    },
     uuid::Uuid,
 };
 
@@ -173,3 +176,103 @@ impl FromCompoundNbt for VaultSharedData {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for Vault {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert(
+            "config",
+            self.config.to_compound_nbt_with_version(data_version),
+        );
+        nbt.insert(
+            "server_data",
+            self.server_data.to_compound_nbt_with_version(data_version),
+        );
+        nbt.insert(
+            "shared_data",
+            self.shared_data.to_compound_nbt_with_version(data_version),
+        );
+
+        nbt
+    }
+}
+
+impl ToVersionedCompoundNbt for VaultConfig {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_optional_tag(&mut nbt, "loot_table", self.loot_table.as_deref());
+        set_optional_tag(
+            &mut nbt,
+            "override_loot_table_to_display",
+            self.override_loot_table_to_display.as_deref(),
+        );
+        set_optional_tag(&mut nbt, "activation_range", self.activation_range);
+        set_optional_tag(&mut nbt, "deactivation_range", self.deactivation_range);
+        set_tag(
+            &mut nbt,
+            "key_item",
+            self.key_item.to_compound_nbt_with_version(data_version),
+        );
+
+        nbt
+    }
+}
+
+impl ToVersionedCompoundNbt for VaultServerData {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_tag(
+            &mut nbt,
+            "rewarded_players",
+            Uuid::to_nbt_list(&self.rewarded_players),
+        );
+        set_optional_tag(
+            &mut nbt,
+            "state_updating_resumes_at",
+            self.state_updating_resumes_at,
+        );
+        set_tag(
+            &mut nbt,
+            "items_to_eject",
+            write_compound_list(&self.items_to_eject, |item| {
+                item.to_compound_nbt_with_version(data_version)
+            }),
+        );
+        set_optional_tag(
+            &mut nbt,
+            "total_ejections_needed",
+            self.total_ejections_needed,
+        );
+
+        nbt
+    }
+}
+
+impl ToVersionedCompoundNbt for VaultSharedData {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_optional_tag(
+            &mut nbt,
+            "display_item",
+            self.display_item
+                .as_ref()
+                .map(|item| item.to_compound_nbt_with_version(data_version)),
+        );
+        set_tag(
+            &mut nbt,
+            "connected_players",
+            Uuid::to_nbt_list(&self.connected_players),
+        );
+        set_optional_tag(
+            &mut nbt,
+            "connected_particles_range",
+            self.connected_particles_range,
+        );
+
+        nbt
+    }
+}
@@ -1,11 +1,17 @@
 use crate::{
     block_entities::{variant::BlockEntityVariant, BlockEntityKind},
     components::Components,
-    error::SculkParseError,
-    traits::{FromCompoundNbt, FromNbt},
-    util::{get_bool, get_optional_components, get_owned_string},
+    error::{ParseMode, ParseWarning, SculkParseError, Substitute},
+    item::check_item_fields,
+    resource_location::{LenientIdScope, ResourceLocation},
+    traits::{FromBorrowedNbt, FromCompoundNbt, FromNbt, ToVersionedCompoundNbt},
+    util::{
+        check_known_fields, get_bool, get_extra, get_optional_components, get_resource_location,
+        merge_compound, write_root_compound,
+    },
+    version::{current_data_version, DataVersionScope},
 };
-use simdnbt::borrow::BaseNbt;
+use simdnbt::borrow::{BaseNbt, NbtCompound};
 use std::io::Cursor;
 
 /// The base fields of a block entity.
@@ -13,7 +19,7 @@ use std::io::Cursor;
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BlockEntityBase {
     /// ID of block entity.
-    pub id: String,
+    pub id: ResourceLocation,
 
     /// If true, this is an invalid block entity, and this block is not immediately placed when a loaded chunk is loaded. If false, this is a normal block entity that can be immediately placed.
     ///
@@ -31,8 +37,17 @@ pub struct BlockEntityBase {
 
     /// Optional map of components.
     pub components: Option<Components>,
+
+    /// Fields that neither the base nor the [`BlockEntityKind`] model, so block entities can be written back without losing data.  
+    /// When parsed on its own, every field besides the base fields.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extra: simdnbt::owned::NbtCompound,
 }
 
+/// Fields of [`BlockEntityBase`], the rest is kept in [`BlockEntityBase::extra`].
+pub(crate) const BLOCK_ENTITY_BASE_FIELDS: &[&str] =
+    &["id", "keepPacked", "x", "y", "z", "components"];
+
 /// The base fields of a block entity.  
 /// This is used in components that excludes x, y, z coordinates.  
 ///
@@ -41,7 +56,7 @@ pub struct BlockEntityBase {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NoCoordinatesBlockEntityBase {
     /// ID of block entity.
-    pub id: String,
+    pub id: ResourceLocation,
 
     /// If true, this is an invalid block entity, and this block is not immediately placed when a loaded chunk is loaded. If false, this is a normal block entity that can be immediately placed.
     ///
@@ -50,8 +65,15 @@ pub struct NoCoordinatesBlockEntityBase {
 
     /// Optional map of components.
     pub components: Option<Components>,
+
+    /// Fields that neither the base nor the [`BlockEntityKind`] model, see [`BlockEntityBase::extra`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extra: simdnbt::owned::NbtCompound,
 }
 
+/// Fields of [`NoCoordinatesBlockEntityBase`], the rest is kept in [`NoCoordinatesBlockEntityBase::extra`].
+pub(crate) const NO_COORDINATES_BASE_FIELDS: &[&str] = &["id", "keepPacked", "components"];
+
 /// The base fields of a block entity.  
 /// This is used for `lazy` block entities.  
 /// So it does not contain the `components` field.  
@@ -59,7 +81,7 @@ pub struct NoCoordinatesBlockEntityBase {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LazyBlockEntityBase {
     /// ID of block entity.
-    pub id: String,
+    pub id: ResourceLocation,
 
     /// If true, this is an invalid block entity, and this block is not immediately placed when a loaded chunk is loaded. If false, this is a normal block entity that can be immediately placed.
     ///
@@ -101,15 +123,18 @@ pub struct NoCoordinatesBlockEntity {
 
 /// Represents a `lazy` byte variant.  
 /// When directly going from bytes > lazy block entity, we can borrow.  
-/// But when we go through chunk data its trickier, i dont even know if its possible to borrow there.  
-/// But for now we can own the bytes, sacrificing some memory.
-#[derive(Debug, Clone, PartialEq)]
+/// When going through chunk data with [`FromBorrowedNbt`], we borrow the compound inside the chunk's parsed nbt.  
+/// Only [`FromCompoundNbt`] has to own the bytes, sacrificing some memory.
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LazyByteVariant<'a> {
     /// Borrowed bytes.
     Borrowed(&'a [u8]),
     /// Owned bytes.
     Owned(Vec<u8>),
+    /// Borrowed compound of an already parsed nbt, like a chunk.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Compound(NbtCompound<'a, 'a>),
 }
 
 /// Represents a `lazy` block entity.  
@@ -127,6 +152,10 @@ pub struct LazyBlockEntity<'a> {
     /// Common fields of a block entity.
     pub base: LazyBlockEntityBase,
 
+    /// The data version the kind and components are read with.  
+    /// Defaults to the current data version when it was created, see [`crate::version::DataVersionScope`].
+    pub data_version: i32,
+
     /// The bytes that was used to parse the block entity.
     // This is a bit ugly but i found no other way with `borrow::Nbt` or `borrow::BaseNbt` to work
     #[cfg_attr(feature = "serde", serde(borrow))]
@@ -140,22 +169,33 @@ impl FromCompoundNbt for LazyBlockEntity<'_> {
     {
         let base = LazyBlockEntityBase::from_compound_nbt(nbt)?;
 
-        let mut buf: Vec<u8> = Vec::new();
-        nbt.to_owned().write(&mut buf);
+        // Needs the root header, or it can't be read back.
+        let buf = write_root_compound(&nbt.to_owned());
 
         Ok(Self {
             base,
+            data_version: current_data_version(),
             nbt_bytes: LazyByteVariant::Owned(buf),
         })
     }
 }
 
+impl<'a> FromBorrowedNbt<'a> for LazyBlockEntity<'a> {
+    fn from_borrowed_nbt(nbt: &NbtCompound<'a, 'a>) -> Result<Self, SculkParseError> {
+        Ok(Self {
+            base: LazyBlockEntityBase::from_compound_nbt(nbt)?,
+            data_version: current_data_version(),
+            nbt_bytes: LazyByteVariant::Compound(*nbt),
+        })
+    }
+}
+
 impl FromCompoundNbt for BlockEntityBase {
     fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
     where
         Self: Sized,
     {
-        let id = get_owned_string(nbt, "id")?;
+        let id = get_resource_location(nbt, "id")?;
         let keep_packed = get_bool(nbt, "keepPacked");
 
         let x = nbt
@@ -177,6 +217,7 @@ impl FromCompoundNbt for BlockEntityBase {
             y,
             z,
             components,
+            extra: get_extra(nbt, BLOCK_ENTITY_BASE_FIELDS),
         })
     }
 }
@@ -186,7 +227,7 @@ impl FromCompoundNbt for NoCoordinatesBlockEntityBase {
     where
         Self: Sized,
     {
-        let id = get_owned_string(nbt, "id")?;
+        let id = get_resource_location(nbt, "id")?;
         let keep_packed = get_bool(nbt, "keepPacked");
 
         let components = get_optional_components(nbt)?;
@@ -195,6 +236,7 @@ impl FromCompoundNbt for NoCoordinatesBlockEntityBase {
             id,
             keep_packed,
             components,
+            extra: get_extra(nbt, NO_COORDINATES_BASE_FIELDS),
         })
     }
 }
@@ -204,7 +246,7 @@ impl FromCompoundNbt for LazyBlockEntityBase {
     where
         Self: Sized,
     {
-        let id = get_owned_string(nbt, "id")?;
+        let id = get_resource_location(nbt, "id")?;
         let keep_packed = get_bool(nbt, "keepPacked");
 
         let x = nbt
@@ -238,8 +280,9 @@ impl FromNbt for BlockEntity {
         };
         let nbt = base_nbt.as_compound();
 
-        let base = BlockEntityBase::from_compound_nbt(&nbt)?;
+        let mut base = BlockEntityBase::from_compound_nbt(&nbt)?;
         let kind = BlockEntityKind::from_compound_nbt(&nbt)?;
+        base.extra = kind_extra(&nbt, &kind, BLOCK_ENTITY_BASE_FIELDS);
 
         Ok(Self { base, kind })
     }
@@ -250,10 +293,18 @@ impl FromCompoundNbt for BlockEntity {
     where
         Self: Sized,
     {
-        let base = BlockEntityBase::from_compound_nbt(nbt)?;
-        let kind = BlockEntityKind::from_compound_nbt(nbt)?;
+        let parse = || {
+            let mut base = BlockEntityBase::from_compound_nbt(nbt)?;
+            let kind = BlockEntityKind::from_compound_nbt(nbt)?;
+            base.extra = kind_extra(nbt, &kind, BLOCK_ENTITY_BASE_FIELDS);
 
-        Ok(Self { base, kind })
+            Ok(Self { base, kind })
+        };
+
+        parse().map_err(|e: SculkParseError| match nbt.string("id") {
+            Some(id) => e.with_id(id.to_str()),
+            None => e,
+        })
     }
 }
 
@@ -262,13 +313,28 @@ impl FromCompoundNbt for NoCoordinatesBlockEntity {
     where
         Self: Sized,
     {
-        let base = NoCoordinatesBlockEntityBase::from_compound_nbt(nbt)?;
+        let mut base = NoCoordinatesBlockEntityBase::from_compound_nbt(nbt)?;
         let kind = BlockEntityKind::from_compound_nbt(nbt)?;
+        base.extra = kind_extra(nbt, &kind, NO_COORDINATES_BASE_FIELDS);
 
         Ok(Self { base, kind })
     }
 }
 
+impl PartialEq for LazyByteVariant<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (LazyByteVariant::Borrowed(a), LazyByteVariant::Borrowed(b)) => a == b,
+            (LazyByteVariant::Owned(a), LazyByteVariant::Owned(b)) => a == b,
+            // Borrowed compounds can only be compared as owned nbt.
+            (LazyByteVariant::Compound(a), LazyByteVariant::Compound(b)) => {
+                a.to_owned() == b.to_owned()
+            }
+            _ => false,
+        }
+    }
+}
+
 // It got its own silly implementation :3
 impl<'a> LazyBlockEntity<'a> {
     fn from_nbt(nbt: simdnbt::borrow::Nbt, bytes: &'a [u8]) -> Result<Self, SculkParseError>
@@ -284,12 +350,66 @@ impl<'a> LazyBlockEntity<'a> {
 
         Ok(Self {
             base: LazyBlockEntityBase::from_compound_nbt(&compound_nbt)?,
+            data_version: current_data_version(),
             nbt_bytes: LazyByteVariant::Borrowed(bytes),
         })
     }
 }
 
 impl BlockEntity {
+    /// Parses a block entity with the given [`ParseMode`].  
+    /// Lenient keeps the raw nbt as [`BlockEntityKind::Unknown`] if the specific data fails,
+    /// strict fails on unknown block entity, item and component keys.
+    pub fn from_compound_nbt_with_mode(
+        nbt: &simdnbt::borrow::NbtCompound,
+        mode: ParseMode,
+        warnings: &mut Vec<ParseWarning>,
+    ) -> Result<Self, SculkParseError> {
+        if mode == ParseMode::Default {
+            return BlockEntity::from_compound_nbt(nbt);
+        }
+
+        let start = warnings.len();
+        let with_id = |e: SculkParseError| match nbt.string("id") {
+            Some(id) => e.with_id(id.to_str()),
+            None => e,
+        };
+
+        let ids = LenientIdScope::enter(mode);
+        let mut parse = || {
+            let base = LazyBlockEntityBase::from_compound_nbt(nbt)?;
+            let components = Components::from_compound_nbt_with_mode(nbt, mode, warnings)?;
+            let kind = mode.recover(
+                BlockEntityKind::from_compound_nbt(nbt),
+                warnings,
+                Substitute::Raw,
+                || BlockEntityKind::Unknown(nbt.to_owned()),
+            )?;
+            if mode == ParseMode::Strict {
+                check_block_entity_fields(nbt, &kind)?;
+            }
+
+            Ok(BlockEntity {
+                base: BlockEntityBase {
+                    id: base.id,
+                    keep_packed: base.keep_packed,
+                    x: base.x,
+                    y: base.y,
+                    z: base.z,
+                    components,
+                    extra: kind_extra(nbt, &kind, BLOCK_ENTITY_BASE_FIELDS),
+                },
+                kind,
+            })
+        };
+
+        let result = parse().map_err(with_id);
+        ids.finish(warnings);
+        ParseWarning::add_context(&mut warnings[start..], with_id);
+
+        result
+    }
+
     /// Get the variant of the block entity.
     pub fn variant(&self) -> BlockEntityVariant {
         self.kind.variant()
@@ -307,60 +427,153 @@ impl BlockEntity {
     }
 }
 
+/// The fields of a block entity that neither the base nor the kind model.  
+/// [`BlockEntityKind::Unknown`] already keeps the whole compound.
+pub(crate) fn kind_extra(
+    nbt: &NbtCompound,
+    kind: &BlockEntityKind,
+    base_fields: &[&str],
+) -> simdnbt::owned::NbtCompound {
+    match kind {
+        BlockEntityKind::Unknown(_) => simdnbt::owned::NbtCompound::new(),
+        _ => get_extra(nbt, &[base_fields, kind.fields()].concat()),
+    }
+}
+
+/// Writes the base fields over the kind and [`BlockEntityBase::extra`].  
+/// A raw [`BlockEntityKind::Unknown`] has the base fields too, they're replaced in place.
+fn write_block_entity(
+    extra: &simdnbt::owned::NbtCompound,
+    base: simdnbt::owned::NbtCompound,
+    kind: &BlockEntityKind,
+    data_version: i32,
+) -> simdnbt::owned::NbtCompound {
+    let mut nbt = extra.clone();
+    merge_compound(&mut nbt, kind.to_compound_nbt_with_version(data_version));
+    merge_compound(&mut nbt, base);
+
+    nbt
+}
+
+/// Writes `id`, `keepPacked` and `components`, which every block entity has.
+fn write_base_fields(
+    id: &ResourceLocation,
+    keep_packed: bool,
+    components: Option<&Components>,
+    data_version: i32,
+) -> simdnbt::owned::NbtCompound {
+    let mut nbt = simdnbt::owned::NbtCompound::new();
+
+    nbt.insert("id", id.to_string());
+    if keep_packed {
+        nbt.insert("keepPacked", true);
+    }
+    if let Some(components) = components {
+        nbt.insert(
+            "components",
+            components.to_compound_nbt_with_version(data_version),
+        );
+    }
+
+    nbt
+}
+
+impl ToVersionedCompoundNbt for BlockEntity {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut base = write_base_fields(
+            &self.base.id,
+            self.base.keep_packed,
+            self.base.components.as_ref(),
+            data_version,
+        );
+        base.insert("x", self.base.x);
+        base.insert("y", self.base.y);
+        base.insert("z", self.base.z);
+
+        write_block_entity(&self.base.extra, base, &self.kind, data_version)
+    }
+}
+
+impl ToVersionedCompoundNbt for NoCoordinatesBlockEntity {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let base = write_base_fields(
+            &self.base.id,
+            self.base.keep_packed,
+            self.base.components.as_ref(),
+            data_version,
+        );
+
+        write_block_entity(&self.base.extra, base, &self.kind, data_version)
+    }
+}
+
+/// Fails on a key the block entity's kind doesn't read, or on an unknown key of the items it holds.
+fn check_block_entity_fields(
+    nbt: &NbtCompound,
+    kind: &BlockEntityKind,
+) -> Result<(), SculkParseError> {
+    check_known_fields(
+        nbt,
+        &[
+            BLOCK_ENTITY_BASE_FIELDS,
+            kind.fields(),
+            kind.undecoded_fields(),
+        ]
+        .concat(),
+    )?;
+
+    if let Some(items) = nbt.list("Items").and_then(|list| list.compounds()) {
+        for (index, item) in items.into_iter().enumerate() {
+            check_item_fields(&item).map_err(|e| e.at_index("Items", index))?;
+        }
+    }
+    for key in ["item", "RecordItem", "Book"] {
+        if let Some(item) = nbt.compound(key) {
+            check_item_fields(&item).map_err(|e| e.at_field(key))?;
+        }
+    }
+
+    Ok(())
+}
+
 impl<'a> LazyBlockEntity<'a> {
     /// Gets the specific data for the block entity.
     pub fn kind(&self) -> Result<BlockEntityKind, SculkParseError> {
-        let bytes = match &self.nbt_bytes {
-            LazyByteVariant::Borrowed(bytes) => bytes,
-            LazyByteVariant::Owned(bytes) => bytes.as_slice(),
-        };
-
-        let nbt = match simdnbt::borrow::read(&mut Cursor::new(bytes)) {
-            Ok(nbt) => nbt,
-            Err(err) => return Err(SculkParseError::NbtError(err)),
-        };
-        let base_nbt = match nbt.is_none() {
-            true => return Err(SculkParseError::NoNbt),
-            false => nbt.unwrap(),
-        };
-        let compound_nbt = base_nbt.as_compound();
-
-        BlockEntityKind::from_compound_nbt(&compound_nbt)
+        self.parse(BlockEntityKind::from_compound_nbt)
     }
 
     /// Get the components of the block entity.
     pub fn get_components(&self) -> Result<Option<Components>, SculkParseError> {
-        let bytes = match &self.nbt_bytes {
-            LazyByteVariant::Borrowed(bytes) => bytes,
-            LazyByteVariant::Owned(bytes) => bytes.as_slice(),
-        };
-
-        let nbt = match simdnbt::borrow::read(&mut Cursor::new(bytes)) {
-            Ok(nbt) => nbt,
-            Err(err) => return Err(SculkParseError::NbtError(err)),
-        };
-        let base_nbt = match nbt.is_none() {
-            true => return Err(SculkParseError::NoNbt),
-            false => nbt.unwrap(),
-        };
-        let compound_nbt = base_nbt.as_compound();
-
-        get_optional_components(&compound_nbt)
+        self.parse(get_optional_components)
     }
 
     /// Converts a [`LazyBlockEntity`] to an owned [`BlockEntity`].
     pub fn to_owned(&self) -> Result<BlockEntity, SculkParseError> {
+        self.parse(BlockEntity::from_compound_nbt)
+    }
+
+    /// Runs the conversion on the compound, reading the bytes first if needed.
+    fn parse<T>(
+        &self,
+        nbt_conversion: fn(nbt: &NbtCompound) -> Result<T, SculkParseError>,
+    ) -> Result<T, SculkParseError> {
+        let _data_version = DataVersionScope::enter(self.data_version);
         let bytes = match &self.nbt_bytes {
             LazyByteVariant::Borrowed(bytes) => bytes,
             LazyByteVariant::Owned(bytes) => bytes.as_slice(),
+            LazyByteVariant::Compound(nbt) => return nbt_conversion(nbt),
         };
 
         let nbt = match simdnbt::borrow::read(&mut Cursor::new(bytes)) {
             Ok(nbt) => nbt,
             Err(err) => return Err(SculkParseError::NbtError(err)),
         };
+        let base_nbt = match nbt.is_none() {
+            true => return Err(SculkParseError::NoNbt),
+            false => nbt.unwrap(),
+        };
 
-        BlockEntity::from_nbt(nbt)
+        nbt_conversion(&base_nbt.as_compound())
     }
 
     /// Converts from bytes to a block entity.
@@ -387,3 +600,73 @@ fn test() {
     let _ = BlockEntity::from_bytes(bytes.as_slice()).unwrap();
     println!("LazyBlockEntity: {:?}", instant.elapsed());
 }
+
+#[cfg(test)]
+#[test]
+fn lazy_from_compound() {
+    let bytes = include_bytes!("../test_data/chest.nbt");
+    let nbt = simdnbt::borrow::read(&mut Cursor::new(bytes.as_slice()))
+        .unwrap()
+        .unwrap();
+    let owned = BlockEntity::from_compound_nbt(&nbt.as_compound()).unwrap();
+
+    let lazy = LazyBlockEntity::from_compound_nbt(&nbt.as_compound()).unwrap();
+    assert_eq!(lazy.to_owned().unwrap(), owned);
+
+    let lazy = LazyBlockEntity::from_borrowed_nbt(&nbt.as_compound()).unwrap();
+    assert_eq!(lazy.kind().unwrap(), owned.kind);
+}
+
+#[cfg(test)]
+#[test]
+fn round_trip() {
+    use crate::util::MC_DATA_VERSION;
+
+    for bytes in [
+        include_bytes!("../test_data/chest.nbt").as_slice(),
+        include_bytes!("../test_data/chest_tool.nbt").as_slice(),
+        include_bytes!("../test_data/chest_banner.nbt").as_slice(),
+    ] {
+        let block_entity = BlockEntity::from_bytes(bytes).unwrap();
+        // The items are parsed into the kind, so they aren't copied into extra.
+        assert!(!block_entity.base.extra.contains("Items"));
+
+        let written = block_entity.to_compound_nbt_with_version(MC_DATA_VERSION);
+        let bytes = write_root_compound(&written);
+        assert_eq!(BlockEntity::from_bytes(&bytes).unwrap(), block_entity);
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn lazy_keeps_data_version() {
+    use crate::{
+        components::Component,
+        version::{DataVersionScope, V1_21_5},
+    };
+    use simdnbt::owned;
+
+    let mut name = owned::NbtCompound::new();
+    name.insert("text", "Name");
+    let mut components = owned::NbtCompound::new();
+    components.insert("minecraft:custom_name", owned::NbtTag::Compound(name));
+    let mut nbt = owned::NbtCompound::new();
+    nbt.insert("id", "minecraft:chest");
+    nbt.insert("x", 0);
+    nbt.insert("y", 64);
+    nbt.insert("z", 0);
+    nbt.insert("components", owned::NbtTag::Compound(components));
+
+    let bytes = write_root_compound(&nbt);
+    let lazy = {
+        let _data_version = DataVersionScope::enter(V1_21_5);
+        LazyBlockEntity::from_bytes(bytes.as_slice()).unwrap()
+    };
+    assert_eq!(lazy.data_version, V1_21_5);
+
+    let components = lazy.get_components().unwrap().unwrap();
+    assert_eq!(
+        components.get("minecraft:custom_name"),
+        Some(&Component::CustomName(r#"{"text":"Name"}"#.into()))
+    );
+}
@@ -0,0 +1,632 @@
+//! Zero-copy variants of the core types.
+//! Strings are `Cow<'a, str>` borrowed from the Nbt buffer, and only allocate if they aren't valid utf-8 as is.
+//! Nested data like block entity kinds and components is kept as borrowed Nbt and only parsed when asked for.
+//!
+//! Parse them with [`FromBorrowedNbt`] on the compound of a `simdnbt::borrow::read`,
+//! and call `.into_owned()` to get the regular types.
+
+use crate::{
+    block_entities::BlockEntityKind,
+    block_entity::{kind_extra, BlockEntity, BlockEntityBase, BLOCK_ENTITY_BASE_FIELDS},
+    chunk::{
+        section::{Biomes, BlockStates, Palette, PaletteNoProps},
+        tile_tick::TileTick,
+        Chunk,
+    },
+    components::{component_context, parse_component, Component, Components},
+    error::SculkParseError,
+    item::{Item, ITEM_FIELDS},
+    kv::KVPair,
+    traits::{FromBorrowedNbt, FromCompoundNbt, FromVersionedCompoundNbt},
+    util::{get_bool, get_cow_string, get_extra},
+    version::{current_data_version, DataVersionScope},
+};
+use simdnbt::borrow::NbtCompound;
+use std::borrow::Cow;
+
+/// Borrowed variant of [`Palette`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BorrowedPalette<'a> {
+    /// Block resource location.
+    /// `Name`
+    pub name: Cow<'a, str>,
+
+    /// Block state properties, in the order they are stored.
+    /// `Properties`
+    pub properties: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+}
+
+/// Borrowed variant of [`TileTick`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BorrowedTileTick<'a> {
+    /// The ID of the block; used to activate the correct block update procedure.
+    pub i: Cow<'a, str>,
+
+    /// If multiple tile ticks are scheduled for the same tick, tile ticks with lower p are processed first.
+    pub p: i32,
+
+    /// The number of ticks until processing should occur. May be negative when processing is overdue.
+    pub t: i32,
+
+    /// X position
+    pub x: i32,
+    /// Y position
+    pub y: i32,
+    /// Z position
+    pub z: i32,
+}
+
+/// Borrowed variant of [`Components`].
+/// Keys are borrowed and each component is only parsed when it's accessed.
+#[derive(Debug, Clone, Copy)]
+pub struct BorrowedComponents<'a> {
+    /// The `components` compound.
+    components: NbtCompound<'a, 'a>,
+
+    /// The compound holding `components`, some components read from it.
+    parent: NbtCompound<'a, 'a>,
+
+    /// The data version the components are read with.
+    data_version: i32,
+}
+
+/// Borrowed variant of [`Item`].
+#[derive(Debug, Clone)]
+pub struct BorrowedItem<'a> {
+    /// The inventory slot the item is in.
+    /// `Slot`
+    pub slot: i8,
+
+    /// The resource location of the item.
+    pub id: Cow<'a, str>,
+
+    /// Number of items stacked in this inventory slot.
+    /// `count`
+    pub count: i32,
+
+    /// Optional map of data components.
+    pub components: Option<BorrowedComponents<'a>>,
+
+    nbt: NbtCompound<'a, 'a>,
+}
+
+/// Borrowed variant of [`BlockEntityBase`].
+#[derive(Debug, Clone)]
+pub struct BorrowedBlockEntityBase<'a> {
+    /// ID of block entity.
+    pub id: Cow<'a, str>,
+
+    /// `keepPacked`
+    pub keep_packed: bool,
+
+    /// X coordinate of the block entity.
+    pub x: i32,
+    /// Y coordinate of the block entity.
+    pub y: i32,
+    /// Z coordinate of the block entity.
+    pub z: i32,
+
+    /// Optional map of components.
+    pub components: Option<BorrowedComponents<'a>>,
+
+    nbt: NbtCompound<'a, 'a>,
+}
+
+/// Borrowed variant of [`BlockEntity`].
+/// The [`BlockEntityKind`] is only parsed when calling `.kind()`.
+#[derive(Debug, Clone)]
+pub struct BorrowedBlockEntity<'a> {
+    /// Common fields of a block entity.
+    pub base: BorrowedBlockEntityBase<'a>,
+
+    nbt: NbtCompound<'a, 'a>,
+
+    /// The data version the kind is read with.
+    data_version: i32,
+}
+
+/// Borrowed variant of [`BlockStates`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BorrowedBlockStates<'a> {
+    /// Set of different block states used in this particular section.
+    pub palette: Vec<BorrowedPalette<'a>>,
+
+    /// A packed array of 4096 indices pointing to the palette.
+    pub data: Option<Vec<i64>>,
+}
+
+/// Borrowed variant of [`Biomes`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BorrowedBiomes<'a> {
+    /// Set of different biomes used in this particular section.
+    pub palette: Vec<Cow<'a, str>>,
+
+    /// A packed array of 64 indices pointing to the palette.
+    pub data: Option<Vec<i64>>,
+}
+
+/// Borrowed variant of a chunk section, only holding its blocks and biomes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BorrowedChunkSection<'a> {
+    /// The Y position of this section.
+    /// `Y`
+    pub y: i8,
+
+    pub block_states: Option<BorrowedBlockStates<'a>>,
+
+    pub biomes: Option<BorrowedBiomes<'a>>,
+}
+
+/// Borrowed variant of [`Chunk`], holding the fields region scanners usually need.
+/// `.into_owned()` parses the full [`Chunk`] from the same Nbt.
+#[derive(Debug, Clone)]
+pub struct BorrowedChunk<'a> {
+    /// `DataVersion`
+    pub data_version: i32,
+
+    /// `xPos`
+    pub x_pos: i32,
+    /// `zPos`
+    pub z_pos: i32,
+    /// `yPos`
+    pub y_pos: i32,
+
+    /// `Status`
+    pub status: Cow<'a, str>,
+
+    /// `LastUpdate`
+    pub last_update: i64,
+
+    /// `InhabitedTime`
+    pub inhabited_time: i64,
+
+    /// `sections`
+    pub sections: Vec<BorrowedChunkSection<'a>>,
+
+    /// `block_entities`
+    pub block_entities: Vec<BorrowedBlockEntity<'a>>,
+
+    /// `block_ticks`
+    pub block_ticks: Vec<BorrowedTileTick<'a>>,
+
+    /// `fluid_ticks`
+    pub fluid_ticks: Vec<BorrowedTileTick<'a>>,
+
+    nbt: NbtCompound<'a, 'a>,
+}
+
+impl<'a> FromBorrowedNbt<'a> for BorrowedPalette<'a> {
+    fn from_borrowed_nbt(nbt: &NbtCompound<'a, 'a>) -> Result<Self, SculkParseError> {
+        let name = get_cow_string(nbt, "Name")?;
+
+        let properties = match nbt.compound("Properties") {
+            Some(properties) => properties
+                .iter()
+                .map(|(key, value)| {
+                    let value = value
+                        .string()
+                        .ok_or(SculkParseError::InvalidField("Properties".into()))?;
+                    Ok((key.to_str(), value.to_str()))
+                })
+                .collect::<Result<Vec<_>, SculkParseError>>()?,
+            None => vec![],
+        };
+
+        Ok(BorrowedPalette { name, properties })
+    }
+}
+
+impl<'a> FromBorrowedNbt<'a> for BorrowedTileTick<'a> {
+    fn from_borrowed_nbt(nbt: &NbtCompound<'a, 'a>) -> Result<Self, SculkParseError> {
+        let get_int = |key: &'static str| {
+            nbt.int(key)
+                .ok_or(SculkParseError::MissingField(key.into()))
+        };
+
+        Ok(BorrowedTileTick {
+            i: get_cow_string(nbt, "i")?,
+            p: get_int("p")?,
+            t: get_int("t")?,
+            x: get_int("x")?,
+            y: get_int("y")?,
+            z: get_int("z")?,
+        })
+    }
+}
+
+impl<'a> FromBorrowedNbt<'a> for BorrowedItem<'a> {
+    fn from_borrowed_nbt(nbt: &NbtCompound<'a, 'a>) -> Result<Self, SculkParseError> {
+        let slot = nbt
+            .byte("Slot")
+            .ok_or(SculkParseError::MissingField("Slot".into()))?;
+        let id = get_cow_string(nbt, "id")?;
+        let count = nbt
+            .int("count")
+            .unwrap_or_else(|| nbt.byte("Count").unwrap_or(1) as i32);
+
+        Ok(BorrowedItem {
+            slot,
+            id,
+            count,
+            components: BorrowedComponents::from_parent(nbt),
+            nbt: *nbt,
+        })
+    }
+}
+
+impl<'a> FromBorrowedNbt<'a> for BorrowedBlockEntityBase<'a> {
+    fn from_borrowed_nbt(nbt: &NbtCompound<'a, 'a>) -> Result<Self, SculkParseError> {
+        let get_int = |key: &'static str| {
+            nbt.int(key)
+                .ok_or(SculkParseError::MissingField(key.into()))
+        };
+
+        Ok(BorrowedBlockEntityBase {
+            id: get_cow_string(nbt, "id")?,
+            keep_packed: get_bool(nbt, "keepPacked"),
+            x: get_int("x")?,
+            y: get_int("y")?,
+            z: get_int("z")?,
+            components: BorrowedComponents::from_parent(nbt),
+            nbt: *nbt,
+        })
+    }
+}
+
+impl<'a> FromBorrowedNbt<'a> for BorrowedBlockEntity<'a> {
+    fn from_borrowed_nbt(nbt: &NbtCompound<'a, 'a>) -> Result<Self, SculkParseError> {
+        Ok(BorrowedBlockEntity {
+            base: BorrowedBlockEntityBase::from_borrowed_nbt(nbt)?,
+            nbt: *nbt,
+            data_version: current_data_version(),
+        })
+    }
+}
+
+impl<'a> FromBorrowedNbt<'a> for BorrowedBlockStates<'a> {
+    fn from_borrowed_nbt(nbt: &NbtCompound<'a, 'a>) -> Result<Self, SculkParseError> {
+        Ok(BorrowedBlockStates {
+            palette: get_borrowed_vec(nbt, "palette")?,
+            data: nbt.long_array("data"),
+        })
+    }
+}
+
+impl<'a> FromBorrowedNbt<'a> for BorrowedBiomes<'a> {
+    fn from_borrowed_nbt(nbt: &NbtCompound<'a, 'a>) -> Result<Self, SculkParseError> {
+        let palette = match nbt.list("palette") {
+            Some(list) => list
+                .strings()
+                .ok_or(SculkParseError::InvalidField("palette".into()))?
+                .iter()
+                .map(|s| s.to_str())
+                .collect(),
+            None => vec![],
+        };
+
+        Ok(BorrowedBiomes {
+            palette,
+            data: nbt.long_array("data"),
+        })
+    }
+}
+
+impl<'a> FromBorrowedNbt<'a> for BorrowedChunkSection<'a> {
+    fn from_borrowed_nbt(nbt: &NbtCompound<'a, 'a>) -> Result<Self, SculkParseError> {
+        let y = nbt
+            .byte("Y")
+            .ok_or(SculkParseError::MissingField("Y".into()))?;
+
+        let block_states = match nbt.compound("block_states") {
+            Some(nbt) => Some(BorrowedBlockStates::from_borrowed_nbt(&nbt)?),
+            None => None,
+        };
+        let biomes = match nbt.compound("biomes") {
+            Some(nbt) => Some(BorrowedBiomes::from_borrowed_nbt(&nbt)?),
+            None => None,
+        };
+
+        Ok(BorrowedChunkSection {
+            y,
+            block_states,
+            biomes,
+        })
+    }
+}
+
+impl<'a> FromBorrowedNbt<'a> for BorrowedChunk<'a> {
+    fn from_borrowed_nbt(nbt: &NbtCompound<'a, 'a>) -> Result<Self, SculkParseError> {
+        let get_int = |key: &'static str| {
+            nbt.int(key)
+                .ok_or(SculkParseError::MissingField(key.into()))
+        };
+        let get_long = |key: &'static str| {
+            nbt.long(key)
+                .ok_or(SculkParseError::MissingField(key.into()))
+        };
+
+        let data_version = get_int("DataVersion")?;
+        // The block entities keep the chunk's data version for when they're parsed later.
+        let _data_version = DataVersionScope::enter(data_version);
+
+        Ok(BorrowedChunk {
+            data_version,
+            x_pos: get_int("xPos")?,
+            z_pos: get_int("zPos")?,
+            y_pos: get_int("yPos")?,
+            status: get_cow_string(nbt, "Status")?,
+            last_update: get_long("LastUpdate")?,
+            inhabited_time: get_long("InhabitedTime")?,
+            sections: get_borrowed_vec(nbt, "sections")?,
+            block_entities: get_borrowed_vec(nbt, "block_entities")?,
+            block_ticks: get_borrowed_vec(nbt, "block_ticks")?,
+            fluid_ticks: get_borrowed_vec(nbt, "fluid_ticks")?,
+            nbt: *nbt,
+        })
+    }
+}
+
+impl BorrowedPalette<'_> {
+    /// Converts to the owned [`Palette`].
+    pub fn into_owned(self) -> Result<Palette, SculkParseError> {
+        let mut properties = KVPair::default();
+        for (key, value) in self.properties {
+            properties.insert(key.into_owned(), value.into_owned());
+        }
+
+        Ok(Palette {
+            name: self.name.parse()?,
+            properties,
+        })
+    }
+}
+
+impl BorrowedTileTick<'_> {
+    /// Converts to the owned [`TileTick`].
+    pub fn into_owned(self) -> Result<TileTick, SculkParseError> {
+        Ok(TileTick {
+            i: self.i.parse()?,
+            p: self.p,
+            t: self.t,
+            x: self.x,
+            y: self.y,
+            z: self.z,
+        })
+    }
+}
+
+impl<'a> BorrowedComponents<'a> {
+    /// Gets the components of a compound that has a `components` field.  
+    /// They're read with the current data version, see [`crate::version::DataVersionScope`].
+    pub fn from_parent(parent: &NbtCompound<'a, 'a>) -> Option<Self> {
+        Self::from_parent_with_version(parent, current_data_version())
+    }
+
+    /// Gets the components of a compound that has a `components` field, read with the given data version.
+    pub fn from_parent_with_version(
+        parent: &NbtCompound<'a, 'a>,
+        data_version: i32,
+    ) -> Option<Self> {
+        parent
+            .compound("components")
+            .map(|components| BorrowedComponents {
+                components,
+                parent: *parent,
+                data_version,
+            })
+    }
+
+    /// The data version the components are read with.
+    pub fn data_version(&self) -> i32 {
+        self.data_version
+    }
+
+    /// The keys of all components, borrowed from the Nbt buffer.
+    pub fn keys(&self) -> impl Iterator<Item = Cow<'a, str>> + 'a {
+        self.components.iter().map(|(key, _)| key.to_str())
+    }
+
+    /// Amount of components.
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Whether there are no components.
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// Whether a component with the key exists.
+    pub fn contains(&self, key: &str) -> bool {
+        self.components.contains(key)
+    }
+
+    /// Parses the component with the given key.
+    /// Returns `None` if the key does not exist.
+    pub fn get(&self, key: &str) -> Result<Option<Component>, SculkParseError> {
+        match self.components.get(key) {
+            Some(value) => Ok(Some(
+                parse_component(key, &value, &self.components, self.data_version)
+                    .map_err(|e| component_context(e, key))?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Parses every component into the owned [`Components`].
+    pub fn into_owned(self) -> Result<Components, SculkParseError> {
+        Components::from_compound_nbt_with_version(&self.parent, self.data_version)
+    }
+}
+
+impl BorrowedItem<'_> {
+    /// Converts to the owned [`Item`], parsing the components.
+    pub fn into_owned(self) -> Result<Item, SculkParseError> {
+        Ok(Item {
+            slot: self.slot,
+            id: self.id.parse()?,
+            count: self.count,
+            components: match self.components {
+                Some(components) => Some(components.into_owned()?),
+                None => Components::from_legacy_item_nbt(&self.nbt)?,
+            },
+            extra: get_extra(&self.nbt, ITEM_FIELDS),
+        })
+    }
+}
+
+impl BorrowedBlockEntityBase<'_> {
+    /// Converts to the owned [`BlockEntityBase`], parsing the components.
+    pub fn into_owned(self) -> Result<BlockEntityBase, SculkParseError> {
+        Ok(BlockEntityBase {
+            id: self.id.parse()?,
+            keep_packed: self.keep_packed,
+            x: self.x,
+            y: self.y,
+            z: self.z,
+            components: self.components.map(|c| c.into_owned()).transpose()?,
+            extra: get_extra(&self.nbt, BLOCK_ENTITY_BASE_FIELDS),
+        })
+    }
+}
+
+impl BorrowedBlockEntity<'_> {
+    /// Parses the specific data of the block entity.
+    pub fn kind(&self) -> Result<BlockEntityKind, SculkParseError> {
+        let _data_version = DataVersionScope::enter(self.data_version);
+        BlockEntityKind::from_compound_nbt(&self.nbt)
+    }
+
+    /// Converts to the owned [`BlockEntity`], parsing the kind and components.
+    pub fn into_owned(self) -> Result<BlockEntity, SculkParseError> {
+        let kind = self.kind()?;
+        let mut base = self.base.into_owned()?;
+        base.extra = kind_extra(&self.nbt, &kind, BLOCK_ENTITY_BASE_FIELDS);
+
+        Ok(BlockEntity { base, kind })
+    }
+}
+
+impl BorrowedBlockStates<'_> {
+    /// Converts to the owned [`BlockStates`].
+    pub fn into_owned(self) -> Result<BlockStates, SculkParseError> {
+        Ok(BlockStates {
+            palette: self
+                .palette
+                .into_iter()
+                .map(|p| p.into_owned())
+                .collect::<Result<_, _>>()?,
+            data: self.data,
+        })
+    }
+}
+
+impl BorrowedBiomes<'_> {
+    /// Converts to the owned [`Biomes`].
+    pub fn into_owned(self) -> Result<Biomes, SculkParseError> {
+        Ok(Biomes {
+            palette: self
+                .palette
+                .into_iter()
+                .map(|name| {
+                    Ok(PaletteNoProps {
+                        name: name.parse()?,
+                    })
+                })
+                .collect::<Result<_, SculkParseError>>()?,
+            data: self.data,
+        })
+    }
+}
+
+impl BorrowedChunk<'_> {
+    /// Parses the full owned [`Chunk`] from the same Nbt.
+    pub fn into_owned(self) -> Result<Chunk, SculkParseError> {
+        Chunk::from_compound_nbt(&self.nbt)
+    }
+}
+
+fn get_borrowed_vec<'a, T: FromBorrowedNbt<'a>>(
+    nbt: &NbtCompound<'a, 'a>,
+    key: &'static str,
+) -> Result<Vec<T>, SculkParseError> {
+    let list = match nbt.list(key) {
+        Some(list) => list,
+        None => return Ok(vec![]),
+    };
+
+    if list.empty() {
+        return Ok(vec![]);
+    }
+
+    list.compounds()
+        .ok_or(SculkParseError::InvalidField(key.into()))?
+        .into_iter()
+        .enumerate()
+        .map(|(index, nbt)| T::from_borrowed_nbt(&nbt).map_err(|e| e.at_index(key, index)))
+        .collect()
+}
+
+#[cfg(test)]
+#[test]
+fn borrowed_block_entity() {
+    use std::io::Cursor;
+
+    let bytes = include_bytes!("../test_data/chest.nbt");
+    let nbt = simdnbt::borrow::read(&mut Cursor::new(bytes.as_slice()))
+        .unwrap()
+        .unwrap();
+
+    let borrowed = BorrowedBlockEntity::from_borrowed_nbt(&nbt.as_compound()).unwrap();
+    assert!(matches!(borrowed.base.id, Cow::Borrowed(_)));
+
+    let owned = BlockEntity::from_compound_nbt(&nbt.as_compound()).unwrap();
+    assert_eq!(borrowed.into_owned().unwrap(), owned);
+}
+
+#[cfg(test)]
+#[test]
+fn borrowed_components_keep_data_version() {
+    use crate::{
+        util::write_root_compound,
+        version::{DataVersionScope, V1_21_5},
+    };
+    use simdnbt::owned;
+    use std::io::Cursor;
+
+    // Text components are nbt since 1.21.5, they fail to read as 1.21 JSON strings.
+    let mut name = owned::NbtCompound::new();
+    name.insert("text", "Name");
+    let mut components = owned::NbtCompound::new();
+    components.insert("minecraft:custom_name", owned::NbtTag::Compound(name));
+    let mut item = owned::NbtCompound::new();
+    item.insert("Slot", 0i8);
+    item.insert("id", "minecraft:stone");
+    item.insert("count", 1);
+    item.insert("components", owned::NbtTag::Compound(components));
+
+    let bytes = write_root_compound(&item);
+    let nbt = simdnbt::borrow::read(&mut Cursor::new(bytes.as_slice()))
+        .unwrap()
+        .unwrap();
+
+    let item = {
+        let _data_version = DataVersionScope::enter(V1_21_5);
+        BorrowedItem::from_borrowed_nbt(&nbt.as_compound()).unwrap()
+    };
+    let components = item.components.unwrap();
+    assert_eq!(components.data_version(), V1_21_5);
+
+    let name = Component::CustomName(r#"{"text":"Name"}"#.into());
+    assert_eq!(
+        components.get("minecraft:custom_name").unwrap(),
+        Some(name.clone())
+    );
+    assert_eq!(
+        components
+            .into_owned()
+            .unwrap()
+            .get("minecraft:custom_name"),
+        Some(&name)
+    );
+}
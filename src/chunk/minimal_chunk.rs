@@ -2,12 +2,14 @@ use std::io::Cursor;
 
 use crate::{
     block_entity::BlockEntity, error::SculkParseError, traits::FromCompoundNbt,
-    util::get_t_compound_vec,
+    util::get_t_compound_vec, version::DataVersionScope,
 };
 
+use super::packed_pos::{get_packed_positions, PackedSectionPos};
+
 use super::{
     section::ChunkSection, status::ChunkStatus, structure::Structures, tile_tick::TileTick,
-    BlendingData,
+    BlendingData, ChunkParseOptions,
 };
 
 /// A chunk that has minimal amount of data fields to represent the physical chunk.  
@@ -30,8 +32,9 @@ pub struct MinimalChunk {
     /// `yPos`
     pub y_pos: i32,
 
+    /// Positions of blocks that need an update when a proto-chunk turns into a full chunk, per section.  
     /// `PostProcessing`
-    pub post_processing: Option<Vec<i16>>,
+    pub post_processing: Option<Vec<Vec<PackedSectionPos>>>,
 
     /// Defines the world generation status of this chunk.  
     /// `Status`
@@ -66,9 +69,33 @@ impl FromCompoundNbt for MinimalChunk {
     where
         Self: Sized,
     {
+        MinimalChunk::from_compound_nbt_with_options(nbt, &ChunkParseOptions::default())
+    }
+}
+
+impl MinimalChunk {
+    /// Parses a minimal chunk, only decoding the fields selected in the options.  
+    /// Fields that a [`MinimalChunk`] doesn't have are ignored.
+    pub fn from_compound_nbt_with_options(
+        nbt: &simdnbt::borrow::NbtCompound,
+        options: &ChunkParseOptions,
+    ) -> Result<Self, SculkParseError> {
+        MinimalChunk::parse_with_options(nbt, options).map_err(|e| {
+            match (nbt.int("xPos"), nbt.int("zPos")) {
+                (Some(x), Some(z)) => e.in_chunk(x, z),
+                _ => e,
+            }
+        })
+    }
+
+    fn parse_with_options(
+        nbt: &simdnbt::borrow::NbtCompound,
+        options: &ChunkParseOptions,
+    ) -> Result<Self, SculkParseError> {
         let data_version = nbt
             .int("DataVersion")
             .ok_or(SculkParseError::MissingField("DataVersion".into()))?;
+        let _data_version = DataVersionScope::enter(data_version);
 
         let x_pos = nbt
             .int("xPos")
@@ -85,25 +112,42 @@ impl FromCompoundNbt for MinimalChunk {
             .map(|s| ChunkStatus::from(s.to_str().as_ref()))
             .ok_or(SculkParseError::MissingField("Status".into()))?;
 
-        let sections = get_t_compound_vec(nbt, "sections", ChunkSection::from_compound_nbt)?;
-        let block_entities =
-            get_t_compound_vec(nbt, "block_entities", BlockEntity::from_compound_nbt)?;
+        let sections = match options.sections {
+            true => get_t_compound_vec(nbt, "sections", ChunkSection::from_compound_nbt)?,
+            false => vec![],
+        };
+        let block_entities = match options.block_entities {
+            true => get_t_compound_vec(nbt, "block_entities", BlockEntity::from_compound_nbt)?,
+            false => vec![],
+        };
 
-        let fluid_ticks = get_t_compound_vec(nbt, "fluid_ticks", TileTick::from_compound_nbt)?;
-        let block_ticks = get_t_compound_vec(nbt, "block_ticks", TileTick::from_compound_nbt)?;
+        let (fluid_ticks, block_ticks) = match options.ticks {
+            true => (
+                get_t_compound_vec(nbt, "fluid_ticks", TileTick::from_compound_nbt)?,
+                get_t_compound_vec(nbt, "block_ticks", TileTick::from_compound_nbt)?,
+            ),
+            false => (vec![], vec![]),
+        };
 
-        let blending_data = if let Some(nbt) = nbt.compound("blending_data") {
-            Some(BlendingData::from_compound_nbt(&nbt)?)
-        } else {
-            None
+        let blending_data = match nbt.compound("blending_data") {
+            Some(nbt) if options.proto_chunk => Some(BlendingData::from_compound_nbt(&nbt)?),
+            _ => None,
         };
 
-        let post_processing = None;
+        let post_processing = match nbt.list("PostProcessing") {
+            Some(list) if options.proto_chunk => {
+                Some(get_packed_positions(&list, "PostProcessing")?)
+            }
+            _ => None,
+        };
 
-        let structures = nbt
-            .compound("structures")
-            .map(|nbt| Structures::from_compound_nbt(&nbt))
-            .ok_or(SculkParseError::MissingField("structures".into()))??;
+        let structures = match options.structures {
+            true => nbt
+                .compound("structures")
+                .map(|nbt| Structures::from_compound_nbt(&nbt))
+                .ok_or(SculkParseError::MissingField("structures".into()))??,
+            false => Structures::default(),
+        };
 
         Ok(MinimalChunk {
             data_version,
@@ -124,6 +168,14 @@ impl FromCompoundNbt for MinimalChunk {
 
 impl MinimalChunk {
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, SculkParseError> {
+        MinimalChunk::from_bytes_with_options(bytes, &ChunkParseOptions::default())
+    }
+
+    /// Converts from bytes to a minimal chunk, only decoding the fields selected in the options.
+    pub fn from_bytes_with_options(
+        bytes: &[u8],
+        options: &ChunkParseOptions,
+    ) -> Result<Self, SculkParseError> {
         let nbt = simdnbt::borrow::read(&mut Cursor::new(bytes))?;
 
         let nbt = match nbt {
@@ -132,6 +184,6 @@ impl MinimalChunk {
         };
         let compound = nbt.as_compound();
 
-        MinimalChunk::from_compound_nbt(&compound)
+        MinimalChunk::from_compound_nbt_with_options(&compound, options)
     }
 }
@@ -1,20 +1,32 @@
 use crate::{
-    block_entity::BlockEntity, entity::Entity, error::SculkParseError, traits::FromCompoundNbt,
-    util::get_t_compound_vec,
+    block_entity::{BlockEntity, LazyBlockEntity},
+    entity::{check_entity_fields, Entity},
+    error::{ParseMode, ParseWarning, SculkParseError, Substitute},
+    resource_location::LenientIdScope,
+    traits::{FromBorrowedNbt, FromCompoundNbt, ToCompoundNbt, ToVersionedCompoundNbt},
+    util::{
+        check_known_fields, get_extra, get_t_compound_vec, get_t_compound_vec_with_mode,
+        merge_compound, set_optional_tag, write_compound_list,
+    },
+    version::DataVersionScope,
 };
-use section::ChunkSection;
+use packed_pos::{get_packed_positions, write_packed_positions, PackedSectionPos};
+use section::{ChunkSection, SECTION_FIELDS};
 use status::ChunkStatus;
 use std::io::Cursor;
 use structure::Structures;
 use tile_tick::TileTick;
 
 mod minimal_chunk;
+mod visitor;
 
+pub mod packed_pos;
 pub mod section;
 pub mod status;
 pub mod structure;
 pub mod tile_tick;
 pub use minimal_chunk::*;
+pub use visitor::*;
 
 /// Represents a chunk in the world.  
 /// [Minecraft Wiki](https://minecraft.wiki/w/Chunk_format)  
@@ -59,7 +71,7 @@ pub struct Chunk {
 
     ///  A List of 16 lists that store positions of light sources per chunk section as shorts, only for proto-chunks (not confirmed for 1.18 format).  
     /// `Lights`
-    pub lights: Vec<Vec<i16>>,
+    pub lights: Vec<Vec<PackedSectionPos>>,
 
     /// A list of entities in the proto-chunks, used when generating. As of 1.17, this list is not present for fully generated chunks and entities are moved to a separated region files once the chunk is generated, see Entity format for more details (not confirmed for 1.18 format).  
     /// `Entities`
@@ -85,10 +97,15 @@ pub struct Chunk {
 
     /// A List of 24  Lists that store the positions of blocks that need to receive an update when a proto-chunk turns into a full chunk, packed in  Shorts. Each list corresponds to specific section in the height of the chunk.  
     /// `PostProcessing`
-    pub post_processing: Option<Vec<i16>>,
+    pub post_processing: Option<Vec<Vec<PackedSectionPos>>>,
 
     /// Structure data in this chunk.
     pub structures: Option<Structures>,
+
+    /// Fields that aren't modelled, like `isLightOn`, `UpgradeData` or data added by mods and servers.  
+    /// Fields skipped with [`ChunkParseOptions`] aren't kept.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extra: simdnbt::owned::NbtCompound,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -98,7 +115,7 @@ pub struct BlendingData {
     pub max_section: i32,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HeightMaps {
     /// MOTION_BLOCKING
@@ -126,15 +143,159 @@ pub struct CarvingMasks {
     pub liquid: Vec<i8>,
 }
 
-//
+/// Which top-level fields of a [`Chunk`] or [`MinimalChunk`] to decode.  
+/// Skipped fields are left empty, the position, status and timestamps are always decoded.
+///
+/// ```
+/// use sculk::chunk::ChunkParseOptions;
+///
+/// // Only decode block entities, skipping sections entirely.
+/// let options = ChunkParseOptions {
+///     block_entities: true,
+///     ..ChunkParseOptions::none()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChunkParseOptions {
+    /// `sections`
+    pub sections: bool,
+
+    /// `block_entities`
+    pub block_entities: bool,
+
+    /// `Heightmaps`
+    pub heightmaps: bool,
+
+    /// `fluid_ticks` and `block_ticks`
+    pub ticks: bool,
+
+    /// `structures`
+    pub structures: bool,
+
+    /// `Lights`
+    pub lights: bool,
+
+    /// `Entities`, `CarvingMasks`, `blending_data` and `PostProcessing`, which are mostly found in proto-chunks.
+    pub proto_chunk: bool,
+
+    /// How malformed data and unknown fields are handled, only used by [`Chunk`].  
+    /// Use [`Chunk::from_compound_nbt_with_warnings`] to get the warnings of [`ParseMode::Lenient`].
+    pub mode: ParseMode,
+}
+
+/// Fields of a chunk that are decoded, the rest is kept in [`Chunk::extra`].
+const CHUNK_FIELDS: &[&str] = &[
+    "DataVersion",
+    "xPos",
+    "zPos",
+    "yPos",
+    "Status",
+    "LastUpdate",
+    "sections",
+    "block_entities",
+    "CarvingMasks",
+    "Heightmaps",
+    "Lights",
+    "Entities",
+    "fluid_ticks",
+    "block_ticks",
+    "InhabitedTime",
+    "blending_data",
+    "PostProcessing",
+    "structures",
+];
+
+/// Vanilla fields of a chunk that aren't decoded, but that [`ParseMode::Strict`] still accepts.
+const UNDECODED_CHUNK_FIELDS: &[&str] = &["isLightOn", "UpgradeData", "below_zero_retrogen"];
+
+impl Default for ChunkParseOptions {
+    fn default() -> Self {
+        ChunkParseOptions::all()
+    }
+}
+
+impl ChunkParseOptions {
+    /// Decodes every field, the same as the default.
+    pub fn all() -> Self {
+        ChunkParseOptions {
+            sections: true,
+            block_entities: true,
+            heightmaps: true,
+            ticks: true,
+            structures: true,
+            lights: true,
+            proto_chunk: true,
+            mode: ParseMode::Default,
+        }
+    }
+
+    /// Only decodes the fields that are always decoded.
+    pub fn none() -> Self {
+        ChunkParseOptions {
+            sections: false,
+            block_entities: false,
+            heightmaps: false,
+            ticks: false,
+            structures: false,
+            lights: false,
+            proto_chunk: false,
+            mode: ParseMode::Default,
+        }
+    }
+}
+
 impl FromCompoundNbt for Chunk {
     fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
     where
         Self: Sized,
     {
+        Chunk::from_compound_nbt_with_options(nbt, &ChunkParseOptions::default())
+    }
+}
+
+impl Chunk {
+    /// Parses a chunk, only decoding the fields selected in the options.
+    pub fn from_compound_nbt_with_options(
+        nbt: &simdnbt::borrow::NbtCompound,
+        options: &ChunkParseOptions,
+    ) -> Result<Self, SculkParseError> {
+        Chunk::from_compound_nbt_with_warnings(nbt, options).map(|(chunk, _)| chunk)
+    }
+
+    /// Parses a chunk, also returning the warnings recorded with [`ParseMode::Lenient`].
+    pub fn from_compound_nbt_with_warnings(
+        nbt: &simdnbt::borrow::NbtCompound,
+        options: &ChunkParseOptions,
+    ) -> Result<(Self, Vec<ParseWarning>), SculkParseError> {
+        let mut warnings = vec![];
+        let in_chunk = |e: SculkParseError| match (nbt.int("xPos"), nbt.int("zPos")) {
+            (Some(x), Some(z)) => e.in_chunk(x, z),
+            _ => e,
+        };
+
+        let ids = LenientIdScope::enter(options.mode);
+        let chunk = Chunk::parse_with_options(nbt, options, &mut warnings).map_err(in_chunk)?;
+        ids.finish(&mut warnings);
+        ParseWarning::add_context(&mut warnings, in_chunk);
+
+        Ok((chunk, warnings))
+    }
+
+    fn parse_with_options(
+        nbt: &simdnbt::borrow::NbtCompound,
+        options: &ChunkParseOptions,
+        warnings: &mut Vec<ParseWarning>,
+    ) -> Result<Self, SculkParseError> {
+        let mode = options.mode;
+        if mode == ParseMode::Strict {
+            check_known_fields(nbt, &[CHUNK_FIELDS, UNDECODED_CHUNK_FIELDS].concat())?;
+        }
+
         let data_version = nbt
             .int("DataVersion")
             .ok_or(SculkParseError::MissingField("DataVersion".into()))?;
+        let _data_version = DataVersionScope::enter(data_version);
 
         let x_pos = nbt
             .int("xPos")
@@ -155,80 +316,132 @@ impl FromCompoundNbt for Chunk {
             .long("LastUpdate")
             .ok_or(SculkParseError::MissingField("LastUpdate".into()))?;
 
-        let sections = get_t_compound_vec(nbt, "sections", ChunkSection::from_compound_nbt)?;
-        let block_entities =
-            get_t_compound_vec(nbt, "block_entities", BlockEntity::from_compound_nbt)?;
-
-        let carving_masks = if let Some(nbt) = nbt.compound("CarvingMasks") {
-            Some(CarvingMasks::from_compound_nbt(&nbt)?)
-        } else {
-            None
+        let sections = match options.sections {
+            true => {
+                if mode == ParseMode::Strict {
+                    check_section_fields(nbt)?;
+                }
+                get_t_compound_vec_with_mode(
+                    nbt,
+                    "sections",
+                    ChunkSection::from_compound_nbt,
+                    mode,
+                    warnings,
+                )?
+            }
+            false => vec![],
+        };
+        let block_entities = match options.block_entities {
+            true => get_block_entities(nbt, mode, warnings)?,
+            false => vec![],
         };
 
-        let height_maps = nbt
-            .compound("Heightmaps")
-            .map(|nbt| HeightMaps::from_compound_nbt(&nbt))
-            .ok_or(SculkParseError::MissingField("Heightmaps".into()))??;
-
-        let lights = if let Some(lights) = nbt.list("Lights") {
-            let lights = lights
-                .lists()
-                .ok_or(SculkParseError::InvalidField("Lights".into()))?;
-
-            let mut lights_vec: Vec<Vec<i16>> = Vec::with_capacity(lights.len());
-
-            for light in lights {
-                let inner_lights = light
-                    .shorts()
-                    .ok_or(SculkParseError::InvalidField("Lights".into()))?;
+        let carving_masks = match nbt.compound("CarvingMasks") {
+            Some(nbt) if options.proto_chunk => mode.recover(
+                CarvingMasks::from_compound_nbt(&nbt)
+                    .map(Some)
+                    .map_err(|e| e.at_field("CarvingMasks")),
+                warnings,
+                Substitute::Default,
+                || None,
+            )?,
+            _ => None,
+        };
 
-                lights_vec.push(inner_lights.to_vec());
-            }
+        let height_maps = match options.heightmaps {
+            true => mode.recover(
+                nbt.compound("Heightmaps")
+                    .map(|nbt| HeightMaps::from_compound_nbt(&nbt))
+                    .unwrap_or(Err(SculkParseError::MissingField("Heightmaps".into()))),
+                warnings,
+                Substitute::Default,
+                HeightMaps::default,
+            )?,
+            false => HeightMaps::default(),
+        };
 
-            lights_vec
-        } else {
-            Vec::new()
+        let lights = match nbt.list("Lights") {
+            Some(lights) if options.lights => mode.recover(
+                get_packed_positions(&lights, "Lights"),
+                warnings,
+                Substitute::Default,
+                Vec::new,
+            )?,
+            _ => Vec::new(),
         };
 
-        let entities = match get_t_compound_vec(nbt, "Entities", Entity::from_compound_nbt) {
-            Ok(entities) => Some(entities),
-            Err(SculkParseError::MissingField(_)) => None,
-            Err(e) => return Err(e),
+        if mode == ParseMode::Strict && options.proto_chunk {
+            check_entities_fields(nbt)?;
+        }
+        let entities = match options.proto_chunk {
+            true => match get_t_compound_vec_with_mode(
+                nbt,
+                "Entities",
+                Entity::from_compound_nbt,
+                mode,
+                warnings,
+            ) {
+                Ok(entities) => Some(entities),
+                Err(e) if matches!(e.root_cause(), SculkParseError::MissingField(_)) => None,
+                Err(e) => return Err(e),
+            },
+            false => None,
+        };
+        let (fluid_ticks, block_ticks) = match options.ticks {
+            true => (
+                get_t_compound_vec_with_mode(
+                    nbt,
+                    "fluid_ticks",
+                    TileTick::from_compound_nbt,
+                    mode,
+                    warnings,
+                )?,
+                get_t_compound_vec_with_mode(
+                    nbt,
+                    "block_ticks",
+                    TileTick::from_compound_nbt,
+                    mode,
+                    warnings,
+                )?,
+            ),
+            false => (vec![], vec![]),
         };
-        let fluid_ticks = get_t_compound_vec(nbt, "fluid_ticks", TileTick::from_compound_nbt)?;
-        let block_ticks = get_t_compound_vec(nbt, "block_ticks", TileTick::from_compound_nbt)?;
 
         let inhabited_time = nbt
             .long("InhabitedTime")
             .ok_or(SculkParseError::MissingField("InhabitedTime".into()))?;
 
-        let blending_data = if let Some(nbt) = nbt.compound("blending_data") {
-            Some(BlendingData::from_compound_nbt(&nbt)?)
-        } else {
-            None
+        let blending_data = match nbt.compound("blending_data") {
+            Some(nbt) if options.proto_chunk => mode.recover(
+                BlendingData::from_compound_nbt(&nbt)
+                    .map(Some)
+                    .map_err(|e| e.at_field("blending_data")),
+                warnings,
+                Substitute::Default,
+                || None,
+            )?,
+            _ => None,
         };
 
-        // let post_processing = if let Some(list) = nbt.list("PostProcessing") {
-        //     if list.empty() {
-        //         None
-        //     } else {
-        //         let post_processing = list
-        //             .shorts()
-        //             .ok_or(SculkParseError::InvalidField("PostProcessing:Inner".into()))?
-        //             .iter()
-        //             .map(|l| *l)
-        //             .collect();
-
-        //         Some(post_processing)
-        //     }
-        // } else {
-        //     None
-        // };
-        let post_processing = None;
-
-        let structures = nbt
-            .compound("structures")
-            .and_then(|nbt| Structures::from_compound_nbt(&nbt).ok());
+        let post_processing = match nbt.list("PostProcessing") {
+            Some(list) if options.proto_chunk => mode.recover(
+                get_packed_positions(&list, "PostProcessing").map(Some),
+                warnings,
+                Substitute::Default,
+                || None,
+            )?,
+            _ => None,
+        };
+
+        let structures = match nbt.compound("structures") {
+            Some(nbt) if options.structures => {
+                let structures =
+                    Structures::from_compound_nbt(&nbt).map_err(|e| e.at_field("structures"));
+
+                mode.recover(structures.map(Some), warnings, Substitute::Default, || None)?
+            }
+            _ => None,
+        };
 
         Ok(Chunk {
             data_version,
@@ -249,10 +462,82 @@ impl FromCompoundNbt for Chunk {
             blending_data,
             post_processing,
             structures,
+            extra: get_extra(nbt, CHUNK_FIELDS),
         })
     }
 }
 
+fn check_section_fields(nbt: &simdnbt::borrow::NbtCompound) -> Result<(), SculkParseError> {
+    let sections = match nbt.list("sections").and_then(|list| list.compounds()) {
+        Some(sections) => sections,
+        None => return Ok(()),
+    };
+
+    for (index, section) in sections.into_iter().enumerate() {
+        check_known_fields(&section, SECTION_FIELDS).map_err(|e| e.at_index("sections", index))?;
+    }
+
+    Ok(())
+}
+
+/// Block entities with the given mode, in lenient mode the ones without an id or position are skipped.
+fn check_entities_fields(nbt: &simdnbt::borrow::NbtCompound) -> Result<(), SculkParseError> {
+    let entities = match nbt.list("Entities").and_then(|list| list.compounds()) {
+        Some(entities) => entities,
+        None => return Ok(()),
+    };
+
+    for (index, entity) in entities.into_iter().enumerate() {
+        check_entity_fields(&entity).map_err(|e| e.at_index("Entities", index))?;
+    }
+
+    Ok(())
+}
+
+fn get_block_entities(
+    nbt: &simdnbt::borrow::NbtCompound,
+    mode: ParseMode,
+    warnings: &mut Vec<ParseWarning>,
+) -> Result<Vec<BlockEntity>, SculkParseError> {
+    if mode == ParseMode::Default {
+        return get_t_compound_vec(nbt, "block_entities", BlockEntity::from_compound_nbt);
+    }
+
+    let list = match nbt.list("block_entities") {
+        Some(list) if !list.empty() => list,
+        _ => return Ok(vec![]),
+    };
+    let compounds = mode.recover(
+        list.compounds()
+            .map(|compounds| compounds.into_iter().collect())
+            .ok_or(SculkParseError::InvalidField("block_entities".into())),
+        warnings,
+        Substitute::Default,
+        Vec::new,
+    )?;
+
+    let mut block_entities = vec![];
+    for (index, compound) in compounds.iter().enumerate() {
+        let start = warnings.len();
+        let block_entity = BlockEntity::from_compound_nbt_with_mode(compound, mode, warnings)
+            .map_err(|e| e.at_index("block_entities", index));
+        ParseWarning::add_context(&mut warnings[start..], |e| {
+            e.at_index("block_entities", index)
+        });
+
+        if let Some(block_entity) = mode.recover(
+            block_entity.map(Some),
+            warnings,
+            Substitute::Skipped,
+            || None,
+        )? {
+            block_entities.push(block_entity);
+        }
+    }
+
+    Ok(block_entities)
+}
+
 impl FromCompoundNbt for BlendingData {
     fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
     where
@@ -318,8 +603,134 @@ impl FromCompoundNbt for CarvingMasks {
     }
 }
 
+impl ToCompoundNbt for Chunk {
+    /// Writes the chunk with its own [`Chunk::data_version`].
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let data_version = self.data_version;
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("DataVersion", data_version);
+        nbt.insert("xPos", self.x_pos);
+        nbt.insert("zPos", self.z_pos);
+        nbt.insert("yPos", self.y_pos);
+        nbt.insert("Status", String::from(self.status.clone()));
+        nbt.insert("LastUpdate", self.last_update);
+        nbt.insert(
+            "sections",
+            write_compound_list(&self.sections, ChunkSection::to_compound_nbt),
+        );
+        nbt.insert(
+            "block_entities",
+            write_compound_list(&self.block_entities, |block_entity| {
+                block_entity.to_compound_nbt_with_version(data_version)
+            }),
+        );
+        set_optional_tag(
+            &mut nbt,
+            "CarvingMasks",
+            self.carving_masks
+                .as_ref()
+                .map(CarvingMasks::to_compound_nbt),
+        );
+        nbt.insert("Heightmaps", self.height_maps.to_compound_nbt());
+        if !self.lights.is_empty() {
+            nbt.insert("Lights", write_packed_positions(&self.lights));
+        }
+        if let Some(entities) = &self.entities {
+            nbt.insert(
+                "Entities",
+                write_compound_list(entities, |entity| {
+                    entity.to_compound_nbt_with_version(data_version)
+                }),
+            );
+        }
+        nbt.insert(
+            "fluid_ticks",
+            write_compound_list(&self.fluid_ticks, TileTick::to_compound_nbt),
+        );
+        nbt.insert(
+            "block_ticks",
+            write_compound_list(&self.block_ticks, TileTick::to_compound_nbt),
+        );
+        nbt.insert("InhabitedTime", self.inhabited_time);
+        set_optional_tag(
+            &mut nbt,
+            "blending_data",
+            self.blending_data
+                .as_ref()
+                .map(BlendingData::to_compound_nbt),
+        );
+        set_optional_tag(
+            &mut nbt,
+            "PostProcessing",
+            self.post_processing.as_deref().map(write_packed_positions),
+        );
+        set_optional_tag(
+            &mut nbt,
+            "structures",
+            self.structures.as_ref().map(Structures::to_compound_nbt),
+        );
+
+        let mut chunk = self.extra.clone();
+        merge_compound(&mut chunk, nbt);
+        chunk
+    }
+}
+
+impl ToCompoundNbt for BlendingData {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+        nbt.insert("min_section", self.min_section);
+        nbt.insert("max_section", self.max_section);
+        nbt
+    }
+}
+
+impl ToCompoundNbt for HeightMaps {
+    /// Heightmaps that are empty aren't written, they're missing before the chunk is generated.
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        for (key, heightmap) in [
+            ("MOTION_BLOCKING", &self.motion_blocking),
+            ("MOTION_BLOCKING_NO_LEAVES", &self.motion_blocking_no_leaves),
+            ("OCEAN_FLOOR", &self.ocean_floor),
+            ("OCEAN_FLOOR_WG", &self.ocean_floor_wg),
+            ("WORLD_SURFACE", &self.world_surface),
+            ("WORLD_SURFACE_WG", &self.world_surface_wg),
+        ] {
+            if !heightmap.is_empty() {
+                nbt.insert(key, simdnbt::owned::NbtTag::LongArray(heightmap.clone()));
+            }
+        }
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for CarvingMasks {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let bytes = |mask: &[i8]| {
+            simdnbt::owned::NbtTag::ByteArray(mask.iter().map(|b| *b as u8).collect())
+        };
+
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+        nbt.insert("AIR", bytes(&self.air));
+        nbt.insert("LIQUID", bytes(&self.liquid));
+        nbt
+    }
+}
+
 impl Chunk {
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, SculkParseError> {
+        Chunk::from_bytes_with_options(bytes, &ChunkParseOptions::default())
+    }
+
+    /// Converts from bytes to a chunk, only decoding the fields selected in the options.
+    pub fn from_bytes_with_options(
+        bytes: &[u8],
+        options: &ChunkParseOptions,
+    ) -> Result<Self, SculkParseError> {
         let nbt = simdnbt::borrow::read(&mut Cursor::new(bytes))?;
 
         let nbt = match nbt {
@@ -328,6 +739,522 @@ impl Chunk {
         };
         let compound = nbt.as_compound();
 
-        Chunk::from_compound_nbt(&compound)
+        Chunk::from_compound_nbt_with_options(&compound, options)
+    }
+
+    /// Gets the block entities of a chunk's nbt without parsing them.  
+    /// Each [`LazyBlockEntity`] borrows its compound from the chunk nbt, so only the base fields are read
+    /// and the rest is parsed when calling `.kind()` or `.to_owned()`.
+    ///
+    /// Useful when scanning a region for specific block entities, like only `minecraft:chest`.
+    pub fn block_entities_lazy<'a>(
+        nbt: &simdnbt::borrow::NbtCompound<'a, 'a>,
+    ) -> Result<Vec<LazyBlockEntity<'a>>, SculkParseError> {
+        let list = match nbt.list("block_entities") {
+            Some(list) => list,
+            None => return Ok(vec![]),
+        };
+
+        if list.empty() {
+            return Ok(vec![]);
+        }
+
+        list.compounds()
+            .ok_or(SculkParseError::InvalidField("block_entities".into()))?
+            .into_iter()
+            .map(|nbt| LazyBlockEntity::from_borrowed_nbt(&nbt))
+            .collect()
+    }
+
+    /// The absolute positions of the blocks in `PostProcessing`, which get updated when the proto-chunk turns into a full chunk.
+    pub fn post_processing_positions(&self) -> Vec<[i32; 3]> {
+        let mut positions = vec![];
+
+        for (index, section) in self.post_processing.iter().flatten().enumerate() {
+            let section_y = self.y_pos + index as i32;
+            for pos in section {
+                positions.push(pos.block_pos(self.x_pos, section_y, self.z_pos));
+            }
+        }
+
+        positions
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn parse_modes() {
+    use crate::{block_entities::BlockEntityKind, error::Substitute};
+    use simdnbt::owned::{NbtCompound, NbtList};
+
+    let mut chest = NbtCompound::new();
+    chest.insert("id", "minecraft:chest");
+    chest.insert("x", 0);
+    chest.insert("y", 0);
+    chest.insert("z", 0);
+    // Malformed, should be a list of item compounds.
+    chest.insert("Items", NbtList::Int(vec![1]));
+
+    let mut chunk = NbtCompound::new();
+    chunk.insert("DataVersion", 3953);
+    chunk.insert("xPos", 3);
+    chunk.insert("zPos", -5);
+    chunk.insert("yPos", -4);
+    chunk.insert("Status", "minecraft:full");
+    chunk.insert("LastUpdate", 0i64);
+    chunk.insert("InhabitedTime", 0i64);
+    chunk.insert("block_entities", NbtList::Compound(vec![chest]));
+    chunk.insert("foo", 0);
+    chunk.insert(
+        "PostProcessing",
+        NbtList::List(vec![NbtList::Empty, NbtList::Short(vec![0x0a53])]),
+    );
+
+    let bytes = crate::util::write_root_compound(&chunk);
+    let nbt = simdnbt::borrow::read(&mut Cursor::new(bytes.as_slice()))
+        .unwrap()
+        .unwrap();
+    let nbt = nbt.as_compound();
+
+    // Missing heightmaps and the malformed chest both fail by default.
+    assert!(Chunk::from_compound_nbt(&nbt).is_err());
+
+    let options = ChunkParseOptions {
+        mode: ParseMode::Lenient,
+        ..Default::default()
+    };
+    let (chunk, warnings) = Chunk::from_compound_nbt_with_warnings(&nbt, &options).unwrap();
+    assert_eq!(warnings.len(), 2);
+    assert_eq!(warnings[0].substitute, Substitute::Raw);
+    assert_eq!(
+        warnings[0].error.context().unwrap().path_string(),
+        "block_entities[0]"
+    );
+    assert!(matches!(
+        &chunk.block_entities[0].kind,
+        BlockEntityKind::Unknown(raw) if raw.contains("Items")
+    ));
+    assert_eq!(chunk.post_processing_positions(), vec![[51, -43, -70]]);
+    // Unmodelled fields are kept, the raw kind already holds the block entity.
+    assert_eq!(chunk.extra.int("foo"), Some(0));
+    assert!(chunk.block_entities[0].base.extra.is_empty());
+
+    let options = ChunkParseOptions {
+        mode: ParseMode::Strict,
+        ..Default::default()
+    };
+    let err = Chunk::from_compound_nbt_with_options(&nbt, &options).unwrap_err();
+    assert!(matches!(err.root_cause(), SculkParseError::UnknownField(_)));
+}
+
+#[cfg(test)]
+#[test]
+fn lenient_raw_ids() {
+    use crate::{block_entities::BlockEntityKind, error::Substitute};
+    use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+
+    let mut item = NbtCompound::new();
+    item.insert("Slot", 0i8);
+    item.insert("id", "MyMod:Ruby");
+    item.insert("count", 1);
+    let mut chest = NbtCompound::new();
+    chest.insert("id", "minecraft:chest");
+    chest.insert("x", 0);
+    chest.insert("y", 0);
+    chest.insert("z", 0);
+    chest.insert("Items", NbtList::Compound(vec![item]));
+
+    let mut heightmaps = NbtCompound::new();
+    heightmaps.insert("WORLD_SURFACE", NbtTag::LongArray(vec![0; 37]));
+    let mut chunk = NbtCompound::new();
+    chunk.insert("DataVersion", 3953);
+    chunk.insert("xPos", 0);
+    chunk.insert("zPos", 0);
+    chunk.insert("yPos", -4);
+    chunk.insert("Status", "minecraft:full");
+    chunk.insert("LastUpdate", 0i64);
+    chunk.insert("InhabitedTime", 0i64);
+    chunk.insert("Heightmaps", heightmaps);
+    chunk.insert("block_entities", NbtList::Compound(vec![chest]));
+
+    let bytes = crate::util::write_root_compound(&chunk);
+    let nbt = simdnbt::borrow::read(&mut Cursor::new(bytes.as_slice()))
+        .unwrap()
+        .unwrap();
+    let nbt = nbt.as_compound();
+
+    let err = Chunk::from_compound_nbt(&nbt).unwrap_err();
+    assert!(matches!(
+        err.root_cause(),
+        SculkParseError::InvalidResourceLocation(_)
+    ));
+
+    // Lenient keeps the chest and its item, with the id as it was written.
+    let options = ChunkParseOptions {
+        mode: ParseMode::Lenient,
+        ..Default::default()
+    };
+    let (chunk, warnings) = Chunk::from_compound_nbt_with_warnings(&nbt, &options).unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].substitute, Substitute::Raw);
+    let context = warnings[0].error.context().unwrap();
+    assert_eq!(context.id.as_deref(), Some("minecraft:chest"));
+    assert_eq!(context.chunk, Some([0, 0]));
+
+    let chest = match &chunk.block_entities[0].kind {
+        BlockEntityKind::Chest(chest) => chest,
+        kind => panic!("expected a chest, got {kind:?}"),
+    };
+    assert_eq!(chest.items[0].id.to_string(), "MyMod:Ruby");
+    assert!(!chest.items[0].id.is_valid());
+}
+
+#[cfg(test)]
+#[test]
+fn legacy_item_round_trip() {
+    use crate::{block_entities::BlockEntityKind, traits::ToVersionedCompoundNbt};
+    use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+
+    let mut tag = NbtCompound::new();
+    tag.insert("Damage", 5);
+    let mut item = NbtCompound::new();
+    item.insert("Slot", 0i8);
+    item.insert("id", "minecraft:diamond_sword");
+    item.insert("Count", 3i8);
+    item.insert("tag", tag);
+    let mut chest = NbtCompound::new();
+    chest.insert("id", "minecraft:chest");
+    chest.insert("x", 0);
+    chest.insert("y", 0);
+    chest.insert("z", 0);
+    chest.insert("Items", NbtList::Compound(vec![item]));
+
+    let mut heightmaps = NbtCompound::new();
+    heightmaps.insert("WORLD_SURFACE", NbtTag::LongArray(vec![0; 37]));
+    let mut chunk = NbtCompound::new();
+    chunk.insert("DataVersion", 3700);
+    chunk.insert("xPos", 0);
+    chunk.insert("zPos", 0);
+    chunk.insert("yPos", -4);
+    chunk.insert("Status", "minecraft:full");
+    chunk.insert("LastUpdate", 0i64);
+    chunk.insert("InhabitedTime", 0i64);
+    chunk.insert("Heightmaps", heightmaps);
+    chunk.insert("block_entities", NbtList::Compound(vec![chest]));
+
+    let bytes = crate::util::write_root_compound(&chunk);
+    let nbt = simdnbt::borrow::read(&mut Cursor::new(bytes.as_slice()))
+        .unwrap()
+        .unwrap();
+    let chunk = Chunk::from_compound_nbt(&nbt.as_compound()).unwrap();
+    let chest = match &chunk.block_entities[0].kind {
+        BlockEntityKind::Chest(chest) => chest,
+        kind => panic!("expected a chest, got {kind:?}"),
+    };
+    assert_eq!(chest.items[0].count, 3);
+    assert_eq!(
+        chest.items[0].components.as_ref().unwrap().damage(),
+        Some(5)
+    );
+
+    // The chunk writes its items back the way 1.20.4 stored them.
+    let written = chunk.to_compound_nbt();
+    let item = &written.list("block_entities").unwrap().compounds().unwrap()[0]
+        .list("Items")
+        .unwrap()
+        .compounds()
+        .unwrap()[0];
+    assert_eq!(item.byte("Count"), Some(3));
+    assert_eq!(item.compound("tag").unwrap().int("Damage"), Some(5));
+    assert!(item.int("count").is_none());
+    assert!(item.compound("components").is_none());
+
+    // Upgrading the item drops the tag in favour of the components.
+    let item = chest.items[0].to_compound_nbt_with_version(crate::version::V1_21);
+    assert_eq!(item.int("count"), Some(3));
+    assert_eq!(
+        item.compound("components").unwrap().int("minecraft:damage"),
+        Some(5)
+    );
+    assert!(item.compound("tag").is_none());
+    assert!(item.byte("Count").is_none());
+}
+
+#[cfg(test)]
+#[test]
+fn strict_nested_fields() {
+    use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+
+    fn chunk_nbt(chest: NbtCompound, entity: NbtCompound, structures: NbtCompound) -> NbtCompound {
+        let mut heightmaps = NbtCompound::new();
+        heightmaps.insert("WORLD_SURFACE", NbtTag::LongArray(vec![0; 37]));
+
+        let mut chunk = NbtCompound::new();
+        chunk.insert("DataVersion", 3953);
+        chunk.insert("xPos", 0);
+        chunk.insert("zPos", 0);
+        chunk.insert("yPos", -4);
+        chunk.insert("Status", "minecraft:full");
+        chunk.insert("LastUpdate", 0i64);
+        chunk.insert("InhabitedTime", 0i64);
+        chunk.insert("Heightmaps", heightmaps);
+        chunk.insert("block_entities", NbtList::Compound(vec![chest]));
+        chunk.insert("Entities", NbtList::Compound(vec![entity]));
+        chunk.insert("structures", structures);
+        chunk
+    }
+
+    fn parse(chunk: &NbtCompound, mode: ParseMode) -> Result<Chunk, SculkParseError> {
+        let bytes = crate::util::write_root_compound(chunk);
+        let nbt = simdnbt::borrow::read(&mut Cursor::new(bytes.as_slice()))
+            .unwrap()
+            .unwrap();
+        let options = ChunkParseOptions {
+            mode,
+            ..Default::default()
+        };
+
+        Chunk::from_compound_nbt_with_options(&nbt.as_compound(), &options)
     }
+
+    let mut item = NbtCompound::new();
+    item.insert("Slot", 0i8);
+    item.insert("id", "minecraft:stone");
+    item.insert("count", 1);
+
+    let chest_with = |item: NbtCompound| {
+        let mut chest = NbtCompound::new();
+        chest.insert("id", "minecraft:chest");
+        chest.insert("x", 0);
+        chest.insert("y", 0);
+        chest.insert("z", 0);
+        chest.insert("Items", NbtList::Compound(vec![item]));
+        chest
+    };
+    let chest = chest_with(item.clone());
+
+    let mut entity = NbtCompound::new();
+    entity.insert("id", "minecraft:pig");
+    entity.insert("Health", 10f32);
+    entity.insert("Saddle", 1i8);
+
+    let mut structures = NbtCompound::new();
+    structures.insert("References", NbtCompound::new());
+    structures.insert("starts", NbtCompound::new());
+
+    let chunk = chunk_nbt(chest.clone(), entity.clone(), structures.clone());
+    assert!(parse(&chunk, ParseMode::Strict).is_ok());
+
+    let unknown_field = |chunk: &NbtCompound| match parse(chunk, ParseMode::Strict) {
+        Err(err) => match err.root_cause() {
+            SculkParseError::UnknownField(field) => field.clone(),
+            err => panic!("unexpected error {err:?}"),
+        },
+        Ok(_) => panic!("unknown field was accepted"),
+    };
+
+    let mut bad_chest = chest.clone();
+    bad_chest.insert("foo", 0);
+    let chunk = chunk_nbt(bad_chest, entity.clone(), structures.clone());
+    assert_eq!(unknown_field(&chunk), "foo");
+    // Unknown keys are still kept by default.
+    assert!(parse(&chunk, ParseMode::Default).is_ok());
+
+    let mut bad_item = item;
+    bad_item.insert("bar", 0);
+    let chunk = chunk_nbt(chest_with(bad_item), entity.clone(), structures.clone());
+    assert_eq!(unknown_field(&chunk), "bar");
+
+    let mut bad_entity = entity.clone();
+    bad_entity.insert("baz", 0);
+    let chunk = chunk_nbt(chest.clone(), bad_entity, structures);
+    assert_eq!(unknown_field(&chunk), "baz");
+
+    // Structures that fail to parse aren't dropped silently.
+    let chunk = chunk_nbt(chest, entity, NbtCompound::new());
+    assert!(parse(&chunk, ParseMode::Default).is_err());
+    let bytes = crate::util::write_root_compound(&chunk);
+    let nbt = simdnbt::borrow::read(&mut Cursor::new(bytes.as_slice()))
+        .unwrap()
+        .unwrap();
+    let options = ChunkParseOptions {
+        mode: ParseMode::Lenient,
+        ..Default::default()
+    };
+    let (chunk, warnings) =
+        Chunk::from_compound_nbt_with_warnings(&nbt.as_compound(), &options).unwrap();
+    assert!(chunk.structures.is_none());
+    assert_eq!(warnings.len(), 1);
+}
+
+#[cfg(test)]
+#[test]
+fn parse_options() {
+    use simdnbt::owned::{NbtCompound, NbtList};
+
+    let mut chest = NbtCompound::new();
+    chest.insert("id", "minecraft:chest");
+    chest.insert("x", 0);
+    chest.insert("y", 0);
+    chest.insert("z", 0);
+    chest.insert("Items", NbtList::Empty);
+
+    let mut chunk = NbtCompound::new();
+    chunk.insert("DataVersion", 3953);
+    chunk.insert("xPos", 0);
+    chunk.insert("zPos", 0);
+    chunk.insert("yPos", -4);
+    chunk.insert("Status", "minecraft:full");
+    chunk.insert("LastUpdate", 0i64);
+    chunk.insert("InhabitedTime", 0i64);
+    chunk.insert("block_entities", NbtList::Compound(vec![chest]));
+    // Malformed and missing fields, which fail the parse unless they're skipped.
+    chunk.insert("sections", NbtList::Int(vec![1]));
+    chunk.insert("fluid_ticks", NbtList::Int(vec![1]));
+    chunk.insert("Lights", 0);
+
+    let bytes = crate::util::write_root_compound(&chunk);
+    let nbt = simdnbt::borrow::read(&mut Cursor::new(bytes.as_slice()))
+        .unwrap()
+        .unwrap();
+    let nbt = nbt.as_compound();
+
+    assert!(Chunk::from_compound_nbt(&nbt).is_err());
+
+    let options = ChunkParseOptions {
+        block_entities: true,
+        ..ChunkParseOptions::none()
+    };
+    let chunk = Chunk::from_compound_nbt_with_options(&nbt, &options).unwrap();
+    assert_eq!(chunk.block_entities.len(), 1);
+    assert_eq!(chunk.block_entities[0].base.id, "minecraft:chest");
+    assert!(chunk.sections.is_empty());
+    assert!(chunk.fluid_ticks.is_empty());
+    assert!(chunk.lights.is_empty());
+    assert_eq!(chunk.height_maps, HeightMaps::default());
+    // Skipped fields aren't kept either.
+    assert!(chunk.extra.is_empty());
+
+    let options = ChunkParseOptions {
+        sections: true,
+        ..ChunkParseOptions::none()
+    };
+    assert!(Chunk::from_compound_nbt_with_options(&nbt, &options).is_err());
+}
+
+#[cfg(test)]
+#[test]
+fn chunk_round_trip() {
+    use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+
+    let mut stone = NbtCompound::new();
+    stone.insert("Name", "minecraft:stone");
+    let mut stairs = NbtCompound::new();
+    stairs.insert("Name", "minecraft:oak_stairs");
+    let mut properties = NbtCompound::new();
+    properties.insert("facing", "north");
+    stairs.insert("Properties", properties);
+    let mut block_states = NbtCompound::new();
+    block_states.insert("palette", NbtList::Compound(vec![stone, stairs]));
+    block_states.insert("data", NbtTag::LongArray(vec![0; 256]));
+    let mut biomes = NbtCompound::new();
+    biomes.insert("palette", vec!["minecraft:plains".to_string()]);
+    let mut section = NbtCompound::new();
+    section.insert("Y", -4i8);
+    section.insert("block_states", block_states);
+    section.insert("biomes", biomes);
+    section.insert("BlockLight", NbtTag::ByteArray(vec![15; 2048]));
+
+    let mut name = NbtCompound::new();
+    name.insert("minecraft:custom_name", "\"Stone\"");
+    let mut item = NbtCompound::new();
+    item.insert("Slot", 0i8);
+    item.insert("id", "minecraft:stone");
+    item.insert("count", 1);
+    item.insert("components", name);
+    let mut chest = NbtCompound::new();
+    chest.insert("id", "minecraft:chest");
+    chest.insert("x", 48);
+    chest.insert("y", -60);
+    chest.insert("z", -80);
+    chest.insert("Items", NbtList::Compound(vec![item]));
+    chest.insert("foo", 1);
+
+    let mut entity = NbtCompound::new();
+    entity.insert("id", "minecraft:pig");
+    entity.insert("Pos", NbtList::Double(vec![48.5, -60.0, -79.5]));
+    entity.insert("Saddle", 1i8);
+
+    let mut tick = NbtCompound::new();
+    tick.insert("i", "minecraft:water");
+    tick.insert("p", 0);
+    tick.insert("t", 5);
+    tick.insert("x", 49);
+    tick.insert("y", -60);
+    tick.insert("z", -80);
+
+    let mut heightmaps = NbtCompound::new();
+    heightmaps.insert("WORLD_SURFACE", NbtTag::LongArray(vec![1; 37]));
+
+    let mut start = NbtCompound::new();
+    start.insert("id", "minecraft:mineshaft");
+    start.insert("ChunkX", 3);
+    start.insert("ChunkZ", -5);
+    let mut starts = NbtCompound::new();
+    starts.insert("minecraft:mineshaft", start);
+    let mut references = NbtCompound::new();
+    references.insert(
+        "minecraft:mineshaft",
+        NbtTag::LongArray(vec![(-5i64 << 32) | 3]),
+    );
+    let mut structures = NbtCompound::new();
+    structures.insert("References", references);
+    structures.insert("starts", starts);
+
+    let mut chunk = NbtCompound::new();
+    chunk.insert("DataVersion", 3953);
+    chunk.insert("xPos", 3);
+    chunk.insert("zPos", -5);
+    chunk.insert("yPos", -4);
+    chunk.insert("Status", "minecraft:full");
+    chunk.insert("LastUpdate", 100i64);
+    chunk.insert("InhabitedTime", 20i64);
+    chunk.insert("sections", NbtList::Compound(vec![section]));
+    chunk.insert("block_entities", NbtList::Compound(vec![chest]));
+    chunk.insert("Entities", NbtList::Compound(vec![entity]));
+    chunk.insert("fluid_ticks", NbtList::Compound(vec![tick]));
+    chunk.insert("Heightmaps", heightmaps);
+    chunk.insert(
+        "PostProcessing",
+        NbtList::List(vec![NbtList::Empty, NbtList::Short(vec![0x0a53])]),
+    );
+    chunk.insert("structures", structures);
+    chunk.insert("isLightOn", 1i8);
+
+    let parse = |chunk: &NbtCompound| {
+        let bytes = crate::util::write_root_compound(chunk);
+        let nbt = simdnbt::borrow::read(&mut Cursor::new(bytes.as_slice()))
+            .unwrap()
+            .unwrap();
+        Chunk::from_compound_nbt(&nbt.as_compound()).unwrap()
+    };
+
+    let chunk = parse(&chunk);
+    let written = chunk.to_compound_nbt();
+    assert_eq!(parse(&written), chunk);
+
+    // Unmodelled fields are written back.
+    assert_eq!(written.byte("isLightOn"), Some(1));
+    let chest = &written.list("block_entities").unwrap().compounds().unwrap()[0];
+    assert_eq!(chest.int("foo"), Some(1));
+    let section = &written.list("sections").unwrap().compounds().unwrap()[0];
+    assert_eq!(
+        section.byte_array("BlockLight").map(|light| light.len()),
+        Some(2048)
+    );
+
+    // The light is decoded rather than kept in the section's extra fields.
+    assert_eq!(chunk.sections[0].block_light, Some(vec![15; 2048]));
+    assert_eq!(chunk.sections[0].sky_light, None);
+    assert!(chunk.sections[0].extra.is_empty());
 }
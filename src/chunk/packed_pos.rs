@@ -0,0 +1,106 @@
+use crate::{
+    error::{NbtType, SculkParseError},
+    util::invalid_list_type,
+};
+
+/// A block position inside a chunk section, packed in a short as `x | y << 4 | z << 8`.
+/// Used by `PostProcessing` and `Lights`, which store a list of these per section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PackedSectionPos {
+    /// X position inside the section, 0-15.
+    pub x: u8,
+    /// Y position inside the section, 0-15.
+    pub y: u8,
+    /// Z position inside the section, 0-15.
+    pub z: u8,
+}
+
+impl PackedSectionPos {
+    /// Packs the position back into a short.
+    pub fn to_packed(&self) -> i16 {
+        (self.x as i16 & 15) | (self.y as i16 & 15) << 4 | (self.z as i16 & 15) << 8
+    }
+
+    /// The absolute block position, given the chunk position and the section y.
+    /// The section y is the `yPos` of the chunk plus the index of the list the position is from.
+    pub fn block_pos(&self, chunk_x: i32, section_y: i32, chunk_z: i32) -> [i32; 3] {
+        [
+            chunk_x * 16 + self.x as i32,
+            section_y * 16 + self.y as i32,
+            chunk_z * 16 + self.z as i32,
+        ]
+    }
+}
+
+impl From<i16> for PackedSectionPos {
+    fn from(packed: i16) -> Self {
+        PackedSectionPos {
+            x: (packed & 15) as u8,
+            y: (packed >> 4 & 15) as u8,
+            z: (packed >> 8 & 15) as u8,
+        }
+    }
+}
+
+/// Reads a list of per section short lists, like `PostProcessing` and `Lights`.
+/// Sections without positions are usually stored as empty lists.
+pub(crate) fn get_packed_positions(
+    list: &simdnbt::borrow::NbtList,
+    key: &'static str,
+) -> Result<Vec<Vec<PackedSectionPos>>, SculkParseError> {
+    if list.empty() {
+        return Ok(vec![]);
+    }
+
+    let lists = list
+        .lists()
+        .ok_or_else(|| invalid_list_type(list, key, NbtType::List))?;
+
+    let mut sections: Vec<Vec<PackedSectionPos>> = Vec::with_capacity(lists.len());
+
+    for (index, inner) in lists.into_iter().enumerate() {
+        if inner.empty() {
+            sections.push(vec![]);
+            continue;
+        }
+
+        let shorts = inner
+            .shorts()
+            .ok_or_else(|| invalid_list_type(&inner, key, NbtType::Short).at_index(key, index))?;
+
+        sections.push(shorts.into_iter().map(PackedSectionPos::from).collect());
+    }
+
+    Ok(sections)
+}
+
+/// Writes a list of per section short lists, the inverse of [`get_packed_positions`].
+pub(crate) fn write_packed_positions(
+    sections: &[Vec<PackedSectionPos>],
+) -> simdnbt::owned::NbtList {
+    if sections.is_empty() {
+        return simdnbt::owned::NbtList::Empty;
+    }
+
+    simdnbt::owned::NbtList::List(
+        sections
+            .iter()
+            .map(|positions| match positions.is_empty() {
+                true => simdnbt::owned::NbtList::Empty,
+                false => simdnbt::owned::NbtList::Short(
+                    positions.iter().map(PackedSectionPos::to_packed).collect(),
+                ),
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+#[test]
+fn packed_section_pos() {
+    let pos = PackedSectionPos::from(0x0a53);
+    assert_eq!(pos, PackedSectionPos { x: 3, y: 5, z: 10 });
+    assert_eq!(pos.to_packed(), 0x0a53);
+    assert_eq!(pos.block_pos(-1, -4, 2), [-13, -59, 42]);
+}
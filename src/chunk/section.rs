@@ -1,9 +1,15 @@
 use crate::{
+    block_entities::structure_block::{StructureBlockMirror, StructureBlockRotation},
     error::SculkParseError,
     kv::KVPair,
-    traits::FromCompoundNbt,
-    util::{get_owned_string, get_t_compound_vec},
+    resource_location::{parse_id, ResourceLocation},
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::{
+        get_extra, get_resource_location, get_t_compound_vec, merge_compound, set_optional_tag,
+    },
 };
+use simdnbt::owned::NbtTag;
+use std::{fmt::Display, str::FromStr};
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -16,11 +22,24 @@ pub struct ChunkSection {
 
     pub biomes: Option<Biomes>,
 
+    /// Block light levels, 4 bits per block.  
+    /// `BlockLight`
     pub block_light: Option<Vec<u8>>,
 
+    /// Sky light levels, 4 bits per block.  
+    /// `SkyLight`
     pub sky_light: Option<Vec<u8>>,
+
+    /// Fields that aren't modelled, like data added by mods.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extra: simdnbt::owned::NbtCompound,
 }
 
+/// Fields of a chunk section that are decoded, the rest is kept in [`ChunkSection::extra`].
+/// Also the fields that [`crate::error::ParseMode::Strict`] accepts.
+pub(crate) const SECTION_FIELDS: &[&str] =
+    &["Y", "block_states", "biomes", "BlockLight", "SkyLight"];
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Biomes {
@@ -52,7 +71,7 @@ pub struct BlockStates {
 pub struct Palette {
     /// Block resource location.  
     /// `Name`
-    pub name: String,
+    pub name: ResourceLocation,
 
     /// List of block state properties, with name being the name of the block state property.   
     /// `Properties`
@@ -64,7 +83,7 @@ pub struct Palette {
 pub struct PaletteNoProps {
     /// Block resource location.  
     /// `Name`
-    pub name: String,
+    pub name: ResourceLocation,
 }
 
 impl FromCompoundNbt for ChunkSection {
@@ -87,13 +106,9 @@ impl FromCompoundNbt for ChunkSection {
             None
         };
 
-        let block_light = nbt
-            .byte_array("block_light")
-            .map(|x| x.to_vec());
+        let block_light = nbt.byte_array("BlockLight").map(|x| x.to_vec());
 
-        let sky_light = nbt
-            .byte_array("sky_light")
-            .map(|x| x.to_vec());
+        let sky_light = nbt.byte_array("SkyLight").map(|x| x.to_vec());
 
         Ok(ChunkSection {
             y,
@@ -101,10 +116,43 @@ impl FromCompoundNbt for ChunkSection {
             biomes,
             block_light,
             sky_light,
+            extra: get_extra(nbt, SECTION_FIELDS),
         })
     }
 }
 
+impl ToCompoundNbt for ChunkSection {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("Y", self.y);
+        set_optional_tag(
+            &mut nbt,
+            "block_states",
+            self.block_states.as_ref().map(BlockStates::to_compound_nbt),
+        );
+        set_optional_tag(
+            &mut nbt,
+            "biomes",
+            self.biomes.as_ref().map(Biomes::to_compound_nbt),
+        );
+        set_optional_tag(
+            &mut nbt,
+            "BlockLight",
+            self.block_light.clone().map(NbtTag::ByteArray),
+        );
+        set_optional_tag(
+            &mut nbt,
+            "SkyLight",
+            self.sky_light.clone().map(NbtTag::ByteArray),
+        );
+
+        let mut section = self.extra.clone();
+        merge_compound(&mut section, nbt);
+        section
+    }
+}
+
 impl FromCompoundNbt for Biomes {
     fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
     where
@@ -117,10 +165,12 @@ impl FromCompoundNbt for Biomes {
 
             palette
                 .iter()
-                .map(|s| PaletteNoProps {
-                    name: (*s).to_string(),
+                .map(|s| {
+                    Ok(PaletteNoProps {
+                        name: parse_id(&s.to_str(), "palette")?,
+                    })
                 })
-                .collect::<Vec<PaletteNoProps>>()
+                .collect::<Result<Vec<PaletteNoProps>, SculkParseError>>()?
         } else {
             Vec::new()
         };
@@ -146,6 +196,228 @@ impl FromCompoundNbt for BlockStates {
     }
 }
 
+impl BlockStates {
+    /// Amount of blocks in a section.
+    pub const SIZE: usize = 4096;
+
+    /// Unpacks `data` into 4096 palette indices.  
+    /// The index of a block is `(y * 16 + z) * 16 + x`.
+    pub fn indices(&self) -> Vec<u32> {
+        match &self.data {
+            Some(data) => unpack_indices(data, bits_for_palette(self.palette.len(), 4), Self::SIZE),
+            None => vec![0; Self::SIZE],
+        }
+    }
+
+    /// Gets the block state at the given section relative coordinates.
+    pub fn get(&self, x: usize, y: usize, z: usize) -> Option<&Palette> {
+        let index = (y * 16 + z) * 16 + x;
+        let state = match &self.data {
+            Some(data) => {
+                let bits = bits_for_palette(self.palette.len(), 4);
+                let per_long = 64 / bits;
+                let long = *data.get(index / per_long)? as u64;
+                (long >> ((index % per_long) * bits)) & ((1 << bits) - 1)
+            }
+            None => 0,
+        };
+
+        self.palette.get(state as usize)
+    }
+
+    /// Packs 4096 palette indices into block states.  
+    /// The palette is compacted, so unused states are removed.  
+    /// Fails if an index is outside of the palette.
+    pub fn from_indices(palette: &[Palette], indices: &[u32]) -> Result<Self, SculkParseError> {
+        let mut remap: Vec<Option<u32>> = vec![None; palette.len()];
+        let mut compact_palette = vec![];
+        let mut compact_indices = Vec::with_capacity(indices.len());
+
+        for index in indices {
+            let slot = remap
+                .get_mut(*index as usize)
+                .ok_or_else(|| SculkParseError::InvalidField(format!("palette index {index}")))?;
+            let new_index = *slot.get_or_insert_with(|| {
+                compact_palette.push(palette[*index as usize].clone());
+                (compact_palette.len() - 1) as u32
+            });
+            compact_indices.push(new_index);
+        }
+
+        let data = if compact_palette.len() > 1 {
+            Some(pack_indices(
+                &compact_indices,
+                bits_for_palette(compact_palette.len(), 4),
+            ))
+        } else {
+            None
+        };
+
+        Ok(BlockStates {
+            palette: compact_palette,
+            data,
+        })
+    }
+}
+
+/// The minimum amount of bits needed for a palette of the given length, but at least `min`.
+pub(crate) fn bits_for_palette(len: usize, min: usize) -> usize {
+    let bits = (usize::BITS - len.saturating_sub(1).leading_zeros()) as usize;
+    bits.max(min)
+}
+
+/// Unpacks indices that are not packed across longs, like chunk sections since 1.16.
+pub(crate) fn unpack_indices(data: &[i64], bits: usize, len: usize) -> Vec<u32> {
+    let per_long = 64 / bits;
+    let mask = (1u64 << bits) - 1;
+
+    (0..len)
+        .map(|i| {
+            let long = data.get(i / per_long).copied().unwrap_or(0) as u64;
+            ((long >> ((i % per_long) * bits)) & mask) as u32
+        })
+        .collect()
+}
+
+/// Packs indices without spanning them across longs, like chunk sections since 1.16.
+pub(crate) fn pack_indices(indices: &[u32], bits: usize) -> Vec<i64> {
+    let per_long = 64 / bits;
+    let mut data = vec![0i64; indices.len().div_ceil(per_long)];
+
+    for (i, index) in indices.iter().enumerate() {
+        data[i / per_long] |= ((*index as u64) << ((i % per_long) * bits)) as i64;
+    }
+
+    data
+}
+
+/// Unpacks indices that may span across two longs, like chunk sections before 1.16 and litematics.
+pub(crate) fn unpack_spanning_indices(data: &[i64], bits: usize, len: usize) -> Vec<u32> {
+    let mask = (1u64 << bits) - 1;
+
+    (0..len)
+        .map(|i| {
+            let bit = i * bits;
+            let start = bit / 64;
+            let offset = bit % 64;
+            let low = data.get(start).copied().unwrap_or(0) as u64 >> offset;
+
+            let value = if offset + bits > 64 {
+                let high = data.get(start + 1).copied().unwrap_or(0) as u64;
+                low | (high << (64 - offset))
+            } else {
+                low
+            };
+
+            (value & mask) as u32
+        })
+        .collect()
+}
+
+/// Packs indices so they may span across two longs, like chunk sections before 1.16 and litematics.
+pub(crate) fn pack_spanning_indices(indices: &[u32], bits: usize) -> Vec<i64> {
+    let mut data = vec![0u64; (indices.len() * bits).div_ceil(64)];
+
+    for (i, index) in indices.iter().enumerate() {
+        let bit = i * bits;
+        let start = bit / 64;
+        let offset = bit % 64;
+        let index = *index as u64;
+
+        data[start] |= index << offset;
+        if offset + bits > 64 {
+            data[start + 1] |= index >> (64 - offset);
+        }
+    }
+
+    data.into_iter().map(|long| long as i64).collect()
+}
+
+impl ToCompoundNbt for BlockStates {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        let palette = self.palette.iter().map(|p| p.to_compound_nbt()).collect();
+        nbt.insert("palette", simdnbt::owned::NbtList::Compound(palette));
+        if let Some(data) = &self.data {
+            nbt.insert("data", NbtTag::LongArray(data.clone()));
+        }
+
+        nbt
+    }
+}
+
+impl Biomes {
+    /// Amount of biome cells in a section, one per 4x4x4 blocks.
+    pub const SIZE: usize = 64;
+
+    /// Unpacks `data` into 64 palette indices.  
+    /// The index of a cell is `(y * 4 + z) * 4 + x`.
+    pub fn indices(&self) -> Vec<u32> {
+        match &self.data {
+            Some(data) => unpack_indices(data, bits_for_palette(self.palette.len(), 1), Self::SIZE),
+            None => vec![0; Self::SIZE],
+        }
+    }
+
+    /// Packs 64 palette indices into biomes.  
+    /// The palette is compacted, so unused biomes are removed.  
+    /// Fails if an index is outside of the palette.
+    pub fn from_indices(
+        palette: &[ResourceLocation],
+        indices: &[u32],
+    ) -> Result<Self, SculkParseError> {
+        let mut remap: Vec<Option<u32>> = vec![None; palette.len()];
+        let mut compact_palette = vec![];
+        let mut compact_indices = Vec::with_capacity(indices.len());
+
+        for index in indices {
+            let slot = remap
+                .get_mut(*index as usize)
+                .ok_or_else(|| SculkParseError::InvalidField(format!("palette index {index}")))?;
+            let new_index = *slot.get_or_insert_with(|| {
+                compact_palette.push(PaletteNoProps {
+                    name: palette[*index as usize].clone(),
+                });
+                (compact_palette.len() - 1) as u32
+            });
+            compact_indices.push(new_index);
+        }
+
+        let data = if compact_palette.len() > 1 {
+            Some(pack_indices(
+                &compact_indices,
+                bits_for_palette(compact_palette.len(), 1),
+            ))
+        } else {
+            None
+        };
+
+        Ok(Biomes {
+            palette: compact_palette,
+            data,
+        })
+    }
+}
+
+impl ToCompoundNbt for Biomes {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        let palette = self
+            .palette
+            .iter()
+            .map(|p| p.name.to_string().into())
+            .collect();
+        nbt.insert("palette", simdnbt::owned::NbtList::String(palette));
+        if let Some(data) = &self.data {
+            nbt.insert("data", NbtTag::LongArray(data.clone()));
+        }
+
+        nbt
+    }
+}
+
 impl FromCompoundNbt for PaletteNoProps {
     fn from_compound_nbt(
         nbt: &simdnbt::borrow::NbtCompound,
@@ -153,7 +425,7 @@ impl FromCompoundNbt for PaletteNoProps {
     where
         Self: Sized,
     {
-        let name = get_owned_string(nbt, "name")?;
+        let name = get_resource_location(nbt, "name")?;
 
         Ok(PaletteNoProps { name })
     }
@@ -166,9 +438,203 @@ impl FromCompoundNbt for Palette {
     where
         Self: Sized,
     {
-        let name = get_owned_string(nbt, "Name")?;
-        let properties = KVPair::<String>::from_compound_nbt(nbt)?;
+        let name = get_resource_location(nbt, "Name")?;
+        let properties = match nbt.compound("Properties") {
+            Some(properties) => KVPair::<String>::from_compound_nbt(&properties)?,
+            None => KVPair::default(),
+        };
 
         Ok(Palette { name, properties })
     }
 }
+
+impl ToCompoundNbt for Palette {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+        nbt.insert("Name", self.name.to_string());
+
+        if !self.properties.is_empty() {
+            let mut properties = simdnbt::owned::NbtCompound::new();
+            for (key, value) in self.properties.iter() {
+                properties.insert(key.as_str(), value.as_str());
+            }
+            nbt.insert("Properties", NbtTag::Compound(properties));
+        }
+
+        nbt
+    }
+}
+
+impl Palette {
+    /// Creates a block state with no properties.
+    pub fn new(name: ResourceLocation) -> Self {
+        Palette {
+            name,
+            properties: KVPair::default(),
+        }
+    }
+
+    /// `minecraft:air`, the block state of empty space.
+    pub fn air() -> Self {
+        Palette::new(ResourceLocation::minecraft("air").expect("air is a valid resource location"))
+    }
+
+    /// Whether the block is air, cave air or void air.
+    pub fn is_air(&self) -> bool {
+        self.name.is_minecraft() && matches!(self.name.path(), "air" | "cave_air" | "void_air")
+    }
+}
+
+/// Formats the block state as `minecraft:oak_stairs[facing=north,half=bottom]`.  
+/// Properties are sorted by name so the output is stable.
+impl Display for Palette {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+
+        if !self.properties.is_empty() {
+            let mut properties = self.properties.iter().collect::<Vec<_>>();
+            properties.sort();
+
+            let properties = properties
+                .into_iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect::<Vec<String>>();
+            write!(f, "[{}]", properties.join(","))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a block state string like `minecraft:oak_stairs[facing=north,half=bottom]`.
+impl FromStr for Palette {
+    type Err = SculkParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, properties) = match s.split_once('[') {
+            Some((name, rest)) => (
+                name,
+                rest.strip_suffix(']')
+                    .ok_or(SculkParseError::InvalidField(s.into()))?,
+            ),
+            None => (s, ""),
+        };
+
+        let mut palette = Palette::new(name.parse()?);
+        for property in properties.split(',').filter(|p| !p.is_empty()) {
+            let (key, value) = property
+                .split_once('=')
+                .ok_or(SculkParseError::InvalidField(s.into()))?;
+            palette.properties.insert(key.into(), value.into());
+        }
+
+        Ok(palette)
+    }
+}
+
+const HORIZONTAL: [&str; 4] = ["north", "east", "south", "west"];
+
+impl Palette {
+    /// Returns the block state rotated clockwise by the given rotation.  
+    /// Handles `facing`, `axis`, `rotation` and the `north`/`east`/`south`/`west` connection properties.
+    pub fn rotate(&self, rotation: &StructureBlockRotation) -> Palette {
+        let steps = match rotation {
+            StructureBlockRotation::None => return self.clone(),
+            StructureBlockRotation::Clockwise90 => 1,
+            StructureBlockRotation::Clockwise180 => 2,
+            StructureBlockRotation::CounterClockwise90 => 3,
+        };
+
+        let rotate_direction = |dir: &str| -> Option<&'static str> {
+            let i = HORIZONTAL.iter().position(|d| *d == dir)?;
+            Some(HORIZONTAL[(i + steps) % 4])
+        };
+
+        let mut properties = KVPair::default();
+        for (key, value) in self.properties.iter() {
+            let (key, value) = match key.as_str() {
+                "facing" => (
+                    key.clone(),
+                    rotate_direction(value).map_or(value.clone(), String::from),
+                ),
+                "axis" if steps % 2 == 1 => (
+                    key.clone(),
+                    match value.as_str() {
+                        "x" => "z".into(),
+                        "z" => "x".into(),
+                        _ => value.clone(),
+                    },
+                ),
+                "rotation" => (
+                    key.clone(),
+                    value
+                        .parse::<usize>()
+                        .map_or(value.clone(), |r| ((r + steps * 4) % 16).to_string()),
+                ),
+                _ => (
+                    rotate_direction(key).map_or(key.clone(), String::from),
+                    value.clone(),
+                ),
+            };
+
+            properties.insert(key, value);
+        }
+
+        Palette {
+            name: self.name.clone(),
+            properties,
+        }
+    }
+
+    /// Returns the block state mirrored by the given mirror.  
+    /// Handles the same properties as [`Palette::rotate`].
+    pub fn mirror(&self, mirror: &StructureBlockMirror) -> Palette {
+        let swap: [&str; 2] = match mirror {
+            StructureBlockMirror::None => return self.clone(),
+            StructureBlockMirror::LeftRight => ["north", "south"],
+            StructureBlockMirror::FrontBack => ["east", "west"],
+        };
+
+        let mirror_direction = |dir: &str| -> Option<&'static str> {
+            if dir == swap[0] {
+                Some(swap[1])
+            } else if dir == swap[1] {
+                Some(swap[0])
+            } else {
+                None
+            }
+        };
+
+        let mut properties = KVPair::default();
+        for (key, value) in self.properties.iter() {
+            let (key, value) = match key.as_str() {
+                "facing" => (
+                    key.clone(),
+                    mirror_direction(value).map_or(value.clone(), String::from),
+                ),
+                "rotation" => (
+                    key.clone(),
+                    value.parse::<i32>().map_or(value.clone(), |r| {
+                        let r = if r > 8 { r - 16 } else { r };
+                        match mirror {
+                            StructureBlockMirror::LeftRight => (8 - r + 16) % 16,
+                            _ => (16 - r) % 16,
+                        }
+                        .to_string()
+                    }),
+                ),
+                _ => (
+                    mirror_direction(key).map_or(key.clone(), String::from),
+                    value.clone(),
+                ),
+            };
+
+            properties.insert(key, value);
+        }
+
+        Palette {
+            name: self.name.clone(),
+            properties,
+        }
+    }
+}
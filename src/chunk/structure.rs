@@ -1,16 +1,20 @@
 //! Honestly i dont know what the fuck this shit is but uh i want to fully support everything in chunks soooo?
 
 use crate::{
+    block_entities::structure_block::StructureBlockRotation,
     error::SculkParseError,
     kv::KVPair,
-    traits::FromCompoundNbt,
-    util::{get_owned_optional_string, get_owned_string},
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::{
+        get_extra, get_owned_optional_string, get_owned_string, get_t_compound_vec, merge_compound,
+        set_optional_tag, write_compound_list,
+    },
 };
 use simdnbt::borrow::NbtCompound;
 use std::collections::HashMap;
 
 /// Structure data in this chunk.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Structures {
     /// Coordinates of chunks that contain Starts.   
@@ -32,9 +36,9 @@ pub struct Structure {
     /// The biome id this structure is in. Absent if id is `INVALID`.
     pub biome: Option<String>,
 
-    ///  List of structure pieces making up this structure, that were not generated yet. Absent if id is `INVALID`.  
+    ///  List of structure pieces making up this structure, that were not generated yet. Empty if id is `INVALID`.  
     /// `Children`
-    // pub children: Vec<StructureChild>,
+    pub children: Vec<StructurePiece>,
 
     /// Chunk X coordinate of the start of the structure. Absent if id is `INVALID`.  
     /// `ChunkX`
@@ -56,6 +60,94 @@ pub struct Structure {
     pub valid: Option<bool>,
 }
 
+/// A piece of a structure, like a single room of a stronghold or a house of a village.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructurePiece {
+    /// The piece type, like `minecraft:jigsaw` or `minecraft:shcs` for a stronghold crossing.  
+    /// `id`
+    pub id: String,
+
+    /// Bounding box of the piece, the minimum X, Y, and Z coordinates followed by the maximum X, Y, and Z coordinates.  
+    /// `BB`
+    pub bb: Option<[i32; 6]>,
+
+    /// How many pieces away from the start this piece is.  
+    /// `GD`
+    pub gen_depth: Option<i32>,
+
+    /// Direction of the piece as a 2D data value: 0 south, 1 west, 2 north and 3 east. `None` if the piece has no direction.  
+    /// `O`
+    pub orientation: Option<i32>,
+
+    /// The jigsaw data, only for `minecraft:jigsaw` pieces, which make up villages, trial chambers, bastions and more.
+    pub jigsaw: Option<JigsawPiece>,
+
+    /// Every field that isn't common to all pieces, including the ones parsed into [`StructurePiece::jigsaw`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub data: simdnbt::owned::NbtCompound,
+}
+
+/// Fields every [`StructurePiece`] has, the rest is kept in [`StructurePiece::data`].
+const PIECE_FIELDS: &[&str] = &["id", "BB", "GD", "O"];
+
+/// A piece placed from a template pool, used by jigsaw structures.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JigsawPiece {
+    /// Position the pool element is placed at.  
+    /// `pos_x`, `pos_y`, `pos_z`
+    pub pos: [i32; 3],
+
+    /// Offset of the ground level from the piece's position.  
+    /// `ground_level_delta`
+    pub ground_level_delta: i32,
+
+    /// The element of the template pool this piece was placed from.  
+    /// `pool_element`
+    pub pool_element: PoolElement,
+
+    /// `rotation`
+    pub rotation: StructureBlockRotation,
+
+    /// Connections to other pieces, used to smooth out terrain around them.  
+    /// `junctions`
+    pub junctions: Vec<JigsawJunction>,
+}
+
+/// An element of a template pool.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolElement {
+    /// The element type, like `minecraft:single_pool_element` or `minecraft:feature_pool_element`.  
+    /// `element_type`
+    pub element_type: String,
+
+    /// The structure template placed, for single and legacy pool elements.  
+    /// `location`
+    pub location: Option<String>,
+
+    /// Either `rigid` or `terrain_matching`.  
+    /// `projection`
+    pub projection: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JigsawJunction {
+    /// `source_x`
+    pub source_x: i32,
+    /// `source_ground_y`
+    pub source_ground_y: i32,
+    /// `source_z`
+    pub source_z: i32,
+    /// `delta_y`
+    pub delta_y: i32,
+    /// Either `rigid` or `terrain_matching`.  
+    /// `dest_proj`
+    pub dest_proj: String,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProcessedChunk {
@@ -86,6 +178,26 @@ impl FromCompoundNbt for Structures {
     }
 }
 
+impl Structures {
+    /// The chunks that contain a start of the given structure, like `minecraft:village_plains`.
+    pub fn reference_chunks(&self, structure: &str) -> Vec<(i32, i32)> {
+        self.references
+            .get(structure)
+            .map(|references| {
+                references
+                    .iter()
+                    .map(|&packed| unpack_chunk_pos(packed))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Unpacks a chunk position from [`Structures::references`], X is in the low 32 bits and Z in the high 32 bits.
+pub fn unpack_chunk_pos(packed: i64) -> (i32, i32) {
+    (packed as i32, (packed >> 32) as i32)
+}
+
 impl FromCompoundNbt for KVPair<Vec<i64>> {
     fn from_compound_nbt(nbt: &NbtCompound) -> Result<Self, SculkParseError>
     where
@@ -132,15 +244,10 @@ impl FromCompoundNbt for Structure {
     where
         Self: Sized,
     {
-        let bb = nbt.int_array("BB").map(|arr| {
-            let mut bb = [0; 6];
-            for (i, val) in arr.iter().enumerate() {
-                bb[i] = *val;
-            }
-            bb
-        });
+        let bb = get_bounding_box(nbt);
 
         let biome = get_owned_optional_string(nbt, "biome");
+        let children = get_t_compound_vec(nbt, "Children", StructurePiece::from_compound_nbt)?;
         let chunk_x = nbt.int("ChunkX");
         let chunk_z = nbt.int("ChunkZ");
         let id = get_owned_string(nbt, "id")?;
@@ -163,6 +270,7 @@ impl FromCompoundNbt for Structure {
         Ok(Structure {
             bb,
             biome,
+            children,
             chunk_x,
             chunk_z,
             id,
@@ -172,6 +280,105 @@ impl FromCompoundNbt for Structure {
     }
 }
 
+impl FromCompoundNbt for StructurePiece {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        let id = get_owned_string(nbt, "id")?;
+        let bb = get_bounding_box(nbt);
+        let gen_depth = nbt.int("GD");
+        let orientation = nbt.int("O").filter(|o| *o >= 0);
+
+        let jigsaw = match id.as_str() {
+            "minecraft:jigsaw" => Some(JigsawPiece::from_compound_nbt(nbt)?),
+            _ => None,
+        };
+
+        Ok(StructurePiece {
+            id,
+            bb,
+            gen_depth,
+            orientation,
+            jigsaw,
+            data: get_extra(nbt, PIECE_FIELDS),
+        })
+    }
+}
+
+impl FromCompoundNbt for JigsawPiece {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        let get_int = |key: &'static str| {
+            nbt.int(key)
+                .ok_or(SculkParseError::MissingField(key.into()))
+        };
+
+        let pos = [get_int("pos_x")?, get_int("pos_y")?, get_int("pos_z")?];
+        let ground_level_delta = get_int("ground_level_delta")?;
+
+        let pool_element = nbt
+            .compound("pool_element")
+            .map(|nbt| PoolElement::from_compound_nbt(&nbt))
+            .ok_or(SculkParseError::MissingField("pool_element".into()))?
+            .map_err(|e| e.at_field("pool_element"))?;
+
+        let rotation = nbt
+            .string("rotation")
+            .map(|string| StructureBlockRotation::from(string.to_str().as_ref()))
+            .ok_or(SculkParseError::MissingField("rotation".into()))?;
+
+        let junctions = get_t_compound_vec(nbt, "junctions", JigsawJunction::from_compound_nbt)?;
+
+        Ok(JigsawPiece {
+            pos,
+            ground_level_delta,
+            pool_element,
+            rotation,
+            junctions,
+        })
+    }
+}
+
+impl FromCompoundNbt for PoolElement {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        let element_type = get_owned_string(nbt, "element_type")?;
+        let location = get_owned_optional_string(nbt, "location");
+        let projection = get_owned_optional_string(nbt, "projection");
+
+        Ok(PoolElement {
+            element_type,
+            location,
+            projection,
+        })
+    }
+}
+
+impl FromCompoundNbt for JigsawJunction {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        let get_int = |key: &'static str| {
+            nbt.int(key)
+                .ok_or(SculkParseError::MissingField(key.into()))
+        };
+
+        Ok(JigsawJunction {
+            source_x: get_int("source_x")?,
+            source_ground_y: get_int("source_ground_y")?,
+            source_z: get_int("source_z")?,
+            delta_y: get_int("delta_y")?,
+            dest_proj: get_owned_string(nbt, "dest_proj")?,
+        })
+    }
+}
+
 impl FromCompoundNbt for ProcessedChunk {
     fn from_compound_nbt(
         nbt: &simdnbt::borrow::NbtCompound,
@@ -189,3 +396,160 @@ impl FromCompoundNbt for ProcessedChunk {
         Ok(ProcessedChunk { x, z })
     }
 }
+
+fn get_bounding_box(nbt: &simdnbt::borrow::NbtCompound) -> Option<[i32; 6]> {
+    nbt.int_array("BB").map(|arr| {
+        let mut bb = [0; 6];
+        for (i, val) in arr.iter().take(6).enumerate() {
+            bb[i] = *val;
+        }
+        bb
+    })
+}
+
+impl ToCompoundNbt for Structures {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        let mut references = simdnbt::owned::NbtCompound::new();
+        let mut keys: Vec<&String> = self.references.keys().collect();
+        keys.sort();
+        for key in keys {
+            references.insert(
+                key.as_str(),
+                simdnbt::owned::NbtTag::LongArray(self.references[key].clone()),
+            );
+        }
+        nbt.insert("References", references);
+
+        let mut starts = simdnbt::owned::NbtCompound::new();
+        let mut keys: Vec<&String> = self.starts.keys().collect();
+        keys.sort();
+        for key in keys {
+            starts.insert(key.as_str(), self.starts[key].to_compound_nbt());
+        }
+        nbt.insert("starts", starts);
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for Structure {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_optional_tag(
+            &mut nbt,
+            "BB",
+            self.bb
+                .map(|bb| simdnbt::owned::NbtTag::IntArray(bb.to_vec())),
+        );
+        set_optional_tag(&mut nbt, "biome", self.biome.clone());
+        if !self.children.is_empty() {
+            nbt.insert(
+                "Children",
+                write_compound_list(&self.children, StructurePiece::to_compound_nbt),
+            );
+        }
+        set_optional_tag(&mut nbt, "ChunkX", self.chunk_x);
+        set_optional_tag(&mut nbt, "ChunkZ", self.chunk_z);
+        nbt.insert("id", self.id.as_str());
+        if let Some(processed) = &self.processed {
+            nbt.insert(
+                "processed",
+                write_compound_list(processed, ProcessedChunk::to_compound_nbt),
+            );
+        }
+        set_optional_tag(&mut nbt, "Valid", self.valid);
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for StructurePiece {
+    /// The jigsaw fields are written from [`StructurePiece::data`], which they are parsed from.
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("id", self.id.as_str());
+        set_optional_tag(
+            &mut nbt,
+            "BB",
+            self.bb
+                .map(|bb| simdnbt::owned::NbtTag::IntArray(bb.to_vec())),
+        );
+        set_optional_tag(&mut nbt, "GD", self.gen_depth);
+        // Pieces without a direction are stored as -1.
+        nbt.insert("O", self.orientation.unwrap_or(-1));
+
+        let mut data = self.data.clone();
+        merge_compound(&mut data, nbt);
+        data
+    }
+}
+
+impl ToCompoundNbt for ProcessedChunk {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+        nbt.insert("X", self.x);
+        nbt.insert("Z", self.z);
+        nbt
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn structure_children() {
+    use crate::util::write_root_compound;
+    use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+
+    let mut pool_element = NbtCompound::new();
+    pool_element.insert("element_type", "minecraft:single_pool_element");
+    pool_element.insert(
+        "location",
+        "minecraft:village/plains/houses/plains_small_house_1",
+    );
+    pool_element.insert("projection", "rigid");
+
+    let mut piece = NbtCompound::new();
+    piece.insert("id", "minecraft:jigsaw");
+    piece.insert("BB", NbtTag::IntArray(vec![0, 64, 0, 8, 72, 8]));
+    piece.insert("GD", 1);
+    piece.insert("O", -1);
+    piece.insert("pos_x", 0);
+    piece.insert("pos_y", 64);
+    piece.insert("pos_z", 0);
+    piece.insert("ground_level_delta", 1);
+    piece.insert("pool_element", NbtTag::Compound(pool_element));
+    piece.insert("rotation", "CLOCKWISE_90");
+    piece.insert("junctions", NbtList::Empty);
+
+    let mut start = NbtCompound::new();
+    start.insert("id", "minecraft:village_plains");
+    start.insert("ChunkX", 0);
+    start.insert("ChunkZ", 0);
+    start.insert("Children", NbtList::Compound(vec![piece]));
+
+    let bytes = write_root_compound(&start);
+    let nbt = simdnbt::borrow::read(&mut std::io::Cursor::new(bytes.as_slice()))
+        .unwrap()
+        .unwrap();
+    let structure = Structure::from_compound_nbt(&nbt.as_compound()).unwrap();
+
+    let piece = &structure.children[0];
+    assert_eq!(piece.orientation, None);
+    assert_eq!(piece.bb, Some([0, 64, 0, 8, 72, 8]));
+    let jigsaw = piece.jigsaw.as_ref().unwrap();
+    assert_eq!(jigsaw.rotation, StructureBlockRotation::Clockwise90);
+    assert_eq!(
+        jigsaw.pool_element.location.as_deref(),
+        Some("minecraft:village/plains/houses/plains_small_house_1")
+    );
+    assert!(piece.data.contains("pool_element"));
+
+    assert_eq!(unpack_chunk_pos((-5i64 << 32) | 3), (3, -5));
+    assert_eq!(
+        unpack_chunk_pos((7i64 << 32) | (-2i32 as u32 as i64)),
+        (-2, 7)
+    );
+}
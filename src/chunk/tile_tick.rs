@@ -1,10 +1,15 @@
-use crate::{error::SculkParseError, traits::FromCompoundNbt, util::get_owned_string};
+use crate::{
+    error::SculkParseError,
+    resource_location::ResourceLocation,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::get_resource_location,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TileTick {
     /// The ID of the block; used to activate the correct block update procedure.
-    pub i: String,
+    pub i: ResourceLocation,
 
     /// If multiple tile ticks are scheduled for the same tick, tile ticks with lower p are processed first. If they also have the same p, the order is unknown.
     pub p: i32,
@@ -27,7 +32,7 @@ impl FromCompoundNbt for TileTick {
     where
         Self: Sized,
     {
-        let i = get_owned_string(nbt, "i")?;
+        let i = get_resource_location(nbt, "i")?;
         let p = nbt
             .int("p")
             .ok_or(SculkParseError::MissingField("p".into()))?;
@@ -48,3 +53,18 @@ impl FromCompoundNbt for TileTick {
         Ok(TileTick { i, p, t, x, y, z })
     }
 }
+
+impl ToCompoundNbt for TileTick {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("i", self.i.to_string());
+        nbt.insert("p", self.p);
+        nbt.insert("t", self.t);
+        nbt.insert("x", self.x);
+        nbt.insert("y", self.y);
+        nbt.insert("z", self.z);
+
+        nbt
+    }
+}
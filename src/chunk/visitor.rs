@@ -0,0 +1,192 @@
+use super::{structure::Structure, Chunk};
+use crate::{
+    block_entity::LazyBlockEntity,
+    borrowed::{BorrowedChunkSection, BorrowedTileTick},
+    error::SculkParseError,
+    traits::{FromBorrowedNbt, FromCompoundNbt},
+    version::DataVersionScope,
+};
+use simdnbt::borrow::NbtCompound;
+use std::io::Cursor;
+
+/// Callbacks for [`Chunk::visit`], called as the chunk nbt is walked.
+/// Every method does nothing by default, so only implement the ones you need.
+///
+/// Everything passed in borrows from the chunk's nbt, and is only valid during the call.
+pub trait ChunkVisitor {
+    /// Called for every section, with its block state palette and biomes.
+    #[allow(unused_variables)]
+    fn visit_section(&mut self, section: &BorrowedChunkSection) {}
+
+    /// Called for every block entity, without parsing its specific data.
+    /// Data parsed during the call is read with the chunk's `DataVersion`.
+    #[allow(unused_variables)]
+    fn visit_block_entity(&mut self, block_entity: &LazyBlockEntity) {}
+
+    /// Called for every entity in `Entities`, only found in proto-chunks.
+    #[allow(unused_variables)]
+    fn visit_entity(&mut self, entity: &NbtCompound) {}
+
+    /// Called for every scheduled block and fluid tick.
+    #[allow(unused_variables)]
+    fn visit_tile_tick(&mut self, tick: &BorrowedTileTick, kind: TileTickKind) {}
+
+    /// Called for every structure start, with the structure's id.
+    #[allow(unused_variables)]
+    fn visit_structure_start(&mut self, id: &str, structure: &Structure) {}
+}
+
+/// Which list a tile tick is from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TileTickKind {
+    /// `block_ticks`
+    Block,
+    /// `fluid_ticks`
+    Fluid,
+}
+
+impl Chunk {
+    /// Walks the chunk nbt and calls the visitor for each part, without building a [`Chunk`].
+    /// Keeps memory flat when scanning whole worlds, since nothing outlives the call.
+    pub fn visit(bytes: &[u8], visitor: &mut impl ChunkVisitor) -> Result<(), SculkParseError> {
+        let nbt = simdnbt::borrow::read(&mut Cursor::new(bytes))?;
+
+        let nbt = match nbt {
+            simdnbt::borrow::Nbt::Some(nbt) => nbt,
+            simdnbt::borrow::Nbt::None => return Err(SculkParseError::NoNbt),
+        };
+
+        Chunk::visit_compound_nbt(&nbt.as_compound(), visitor)
+    }
+
+    /// Same as [`Chunk::visit`], but on already read chunk nbt.
+    pub fn visit_compound_nbt<'a>(
+        nbt: &NbtCompound<'a, 'a>,
+        visitor: &mut impl ChunkVisitor,
+    ) -> Result<(), SculkParseError> {
+        let data_version = nbt
+            .int("DataVersion")
+            .ok_or(SculkParseError::MissingField("DataVersion".into()))?;
+        let _data_version = DataVersionScope::enter(data_version);
+
+        for section in compounds(nbt, "sections")? {
+            visitor.visit_section(&BorrowedChunkSection::from_borrowed_nbt(&section)?);
+        }
+
+        for block_entity in compounds(nbt, "block_entities")? {
+            visitor.visit_block_entity(&LazyBlockEntity::from_borrowed_nbt(&block_entity)?);
+        }
+
+        for entity in compounds(nbt, "Entities")? {
+            visitor.visit_entity(&entity);
+        }
+
+        for (key, kind) in [
+            ("block_ticks", TileTickKind::Block),
+            ("fluid_ticks", TileTickKind::Fluid),
+        ] {
+            for tick in compounds(nbt, key)? {
+                visitor.visit_tile_tick(&BorrowedTileTick::from_borrowed_nbt(&tick)?, kind);
+            }
+        }
+
+        if let Some(starts) = nbt
+            .compound("structures")
+            .and_then(|structures| structures.compound("starts"))
+        {
+            for (id, start) in starts.iter() {
+                let id = id.to_str();
+                let start = start
+                    .compound()
+                    .ok_or(SculkParseError::InvalidField(id.to_string()))?;
+
+                visitor.visit_structure_start(&id, &Structure::from_compound_nbt(&start)?);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The compounds in a list, empty if the list is missing or empty.
+fn compounds<'a>(
+    nbt: &NbtCompound<'a, 'a>,
+    key: &'static str,
+) -> Result<Vec<NbtCompound<'a, 'a>>, SculkParseError> {
+    match nbt.list(key) {
+        Some(list) if !list.empty() => Ok(list
+            .compounds()
+            .ok_or(SculkParseError::InvalidField(key.into()))?
+            .into_iter()
+            .collect()),
+        _ => Ok(vec![]),
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn visit_chunk() {
+    use crate::{
+        util::write_root_compound,
+        version::{current_data_version, V1_21_5},
+    };
+    use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+
+    #[derive(Default)]
+    struct Counter {
+        palette: Vec<String>,
+        block_entities: Vec<(String, i32)>,
+        ticks: usize,
+    }
+
+    impl ChunkVisitor for Counter {
+        fn visit_section(&mut self, section: &BorrowedChunkSection) {
+            if let Some(block_states) = &section.block_states {
+                for palette in &block_states.palette {
+                    self.palette.push(palette.name.to_string());
+                }
+            }
+        }
+
+        fn visit_block_entity(&mut self, block_entity: &LazyBlockEntity) {
+            self.block_entities
+                .push((block_entity.base.id.to_string(), current_data_version()));
+        }
+
+        fn visit_tile_tick(&mut self, _: &BorrowedTileTick, _: TileTickKind) {
+            self.ticks += 1;
+        }
+    }
+
+    let mut palette = NbtCompound::new();
+    palette.insert("Name", "minecraft:chest");
+    let mut block_states = NbtCompound::new();
+    block_states.insert("palette", NbtList::Compound(vec![palette]));
+    let mut section = NbtCompound::new();
+    section.insert("Y", 0i8);
+    section.insert("block_states", NbtTag::Compound(block_states));
+
+    let mut chest = NbtCompound::new();
+    chest.insert("id", "minecraft:chest");
+    chest.insert("x", 0);
+    chest.insert("y", 0);
+    chest.insert("z", 0);
+
+    let mut chunk = NbtCompound::new();
+    chunk.insert("DataVersion", V1_21_5);
+    chunk.insert("sections", NbtList::Compound(vec![section]));
+    chunk.insert("block_entities", NbtList::Compound(vec![chest]));
+    chunk.insert("block_ticks", NbtList::Empty);
+
+    let mut counter = Counter::default();
+    Chunk::visit(&write_root_compound(&chunk), &mut counter).unwrap();
+
+    assert_eq!(counter.palette, vec!["minecraft:chest"]);
+    // The block entity is visited with the chunk's data version.
+    assert_eq!(
+        counter.block_entities,
+        vec![("minecraft:chest".to_string(), V1_21_5)]
+    );
+    assert_eq!(counter.ticks, 0);
+}
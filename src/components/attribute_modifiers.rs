@@ -2,8 +2,8 @@
 
 use crate::{
     error::SculkParseError,
-    traits::FromCompoundNbt,
-    util::{get_bool, get_owned_string, get_t_list},
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::{get_owned_string, get_t_list, write_compound_list},
 };
 
 /// A compound of attribute modifiers.
@@ -123,7 +123,7 @@ impl FromCompoundNbt for AttributeModifiers {
     where
         Self: Sized,
     {
-        let show_in_tooltip = get_bool(nbt, "show_in_tooltip");
+        let show_in_tooltip = nbt.byte("show_in_tooltip").map(|b| b != 0).unwrap_or(true);
 
         let modifiers: Vec<Modifier> = match nbt.list("modifiers") {
             Some(modifiers) => get_t_list(&modifiers, "modifiers", Modifier::from_compound_nbt)?,
@@ -197,3 +197,74 @@ impl Operation {
         }
     }
 }
+
+impl AttributeModifier {
+    /// The value of the component, the inverse of [`AttributeModifier::from_compound_nbt`].
+    pub fn to_tag(&self) -> simdnbt::owned::NbtTag {
+        match self {
+            AttributeModifier::ModifierList(modifiers) => simdnbt::owned::NbtTag::List(
+                write_compound_list(modifiers, Modifier::to_compound_nbt),
+            ),
+            AttributeModifier::Compound(modifiers) => {
+                simdnbt::owned::NbtTag::Compound(modifiers.to_compound_nbt())
+            }
+        }
+    }
+}
+
+impl ToCompoundNbt for AttributeModifiers {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert(
+            "modifiers",
+            write_compound_list(&self.modifiers, Modifier::to_compound_nbt),
+        );
+        if !self.show_in_tooltip {
+            nbt.insert("show_in_tooltip", false);
+        }
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for Modifier {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("type", self.r#type.as_str());
+        nbt.insert("slot", self.slot.to_str());
+        nbt.insert("id", self.id.as_str());
+        nbt.insert("amount", self.amount);
+        nbt.insert("operation", self.operation.to_str());
+
+        nbt
+    }
+}
+
+impl SlotType {
+    fn to_str(&self) -> &'static str {
+        match self {
+            SlotType::Any => "any",
+            SlotType::Hand => "hand",
+            SlotType::Armor => "armor",
+            SlotType::MainHand => "mainhand",
+            SlotType::OffHand => "offhand",
+            SlotType::Head => "head",
+            SlotType::Chest => "chest",
+            SlotType::Legs => "legs",
+            SlotType::Feet => "feet",
+            SlotType::Body => "body",
+        }
+    }
+}
+
+impl Operation {
+    fn to_str(&self) -> &'static str {
+        match self {
+            Operation::AddValue => "add_value",
+            Operation::AddMultipliedBase => "add_multiplied_base",
+            Operation::AddMultipliedTotal => "add_multiplied_total",
+        }
+    }
+}
@@ -19,3 +19,10 @@ impl FromCompoundNbt for BaseColor {
         Ok(BaseColor(base_color))
     }
 }
+
+impl BaseColor {
+    /// The value of the component, the inverse of [`BaseColor::from_compound_nbt`].
+    pub fn to_tag(&self) -> simdnbt::owned::NbtTag {
+        simdnbt::owned::NbtTag::String(self.0.as_str().into())
+    }
+}
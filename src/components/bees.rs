@@ -1,6 +1,10 @@
 //! Bee component for entities in a hive.
 
-use crate::{entity::Entity, error::SculkParseError, traits::FromCompoundNbt};
+use crate::{
+    entity::Entity,
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+};
 
 /// A bee entity in a hive.
 #[derive(Debug, Clone, PartialEq)]
@@ -42,3 +46,18 @@ impl FromCompoundNbt for Bee {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for Bee {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert(
+            "entity_data",
+            self.entity_data.to_compound_nbt_with_version(data_version),
+        );
+        nbt.insert("min_ticks_in_hive", self.min_ticks_in_hive);
+        nbt.insert("ticks_in_hive", self.ticks_in_hive);
+
+        nbt
+    }
+}
@@ -0,0 +1,208 @@
+//! Blocks attacks component, added in 1.21.5. Used by shields.
+
+use super::{
+    id_set::{get_id_set, IdSet},
+    instrument::{get_sound_event, SoundEvent},
+};
+use crate::{
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::{get_owned_optional_string, get_t_compound_vec, set_optional_tag, write_compound_list},
+};
+
+/// An item that blocks attacks while it's being used.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlocksAttacks {
+    /// How long the item has to be used before it blocks, in seconds. Defaults to 0.
+    pub block_delay_seconds: f32,
+
+    /// The multiplier for how long the item is disabled when hit by a weapon that disables blocking. Defaults to 1.
+    pub disable_cooldown_scale: f32,
+
+    /// How much damage is blocked, by damage type. Defaults to fully blocking all damage.
+    pub damage_reductions: Vec<DamageReduction>,
+
+    /// How much durability the item loses when blocking.
+    pub item_damage: ItemDamage,
+
+    /// The damage type tag of damage that bypasses blocking. Optional.
+    pub bypassed_by: Option<String>,
+
+    /// The sound played when an attack is blocked. Optional.
+    pub block_sound: Option<SoundEvent>,
+
+    /// The sound played when the item is disabled. Optional.
+    pub disabled_sound: Option<SoundEvent>,
+}
+
+/// How much of a damage type is blocked, `base + factor * damage`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DamageReduction {
+    /// The max angle between the attack and where the blocker is facing, in degrees. Defaults to 90.
+    pub horizontal_blocking_angle: f32,
+
+    /// The damage types this reduction applies to. Optional, applies to all if omitted.
+    pub r#type: Option<IdSet>,
+
+    /// The constant amount of damage blocked.
+    pub base: f32,
+
+    /// The fraction of the damage blocked.
+    pub factor: f32,
+}
+
+/// How much durability is lost when blocking, `base + factor * damage` if the damage is at least the threshold.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ItemDamage {
+    /// The minimum damage blocked for the item to lose durability.
+    pub threshold: f32,
+
+    /// The constant amount of durability lost.
+    pub base: f32,
+
+    /// The fraction of the damage lost as durability.
+    pub factor: f32,
+}
+
+impl Default for ItemDamage {
+    fn default() -> Self {
+        ItemDamage {
+            threshold: 1.0,
+            base: 0.0,
+            factor: 1.0,
+        }
+    }
+}
+
+impl FromCompoundNbt for BlocksAttacks {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        let damage_reductions = match nbt.get("damage_reductions") {
+            Some(_) => {
+                get_t_compound_vec(nbt, "damage_reductions", DamageReduction::from_compound_nbt)?
+            }
+            None => vec![DamageReduction {
+                horizontal_blocking_angle: 90.0,
+                r#type: None,
+                base: 0.0,
+                factor: 1.0,
+            }],
+        };
+
+        let item_damage = match nbt.compound("item_damage") {
+            Some(nbt) => ItemDamage::from_compound_nbt(&nbt)?,
+            None => ItemDamage::default(),
+        };
+
+        let get_optional_sound = |key: &'static str| match nbt.get(key) {
+            Some(_) => get_sound_event(nbt, key).map(Some),
+            None => Ok(None),
+        };
+
+        Ok(BlocksAttacks {
+            block_delay_seconds: nbt.float("block_delay_seconds").unwrap_or(0.0),
+            disable_cooldown_scale: nbt.float("disable_cooldown_scale").unwrap_or(1.0),
+            damage_reductions,
+            item_damage,
+            bypassed_by: get_owned_optional_string(nbt, "bypassed_by"),
+            block_sound: get_optional_sound("block_sound")?,
+            disabled_sound: get_optional_sound("disabled_sound")?,
+        })
+    }
+}
+
+impl FromCompoundNbt for DamageReduction {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        let base = nbt
+            .float("base")
+            .ok_or(SculkParseError::MissingField("base".into()))?;
+        let factor = nbt
+            .float("factor")
+            .ok_or(SculkParseError::MissingField("factor".into()))?;
+
+        Ok(DamageReduction {
+            horizontal_blocking_angle: nbt.float("horizontal_blocking_angle").unwrap_or(90.0),
+            r#type: get_id_set(nbt, "type")?,
+            base,
+            factor,
+        })
+    }
+}
+
+impl FromCompoundNbt for ItemDamage {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        let get_float = |key: &'static str| {
+            nbt.float(key)
+                .ok_or(SculkParseError::MissingField(key.into()))
+        };
+
+        Ok(ItemDamage {
+            threshold: get_float("threshold")?,
+            base: get_float("base")?,
+            factor: get_float("factor")?,
+        })
+    }
+}
+
+impl ToCompoundNbt for BlocksAttacks {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("block_delay_seconds", self.block_delay_seconds);
+        nbt.insert("disable_cooldown_scale", self.disable_cooldown_scale);
+        nbt.insert(
+            "damage_reductions",
+            write_compound_list(&self.damage_reductions, DamageReduction::to_compound_nbt),
+        );
+        nbt.insert("item_damage", self.item_damage.to_compound_nbt());
+        set_optional_tag(&mut nbt, "bypassed_by", self.bypassed_by.as_deref());
+        set_optional_tag(
+            &mut nbt,
+            "block_sound",
+            self.block_sound.as_ref().map(SoundEvent::to_tag),
+        );
+        set_optional_tag(
+            &mut nbt,
+            "disabled_sound",
+            self.disabled_sound.as_ref().map(SoundEvent::to_tag),
+        );
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for DamageReduction {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("horizontal_blocking_angle", self.horizontal_blocking_angle);
+        set_optional_tag(&mut nbt, "type", self.r#type.as_ref().map(IdSet::to_tag));
+        nbt.insert("base", self.base);
+        nbt.insert("factor", self.factor);
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for ItemDamage {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("threshold", self.threshold);
+        nbt.insert("base", self.base);
+        nbt.insert("factor", self.factor);
+
+        nbt
+    }
+}
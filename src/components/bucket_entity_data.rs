@@ -1,6 +1,9 @@
 //! Bucket entity data component.
 
-use crate::traits::FromCompoundNbt;
+use crate::{
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::set_optional_tag,
+};
 use simdnbt::borrow::NbtCompound;
 
 /// Represents the bucket entity data component.  
@@ -66,8 +69,11 @@ impl FromCompoundNbt for BucketEntityData {
     where
         Self: Sized,
     {
+        // Written as bytes, but older data may have them as ints.
         fn bool(nbt: &NbtCompound, key: &'static str) -> Option<bool> {
-            nbt.int(key).map(|b| b != 0)
+            nbt.byte(key)
+                .map(|b| b != 0)
+                .or_else(|| nbt.int(key).map(|i| i != 0))
         }
 
         let no_ai = bool(nbt, "NoAI");
@@ -95,3 +101,22 @@ impl FromCompoundNbt for BucketEntityData {
         })
     }
 }
+
+impl ToCompoundNbt for BucketEntityData {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_optional_tag(&mut nbt, "NoAI", self.no_ai);
+        set_optional_tag(&mut nbt, "Silent", self.silent);
+        set_optional_tag(&mut nbt, "NoGravity", self.no_gravity);
+        set_optional_tag(&mut nbt, "Glowing", self.glowing);
+        set_optional_tag(&mut nbt, "Invulnerable", self.invulnerable);
+        set_optional_tag(&mut nbt, "Health", self.health);
+        set_optional_tag(&mut nbt, "Age", self.age);
+        set_optional_tag(&mut nbt, "Variant", self.variant);
+        set_optional_tag(&mut nbt, "HuntingCooldown", self.hunting_cooldown);
+        set_optional_tag(&mut nbt, "BucketVariantTag", self.bucket_variant_tag);
+
+        nbt
+    }
+}
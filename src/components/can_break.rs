@@ -2,7 +2,11 @@
 
 use super::block_state::BlockState;
 use crate::{
-    block_entity::BlockEntity, error::SculkParseError, traits::FromCompoundNbt, util::get_t_list,
+    block_entity::BlockEntity,
+    error::SculkParseError,
+    resource_location::IdOrTag,
+    traits::{FromCompoundNbt, ToCompoundNbt, ToVersionedCompoundNbt},
+    util::{get_t_list, write_compound_list},
 };
 
 /// If an item can break blocks.
@@ -55,10 +59,10 @@ pub struct Predicate {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Blocks {
     /// A single block ID or block tag with a #.
-    Block(String),
+    Block(IdOrTag),
 
     /// A list of block IDs or block tags with a #.
-    Blocks(Vec<String>),
+    Blocks(Vec<IdOrTag>),
 }
 
 impl FromCompoundNbt for CanBreak {
@@ -66,7 +70,7 @@ impl FromCompoundNbt for CanBreak {
     where
         Self: Sized,
     {
-        if nbt.compound("state").is_some() {
+        if nbt.get("blocks").is_some() {
             // Single
             let blocks = Blocks::from_compound_nbt(nbt)?;
             let struct_nbt = if let Some(nbt) = nbt.compound("nbt") {
@@ -108,6 +112,25 @@ impl FromCompoundNbt for CanBreak {
     }
 }
 
+impl CanBreak {
+    /// Reads the component value, which is a list of predicates since 1.21.5.
+    pub(crate) fn from_tag(
+        value: &simdnbt::borrow::NbtTag,
+        key: &'static str,
+    ) -> Result<Self, SculkParseError> {
+        if let Some(nbt) = value.compound() {
+            CanBreak::from_compound_nbt(&nbt)
+        } else if let Some(list) = value.list() {
+            Ok(CanBreak::List {
+                predicates: get_t_list(&list, key, Predicate::from_compound_nbt)?,
+                show_in_tooltip: true,
+            })
+        } else {
+            Err(SculkParseError::InvalidField(key.into()))
+        }
+    }
+}
+
 impl FromCompoundNbt for Predicate {
     fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
     where
@@ -143,14 +166,20 @@ impl FromCompoundNbt for Blocks {
         Self: Sized,
     {
         if let Some(string) = nbt.string("blocks") {
-            Ok(Blocks::Block(string.to_string()))
+            Ok(Blocks::Block(
+                string
+                    .to_str()
+                    .parse()
+                    .map_err(|err: SculkParseError| err.at_field("blocks"))?,
+            ))
         } else if let Some(list) = nbt.list("blocks") {
             let blocks = list
                 .strings()
                 .ok_or(SculkParseError::InvalidField("blocks".into()))?
                 .iter()
-                .map(|string| (*string).to_string())
-                .collect::<Vec<String>>();
+                .map(|string| string.to_str().parse())
+                .collect::<Result<Vec<IdOrTag>, SculkParseError>>()
+                .map_err(|err| err.at_field("blocks"))?;
 
             return Ok(Blocks::Blocks(blocks));
         } else {
@@ -158,3 +187,105 @@ impl FromCompoundNbt for Blocks {
         }
     }
 }
+
+impl CanBreak {
+    /// The value of the component, the inverse of how it's parsed.
+    pub fn to_tag(&self, data_version: i32) -> simdnbt::owned::NbtTag {
+        match self {
+            CanBreak::List {
+                predicates,
+                show_in_tooltip: true,
+            } if data_version >= crate::version::V1_21_5 => {
+                simdnbt::owned::NbtTag::List(write_compound_list(predicates, |predicate| {
+                    predicate.to_compound_nbt_with_version(data_version)
+                }))
+            }
+            _ => simdnbt::owned::NbtTag::Compound(self.to_compound_nbt_with_version(data_version)),
+        }
+    }
+}
+
+impl ToVersionedCompoundNbt for CanBreak {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let (mut nbt, show_in_tooltip) = match self {
+            CanBreak::List {
+                predicates,
+                show_in_tooltip,
+            } => {
+                let mut nbt = simdnbt::owned::NbtCompound::new();
+                nbt.insert(
+                    "predicates",
+                    write_compound_list(predicates, |predicate| {
+                        predicate.to_compound_nbt_with_version(data_version)
+                    }),
+                );
+                (nbt, *show_in_tooltip)
+            }
+            CanBreak::Single {
+                blocks,
+                nbt,
+                state,
+                show_in_tooltip,
+            } => (
+                write_predicate(blocks, nbt.as_ref().as_ref(), state.as_ref(), data_version),
+                *show_in_tooltip,
+            ),
+        };
+
+        if !show_in_tooltip {
+            nbt.insert("show_in_tooltip", false);
+        }
+
+        nbt
+    }
+}
+
+impl ToVersionedCompoundNbt for Predicate {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        write_predicate(
+            &self.blocks,
+            self.nbt.as_ref(),
+            self.state.as_ref(),
+            data_version,
+        )
+    }
+}
+
+fn write_predicate(
+    blocks: &Blocks,
+    block_entity: Option<&BlockEntity>,
+    state: Option<&BlockState>,
+    data_version: i32,
+) -> simdnbt::owned::NbtCompound {
+    let mut nbt = simdnbt::owned::NbtCompound::new();
+
+    nbt.insert("blocks", blocks.to_tag());
+    if let Some(block_entity) = block_entity {
+        nbt.insert(
+            "nbt",
+            block_entity.to_compound_nbt_with_version(data_version),
+        );
+    }
+    if let Some(state) = state {
+        nbt.insert("state", state.to_compound_nbt());
+    }
+
+    nbt
+}
+
+impl Blocks {
+    /// The value of the `blocks` field, the inverse of [`Blocks::from_compound_nbt`].
+    pub fn to_tag(&self) -> simdnbt::owned::NbtTag {
+        match self {
+            Blocks::Block(block) => simdnbt::owned::NbtTag::String(block.to_string().into()),
+            Blocks::Blocks(blocks) => {
+                simdnbt::owned::NbtTag::List(simdnbt::owned::NbtList::String(
+                    blocks
+                        .iter()
+                        .map(|block| block.to_string().into())
+                        .collect(),
+                ))
+            }
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! The keys of the components sculk knows about, and a typed getter on [`Components`] for each.
+
+use super::*;
+use crate::error::SculkParseError;
+use std::{fmt, str::FromStr};
+
+/// Declares [`ComponentType`] from a table of `Variant getter "key" kind Type;` rows,
+/// along with [`Component::component_type`] and the typed getters on [`Components`].  
+/// The kind says how the getter hands out the value: `value` by reference, `copy` by value,
+/// `slice` a `Vec` as a slice and `boxed` a `Box` as a reference to its contents.
+macro_rules! component_types {
+    ($($variant:ident $getter:ident $key:literal $kind:ident $ty:ty;)*) => {
+        /// The key of a [`Component`](super::Component), like `minecraft:enchantments`.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub enum ComponentType {
+            $(
+                #[doc = concat!("`", $key, "`")]
+                $variant,
+            )*
+        }
+
+        impl ComponentType {
+            /// Every component type, in the same order as [`Component`](super::Component).
+            pub const ALL: &'static [ComponentType] = &[$(ComponentType::$variant),*];
+
+            /// The key of the component, like `minecraft:enchantments`.
+            pub fn key(&self) -> &'static str {
+                match self {
+                    $(ComponentType::$variant => $key,)*
+                }
+            }
+        }
+
+        impl Component {
+            /// The type of the component.
+            /// Returns `None` for components without a fixed key, like [`Component::EntityVariant`], [`Component::Removed`] and [`Component::Unknown`].
+            pub fn component_type(&self) -> Option<ComponentType> {
+                match self {
+                    $(Component::$variant(_) => Some(ComponentType::$variant),)*
+                    Component::EntityVariant(_) | Component::Removed | Component::Unknown(_) => None,
+                }
+            }
+        }
+
+        /// Typed getters for each component, so they can be read without matching on [`Component`].
+        impl Components {
+            $(component_types!(@getter $variant $getter $key $kind $ty);)*
+        }
+    };
+
+    (@getter $variant:ident $getter:ident $key:literal value $ty:ty) => {
+        #[doc = concat!("`", $key, "`")]
+        pub fn $getter(&self) -> Option<&$ty> {
+            match self.get($key) {
+                Some(Component::$variant(value)) => Some(value),
+                _ => None,
+            }
+        }
+    };
+    (@getter $variant:ident $getter:ident $key:literal copy $ty:ty) => {
+        #[doc = concat!("`", $key, "`")]
+        pub fn $getter(&self) -> Option<$ty> {
+            match self.get($key) {
+                Some(Component::$variant(value)) => Some(*value),
+                _ => None,
+            }
+        }
+    };
+    (@getter $variant:ident $getter:ident $key:literal slice $ty:ty) => {
+        #[doc = concat!("`", $key, "`")]
+        pub fn $getter(&self) -> Option<&[$ty]> {
+            match self.get($key) {
+                Some(Component::$variant(value)) => Some(value.as_slice()),
+                _ => None,
+            }
+        }
+    };
+    (@getter $variant:ident $getter:ident $key:literal boxed $ty:ty) => {
+        #[doc = concat!("`", $key, "`")]
+        pub fn $getter(&self) -> Option<&$ty> {
+            match self.get($key) {
+                Some(Component::$variant(value)) => Some(value.as_ref()),
+                _ => None,
+            }
+        }
+    };
+}
+
+component_types! {
+    AttributeModifiers attribute_modifiers "minecraft:attribute_modifiers" value attribute_modifiers::AttributeModifier;
+    BannerPatterns banner_patterns "minecraft:banner_patterns" slice banner_patterns::BannerPattern;
+    BaseColor base_color "minecraft:base_color" value base_color::BaseColor;
+    Bees bees "minecraft:bees" slice bees::Bee;
+    BlocksAttacks blocks_attacks "minecraft:blocks_attacks" value blocks_attacks::BlocksAttacks;
+    BlockEntityData block_entity_data "minecraft:block_entity_data" boxed NoCoordinatesBlockEntity;
+    BlockState block_state "minecraft:block_state" value block_state::BlockState;
+    BreakSound break_sound "minecraft:break_sound" value instrument::SoundEvent;
+    BucketEntityData bucket_entity_data "minecraft:bucket_entity_data" value bucket_entity_data::BucketEntityData;
+    BundleContents bundle_contents "minecraft:bundle_contents" slice ItemWithNoSlot;
+    CanBreak can_break "minecraft:can_break" value can_break::CanBreak;
+    CanPlaceOn can_place_on "minecraft:can_place_on" value can_break::CanBreak;
+    ChargedProjectiles charged_projectiles "minecraft:charged_projectiles" slice ItemWithNoSlot;
+    Consumable consumable "minecraft:consumable" value consumable::Consumable;
+    Container container "minecraft:container" slice container::Container;
+    ContainerLoot container_loot "minecraft:container_loot" value container_loot::ContainerLoot;
+    CreativeSlotLock creative_slot_lock "minecraft:creative_slot_lock" copy bool;
+    CustomData custom_data "minecraft:custom_data" value custom_data::CustomData;
+    CustomModelData custom_model_data "minecraft:custom_model_data" value custom_model_data::CustomModelData;
+    CustomName custom_name "minecraft:custom_name" value String;
+    Damage damage "minecraft:damage" copy i32;
+    DamageResistant damage_resistant "minecraft:damage_resistant" value String;
+    DeathProtection death_protection "minecraft:death_protection" slice consumable::ConsumeEffect;
+    DebugStickState debug_stick_state "minecraft:debug_stick_state" value KVPair<String>;
+    DyedColor dyed_color "minecraft:dyed_color" value dyed_color::DyedColor;
+    Enchantable enchantable "minecraft:enchantable" copy i32;
+    EnchantmentGlintOverride enchantment_glint_override "minecraft:enchantment_glint_override" copy bool;
+    Enchantments enchantments "minecraft:enchantments" value enchantments::Enchantments;
+    EntityData entity_data "minecraft:entity_data" value Entity;
+    Equippable equippable "minecraft:equippable" value equippable::Equippable;
+    FireResistant fire_resistant "minecraft:fire_resistant" copy bool;
+    FireworkExplosion firework_explosion "minecraft:firework_explosion" value firework_explosion::FireworkExplosion;
+    Fireworks fireworks "minecraft:fireworks" value fireworks::Fireworks;
+    Food food "minecraft:food" value food::Food;
+    Glider glider "minecraft:glider" copy bool;
+    HideAdditionalTooltip hide_additional_tooltip "minecraft:hide_additional_tooltip" copy bool;
+    HideTooltip hide_tooltip "minecraft:hide_tooltip" copy bool;
+    Instrument instrument "minecraft:instrument" value instrument::Instrument;
+    IntangibleProjectile intangible_projectile "minecraft:intangible_projectile" copy bool;
+    ItemModel item_model "minecraft:item_model" value String;
+    ItemName item_name "minecraft:item_name" value String;
+    JukeboxPlayable jukebox_playable "minecraft:jukebox_playable" value jukebox_playable::JukeboxPlayable;
+    Lock lock "minecraft:lock" value lock::Lock;
+    LodestoneTracker lodestone_tracker "minecraft:lodestone_tracker" value lodestone_tracker::LodestoneTracker;
+    Lore lore "minecraft:lore" slice String;
+    MapColor map_color "minecraft:map_color" value RGB;
+    MapDecorations map_decorations "minecraft:map_decorations" value map_decorations::MapDecorations;
+    MapId map_id "minecraft:map_id" copy i32;
+    MapPostProcessing map_post_processing "minecraft:map_post_processing" value map_post_processing::MapPostProcessing;
+    MaxDamage max_damage "minecraft:max_damage" copy i32;
+    MaxStackSize max_stack_size "minecraft:max_stack_size" copy i32;
+    NoteBlockSound note_block_sound "minecraft:note_block_sound" value String;
+    OminousBottleAmplifier ominous_bottle_amplifier "minecraft:ominous_bottle_amplifier" copy i32;
+    PotDecorations pot_decorations "minecraft:pot_decorations" slice String;
+    PotionContents potion_contents "minecraft:potion_contents" value potion_contents::PotionContents;
+    PotionDurationScale potion_duration_scale "minecraft:potion_duration_scale" copy f32;
+    Profile profile "minecraft:profile" value skull::SkullProfile;
+    ProvidesBannerPatterns provides_banner_patterns "minecraft:provides_banner_patterns" value String;
+    ProvidesTrimMaterial provides_trim_material "minecraft:provides_trim_material" value String;
+    Rarity rarity "minecraft:rarity" value Rarity;
+    Recipes recipes "minecraft:recipes" slice String;
+    Repairable repairable "minecraft:repairable" value id_set::IdSet;
+    RepairCost repair_cost "minecraft:repair_cost" copy i32;
+    StoredEnchantments stored_enchantments "minecraft:stored_enchantments" value enchantments::Enchantments;
+    SuspiciousStewEffects suspicious_stew_effects "minecraft:suspicious_stew_effects" slice suspicious_stew_effects::SuspiciousStewEffects;
+    Tool tool "minecraft:tool" value tool::Tool;
+    TooltipDisplay tooltip_display "minecraft:tooltip_display" value tooltip_display::TooltipDisplay;
+    TooltipStyle tooltip_style "minecraft:tooltip_style" value String;
+    Trim trim "minecraft:trim" value trim::Trim;
+    Unbreakable unbreakable "minecraft:unbreakable" value unbreakable::Unbreakable;
+    UseCooldown use_cooldown "minecraft:use_cooldown" value use_cooldown::UseCooldown;
+    UseRemainder use_remainder "minecraft:use_remainder" boxed ItemWithNoSlot;
+    Weapon weapon "minecraft:weapon" value weapon::Weapon;
+    WritableBookContent writable_book_content "minecraft:writable_book_content" value writable_book_content::WritableBookContent;
+    WrittenBookContent written_book_content "minecraft:written_book_content" value written_book_content::WrittenBookContent;
+}
+
+impl ComponentType {
+    /// The key that marks the component as removed from the item's defaults, like `!minecraft:enchantments`.
+    pub fn removed_key(&self) -> String {
+        format!("!{}", self.key())
+    }
+}
+
+impl FromStr for ComponentType {
+    type Err = SculkParseError;
+
+    /// Converts a component key to a `ComponentType`, the `minecraft:` namespace is optional.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key = s.strip_prefix("minecraft:").unwrap_or(s);
+
+        ComponentType::ALL
+            .iter()
+            .find(|component_type| &component_type.key()["minecraft:".len()..] == key)
+            .copied()
+            .ok_or(SculkParseError::InvalidField(s.into()))
+    }
+}
+
+impl fmt::Display for ComponentType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.key())
+    }
+}
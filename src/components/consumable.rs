@@ -0,0 +1,194 @@
+//! Consumable component, added in 1.21.2. Replaces the eating fields of the food component.
+
+use super::{
+    food::EffectDetails,
+    id_set::{get_id_set, IdSet},
+    instrument::{get_sound_event, SoundEvent},
+};
+use crate::{
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::{get_owned_string, get_t_compound_vec, write_compound_list},
+};
+
+#[cfg(feature = "serde")]
+fn default_consume_seconds() -> f32 {
+    1.6
+}
+
+#[cfg(feature = "serde")]
+fn default_animation() -> String {
+    "eat".into()
+}
+
+/// An item that can be consumed, like food or potions.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Consumable {
+    /// How long it takes to consume the item, in seconds. Defaults to 1.6.
+    #[cfg_attr(feature = "serde", serde(default = "default_consume_seconds"))]
+    pub consume_seconds: f32,
+
+    /// The animation used while consuming, like `eat`, `drink` or `toot_horn`. Defaults to `eat`.
+    #[cfg_attr(feature = "serde", serde(default = "default_animation"))]
+    pub animation: String,
+
+    /// The sound played while consuming. Defaults to `minecraft:entity.generic.eat`.
+    pub sound: SoundEvent,
+
+    /// If `true`, item particles are shown while consuming. Defaults to `true`.
+    #[cfg_attr(feature = "serde", serde(default = "crate::util::default_true"))]
+    pub has_consume_particles: bool,
+
+    /// The effects applied once the item is consumed.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub on_consume_effects: Vec<ConsumeEffect>,
+}
+
+/// An effect applied when an item is consumed, or by `death_protection`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConsumeEffect {
+    /// `minecraft:apply_effects`
+    ApplyEffects {
+        /// The status effects to apply.
+        effects: Vec<EffectDetails>,
+        /// The chance for the effects to be applied, between 0 and 1. Defaults to 1.
+        probability: f32,
+    },
+
+    /// `minecraft:remove_effects`
+    RemoveEffects {
+        /// The status effects to remove.
+        effects: IdSet,
+    },
+
+    /// `minecraft:clear_all_effects`
+    ClearAllEffects,
+
+    /// `minecraft:teleport_randomly`
+    TeleportRandomly {
+        /// The diameter to teleport within. Defaults to 16.
+        diameter: f32,
+    },
+
+    /// `minecraft:play_sound`
+    PlaySound {
+        /// The sound to play.
+        sound: SoundEvent,
+    },
+}
+
+impl FromCompoundNbt for Consumable {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        let consume_seconds = nbt.float("consume_seconds").unwrap_or(1.6);
+        let animation = get_owned_string(nbt, "animation").unwrap_or_else(|_| "eat".into());
+        let sound = match nbt.get("sound") {
+            Some(_) => get_sound_event(nbt, "sound")?,
+            None => SoundEvent::ID("minecraft:entity.generic.eat".into()),
+        };
+        let has_consume_particles = nbt
+            .byte("has_consume_particles")
+            .map(|b| b != 0)
+            .unwrap_or(true);
+        let on_consume_effects =
+            get_t_compound_vec(nbt, "on_consume_effects", ConsumeEffect::from_compound_nbt)?;
+
+        Ok(Consumable {
+            consume_seconds,
+            animation,
+            sound,
+            has_consume_particles,
+            on_consume_effects,
+        })
+    }
+}
+
+impl FromCompoundNbt for ConsumeEffect {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        let r#type = get_owned_string(nbt, "type")?;
+
+        let effect = match r#type.trim_start_matches("minecraft:") {
+            "apply_effects" => ConsumeEffect::ApplyEffects {
+                effects: get_t_compound_vec(nbt, "effects", EffectDetails::from_compound_nbt)?,
+                probability: nbt.float("probability").unwrap_or(1.0),
+            },
+            "remove_effects" => ConsumeEffect::RemoveEffects {
+                effects: get_id_set(nbt, "effects")?
+                    .ok_or(SculkParseError::MissingField("effects".into()))?,
+            },
+            "clear_all_effects" => ConsumeEffect::ClearAllEffects,
+            "teleport_randomly" => ConsumeEffect::TeleportRandomly {
+                diameter: nbt.float("diameter").unwrap_or(16.0),
+            },
+            "play_sound" => ConsumeEffect::PlaySound {
+                sound: get_sound_event(nbt, "sound")?,
+            },
+            _ => return Err(SculkParseError::InvalidField(r#type)),
+        };
+
+        Ok(effect)
+    }
+}
+
+impl ToCompoundNbt for Consumable {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("consume_seconds", self.consume_seconds);
+        nbt.insert("animation", self.animation.as_str());
+        nbt.insert("sound", self.sound.to_tag());
+        nbt.insert("has_consume_particles", self.has_consume_particles);
+        if !self.on_consume_effects.is_empty() {
+            nbt.insert(
+                "on_consume_effects",
+                write_compound_list(&self.on_consume_effects, ConsumeEffect::to_compound_nbt),
+            );
+        }
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for ConsumeEffect {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        match self {
+            ConsumeEffect::ApplyEffects {
+                effects,
+                probability,
+            } => {
+                nbt.insert("type", "minecraft:apply_effects");
+                nbt.insert(
+                    "effects",
+                    write_compound_list(effects, EffectDetails::to_compound_nbt),
+                );
+                nbt.insert("probability", *probability);
+            }
+            ConsumeEffect::RemoveEffects { effects } => {
+                nbt.insert("type", "minecraft:remove_effects");
+                nbt.insert("effects", effects.to_tag());
+            }
+            ConsumeEffect::ClearAllEffects => {
+                nbt.insert("type", "minecraft:clear_all_effects");
+            }
+            ConsumeEffect::TeleportRandomly { diameter } => {
+                nbt.insert("type", "minecraft:teleport_randomly");
+                nbt.insert("diameter", *diameter);
+            }
+            ConsumeEffect::PlaySound { sound } => {
+                nbt.insert("type", "minecraft:play_sound");
+                nbt.insert("sound", sound.to_tag());
+            }
+        }
+
+        nbt
+    }
+}
@@ -1,6 +1,9 @@
 //! Contains the `Container` component.
 
-use crate::{item::ItemWithNoSlot, traits::FromCompoundNbt};
+use crate::{
+    item::ItemWithNoSlot,
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+};
 
 /// The items contained in this container.
 #[derive(Debug, Clone, PartialEq)]
@@ -32,3 +35,14 @@ impl FromCompoundNbt for Container {
         Ok(Container { item, slot })
     }
 }
+
+impl ToVersionedCompoundNbt for Container {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("slot", self.slot);
+        nbt.insert("item", self.item.to_compound_nbt_with_version(data_version));
+
+        nbt
+    }
+}
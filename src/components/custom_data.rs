@@ -1,6 +1,10 @@
 //! Custom data component.
 
-use crate::{error::SculkParseError, kv::KVPair, traits::FromCompoundNbt};
+use crate::{
+    error::SculkParseError,
+    kv::KVPair,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+};
 
 /// Custom data component.
 #[derive(Debug, Clone, PartialEq)]
@@ -8,8 +12,11 @@ use crate::{error::SculkParseError, kv::KVPair, traits::FromCompoundNbt};
 pub enum CustomData {
     /// If its a string, it's the SNBT.
     Snbt(String),
-    /// If its a compound, it's a key-value pair.
+    /// If its a compound of strings, it's a key-value pair.
     KeyValues(KVPair<String>),
+    /// Any other compound, kept as is so it can be written back.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Compound(simdnbt::owned::NbtCompound),
 }
 
 impl FromCompoundNbt for CustomData {
@@ -23,8 +30,12 @@ impl FromCompoundNbt for CustomData {
             let snbt = string.to_string();
             Ok(CustomData::Snbt(snbt))
         } else if let Some(compound) = nbt.compound("minecraft:custom_data") {
-            let map = KVPair::from_compound_nbt(&compound)?;
-            return Ok(CustomData::KeyValues(map));
+            if compound.iter().all(|(_, value)| value.string().is_some()) {
+                let map = KVPair::from_compound_nbt(&compound)?;
+                return Ok(CustomData::KeyValues(map));
+            }
+
+            return Ok(CustomData::Compound(compound.to_owned()));
         } else {
             return Err(SculkParseError::MissingField(
                 "minecraft:custom_data".into(),
@@ -32,3 +43,14 @@ impl FromCompoundNbt for CustomData {
         }
     }
 }
+
+impl CustomData {
+    /// The value of the component, the inverse of [`CustomData::from_compound_nbt`].
+    pub fn to_tag(&self) -> simdnbt::owned::NbtTag {
+        match self {
+            CustomData::Snbt(snbt) => simdnbt::owned::NbtTag::String(snbt.as_str().into()),
+            CustomData::KeyValues(map) => simdnbt::owned::NbtTag::Compound(map.to_compound_nbt()),
+            CustomData::Compound(nbt) => simdnbt::owned::NbtTag::Compound(nbt.clone()),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Custom model data component.
+
+use crate::{
+    color::RGB,
+    error::{NbtType, SculkParseError},
+    util::invalid_list_type,
+};
+use simdnbt::borrow::{NbtCompound, NbtList, NbtTag};
+
+/// Values used by item models to pick a model.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CustomModelData {
+    /// A single integer, before 1.21.4.
+    Int(i32),
+
+    /// Lists of values read by the `custom_model_data` properties of item models, from 1.21.4.
+    Lists {
+        /// Read by `range_dispatch` models.
+        floats: Vec<f32>,
+        /// Read by `condition` models.
+        flags: Vec<bool>,
+        /// Read by `select` models.
+        strings: Vec<String>,
+        /// Read by `custom_model_data` tints.
+        colors: Vec<RGB>,
+    },
+}
+
+impl CustomModelData {
+    /// Reads the component value, which is an int before 1.21.4 and a compound after.
+    pub(crate) fn from_tag(value: &NbtTag) -> Result<Self, SculkParseError> {
+        if let Some(int) = value.int() {
+            return Ok(CustomModelData::Int(int));
+        }
+
+        let nbt = value.compound().ok_or(SculkParseError::InvalidField(
+            "minecraft:custom_model_data".into(),
+        ))?;
+
+        let floats = match get_list(&nbt, "floats") {
+            Some(list) => list
+                .floats()
+                .ok_or_else(|| invalid_list_type(&list, "floats", NbtType::Float))?,
+            None => vec![],
+        };
+        let flags = match get_list(&nbt, "flags") {
+            Some(list) => list
+                .bytes()
+                .ok_or_else(|| invalid_list_type(&list, "flags", NbtType::Byte))?
+                .iter()
+                .map(|b| *b != 0)
+                .collect(),
+            None => vec![],
+        };
+        let strings = match get_list(&nbt, "strings") {
+            Some(list) => list
+                .strings()
+                .ok_or_else(|| invalid_list_type(&list, "strings", NbtType::String))?
+                .iter()
+                .map(|string| string.to_string())
+                .collect(),
+            None => vec![],
+        };
+        let colors = match get_list(&nbt, "colors") {
+            Some(list) => get_colors(&list)?,
+            None => vec![],
+        };
+
+        Ok(CustomModelData::Lists {
+            floats,
+            flags,
+            strings,
+            colors,
+        })
+    }
+}
+
+/// A list that isn't empty, since empty lists have no element type.
+fn get_list<'a, 'tape>(nbt: &NbtCompound<'a, 'tape>, key: &str) -> Option<NbtList<'a, 'tape>> {
+    nbt.list(key).filter(|list| !list.empty())
+}
+
+/// Colors are written as ints, but can also be lists of `[r, g, b]` floats between 0 and 1.
+fn get_colors(list: &NbtList) -> Result<Vec<RGB>, SculkParseError> {
+    if let Some(ints) = list.ints() {
+        return Ok(ints.into_iter().map(RGB::new).collect());
+    }
+
+    let lists = list
+        .lists()
+        .ok_or_else(|| invalid_list_type(list, "colors", NbtType::Int))?;
+
+    let mut colors = vec![];
+    for (index, color) in lists.into_iter().enumerate() {
+        match color.floats().as_deref() {
+            Some(&[r, g, b]) => {
+                let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as i32;
+                colors.push(RGB::new(channel(r) << 16 | channel(g) << 8 | channel(b)));
+            }
+            _ => {
+                return Err(SculkParseError::InvalidField("colors".into()).at_index("colors", index))
+            }
+        }
+    }
+
+    Ok(colors)
+}
+
+impl CustomModelData {
+    /// The value of the component, the inverse of how it's parsed.
+    pub fn to_tag(&self) -> simdnbt::owned::NbtTag {
+        match self {
+            CustomModelData::Int(int) => simdnbt::owned::NbtTag::Int(*int),
+            CustomModelData::Lists {
+                floats,
+                flags,
+                strings,
+                colors,
+            } => {
+                use simdnbt::owned::NbtList;
+
+                let mut nbt = simdnbt::owned::NbtCompound::new();
+                if !floats.is_empty() {
+                    nbt.insert("floats", NbtList::Float(floats.clone()));
+                }
+                if !flags.is_empty() {
+                    nbt.insert(
+                        "flags",
+                        NbtList::Byte(flags.iter().map(|flag| *flag as i8).collect()),
+                    );
+                }
+                if !strings.is_empty() {
+                    nbt.insert(
+                        "strings",
+                        NbtList::String(strings.iter().map(|s| s.as_str().into()).collect()),
+                    );
+                }
+                if !colors.is_empty() {
+                    nbt.insert(
+                        "colors",
+                        NbtList::Int(colors.iter().cloned().map(i32::from).collect()),
+                    );
+                }
+
+                simdnbt::owned::NbtTag::Compound(nbt)
+            }
+        }
+    }
+}
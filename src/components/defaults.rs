@@ -0,0 +1,402 @@
+//! The default components of items in 1.21, which the components of an item are a patch over.
+//!
+//! Only the components that describe the item itself are bundled, `max_stack_size`, `max_damage`, `damage` and `rarity`.
+//! The table is written by hand and isn't complete, items that aren't listed stack to 64 and are common,
+//! which is wrong for the vanilla items it misses.
+
+use super::{Component, ComponentType, Components};
+use crate::{rarity::Rarity, resource_location::ResourceLocation};
+
+/// The default components of an item in 1.21.
+/// Items that aren't known, including every item outside the `minecraft` namespace,
+/// get the defaults of a plain item, a stack size of 64 and common rarity.
+pub fn default_components(id: &ResourceLocation) -> Components {
+    let defaults = if id.is_minecraft() {
+        item_defaults(id.path())
+    } else {
+        ItemDefaults::PLAIN
+    };
+    let mut components = Components::default();
+
+    let mut insert = |component_type: ComponentType, component| {
+        components.insert_with_key(component_type.key(), component);
+    };
+    insert(
+        ComponentType::MaxStackSize,
+        Component::MaxStackSize(defaults.max_stack_size),
+    );
+    if let Some(max_damage) = defaults.max_damage {
+        insert(ComponentType::MaxDamage, Component::MaxDamage(max_damage));
+        insert(ComponentType::Damage, Component::Damage(0));
+    }
+    insert(ComponentType::Rarity, Component::Rarity(defaults.rarity));
+
+    components
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ItemDefaults {
+    max_stack_size: i32,
+    max_damage: Option<i32>,
+    rarity: Rarity,
+}
+
+impl ItemDefaults {
+    const PLAIN: Self = Self::stacks_to(64);
+
+    const fn stacks_to(max_stack_size: i32) -> Self {
+        Self {
+            max_stack_size,
+            max_damage: None,
+            rarity: Rarity::Common,
+        }
+    }
+
+    /// Items that can take damage never stack.
+    const fn durability(max_damage: i32) -> Self {
+        Self {
+            max_stack_size: 1,
+            max_damage: Some(max_damage),
+            rarity: Rarity::Common,
+        }
+    }
+
+    const fn rarity(self, rarity: Rarity) -> Self {
+        Self { rarity, ..self }
+    }
+}
+
+/// The defaults of the listed items in 1.21 that aren't plain items.
+fn item_defaults(id: &str) -> ItemDefaults {
+    use ItemDefaults as D;
+    use Rarity::{Epic, Rare, Uncommon};
+
+    match id {
+        // Tools and weapons.
+        "wooden_sword" | "wooden_shovel" | "wooden_pickaxe" | "wooden_axe" | "wooden_hoe" => {
+            D::durability(59)
+        }
+        "stone_sword" | "stone_shovel" | "stone_pickaxe" | "stone_axe" | "stone_hoe" => {
+            D::durability(131)
+        }
+        "iron_sword" | "iron_shovel" | "iron_pickaxe" | "iron_axe" | "iron_hoe" => {
+            D::durability(250)
+        }
+        "golden_sword" | "golden_shovel" | "golden_pickaxe" | "golden_axe" | "golden_hoe" => {
+            D::durability(32)
+        }
+        "diamond_sword" | "diamond_shovel" | "diamond_pickaxe" | "diamond_axe" | "diamond_hoe" => {
+            D::durability(1561)
+        }
+        "netherite_sword" | "netherite_shovel" | "netherite_pickaxe" | "netherite_axe"
+        | "netherite_hoe" => D::durability(2031),
+        "bow" => D::durability(384),
+        "crossbow" => D::durability(465),
+        "trident" => D::durability(250).rarity(Epic),
+        "mace" => D::durability(500).rarity(Epic),
+        "shield" => D::durability(336),
+        "fishing_rod" | "flint_and_steel" | "brush" => D::durability(64),
+        "shears" => D::durability(238),
+        "carrot_on_a_stick" => D::durability(25),
+        "warped_fungus_on_a_stick" => D::durability(100),
+
+        // Armor.
+        "leather_helmet" => D::durability(55),
+        "leather_chestplate" => D::durability(80),
+        "leather_leggings" => D::durability(75),
+        "leather_boots" => D::durability(65),
+        "chainmail_helmet" | "iron_helmet" => D::durability(165),
+        "chainmail_chestplate" | "iron_chestplate" => D::durability(240),
+        "chainmail_leggings" | "iron_leggings" => D::durability(225),
+        "chainmail_boots" | "iron_boots" => D::durability(195),
+        "golden_helmet" => D::durability(77),
+        "golden_chestplate" => D::durability(112),
+        "golden_leggings" => D::durability(105),
+        "golden_boots" => D::durability(91),
+        "diamond_helmet" => D::durability(363),
+        "diamond_chestplate" => D::durability(528),
+        "diamond_leggings" => D::durability(495),
+        "diamond_boots" => D::durability(429),
+        "netherite_helmet" => D::durability(407),
+        "netherite_chestplate" => D::durability(592),
+        "netherite_leggings" => D::durability(555),
+        "netherite_boots" => D::durability(481),
+        "turtle_helmet" => D::durability(275),
+        "wolf_armor" => D::durability(64),
+        "elytra" => D::durability(432).rarity(Epic),
+        "leather_horse_armor"
+        | "iron_horse_armor"
+        | "golden_horse_armor"
+        | "diamond_horse_armor"
+        | "saddle" => D::stacks_to(1),
+
+        // Vehicles.
+        "oak_boat"
+        | "oak_chest_boat"
+        | "spruce_boat"
+        | "spruce_chest_boat"
+        | "birch_boat"
+        | "birch_chest_boat"
+        | "jungle_boat"
+        | "jungle_chest_boat"
+        | "acacia_boat"
+        | "acacia_chest_boat"
+        | "dark_oak_boat"
+        | "dark_oak_chest_boat"
+        | "mangrove_boat"
+        | "mangrove_chest_boat"
+        | "cherry_boat"
+        | "cherry_chest_boat"
+        | "bamboo_raft"
+        | "bamboo_chest_raft" => D::stacks_to(1),
+        "minecart" | "chest_minecart" | "furnace_minecart" | "tnt_minecart" | "hopper_minecart" => {
+            D::stacks_to(1)
+        }
+        "command_block_minecart" => D::stacks_to(1).rarity(Epic),
+
+        // Buckets, only the empty one stacks.
+        "bucket" => D::stacks_to(16),
+        "water_bucket"
+        | "lava_bucket"
+        | "powder_snow_bucket"
+        | "milk_bucket"
+        | "pufferfish_bucket"
+        | "salmon_bucket"
+        | "cod_bucket"
+        | "tropical_fish_bucket"
+        | "axolotl_bucket"
+        | "tadpole_bucket" => D::stacks_to(1),
+
+        // Food and potions.
+        "mushroom_stew" | "rabbit_stew" | "beetroot_soup" | "suspicious_stew" | "cake" => {
+            D::stacks_to(1)
+        }
+        "potion" | "splash_potion" | "lingering_potion" => D::stacks_to(1),
+        "honey_bottle" => D::stacks_to(16),
+        "golden_apple" => D::PLAIN.rarity(Rare),
+        "enchanted_golden_apple" => D::PLAIN.rarity(Epic),
+        "experience_bottle" | "dragon_breath" | "ominous_bottle" => D::PLAIN.rarity(Uncommon),
+
+        // Books.
+        "writable_book" => D::stacks_to(1),
+        "written_book" => D::stacks_to(16),
+        "enchanted_book" => D::stacks_to(1).rarity(Uncommon),
+        "knowledge_book" => D::stacks_to(1).rarity(Epic),
+
+        // Throwables and placeables that stack to 16.
+        "ender_pearl" | "snowball" | "egg" | "armor_stand" => D::stacks_to(16),
+        "oak_sign" | "spruce_sign" | "birch_sign" | "jungle_sign" | "acacia_sign"
+        | "dark_oak_sign" | "mangrove_sign" | "cherry_sign" | "bamboo_sign" | "crimson_sign"
+        | "warped_sign" => D::stacks_to(16),
+        "oak_hanging_sign"
+        | "spruce_hanging_sign"
+        | "birch_hanging_sign"
+        | "jungle_hanging_sign"
+        | "acacia_hanging_sign"
+        | "dark_oak_hanging_sign"
+        | "mangrove_hanging_sign"
+        | "cherry_hanging_sign"
+        | "bamboo_hanging_sign"
+        | "crimson_hanging_sign"
+        | "warped_hanging_sign" => D::stacks_to(16),
+        "white_banner" | "orange_banner" | "magenta_banner" | "light_blue_banner"
+        | "yellow_banner" | "lime_banner" | "pink_banner" | "gray_banner" | "light_gray_banner"
+        | "cyan_banner" | "purple_banner" | "blue_banner" | "brown_banner" | "green_banner"
+        | "red_banner" | "black_banner" => D::stacks_to(16),
+
+        // Beds and shulker boxes.
+        "white_bed" | "orange_bed" | "magenta_bed" | "light_blue_bed" | "yellow_bed"
+        | "lime_bed" | "pink_bed" | "gray_bed" | "light_gray_bed" | "cyan_bed" | "purple_bed"
+        | "blue_bed" | "brown_bed" | "green_bed" | "red_bed" | "black_bed" => D::stacks_to(1),
+        "shulker_box"
+        | "white_shulker_box"
+        | "orange_shulker_box"
+        | "magenta_shulker_box"
+        | "light_blue_shulker_box"
+        | "yellow_shulker_box"
+        | "lime_shulker_box"
+        | "pink_shulker_box"
+        | "gray_shulker_box"
+        | "light_gray_shulker_box"
+        | "cyan_shulker_box"
+        | "purple_shulker_box"
+        | "blue_shulker_box"
+        | "brown_shulker_box"
+        | "green_shulker_box"
+        | "red_shulker_box"
+        | "black_shulker_box" => D::stacks_to(1),
+
+        // Banner patterns.
+        "flower_banner_pattern" | "globe_banner_pattern" | "piglin_banner_pattern" => {
+            D::stacks_to(1)
+        }
+        "creeper_banner_pattern" | "skull_banner_pattern" => D::stacks_to(1).rarity(Uncommon),
+        "flow_banner_pattern" | "guster_banner_pattern" => D::stacks_to(1).rarity(Rare),
+        "mojang_banner_pattern" => D::stacks_to(1).rarity(Epic),
+
+        // Music discs.
+        "music_disc_13"
+        | "music_disc_cat"
+        | "music_disc_blocks"
+        | "music_disc_chirp"
+        | "music_disc_far"
+        | "music_disc_mall"
+        | "music_disc_mellohi"
+        | "music_disc_stal"
+        | "music_disc_strad"
+        | "music_disc_ward"
+        | "music_disc_11"
+        | "music_disc_wait"
+        | "music_disc_relic"
+        | "music_disc_5"
+        | "music_disc_creator_music_box"
+        | "music_disc_precipice" => D::stacks_to(1).rarity(Uncommon),
+        "music_disc_otherside" | "music_disc_pigstep" | "music_disc_creator" => {
+            D::stacks_to(1).rarity(Rare)
+        }
+
+        // Other unstackable items.
+        "totem_of_undying" => D::stacks_to(1).rarity(Uncommon),
+        "goat_horn" | "spyglass" | "bundle" => D::stacks_to(1),
+        "debug_stick" => D::stacks_to(1).rarity(Epic),
+
+        // Smithing templates.
+        "netherite_upgrade_smithing_template"
+        | "sentry_armor_trim_smithing_template"
+        | "dune_armor_trim_smithing_template"
+        | "coast_armor_trim_smithing_template"
+        | "wild_armor_trim_smithing_template"
+        | "tide_armor_trim_smithing_template"
+        | "snout_armor_trim_smithing_template"
+        | "rib_armor_trim_smithing_template"
+        | "wayfinder_armor_trim_smithing_template"
+        | "shaper_armor_trim_smithing_template"
+        | "raiser_armor_trim_smithing_template"
+        | "host_armor_trim_smithing_template"
+        | "flow_armor_trim_smithing_template"
+        | "bolt_armor_trim_smithing_template" => D::PLAIN.rarity(Uncommon),
+        "ward_armor_trim_smithing_template"
+        | "eye_armor_trim_smithing_template"
+        | "vex_armor_trim_smithing_template"
+        | "spire_armor_trim_smithing_template" => D::PLAIN.rarity(Rare),
+        "silence_armor_trim_smithing_template" => D::PLAIN.rarity(Epic),
+
+        // Other items with a rarity.
+        "echo_shard" | "recovery_compass" | "disc_fragment_5" => D::PLAIN.rarity(Uncommon),
+        "creeper_head"
+        | "piglin_head"
+        | "player_head"
+        | "skeleton_skull"
+        | "wither_skeleton_skull"
+        | "zombie_head" => D::PLAIN.rarity(Uncommon),
+        "dragon_head" => D::PLAIN.rarity(Epic),
+        "heart_of_the_sea" | "nether_star" | "sniffer_egg" | "conduit" => D::PLAIN.rarity(Uncommon),
+        "beacon" | "end_crystal" => D::PLAIN.rarity(Rare),
+        "dragon_egg"
+        | "heavy_core"
+        | "command_block"
+        | "chain_command_block"
+        | "repeating_command_block"
+        | "structure_block"
+        | "structure_void"
+        | "jigsaw"
+        | "barrier"
+        | "light" => D::PLAIN.rarity(Epic),
+
+        _ => D::PLAIN,
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn vanilla_item_defaults() {
+    let max_stack_size = |id: &str| match default_components(&id.parse().unwrap())
+        .get(ComponentType::MaxStackSize.key())
+    {
+        Some(Component::MaxStackSize(max_stack_size)) => *max_stack_size,
+        other => panic!("{id} has no max stack size: {other:?}"),
+    };
+    let max_damage = |id: &str| match default_components(&id.parse().unwrap())
+        .get(ComponentType::MaxDamage.key())
+    {
+        Some(Component::MaxDamage(max_damage)) => Some(*max_damage),
+        _ => None,
+    };
+    let rarity = |id: &str| match default_components(&id.parse().unwrap())
+        .get(ComponentType::Rarity.key())
+    {
+        Some(Component::Rarity(rarity)) => *rarity,
+        other => panic!("{id} has no rarity: {other:?}"),
+    };
+
+    let stack_sizes = [
+        ("minecraft:stone", 64),
+        ("minecraft:written_book", 16),
+        ("minecraft:writable_book", 1),
+        ("minecraft:ender_pearl", 16),
+        ("minecraft:oak_hanging_sign", 16),
+        ("minecraft:red_banner", 16),
+        ("minecraft:bucket", 16),
+        ("minecraft:water_bucket", 1),
+        ("minecraft:mojang_banner_pattern", 1),
+        ("minecraft:lime_shulker_box", 1),
+        ("minecraft:bamboo_chest_raft", 1),
+        ("minecraft:diamond_sword", 1),
+        ("minecraft:golden_apple", 64),
+    ];
+    for (id, expected) in stack_sizes {
+        assert_eq!(max_stack_size(id), expected, "{id}");
+    }
+
+    let durabilities = [
+        ("minecraft:diamond_sword", Some(1561)),
+        ("minecraft:wooden_hoe", Some(59)),
+        ("minecraft:netherite_chestplate", Some(592)),
+        ("minecraft:iron_boots", Some(195)),
+        ("minecraft:turtle_helmet", Some(275)),
+        ("minecraft:elytra", Some(432)),
+        ("minecraft:mace", Some(500)),
+        ("minecraft:shears", Some(238)),
+        ("minecraft:iron_horse_armor", None),
+        ("minecraft:stone", None),
+    ];
+    for (id, expected) in durabilities {
+        assert_eq!(max_damage(id), expected, "{id}");
+    }
+
+    let rarities = [
+        ("minecraft:stone", Rarity::Common),
+        ("minecraft:golden_apple", Rarity::Rare),
+        ("minecraft:enchanted_golden_apple", Rarity::Epic),
+        ("minecraft:enchanted_book", Rarity::Uncommon),
+        ("minecraft:trident", Rarity::Epic),
+        ("minecraft:music_disc_pigstep", Rarity::Rare),
+        ("minecraft:music_disc_cat", Rarity::Uncommon),
+        ("minecraft:beacon", Rarity::Rare),
+        ("minecraft:command_block", Rarity::Epic),
+        (
+            "minecraft:netherite_upgrade_smithing_template",
+            Rarity::Uncommon,
+        ),
+        ("minecraft:spire_armor_trim_smithing_template", Rarity::Rare),
+        (
+            "minecraft:silence_armor_trim_smithing_template",
+            Rarity::Epic,
+        ),
+    ];
+    for (id, expected) in rarities {
+        assert_eq!(rarity(id), expected, "{id}");
+    }
+
+    // Items outside the table, and outside vanilla, are plain items.
+    for id in ["minecraft:not_an_item", "mymod:golden_apple"] {
+        assert_eq!(max_stack_size(id), 64, "{id}");
+        assert_eq!(max_damage(id), None, "{id}");
+        assert_eq!(rarity(id), Rarity::Common, "{id}");
+    }
+    let sword = default_components(&"minecraft:iron_sword".parse().unwrap());
+    assert!(matches!(
+        sword.get(ComponentType::Damage.key()),
+        Some(Component::Damage(0))
+    ));
+}
@@ -48,3 +48,23 @@ impl FromCompoundNbt for DyedColor {
         }
     }
 }
+
+impl DyedColor {
+    /// The value of the component, the inverse of [`DyedColor::from_compound_nbt`].
+    pub fn to_tag(&self) -> simdnbt::owned::NbtTag {
+        match self {
+            DyedColor::Int(rgb) => simdnbt::owned::NbtTag::Int(rgb.clone().into()),
+            DyedColor::Compound {
+                rgb,
+                show_in_tooltip,
+            } => {
+                let mut nbt = simdnbt::owned::NbtCompound::new();
+                nbt.insert("rgb", i32::from(rgb.clone()));
+                if !show_in_tooltip {
+                    nbt.insert("show_in_tooltip", false);
+                }
+                simdnbt::owned::NbtTag::Compound(nbt)
+            }
+        }
+    }
+}
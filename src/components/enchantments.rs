@@ -1,13 +1,19 @@
 //! Enchantments on an item.
 
-use crate::{kv::KVPair, traits::FromCompoundNbt};
+use crate::{
+    error::SculkParseError,
+    resource_location::{parse_id, ResourceLocation},
+    traits::FromCompoundNbt,
+    version::V1_21_5,
+};
+use std::collections::HashMap;
 
 /// Enchantments on an item.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Enchantments {
     /// Contains key-value pairs of levels of enchantments on this item that affect the way the item works.  
-    pub levels: KVPair<i32>,
+    pub levels: HashMap<ResourceLocation, i32>,
 
     /// Show or hide enchantments on this item's tooltip. Defaults to true.
     #[cfg_attr(feature = "serde", serde(default = "crate::util::default_true"))]
@@ -24,7 +30,7 @@ impl FromCompoundNbt for Enchantments {
         if let Some(levels) = nbt.compound("levels") {
             // Field compound
 
-            let levels = KVPair::<i32>::from_compound_nbt(&levels)?;
+            let levels = get_levels(&levels)?;
             let show_in_tooltip = nbt.byte("show_in_tooltip").map(|b| b != 0).unwrap_or(true);
 
             Ok(Enchantments {
@@ -33,7 +39,7 @@ impl FromCompoundNbt for Enchantments {
             })
         } else {
             // key value only
-            let levels = KVPair::<i32>::from_compound_nbt(nbt)?;
+            let levels = get_levels(nbt)?;
 
             // this could be an enum but eh, show_in_tooltip is default true anyway
             Ok(Enchantments {
@@ -43,3 +49,57 @@ impl FromCompoundNbt for Enchantments {
         }
     }
 }
+
+/// The level of each enchantment, entries that aren't an int are skipped.
+fn get_levels(
+    nbt: &simdnbt::borrow::NbtCompound,
+) -> Result<HashMap<ResourceLocation, i32>, SculkParseError> {
+    let mut levels = HashMap::new();
+
+    for (key, value) in nbt.iter() {
+        if let Some(level) = value.int() {
+            levels.insert(parse_id(&key.to_str(), "levels")?, level);
+        }
+    }
+
+    Ok(levels)
+}
+
+impl Enchantments {
+    /// The level of an enchantment, the id can leave out the `minecraft:` namespace.
+    pub fn level(&self, id: &str) -> Option<i32> {
+        self.levels
+            .iter()
+            .find(|(enchantment, _)| **enchantment == *id)
+            .map(|(_, level)| *level)
+    }
+
+    /// The value of the component, the inverse of [`Enchantments::from_compound_nbt`].  
+    /// Since 1.21.5 it's just the levels.
+    pub fn to_tag(&self, data_version: i32) -> simdnbt::owned::NbtTag {
+        if data_version >= V1_21_5 {
+            return simdnbt::owned::NbtTag::Compound(self.levels_nbt());
+        }
+
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+        nbt.insert("levels", self.levels_nbt());
+        if !self.show_in_tooltip {
+            nbt.insert("show_in_tooltip", false);
+        }
+
+        simdnbt::owned::NbtTag::Compound(nbt)
+    }
+
+    /// Sorted so the same levels are always written the same way.
+    fn levels_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut levels: Vec<_> = self.levels.iter().collect();
+        levels.sort();
+
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+        for (enchantment, level) in levels {
+            nbt.insert(enchantment.to_string(), *level);
+        }
+
+        nbt
+    }
+}
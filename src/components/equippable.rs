@@ -0,0 +1,98 @@
+//! Equippable component, added in 1.21.2.
+
+use super::{
+    id_set::{get_id_set, IdSet},
+    instrument::{get_sound_event, SoundEvent},
+};
+use crate::{
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::{get_owned_optional_string, get_owned_string, set_optional_tag},
+};
+
+/// An item that can be equipped in an equipment slot.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Equippable {
+    /// The slot the item is equipped in, like `head`, `chest`, `body` or `saddle`.
+    pub slot: String,
+
+    /// The sound played when the item is equipped. Defaults to `minecraft:item.armor.equip_generic`.
+    pub equip_sound: SoundEvent,
+
+    /// The equipment asset used to render the item when equipped. Optional.  
+    /// `asset_id`, named `model` before 1.21.4.
+    pub asset_id: Option<String>,
+
+    /// The entities that can equip the item. Optional, any entity can if omitted.
+    pub allowed_entities: Option<IdSet>,
+
+    /// If `true`, a dispenser can equip the item on an entity. Defaults to `true`.
+    #[cfg_attr(feature = "serde", serde(default = "crate::util::default_true"))]
+    pub dispensable: bool,
+
+    /// If `true`, the item can be swapped with the equipped item by using it. Defaults to `true`.
+    #[cfg_attr(feature = "serde", serde(default = "crate::util::default_true"))]
+    pub swappable: bool,
+
+    /// If `true`, the item takes damage when the wearer is hurt. Defaults to `true`.
+    #[cfg_attr(feature = "serde", serde(default = "crate::util::default_true"))]
+    pub damage_on_hurt: bool,
+
+    /// If `true`, the item can be equipped on an entity by using it on them. Added in 1.21.5, defaults to `false`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub equip_on_interact: bool,
+
+    /// The texture overlayed on the screen when worn, like a carved pumpkin. Optional.
+    pub camera_overlay: Option<String>,
+}
+
+impl FromCompoundNbt for Equippable {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        let slot = get_owned_string(nbt, "slot")?;
+        let equip_sound = match nbt.get("equip_sound") {
+            Some(_) => get_sound_event(nbt, "equip_sound")?,
+            None => SoundEvent::ID("minecraft:item.armor.equip_generic".into()),
+        };
+        let asset_id = get_owned_optional_string(nbt, "asset_id")
+            .or_else(|| get_owned_optional_string(nbt, "model"));
+        let allowed_entities = get_id_set(nbt, "allowed_entities")?;
+
+        let get_bool = |key: &str, default: bool| nbt.byte(key).map(|b| b != 0).unwrap_or(default);
+
+        Ok(Equippable {
+            slot,
+            equip_sound,
+            asset_id,
+            allowed_entities,
+            dispensable: get_bool("dispensable", true),
+            swappable: get_bool("swappable", true),
+            damage_on_hurt: get_bool("damage_on_hurt", true),
+            equip_on_interact: get_bool("equip_on_interact", false),
+            camera_overlay: get_owned_optional_string(nbt, "camera_overlay"),
+        })
+    }
+}
+
+impl ToCompoundNbt for Equippable {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("slot", self.slot.as_str());
+        nbt.insert("equip_sound", self.equip_sound.to_tag());
+        set_optional_tag(&mut nbt, "asset_id", self.asset_id.as_deref());
+        if let Some(allowed_entities) = &self.allowed_entities {
+            nbt.insert("allowed_entities", allowed_entities.to_tag());
+        }
+        nbt.insert("dispensable", self.dispensable);
+        nbt.insert("swappable", self.swappable);
+        nbt.insert("damage_on_hurt", self.damage_on_hurt);
+        nbt.insert("equip_on_interact", self.equip_on_interact);
+        set_optional_tag(&mut nbt, "camera_overlay", self.camera_overlay.as_deref());
+
+        nbt
+    }
+}
@@ -1,6 +1,10 @@
 //! Firework explosion component.
 
-use crate::{error::SculkParseError, traits::FromCompoundNbt, util::get_bool};
+use crate::{
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::get_bool,
+};
 
 /// The explosion of a firework.
 #[derive(Debug, Clone, PartialEq)]
@@ -76,3 +80,43 @@ impl FireworkShape {
         }
     }
 }
+
+impl FireworkShape {
+    fn to_str(&self) -> &'static str {
+        match self {
+            FireworkShape::SmallBall => "small_ball",
+            FireworkShape::LargeBall => "large_ball",
+            FireworkShape::Star => "star",
+            FireworkShape::Creeper => "creeper",
+            FireworkShape::Burst => "burst",
+        }
+    }
+}
+
+impl ToCompoundNbt for FireworkExplosion {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("shape", self.shape.to_str());
+        if !self.colors.is_empty() {
+            nbt.insert(
+                "colors",
+                simdnbt::owned::NbtTag::IntArray(self.colors.clone()),
+            );
+        }
+        if !self.fade_colors.is_empty() {
+            nbt.insert(
+                "fade_colors",
+                simdnbt::owned::NbtTag::IntArray(self.fade_colors.clone()),
+            );
+        }
+        if self.has_trail {
+            nbt.insert("has_trail", true);
+        }
+        if self.has_twinkle {
+            nbt.insert("has_twinkle", true);
+        }
+
+        nbt
+    }
+}
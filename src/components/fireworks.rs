@@ -1,7 +1,10 @@
 //! Firework rocket item component.
 
 use super::firework_explosion::FireworkExplosion;
-use crate::{traits::FromCompoundNbt, util::get_t_list};
+use crate::{
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::{get_t_list, write_compound_list},
+};
 
 #[cfg(feature = "serde")]
 fn default_flight_duration() -> i8 {
@@ -50,3 +53,17 @@ impl FromCompoundNbt for Fireworks {
         })
     }
 }
+
+impl ToCompoundNbt for Fireworks {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert(
+            "explosions",
+            write_compound_list(&self.explosions, FireworkExplosion::to_compound_nbt),
+        );
+        nbt.insert("flight_duration", self.flight_duration);
+
+        nbt
+    }
+}
@@ -2,8 +2,11 @@
 
 use crate::{
     error::SculkParseError,
-    traits::FromCompoundNbt,
-    util::{get_optional_components, get_owned_string, get_t_compound_vec},
+    traits::{FromCompoundNbt, ToCompoundNbt, ToVersionedCompoundNbt},
+    util::{
+        get_optional_components, get_owned_string, get_t_compound_vec, set_optional_tag,
+        write_compound_list,
+    },
 };
 
 use super::Components;
@@ -180,3 +183,72 @@ impl FromCompoundNbt for EffectDetails {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for Food {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("nutrition", self.nutrition);
+        nbt.insert("saturation", self.saturation);
+        if self.can_always_eat {
+            nbt.insert("can_always_eat", true);
+        }
+        nbt.insert("eat_seconds", self.eat_seconds);
+        if let Some(using_converts_to) = &self.using_converts_to {
+            nbt.insert(
+                "using_converts_to",
+                using_converts_to.to_compound_nbt_with_version(data_version),
+            );
+        }
+        if !self.effects.is_empty() {
+            nbt.insert(
+                "effects",
+                write_compound_list(&self.effects, Effect::to_compound_nbt),
+            );
+        }
+
+        nbt
+    }
+}
+
+impl ToVersionedCompoundNbt for FoodConvertedItem {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("id", self.id.as_str());
+        if let Some(components) = &self.components {
+            nbt.insert(
+                "components",
+                components.to_compound_nbt_with_version(data_version),
+            );
+        }
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for Effect {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("effect", self.effect.to_compound_nbt());
+        nbt.insert("probability", self.probability);
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for EffectDetails {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("id", self.id.as_str());
+        set_optional_tag(&mut nbt, "amplifier", self.amplifier);
+        set_optional_tag(&mut nbt, "duration", self.duration);
+        set_optional_tag(&mut nbt, "ambient", self.ambient);
+        set_optional_tag(&mut nbt, "show_particles", self.show_particles);
+        set_optional_tag(&mut nbt, "show_icon", self.show_icon);
+
+        nbt
+    }
+}
@@ -0,0 +1,78 @@
+//! A set of registry ids, used by components like `repairable` and `equippable`.
+
+use crate::{
+    error::{NbtType, SculkParseError},
+    util::{invalid_list_type, invalid_type},
+};
+
+/// A single id, a `#tag`, or a list of ids.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum IdSet {
+    /// A single id, or a tag if it starts with `#`.
+    Single(String),
+    /// A list of ids.
+    Multiple(Vec<String>),
+}
+
+impl IdSet {
+    /// Whether the set is a tag, which has to be resolved with the registry.
+    pub fn is_tag(&self) -> bool {
+        matches!(self, IdSet::Single(id) if id.starts_with('#'))
+    }
+
+    /// Whether the id is in the set, tags are never matched.
+    pub fn contains(&self, id: &str) -> bool {
+        match self {
+            IdSet::Single(single) => single == id,
+            IdSet::Multiple(ids) => ids.iter().any(|listed| listed == id),
+        }
+    }
+}
+
+/// Reads an optional [`IdSet`] from the compound.
+pub(crate) fn get_id_set(
+    nbt: &simdnbt::borrow::NbtCompound,
+    key: &'static str,
+) -> Result<Option<IdSet>, SculkParseError> {
+    if nbt.get(key).is_none() {
+        return Ok(None);
+    }
+
+    if let Some(id) = nbt.string(key) {
+        return Ok(Some(IdSet::Single(id.to_string())));
+    }
+
+    let list = nbt
+        .list(key)
+        .ok_or_else(|| invalid_type(nbt, key, NbtType::List))?;
+
+    if list.empty() {
+        return Ok(Some(IdSet::Multiple(vec![])));
+    }
+
+    let ids = list
+        .strings()
+        .ok_or_else(|| invalid_list_type(&list, key, NbtType::String))?
+        .iter()
+        .map(|id| id.to_string())
+        .collect();
+
+    Ok(Some(IdSet::Multiple(ids)))
+}
+
+impl IdSet {
+    /// The value of the set, the inverse of how it's parsed.
+    pub fn to_tag(&self) -> simdnbt::owned::NbtTag {
+        match self {
+            IdSet::Single(id) => simdnbt::owned::NbtTag::String(id.as_str().into()),
+            IdSet::Multiple(ids) => simdnbt::owned::NbtTag::List(match ids.is_empty() {
+                true => simdnbt::owned::NbtList::Empty,
+                false => simdnbt::owned::NbtList::String(
+                    ids.iter().map(|id| id.as_str().into()).collect(),
+                ),
+            }),
+        }
+    }
+}
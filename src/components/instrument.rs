@@ -1,6 +1,11 @@
 //! Instrument component.
 
-use crate::{error::SculkParseError, traits::FromCompoundNbt, util::get_owned_string};
+use super::text::{get_text_component, text_component_tag};
+use crate::{
+    error::SculkParseError,
+    traits::{FromCompoundNbt, FromVersionedCompoundNbt, ToCompoundNbt, ToVersionedCompoundNbt},
+    util::{get_owned_string, set_optional_tag},
+};
 
 /// (referenced by ID or inlined)
 #[derive(Debug, Clone, PartialEq)]
@@ -20,11 +25,15 @@ pub struct InstrumentData {
     /// sound event (referenced by ID or inlined)
     pub sound_event: SoundEvent,
 
-    /// A non-negative integer for how long the use duration is.
+    /// A non-negative integer for how long the use duration is, in ticks.  
+    /// Stored in seconds since 1.21.2, which is converted to ticks.
     pub use_duration: i32,
 
     /// A non-negative float for the range of the sound.
     pub range: f32,
+
+    /// The description shown in the tooltip, as a JSON text component. Added in 1.21.2.
+    pub description: Option<String>,
 }
 
 /// (referenced by ID or inlined)
@@ -49,15 +58,18 @@ pub struct SoundEventData {
     pub range: Option<f32>,
 }
 
-impl FromCompoundNbt for Instrument {
-    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+impl FromVersionedCompoundNbt for Instrument {
+    fn from_compound_nbt_with_version(
+        nbt: &simdnbt::borrow::NbtCompound,
+        data_version: i32,
+    ) -> Result<Self, SculkParseError>
     where
         Self: Sized,
     {
         if let Some(id) = nbt.string("minecraft:instrument") {
             Ok(Instrument::ID(id.to_string()))
         } else if let Some(compound) = nbt.compound("minecraft:instrument") {
-            let data = InstrumentData::from_compound_nbt(&compound)?;
+            let data = InstrumentData::from_compound_nbt_with_version(&compound, data_version)?;
 
             return Ok(Instrument::Inline(data));
         } else {
@@ -66,24 +78,34 @@ impl FromCompoundNbt for Instrument {
     }
 }
 
-impl FromCompoundNbt for InstrumentData {
-    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+impl FromVersionedCompoundNbt for InstrumentData {
+    fn from_compound_nbt_with_version(
+        nbt: &simdnbt::borrow::NbtCompound,
+        data_version: i32,
+    ) -> Result<Self, SculkParseError>
     where
         Self: Sized,
     {
         let sound_event = SoundEvent::from_compound_nbt(nbt)?;
 
-        let use_duration = nbt
-            .int("use_duration")
-            .ok_or(SculkParseError::MissingField("use_duration".into()))?;
+        let use_duration = match (nbt.int("use_duration"), nbt.float("use_duration")) {
+            (Some(ticks), _) => ticks,
+            (None, Some(seconds)) => (seconds * 20.0) as i32,
+            _ => return Err(SculkParseError::MissingField("use_duration".into())),
+        };
         let range = nbt
             .float("range")
             .ok_or(SculkParseError::MissingField("range".into()))?;
 
+        let description = nbt
+            .get("description")
+            .and_then(|description| get_text_component(&description, data_version));
+
         Ok(InstrumentData {
             sound_event,
             use_duration,
             range,
+            description,
         })
     }
 }
@@ -95,15 +117,23 @@ impl FromCompoundNbt for SoundEvent {
     where
         Self: Sized,
     {
-        if let Some(id) = nbt.string("sound_event") {
-            Ok(SoundEvent::ID(id.to_string()))
-        } else if let Some(compound) = nbt.compound("sound_event") {
-            let data = SoundEventData::from_compound_nbt(&compound)?;
+        get_sound_event(nbt, "sound_event")
+    }
+}
 
-            return Ok(SoundEvent::Inline(data));
-        } else {
-            return Err(SculkParseError::MissingField("sound_event".into()));
-        }
+/// Reads a [`SoundEvent`] from a field that's either an id or the inlined data.
+pub(crate) fn get_sound_event(
+    nbt: &simdnbt::borrow::NbtCompound,
+    key: &'static str,
+) -> Result<SoundEvent, SculkParseError> {
+    if let Some(id) = nbt.string(key) {
+        Ok(SoundEvent::ID(id.to_string()))
+    } else if let Some(compound) = nbt.compound(key) {
+        Ok(SoundEvent::Inline(SoundEventData::from_compound_nbt(
+            &compound,
+        )?))
+    } else {
+        Err(SculkParseError::MissingField(key.into()))
     }
 }
 
@@ -120,3 +150,51 @@ impl FromCompoundNbt for SoundEventData {
         Ok(SoundEventData { sound_id, range })
     }
 }
+
+impl Instrument {
+    /// The value of the component, the inverse of how it's parsed.
+    pub fn to_tag(&self, data_version: i32) -> simdnbt::owned::NbtTag {
+        match self {
+            Instrument::ID(id) => simdnbt::owned::NbtTag::String(id.as_str().into()),
+            Instrument::Inline(data) => {
+                simdnbt::owned::NbtTag::Compound(data.to_compound_nbt_with_version(data_version))
+            }
+        }
+    }
+}
+
+impl ToVersionedCompoundNbt for InstrumentData {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("sound_event", self.sound_event.to_tag());
+        nbt.insert("use_duration", self.use_duration);
+        nbt.insert("range", self.range);
+        if let Some(description) = &self.description {
+            nbt.insert("description", text_component_tag(description, data_version));
+        }
+
+        nbt
+    }
+}
+
+impl SoundEvent {
+    /// The value of the sound event, the inverse of how it's parsed.
+    pub fn to_tag(&self) -> simdnbt::owned::NbtTag {
+        match self {
+            SoundEvent::ID(id) => simdnbt::owned::NbtTag::String(id.as_str().into()),
+            SoundEvent::Inline(data) => simdnbt::owned::NbtTag::Compound(data.to_compound_nbt()),
+        }
+    }
+}
+
+impl ToCompoundNbt for SoundEventData {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("sound_id", self.sound_id.as_str());
+        set_optional_tag(&mut nbt, "range", self.range);
+
+        nbt
+    }
+}
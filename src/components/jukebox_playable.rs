@@ -1,19 +1,54 @@
 //! Jukebox Playable component.
 
-use crate::{traits::FromCompoundNbt, util::get_owned_string};
+use super::instrument::SoundEvent;
+use crate::{
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::get_owned_string,
+    version::V1_21_5,
+};
 
 /// A Jukebox Playable component.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct JukeboxPlayable {
     /// The song to play.
-    pub song: String,
+    pub song: JukeboxSong,
 
     /// If `true`, the song is shown in the tooltip of the item. Defaults to `true`.
     #[cfg_attr(feature = "serde", serde(default = "crate::util::default_true"))]
     pub show_in_tooltip: bool,
 }
 
+/// (referenced by ID or inlined)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum JukeboxSong {
+    /// The ID of the jukebox song.
+    ID(String),
+    /// The jukebox song data.
+    Inline(JukeboxSongData),
+}
+
+/// Jukebox song data.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JukeboxSongData {
+    /// sound event (referenced by ID or inlined)
+    pub sound_event: SoundEvent,
+
+    /// The description shown in the tooltip.
+    /// Either the plain text, or the translation key if the description is translated.
+    pub description: String,
+
+    /// The length of the song in seconds.
+    pub length_in_seconds: f32,
+
+    /// The redstone signal a comparator outputs while the song is playing, between 0 and 15.
+    pub comparator_output: i32,
+}
+
 impl FromCompoundNbt for JukeboxPlayable {
     fn from_compound_nbt(
         nbt: &simdnbt::borrow::NbtCompound,
@@ -21,7 +56,13 @@ impl FromCompoundNbt for JukeboxPlayable {
     where
         Self: Sized,
     {
-        let song = get_owned_string(nbt, "song")?;
+        let song = if let Some(id) = nbt.string("song") {
+            JukeboxSong::ID(id.to_string())
+        } else if let Some(compound) = nbt.compound("song") {
+            JukeboxSong::Inline(JukeboxSongData::from_compound_nbt(&compound)?)
+        } else {
+            return Err(SculkParseError::MissingField("song".into()));
+        };
 
         let show_in_tooltip = nbt.byte("show_in_tooltip").map(|b| b != 0).unwrap_or(true);
 
@@ -31,3 +72,92 @@ impl FromCompoundNbt for JukeboxPlayable {
         })
     }
 }
+
+impl JukeboxPlayable {
+    /// Reads the component value, which is just the song since 1.21.5.
+    pub(crate) fn from_tag(value: &simdnbt::borrow::NbtTag) -> Result<Self, SculkParseError> {
+        if let Some(id) = value.string() {
+            Ok(JukeboxPlayable {
+                song: JukeboxSong::ID(id.to_string()),
+                show_in_tooltip: true,
+            })
+        } else if let Some(nbt) = value.compound() {
+            // Inlined songs from 1.21.5 don't have the `song` field.
+            match nbt.get("song") {
+                Some(_) => JukeboxPlayable::from_compound_nbt(&nbt),
+                None => Ok(JukeboxPlayable {
+                    song: JukeboxSong::Inline(JukeboxSongData::from_compound_nbt(&nbt)?),
+                    show_in_tooltip: true,
+                }),
+            }
+        } else {
+            Err(SculkParseError::InvalidField("jukebox_playable".into()))
+        }
+    }
+}
+
+impl FromCompoundNbt for JukeboxSongData {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        let sound_event = SoundEvent::from_compound_nbt(nbt)?;
+
+        let description = if let Some(text) = nbt.string("description") {
+            text.to_string()
+        } else if let Some(compound) = nbt.compound("description") {
+            get_owned_string(&compound, "translate")
+                .or_else(|_| get_owned_string(&compound, "text"))?
+        } else {
+            return Err(SculkParseError::MissingField("description".into()));
+        };
+
+        let length_in_seconds = nbt
+            .float("length_in_seconds")
+            .ok_or(SculkParseError::MissingField("length_in_seconds".into()))?;
+        let comparator_output = nbt
+            .int("comparator_output")
+            .ok_or(SculkParseError::MissingField("comparator_output".into()))?;
+
+        Ok(JukeboxSongData {
+            sound_event,
+            description,
+            length_in_seconds,
+            comparator_output,
+        })
+    }
+}
+
+impl JukeboxPlayable {
+    /// The value of the component, the inverse of how it's parsed.
+    pub fn to_tag(&self, data_version: i32) -> simdnbt::owned::NbtTag {
+        let song = match &self.song {
+            JukeboxSong::ID(id) => simdnbt::owned::NbtTag::String(id.as_str().into()),
+            JukeboxSong::Inline(data) => simdnbt::owned::NbtTag::Compound(data.to_compound_nbt()),
+        };
+        if data_version >= V1_21_5 {
+            return song;
+        }
+
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+        nbt.insert("song", song);
+        if !self.show_in_tooltip {
+            nbt.insert("show_in_tooltip", false);
+        }
+
+        simdnbt::owned::NbtTag::Compound(nbt)
+    }
+}
+
+impl ToCompoundNbt for JukeboxSongData {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("sound_event", self.sound_event.to_tag());
+        nbt.insert("description", self.description.as_str());
+        nbt.insert("length_in_seconds", self.length_in_seconds);
+        nbt.insert("comparator_output", self.comparator_output);
+
+        nbt
+    }
+}
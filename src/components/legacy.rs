@@ -0,0 +1,643 @@
+//! Items from before 1.20.5, which store their data in a `tag` compound instead of components.
+//!
+//! The `tag` is converted into the 1.21 components the same way the game upgrades them,
+//! fields that don't have a component are kept in `minecraft:custom_data`.
+
+use super::Components;
+use crate::{
+    error::SculkParseError,
+    traits::FromCompoundNbt,
+    util::from_owned_compound,
+    version::{DataVersionScope, V1_21},
+};
+use simdnbt::{
+    borrow::NbtCompound,
+    owned::{self, NbtList, NbtTag},
+};
+
+// Bits of `HideFlags`.
+const HIDE_ENCHANTMENTS: i32 = 1;
+const HIDE_MODIFIERS: i32 = 2;
+const HIDE_UNBREAKABLE: i32 = 4;
+const HIDE_CAN_DESTROY: i32 = 8;
+const HIDE_CAN_PLACE: i32 = 16;
+const HIDE_ADDITIONAL: i32 = 32;
+const HIDE_DYE: i32 = 64;
+const HIDE_UPGRADES: i32 = 128;
+
+/// Fields of the `tag` that are converted into components, the rest goes into `minecraft:custom_data`.
+const CONVERTED_FIELDS: &[&str] = &[
+    "display",
+    "HideFlags",
+    "Enchantments",
+    "StoredEnchantments",
+    "Damage",
+    "Unbreakable",
+    "BlockEntityTag",
+    "AttributeModifiers",
+    "CanDestroy",
+    "CanPlaceOn",
+    "CustomModelData",
+    "RepairCost",
+    "SkullOwner",
+    "Potion",
+    "CustomPotionColor",
+    "pages",
+    "title",
+    "author",
+    "generation",
+    "resolved",
+    "filtered_pages",
+    "filtered_title",
+    "Items",
+    "ChargedProjectiles",
+    "Charged",
+    "map",
+    "Trim",
+];
+
+impl Components {
+    /// Parses the components of an item from before 1.20.5, converted from its `tag`.
+    /// Returns `None` if the item doesn't have a `tag`.
+    pub fn from_legacy_item_nbt(nbt: &NbtCompound) -> Result<Option<Self>, SculkParseError> {
+        let tag = match nbt.compound("tag") {
+            Some(tag) => tag,
+            None => return Ok(None),
+        };
+        let id = nbt.string("id").map(|id| id.to_str()).unwrap_or_default();
+
+        let mut item = owned::NbtCompound::new();
+        item.insert(
+            "components",
+            NbtTag::Compound(convert_legacy_tag(&id, &tag)),
+        );
+
+        // Text in the `tag` is always JSON, which is how components store it before 1.21.5.
+        let _data_version = DataVersionScope::enter(V1_21);
+        from_owned_compound(&item, Components::from_compound_nbt)
+            .map(Some)
+            .map_err(|e| e.at_field("tag"))
+    }
+}
+
+/// Converts the `tag` of an item from before 1.20.5 into the components compound of 1.21.
+/// The item id is needed since some fields depend on the item, like the pages of a book.
+pub fn convert_legacy_tag(id: &str, tag: &NbtCompound) -> owned::NbtCompound {
+    let mut components = owned::NbtCompound::new();
+    let hide_flags = tag.int("HideFlags").unwrap_or(0);
+    let shown = |flag: i32| -> i8 { (hide_flags & flag == 0) as i8 };
+
+    if let Some(display) = tag.compound("display") {
+        if let Some(name) = display.string("Name") {
+            components.insert("minecraft:custom_name", name.to_string());
+        }
+        if let Some(lore) = display.list("Lore") {
+            components.insert("minecraft:lore", lore.to_owned());
+        }
+        if let Some(color) = display.int("color") {
+            let mut dyed_color = owned::NbtCompound::new();
+            dyed_color.insert("rgb", color);
+            dyed_color.insert("show_in_tooltip", shown(HIDE_DYE));
+            components.insert("minecraft:dyed_color", NbtTag::Compound(dyed_color));
+        }
+    }
+
+    if let Some(enchantments) = tag.list("Enchantments") {
+        match convert_enchantments(&enchantments, shown(HIDE_ENCHANTMENTS)) {
+            Some(enchantments) => components.insert("minecraft:enchantments", enchantments),
+            // An empty list was used to make items glint.
+            None => components.insert("minecraft:enchantment_glint_override", NbtTag::Byte(1)),
+        }
+    }
+    if let Some(enchantments) = tag
+        .list("StoredEnchantments")
+        .and_then(|list| convert_enchantments(&list, shown(HIDE_ADDITIONAL)))
+    {
+        components.insert("minecraft:stored_enchantments", enchantments);
+    }
+
+    if let Some(damage) = tag.int("Damage").filter(|damage| *damage > 0) {
+        components.insert("minecraft:damage", damage);
+    }
+    if tag.byte("Unbreakable").is_some_and(|b| b != 0) {
+        let mut unbreakable = owned::NbtCompound::new();
+        unbreakable.insert("show_in_tooltip", shown(HIDE_UNBREAKABLE));
+        components.insert("minecraft:unbreakable", NbtTag::Compound(unbreakable));
+    }
+    if hide_flags & HIDE_ADDITIONAL != 0 {
+        components.insert(
+            "minecraft:hide_additional_tooltip",
+            NbtTag::Compound(owned::NbtCompound::new()),
+        );
+    }
+
+    if let Some(block_entity) = tag.compound("BlockEntityTag") {
+        let mut block_entity = block_entity.to_owned();
+        // The id was added by the game when placing, items the game can't resolve keep their own id.
+        if !block_entity.contains("id") {
+            block_entity.insert("id", block_entity_id(id).unwrap_or(id));
+        }
+        components.insert(
+            "minecraft:block_entity_data",
+            NbtTag::Compound(block_entity),
+        );
+    }
+
+    if let Some(modifiers) = tag
+        .list("AttributeModifiers")
+        .and_then(|list| list.compounds())
+    {
+        let modifiers = modifiers
+            .into_iter()
+            .filter_map(|modifier| convert_modifier(&modifier))
+            .collect();
+
+        let mut attribute_modifiers = owned::NbtCompound::new();
+        attribute_modifiers.insert("modifiers", NbtTag::List(NbtList::Compound(modifiers)));
+        attribute_modifiers.insert("show_in_tooltip", shown(HIDE_MODIFIERS));
+        components.insert(
+            "minecraft:attribute_modifiers",
+            NbtTag::Compound(attribute_modifiers),
+        );
+    }
+
+    for (key, component, flag) in [
+        ("CanDestroy", "minecraft:can_break", HIDE_CAN_DESTROY),
+        ("CanPlaceOn", "minecraft:can_place_on", HIDE_CAN_PLACE),
+    ] {
+        let blocks = match tag.list(key).and_then(|list| list.strings()) {
+            Some(blocks) => blocks,
+            None => continue,
+        };
+
+        let mut predicate = owned::NbtCompound::new();
+        predicate.insert(
+            "blocks",
+            NbtTag::List(NbtList::String(
+                blocks.iter().map(|block| (*block).to_owned()).collect(),
+            )),
+        );
+        let mut can_break = owned::NbtCompound::new();
+        can_break.insert(
+            "predicates",
+            NbtTag::List(NbtList::Compound(vec![predicate])),
+        );
+        can_break.insert("show_in_tooltip", shown(flag));
+        components.insert(component, NbtTag::Compound(can_break));
+    }
+
+    if let Some(custom_model_data) = tag.int("CustomModelData") {
+        components.insert("minecraft:custom_model_data", custom_model_data);
+    }
+    if let Some(repair_cost) = tag.int("RepairCost").filter(|cost| *cost > 0) {
+        components.insert("minecraft:repair_cost", repair_cost);
+    }
+    if let Some(map) = tag.int("map") {
+        components.insert("minecraft:map_id", map);
+    }
+
+    if let Some(name) = tag.string("SkullOwner") {
+        components.insert("minecraft:profile", name.to_string());
+    } else if let Some(owner) = tag.compound("SkullOwner") {
+        components.insert(
+            "minecraft:profile",
+            NbtTag::Compound(convert_profile(&owner)),
+        );
+    }
+
+    if tag.contains("Potion") || tag.contains("CustomPotionColor") {
+        let mut potion_contents = owned::NbtCompound::new();
+        if let Some(potion) = tag.string("Potion") {
+            potion_contents.insert("potion", potion.to_string());
+        }
+        if let Some(color) = tag.int("CustomPotionColor") {
+            potion_contents.insert("custom_color", color);
+        }
+        components.insert(
+            "minecraft:potion_contents",
+            NbtTag::Compound(potion_contents),
+        );
+    }
+
+    match id {
+        "minecraft:written_book" => {
+            components.insert(
+                "minecraft:written_book_content",
+                NbtTag::Compound(convert_written_book(tag)),
+            );
+        }
+        "minecraft:writable_book" => {
+            let mut content = owned::NbtCompound::new();
+            content.insert("pages", convert_pages(tag, "pages", "filtered_pages"));
+            components.insert("minecraft:writable_book_content", NbtTag::Compound(content));
+        }
+        _ => {}
+    }
+
+    if let Some(items) = tag.list("Items").filter(|items| !items.empty()) {
+        components.insert("minecraft:bundle_contents", items.to_owned());
+    }
+    if let Some(projectiles) = tag
+        .list("ChargedProjectiles")
+        .filter(|projectiles| !projectiles.empty())
+    {
+        components.insert("minecraft:charged_projectiles", projectiles.to_owned());
+    }
+
+    if let Some(trim) = tag.compound("Trim") {
+        let mut trim = trim.to_owned();
+        trim.insert("show_in_tooltip", shown(HIDE_UPGRADES));
+        components.insert("minecraft:trim", NbtTag::Compound(trim));
+    }
+
+    let mut custom_data = owned::NbtCompound::new();
+    for (key, value) in tag.iter() {
+        if !CONVERTED_FIELDS.contains(&key.to_str().as_ref()) {
+            custom_data.insert(key.to_owned(), value.to_owned());
+        }
+    }
+    if !custom_data.is_empty() {
+        components.insert("minecraft:custom_data", NbtTag::Compound(custom_data));
+    }
+
+    components
+}
+
+/// `[{id, lvl}]` into `{levels: {id: lvl}}`, `None` if there's no valid enchantment.
+fn convert_enchantments(list: &simdnbt::borrow::NbtList, show_in_tooltip: i8) -> Option<NbtTag> {
+    let mut levels = owned::NbtCompound::new();
+
+    for enchantment in list.compounds()? {
+        let id = match enchantment.string("id") {
+            Some(id) => id.to_string(),
+            None => continue,
+        };
+        let level = enchantment
+            .short("lvl")
+            .map(i32::from)
+            .or_else(|| enchantment.int("lvl"))
+            .unwrap_or(1);
+
+        if level > 0 {
+            levels.insert(id, level);
+        }
+    }
+
+    if levels.is_empty() {
+        return None;
+    }
+
+    let mut enchantments = owned::NbtCompound::new();
+    enchantments.insert("levels", NbtTag::Compound(levels));
+    enchantments.insert("show_in_tooltip", show_in_tooltip);
+    Some(NbtTag::Compound(enchantments))
+}
+
+/// `{AttributeName, Name, Amount, Operation, UUID, Slot}` into `{type, id, amount, operation, slot}`.
+fn convert_modifier(modifier: &NbtCompound) -> Option<owned::NbtCompound> {
+    let attribute = modifier.string("AttributeName")?.to_str();
+    let amount = modifier.double("Amount")?;
+    let operation = match modifier.int("Operation").unwrap_or(0) {
+        0 => "add_value",
+        1 => "add_multiplied_base",
+        _ => "add_multiplied_total",
+    };
+
+    // Modifiers were identified by UUID, which the game turns into the id.
+    let id = match modifier.int_array("UUID").as_deref() {
+        Some(&[a, b, c, d]) => {
+            let hex = format!(
+                "{:08x}{:08x}{:08x}{:08x}",
+                a as u32, b as u32, c as u32, d as u32
+            );
+            format!(
+                "minecraft:{}-{}-{}-{}-{}",
+                &hex[0..8],
+                &hex[8..12],
+                &hex[12..16],
+                &hex[16..20],
+                &hex[20..32]
+            )
+        }
+        _ => format!(
+            "minecraft:{}",
+            modifier
+                .string("Name")
+                .map(|name| name.to_str().to_lowercase().replace(' ', "_"))
+                .unwrap_or_else(|| "unnamed".into())
+        ),
+    };
+
+    let mut converted = owned::NbtCompound::new();
+    converted.insert(
+        "type",
+        match attribute.contains(':') {
+            true => attribute.to_string(),
+            false => format!("minecraft:{attribute}"),
+        },
+    );
+    converted.insert("id", id);
+    converted.insert("amount", amount);
+    converted.insert("operation", operation);
+    converted.insert(
+        "slot",
+        modifier
+            .string("Slot")
+            .map(|slot| slot.to_string())
+            .unwrap_or_else(|| "any".into()),
+    );
+
+    Some(converted)
+}
+
+/// `{Name, Id, Properties: {textures: [{Value, Signature}]}}` into `{name, id, properties: [{name, value, signature}]}`.
+fn convert_profile(owner: &NbtCompound) -> owned::NbtCompound {
+    let mut profile = owned::NbtCompound::new();
+
+    if let Some(name) = owner.string("Name") {
+        profile.insert("name", name.to_string());
+    }
+    if let Some(id) = owner.int_array("Id") {
+        profile.insert("id", NbtTag::IntArray(id));
+    }
+
+    let mut properties = vec![];
+    if let Some(owner_properties) = owner.compound("Properties") {
+        for (name, values) in owner_properties.iter() {
+            let values = match values.list().and_then(|list| list.compounds()) {
+                Some(values) => values,
+                None => continue,
+            };
+
+            for value in values {
+                let mut property = owned::NbtCompound::new();
+                property.insert("name", name.to_string());
+                if let Some(texture) = value.string("Value") {
+                    property.insert("value", texture.to_string());
+                }
+                if let Some(signature) = value.string("Signature") {
+                    property.insert("signature", signature.to_string());
+                }
+                properties.push(property);
+            }
+        }
+    }
+    profile.insert(
+        "properties",
+        NbtTag::List(match properties.is_empty() {
+            true => NbtList::Empty,
+            false => NbtList::Compound(properties),
+        }),
+    );
+
+    profile
+}
+
+fn convert_written_book(tag: &NbtCompound) -> owned::NbtCompound {
+    let mut content = owned::NbtCompound::new();
+    content.insert("pages", convert_pages(tag, "pages", "filtered_pages"));
+
+    let mut title = owned::NbtCompound::new();
+    title.insert(
+        "raw",
+        tag.string("title")
+            .map(|title| title.to_string())
+            .unwrap_or_default(),
+    );
+    if let Some(filtered) = tag.string("filtered_title") {
+        title.insert("filtered", filtered.to_string());
+    }
+    content.insert("title", NbtTag::Compound(title));
+
+    content.insert(
+        "author",
+        tag.string("author")
+            .map(|author| author.to_string())
+            .unwrap_or_default(),
+    );
+    content.insert("generation", tag.int("generation").unwrap_or(0));
+    content.insert("resolved", tag.byte("resolved").unwrap_or(0));
+
+    content
+}
+
+/// A list of page strings into `[{raw, filtered}]`, the filtered pages are a compound keyed by the page index.
+fn convert_pages(tag: &NbtCompound, key: &str, filtered_key: &str) -> NbtTag {
+    let pages = match tag.list(key).and_then(|list| list.strings()) {
+        Some(pages) => pages,
+        None => return NbtTag::List(NbtList::Empty),
+    };
+    let filtered = tag.compound(filtered_key);
+
+    let pages: Vec<owned::NbtCompound> = pages
+        .iter()
+        .enumerate()
+        .map(|(index, raw)| {
+            let mut page = owned::NbtCompound::new();
+            page.insert("raw", raw.to_string());
+            if let Some(filtered) = filtered
+                .as_ref()
+                .and_then(|filtered| filtered.string(&index.to_string()))
+            {
+                page.insert("filtered", filtered.to_string());
+            }
+            page
+        })
+        .collect();
+
+    match pages.is_empty() {
+        true => NbtTag::List(NbtList::Empty),
+        false => NbtTag::List(NbtList::Compound(pages)),
+    }
+}
+
+/// The id of the block entity an item places, which the game fills in for a `BlockEntityTag` without one.
+fn block_entity_id(item_id: &str) -> Option<&'static str> {
+    let name = item_id.strip_prefix("minecraft:")?;
+
+    let id = match name {
+        "barrel" => "minecraft:barrel",
+        "beacon" => "minecraft:beacon",
+        "beehive" | "bee_nest" => "minecraft:beehive",
+        "bell" => "minecraft:bell",
+        "blast_furnace" => "minecraft:blast_furnace",
+        "brewing_stand" => "minecraft:brewing_stand",
+        "calibrated_sculk_sensor" => "minecraft:calibrated_sculk_sensor",
+        "campfire" | "soul_campfire" => "minecraft:campfire",
+        "chest" => "minecraft:chest",
+        "chiseled_bookshelf" => "minecraft:chiseled_bookshelf",
+        "command_block" | "chain_command_block" | "repeating_command_block" => {
+            "minecraft:command_block"
+        }
+        "comparator" => "minecraft:comparator",
+        "conduit" => "minecraft:conduit",
+        "crafter" => "minecraft:crafter",
+        "creaking_heart" => "minecraft:creaking_heart",
+        "daylight_detector" => "minecraft:daylight_detector",
+        "decorated_pot" => "minecraft:decorated_pot",
+        "dispenser" => "minecraft:dispenser",
+        "dropper" => "minecraft:dropper",
+        "enchanting_table" => "minecraft:enchanting_table",
+        "ender_chest" => "minecraft:ender_chest",
+        "furnace" => "minecraft:furnace",
+        "hopper" => "minecraft:hopper",
+        "jigsaw" => "minecraft:jigsaw",
+        "jukebox" => "minecraft:jukebox",
+        "lectern" => "minecraft:lectern",
+        "sculk_catalyst" => "minecraft:sculk_catalyst",
+        "sculk_sensor" => "minecraft:sculk_sensor",
+        "sculk_shrieker" => "minecraft:sculk_shrieker",
+        "smoker" => "minecraft:smoker",
+        "spawner" => "minecraft:mob_spawner",
+        "structure_block" => "minecraft:structure_block",
+        "suspicious_sand" | "suspicious_gravel" => "minecraft:brushable_block",
+        "trapped_chest" => "minecraft:trapped_chest",
+        "trial_spawner" => "minecraft:trial_spawner",
+        "vault" => "minecraft:vault",
+        "skeleton_skull"
+        | "wither_skeleton_skull"
+        | "zombie_head"
+        | "player_head"
+        | "creeper_head"
+        | "dragon_head"
+        | "piglin_head" => "minecraft:skull",
+        _ if name.ends_with("_hanging_sign") => "minecraft:hanging_sign",
+        _ if name.ends_with("_sign") => "minecraft:sign",
+        _ if name.ends_with("_banner") => "minecraft:banner",
+        _ if name.ends_with("_bed") => "minecraft:bed",
+        _ if name.ends_with("shulker_box") => "minecraft:shulker_box",
+        _ => return None,
+    };
+
+    Some(id)
+}
+
+#[cfg(test)]
+#[test]
+fn legacy_item_tag() {
+    use super::Component;
+    use crate::{item::Item, util::write_root_compound};
+
+    // A sword the way 1.16 writes it.
+    let mut display = owned::NbtCompound::new();
+    display.insert("Name", r#"{"text":"Archive"}"#);
+    let mut sharpness = owned::NbtCompound::new();
+    sharpness.insert("id", "minecraft:sharpness");
+    sharpness.insert("lvl", 5i16);
+    let mut tag = owned::NbtCompound::new();
+    tag.insert("display", NbtTag::Compound(display));
+    tag.insert(
+        "Enchantments",
+        NbtTag::List(NbtList::Compound(vec![sharpness])),
+    );
+    tag.insert("HideFlags", HIDE_ENCHANTMENTS);
+    tag.insert("Damage", 12);
+    tag.insert("Unbreakable", 1i8);
+    tag.insert("Owner", "archive");
+
+    let mut item = owned::NbtCompound::new();
+    item.insert("Slot", 0i8);
+    item.insert("id", "minecraft:diamond_sword");
+    item.insert("Count", 1i8);
+    item.insert("tag", NbtTag::Compound(tag));
+
+    let bytes = write_root_compound(&item);
+    let nbt = simdnbt::borrow::read(&mut std::io::Cursor::new(bytes.as_slice()))
+        .unwrap()
+        .unwrap();
+    let item = Item::from_compound_nbt(&nbt.as_compound()).unwrap();
+    let components = item.components.unwrap();
+
+    assert!(item.extra.contains("tag"));
+    assert_eq!(
+        components.get("minecraft:custom_name"),
+        Some(&Component::CustomName(r#"{"text":"Archive"}"#.into()))
+    );
+    match components.get("minecraft:enchantments") {
+        Some(Component::Enchantments(enchantments)) => {
+            assert_eq!(enchantments.level("minecraft:sharpness"), Some(5));
+            assert!(!enchantments.show_in_tooltip);
+        }
+        other => panic!("{other:?}"),
+    }
+    assert_eq!(
+        components.get("minecraft:damage"),
+        Some(&Component::Damage(12))
+    );
+    match components.get("minecraft:unbreakable") {
+        Some(Component::Unbreakable(unbreakable)) => assert!(unbreakable.show_in_tooltip),
+        other => panic!("{other:?}"),
+    }
+    assert!(components.get("minecraft:custom_data").is_some());
+}
+
+#[cfg(test)]
+#[test]
+fn legacy_block_entity_tag() {
+    use super::Component;
+    use crate::{block_entities::BlockEntityKind, item::Item, util::write_root_compound};
+
+    let parse = |id: &str, block_entity: owned::NbtCompound| {
+        let mut tag = owned::NbtCompound::new();
+        tag.insert("BlockEntityTag", NbtTag::Compound(block_entity));
+        let mut item = owned::NbtCompound::new();
+        item.insert("Slot", 0i8);
+        item.insert("id", id);
+        item.insert("Count", 1i8);
+        item.insert("tag", NbtTag::Compound(tag));
+
+        let bytes = write_root_compound(&item);
+        let nbt = simdnbt::borrow::read(&mut std::io::Cursor::new(bytes.as_slice()))
+            .unwrap()
+            .unwrap();
+        let mut components = Item::from_compound_nbt(&nbt.as_compound())
+            .unwrap()
+            .components
+            .unwrap();
+        match components.remove("minecraft:block_entity_data") {
+            Some(Component::BlockEntityData(block_entity)) => block_entity,
+            other => panic!("{other:?}"),
+        }
+    };
+
+    let mut text = owned::NbtCompound::new();
+    text.insert("color", "black");
+    text.insert("messages", vec![r#""""#.to_string(); 4]);
+    let mut sign = owned::NbtCompound::new();
+    sign.insert("is_waxed", 1i8);
+    sign.insert("front_text", NbtTag::Compound(text.clone()));
+    sign.insert("back_text", NbtTag::Compound(text));
+    let sign = parse("minecraft:oak_sign", sign);
+    assert_eq!(sign.base.id, "minecraft:sign");
+    assert!(matches!(sign.kind, BlockEntityKind::Sign(_)));
+
+    let mut banner = owned::NbtCompound::new();
+    banner.insert("Patterns", NbtTag::List(NbtList::Empty));
+    let banner = parse("minecraft:red_banner", banner);
+    assert_eq!(banner.base.id, "minecraft:banner");
+    assert!(matches!(banner.kind, BlockEntityKind::Banners(_)));
+
+    let head = parse("minecraft:player_head", owned::NbtCompound::new());
+    assert_eq!(head.base.id, "minecraft:skull");
+    assert!(matches!(head.kind, BlockEntityKind::Skull(_)));
+
+    let spawner = parse("minecraft:spawner", owned::NbtCompound::new());
+    assert_eq!(spawner.base.id, "minecraft:mob_spawner");
+
+    let bed = parse("minecraft:white_bed", owned::NbtCompound::new());
+    assert_eq!(bed.base.id, "minecraft:bed");
+    assert_eq!(bed.kind, BlockEntityKind::Bed);
+
+    // An id that's already there is kept.
+    let mut chest = owned::NbtCompound::new();
+    chest.insert("id", "minecraft:trapped_chest");
+    assert_eq!(
+        parse("minecraft:chest", chest).base.id,
+        "minecraft:trapped_chest"
+    );
+
+    // Items without a block entity can't be resolved, so they keep their own id.
+    let sword = parse("minecraft:diamond_sword", owned::NbtCompound::new());
+    assert_eq!(sword.base.id, "minecraft:diamond_sword");
+    assert!(matches!(sword.kind, BlockEntityKind::Unknown(_)));
+}
@@ -0,0 +1,40 @@
+//! Lock component.
+
+use crate::error::SculkParseError;
+use simdnbt::borrow::NbtTag;
+
+/// The key needed to open a container item.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Lock {
+    /// The custom name the key has to have, before 1.21.2.
+    Name(String),
+
+    /// An item predicate the key has to match, from 1.21.2.  
+    /// Like `{components: {"minecraft:custom_name": "..."}}`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Predicate(simdnbt::owned::NbtCompound),
+}
+
+impl Lock {
+    /// Reads the component value, which is a string before 1.21.2 and a compound after.
+    pub(crate) fn from_tag(value: &NbtTag) -> Result<Self, SculkParseError> {
+        if let Some(name) = value.string() {
+            Ok(Lock::Name(name.to_string()))
+        } else if let Some(predicate) = value.compound() {
+            Ok(Lock::Predicate(predicate.to_owned()))
+        } else {
+            Err(SculkParseError::InvalidField("lock".into()))
+        }
+    }
+}
+
+impl Lock {
+    /// The value of the component, the inverse of how it's parsed.
+    pub fn to_tag(&self) -> simdnbt::owned::NbtTag {
+        match self {
+            Lock::Name(name) => simdnbt::owned::NbtTag::String(name.as_str().into()),
+            Lock::Predicate(predicate) => simdnbt::owned::NbtTag::Compound(predicate.clone()),
+        }
+    }
+}
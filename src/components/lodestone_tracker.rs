@@ -1,8 +1,10 @@
 //! Lodestone Tracker component. This component is present in lodestone compasses.
 
 use crate::{
-    traits::FromCompoundNbt,
-    util::{get_int_array, get_owned_string},
+    error::SculkParseError,
+    resource_location::ResourceLocation,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::get_resource_location,
 };
 
 /// The Lodestone Tracker component.
@@ -25,7 +27,7 @@ pub struct LodestoneTarget {
     pub pos: [i32; 3],
 
     /// The ID of the dimension of the lodestone.
-    pub dimension: String,
+    pub dimension: ResourceLocation,
 }
 
 impl FromCompoundNbt for LodestoneTracker {
@@ -35,7 +37,7 @@ impl FromCompoundNbt for LodestoneTracker {
     where
         Self: Sized,
     {
-        let target = if let Some(t) = nbt.compound("tracker") {
+        let target = if let Some(t) = nbt.compound("target") {
             Some(LodestoneTarget::from_compound_nbt(&t)?)
         } else {
             None
@@ -54,11 +56,39 @@ impl FromCompoundNbt for LodestoneTarget {
     where
         Self: Sized,
     {
-        let pos = get_int_array(nbt, "pos")?;
-        let pos = [pos[0], pos[1], pos[2]];
+        let pos = match nbt.int_array("pos").as_deref() {
+            Some(&[x, y, z]) => [x, y, z],
+            _ => return Err(SculkParseError::InvalidField("pos".into())),
+        };
 
-        let dimension = get_owned_string(nbt, "dimension")?;
+        let dimension = get_resource_location(nbt, "dimension")?;
 
         Ok(LodestoneTarget { pos, dimension })
     }
 }
+
+impl ToCompoundNbt for LodestoneTracker {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        if let Some(target) = &self.target {
+            nbt.insert("target", target.to_compound_nbt());
+        }
+        if !self.tracked {
+            nbt.insert("tracked", false);
+        }
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for LodestoneTarget {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("pos", simdnbt::owned::NbtTag::IntArray(self.pos.to_vec()));
+        nbt.insert("dimension", self.dimension.to_string());
+
+        nbt
+    }
+}
@@ -1,6 +1,10 @@
 //! Map decorations component.
 
-use crate::{error::SculkParseError, kv::KVPair, traits::FromCompoundNbt};
+use crate::{
+    error::SculkParseError,
+    kv::KVPair,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+};
 use std::collections::HashMap;
 use std::str::FromStr;
 
@@ -71,7 +75,8 @@ impl FromStr for MapIconType {
 
     /// Converts a string to a `MapIconType`.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s {
+        // Since 1.21 the types are registry ids, like `minecraft:red_x`.
+        Ok(match s.strip_prefix("minecraft:").unwrap_or(s) {
             "player" => Self::Player,
             "frame" => Self::Frame,
             "red_marker" => Self::RedMarker,
@@ -169,3 +174,73 @@ impl FromCompoundNbt for MapIcon {
         })
     }
 }
+
+impl MapIconType {
+    /// The registry id of the type, the inverse of [`MapIconType::from_str`].
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Player => "minecraft:player",
+            Self::Frame => "minecraft:frame",
+            Self::RedMarker => "minecraft:red_marker",
+            Self::BlueMarker => "minecraft:blue_marker",
+            Self::TargetX => "minecraft:target_x",
+            Self::TargetPoint => "minecraft:target_point",
+            Self::PlayerOffMap => "minecraft:player_off_map",
+            Self::PlayerOffLimits => "minecraft:player_off_limits",
+            Self::Mansion => "minecraft:mansion",
+            Self::Monument => "minecraft:monument",
+            Self::BannerWhite => "minecraft:banner_white",
+            Self::BannerOrange => "minecraft:banner_orange",
+            Self::BanneMagenta => "minecraft:banner_magenta",
+            Self::BannerLightBlue => "minecraft:banner_light_blue",
+            Self::BannerYellow => "minecraft:banner_yellow",
+            Self::BannerLime => "minecraft:banner_lime",
+            Self::BannerPink => "minecraft:banner_pink",
+            Self::BannerGray => "minecraft:banner_gray",
+            Self::BannerLightGray => "minecraft:banner_light_gray",
+            Self::BannerCyan => "minecraft:banner_cyan",
+            Self::BannerPurple => "minecraft:banner_purple",
+            Self::BannerBlue => "minecraft:banner_blue",
+            Self::BannerBrown => "minecraft:banner_brown",
+            Self::BannerGreen => "minecraft:banner_green",
+            Self::BannerRed => "minecraft:banner_red",
+            Self::BannerBlack => "minecraft:banner_black",
+            Self::RedX => "minecraft:red_x",
+            Self::VillageDesert => "minecraft:village_desert",
+            Self::VillagePlains => "minecraft:village_plains",
+            Self::VillageSavanna => "minecraft:village_savanna",
+            Self::VillageSnowy => "minecraft:village_snowy",
+            Self::VillageTaiga => "minecraft:village_taiga",
+            Self::JungleTemple => "minecraft:jungle_temple",
+            Self::SwampHut => "minecraft:swamp_hut",
+            Self::Unknown(id) => id,
+        }
+    }
+}
+
+impl ToCompoundNbt for MapDecorations {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut keys = self.0.keys().collect::<Vec<_>>();
+        keys.sort();
+
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+        for key in keys {
+            nbt.insert(key.as_str(), self.0[key].to_compound_nbt());
+        }
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for MapIcon {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("type", self.r#type.as_str());
+        nbt.insert("x", self.x);
+        nbt.insert("z", self.z);
+        nbt.insert("rotation", self.rotation);
+
+        nbt
+    }
+}
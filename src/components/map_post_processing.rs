@@ -0,0 +1,36 @@
+//! Map post processing component.
+
+use crate::error::SculkParseError;
+
+/// The operation applied to a filled map in a cartography table.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MapPostProcessing {
+    /// The map is locked.  
+    /// `0`
+    Lock,
+    /// The map is zoomed out.  
+    /// `1`
+    Scale,
+}
+
+impl MapPostProcessing {
+    pub fn from_i32(value: i32) -> Result<Self, SculkParseError> {
+        match value {
+            0 => Ok(MapPostProcessing::Lock),
+            1 => Ok(MapPostProcessing::Scale),
+            _ => Err(SculkParseError::InvalidField(
+                "minecraft:map_post_processing".into(),
+            )),
+        }
+    }
+}
+
+impl MapPostProcessing {
+    pub fn to_i32(&self) -> i32 {
+        match self {
+            MapPostProcessing::Lock => 0,
+            MapPostProcessing::Scale => 1,
+        }
+    }
+}
@@ -2,26 +2,35 @@ use attribute_modifiers::AttributeModifier;
 use banner_patterns::BannerPattern;
 use base_color::BaseColor;
 use bees::Bee;
+use consumable::ConsumeEffect;
 use container::Container;
+use id_set::get_id_set;
+use instrument::get_sound_event;
 use std::str::FromStr;
-use std::{
-    collections::HashMap,
-    ops::{Deref, DerefMut},
-};
+use std::{collections::HashMap, ops::Deref};
 use suspicious_stew_effects::SuspiciousStewEffects;
+use text::{get_text_component, get_text_components, text_component_tag, text_components_tag};
 use trim::Trim;
 
 use crate::{
-    block_entities::skull::{self, SkullProfile},
-    block_entity::NoCoordinatesBlockEntity,
+    block_entities::{
+        skull::{self, SkullProfile},
+        BlockEntityKind,
+    },
+    block_entity::{
+        kind_extra, NoCoordinatesBlockEntity, NoCoordinatesBlockEntityBase,
+        NO_COORDINATES_BASE_FIELDS,
+    },
     color::RGB,
     entity::Entity,
-    error::SculkParseError,
+    error::{ParseMode, ParseWarning, PathSegment, SculkParseError, Substitute},
     item::ItemWithNoSlot,
     kv::KVPair,
     rarity::Rarity,
-    traits::FromCompoundNbt,
-    util::get_t_list,
+    resource_location::LenientIdScope,
+    traits::{FromCompoundNbt, FromVersionedCompoundNbt, ToCompoundNbt, ToVersionedCompoundNbt},
+    util::{get_owned_string, get_t_compound_vec, get_t_list, write_compound_list},
+    version::{current_data_version, DataVersionScope},
 };
 
 pub mod attribute_modifiers;
@@ -29,31 +38,50 @@ pub mod banner_patterns;
 pub mod base_color;
 pub mod bees;
 pub mod block_state;
+pub mod blocks_attacks;
 pub mod bucket_entity_data;
 pub mod can_break;
+mod component_type;
+pub mod consumable;
 pub mod container;
 pub mod container_loot;
 pub mod custom_data;
+pub mod custom_model_data;
+#[cfg(feature = "default-components")]
+pub mod defaults;
 pub mod dyed_color;
 pub mod enchantments;
+pub mod equippable;
 pub mod firework_explosion;
 pub mod fireworks;
 pub mod food;
+pub mod id_set;
 pub mod instrument;
 pub mod jukebox_playable;
+pub mod legacy;
+pub mod lock;
 pub mod lodestone_tracker;
 pub mod map_decorations;
+pub mod map_post_processing;
 pub mod potion_contents;
 pub mod suspicious_stew_effects;
+pub(crate) mod text;
 pub mod tool;
+pub mod tooltip_display;
 pub mod trim;
 pub mod unbreakable;
+pub mod use_cooldown;
+pub mod weapon;
 pub mod writable_book_content;
 pub mod written_book_content;
+pub use component_type::*;
 
 type InternalMap = HashMap<String, Component>;
 
-/// A collection of components.
+/// A collection of components.  
+/// Since 1.20.5 the components of an item are a patch over the default components of its item type,
+/// `!minecraft:...` keys remove a default, see [`Components::apply_to`].  
+/// The map is only changed through methods like [`Components::insert`], which keep the markers consistent.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Default)]
@@ -67,11 +95,10 @@ impl Deref for Components {
     }
 }
 
-impl DerefMut for Components {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
-    }
-}
+/// Error returned by [`Components::insert`] for a component without a fixed key, which is handed back.
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+#[error("component without a fixed key, insert it with Components::insert_with_key")]
+pub struct KeylessComponent(pub Box<Component>);
 
 impl Components {
     /// Get a reference to the internal map.
@@ -79,21 +106,112 @@ impl Components {
         &self.0
     }
 
-    /// Get a mutable reference to the internal map.
-    pub fn map_mut(&mut self) -> &mut InternalMap {
-        &mut self.0
-    }
-
     /// Get a component by its key.  
     /// Returns `None` if the key does not exist.
     pub fn get(&self, key: &str) -> Option<&Component> {
         self.0.get(key)
     }
+
+    /// Get a component by its type.  
+    /// Returns `None` if the component is not set.
+    pub fn get_type(&self, component_type: ComponentType) -> Option<&Component> {
+        self.0.get(component_type.key())
+    }
+
+    /// Whether the component is set.
+    pub fn contains_type(&self, component_type: ComponentType) -> bool {
+        self.0.contains_key(component_type.key())
+    }
+
+    /// Inserts a component under the key of its variant, returning the component it replaced.  
+    /// Clears the `!minecraft:...` marker if the component was removed.
+    ///
+    /// Fails for components without a fixed key, like [`Component::EntityVariant`] and [`Component::Unknown`],
+    /// insert those with [`Components::insert_with_key`].
+    pub fn insert(&mut self, component: Component) -> Result<Option<Component>, KeylessComponent> {
+        match component.component_type() {
+            Some(component_type) => Ok(self.insert_with_key(component_type.key(), component)),
+            None => Err(KeylessComponent(Box::new(component))),
+        }
+    }
+
+    /// Inserts a component under the given key, returning the component it replaced.  
+    /// [`Component::Removed`] is stored as the `!minecraft:...` marker and replaces the component,
+    /// any other component clears the marker.
+    pub fn insert_with_key(&mut self, key: &str, component: Component) -> Option<Component> {
+        let key = key.trim_start_matches('!');
+        let removed_key = format!("!{key}");
+
+        match component {
+            Component::Removed => {
+                let previous = self.0.remove(key);
+                self.0.insert(removed_key, Component::Removed);
+                previous
+            }
+            component => {
+                self.0.remove(&removed_key);
+                self.0.insert(key.into(), component)
+            }
+        }
+    }
+
+    /// Removes a component by its key, returning it if it was set.  
+    /// Removing a `!minecraft:...` marker lets the item's default component apply again.
+    pub fn remove(&mut self, key: &str) -> Option<Component> {
+        self.0.remove(key)
+    }
+
+    /// Removes a component by its type, returning it if it was set.
+    pub fn remove_type(&mut self, component_type: ComponentType) -> Option<Component> {
+        self.0.remove(component_type.key())
+    }
+
+    /// Whether the component is removed from the item's defaults with a `!minecraft:...` marker.
+    pub fn is_removed(&self, component_type: ComponentType) -> bool {
+        self.0.contains_key(&component_type.removed_key())
+    }
+
+    /// The components the patch adds or replaces, without the `!minecraft:...` markers.
+    pub fn added(&self) -> impl Iterator<Item = (&String, &Component)> {
+        self.0
+            .iter()
+            .filter(|(_, component)| !matches!(component, Component::Removed))
+    }
+
+    /// The keys of the default components the patch removes, without the `!` prefix.
+    pub fn removed(&self) -> impl Iterator<Item = &str> {
+        self.0
+            .iter()
+            .filter(|(_, component)| matches!(component, Component::Removed))
+            .map(|(key, _)| key.trim_start_matches('!'))
+    }
+
+    /// Applies these components as a patch over the defaults of an item, returning the components the item ends up with.
+    pub fn apply_to(&self, defaults: &Components) -> Components {
+        let mut components = defaults.clone();
+
+        for key in self.removed() {
+            components.0.remove(key);
+        }
+        for (key, component) in self.added() {
+            components.0.insert(key.clone(), component.clone());
+        }
+
+        components
+    }
+
+    /// Removes the component from the item's defaults, replacing it if it was set.
+    pub fn mark_removed(&mut self, component_type: ComponentType) {
+        self.0.remove(component_type.key());
+        self.0
+            .insert(component_type.removed_key(), Component::Removed);
+    }
 }
 
-impl FromCompoundNbt for Components {
-    fn from_compound_nbt(
+impl FromVersionedCompoundNbt for Components {
+    fn from_compound_nbt_with_version(
         nbt: &simdnbt::borrow::NbtCompound,
+        data_version: i32,
     ) -> Result<Self, crate::error::SculkParseError>
     where
         Self: Sized,
@@ -107,419 +225,624 @@ impl FromCompoundNbt for Components {
         for (key, value) in nbt_components.iter() {
             let key = key.to_string();
 
-            let component: Component = match key.as_str() {
-                "minecraft:attribute_modifiers" => {
-                    // since the root value is either list or compound, we need to pass parent nbt.
-                    Component::AttributeModifiers(AttributeModifier::from_compound_nbt(nbt)?)
-                }
-                "minecraft:banner_patterns" => {
-                    let list = value.list().ok_or(SculkParseError::InvalidField(
-                        "minecraft:banner_patterns".into(),
-                    ))?;
-                    let patterns = get_t_list(
-                        &list,
-                        "minecraft:banner_patterns",
-                        BannerPattern::from_compound_nbt,
-                    )?;
-
-                    Component::BannerPatterns(patterns)
-                }
-                "minecraft:base_color" => {
-                    Component::BaseColor(BaseColor::from_compound_nbt(&nbt_components)?)
-                }
-                "minecraft:bees" => {
-                    let list = value
-                        .list()
-                        .ok_or(SculkParseError::InvalidField("minecraft:bees".into()))?;
-                    let bees = get_t_list::<Bee>(&list, "minecraft:bees", Bee::from_compound_nbt)?;
+            let component = parse_component(&key, &value, &nbt_components, data_version)
+                .map_err(|e| component_context(e, &key))?;
 
-                    Component::Bees(bees)
-                }
-                "minecraft:block_entity_data" => {
-                    let nbt = value.compound().ok_or(SculkParseError::InvalidField(
-                        "minecraft:block_entity_data".into(),
-                    ))?;
-                    Component::BlockEntityData(Box::from(
-                        NoCoordinatesBlockEntity::from_compound_nbt(&nbt)?,
-                    ))
-                }
-                "minecraft:block_state" => {
-                    let nbt = value.compound().ok_or(SculkParseError::InvalidField(
-                        "minecraft:block_state".into(),
-                    ))?;
-                    Component::BlockState(block_state::BlockState::from_compound_nbt(&nbt)?)
-                }
-                "minecraft:bucket_entity_data" => {
-                    let nbt = value.compound().ok_or(SculkParseError::InvalidField(
-                        "minecraft:bucket_entity_data".into(),
-                    ))?;
-                    Component::BucketEntityData(
-                        bucket_entity_data::BucketEntityData::from_compound_nbt(&nbt)?,
-                    )
-                }
-                "minecraft:bundle_conents" => {
-                    let list = value.list().ok_or(SculkParseError::InvalidField(
-                        "minecraft:bundle_conents".into(),
-                    ))?;
-                    let items = get_t_list(
-                        &list,
-                        "minecraft_bundle_conents",
-                        ItemWithNoSlot::from_compound_nbt,
-                    )?;
-
-                    Component::BundleContents(items)
-                }
-                "minecraft:can_break" => {
-                    let nbt = value
-                        .compound()
-                        .ok_or(SculkParseError::InvalidField("minecraft:can_break".into()))?;
-                    Component::CanBreak(can_break::CanBreak::from_compound_nbt(&nbt)?)
-                }
-                "minecraft:can_place_on" => {
-                    let nbt = value.compound().ok_or(SculkParseError::InvalidField(
-                        "minecraft:can_place_on".into(),
-                    ))?;
-                    Component::CanPlaceOn(can_break::CanBreak::from_compound_nbt(&nbt)?)
-                }
-                "minecraft:charged_projectiles" => {
-                    let list = value.list().ok_or(SculkParseError::InvalidField(
-                        "minecraft:charged_projectiles".into(),
-                    ))?;
-                    let items = get_t_list(
-                        &list,
-                        "minecraft:charged_projectiles",
-                        ItemWithNoSlot::from_compound_nbt,
-                    )?;
-
-                    Component::ChargedProjectiles(items)
-                }
-                "minecraft:container" => {
-                    let items = if let Some(list) = value.list() {
-                        let list = list
-                            .compounds()
-                            .ok_or(SculkParseError::InvalidField("minecraft:container".into()))?;
-
-                        let mut items = vec![];
-
-                        for item_container in list {
-                            items.push(Container::from_compound_nbt(&item_container)?);
-                        }
-
-                        items
-                    } else {
-                        vec![]
-                    };
-                    Component::Container(items)
-                }
-                "minecraft:container_loot" => {
-                    let nbt = value.compound().ok_or(SculkParseError::InvalidField(
-                        "minecraft:container_loot".into(),
-                    ))?;
-                    Component::ContainerLoot(container_loot::ContainerLoot::from_compound_nbt(
-                        &nbt,
-                    )?)
-                }
-                "minecraft:custom_data" => {
-                    Component::CustomData(custom_data::CustomData::from_compound_nbt(nbt)?)
-                }
-                "minecraft:custom_model_data" => {
-                    let value = value.int().ok_or(SculkParseError::InvalidField(
-                        "minecraft:custom_model_data".into(),
-                    ))?;
-                    Component::CustomModelData(value)
-                }
-                "minecraft:custom_name" => {
-                    let value = value
-                        .string()
-                        .ok_or(SculkParseError::InvalidField("custom_name".into()))?;
-                    Component::CustomName(value.to_string())
-                }
-                "minecraft:damage" => {
-                    let value = value
-                        .int()
-                        .ok_or(SculkParseError::InvalidField("damage".into()))?;
-                    Component::Damage(value)
-                }
-                "minecraft:debug_stick_state" => {
-                    let nbt = value
-                        .compound()
-                        .ok_or(SculkParseError::InvalidField("debug_stick_state".into()))?;
-                    Component::DebugStickState(KVPair::from_compound_nbt(&nbt)?)
-                }
-                "minecraft:dyed_color" => {
-                    Component::DyedColor(dyed_color::DyedColor::from_compound_nbt(nbt)?)
-                }
-                "minecraft:enchantment_glint_override" => {
-                    let value = value.byte().ok_or(SculkParseError::InvalidField(
-                        "enchantment_glint_override".into(),
-                    ))?;
-                    Component::EnchantmentGlintOverride(value != 0)
-                }
-                "minecraft:enchantments" => {
-                    let nbt = value
-                        .compound()
-                        .ok_or(SculkParseError::InvalidField("enchantments".into()))?;
-                    Component::Enchantments(enchantments::Enchantments::from_compound_nbt(&nbt)?)
-                }
-                "minecraft:entity_data" => {
-                    let nbt = value
-                        .compound()
-                        .ok_or(SculkParseError::InvalidField("entity_data".into()))?;
-                    Component::EntityData(Entity::from_compound_nbt(&nbt)?)
-                }
-                "minecraft:fire_resistant" => {
-                    value.compound().ok_or(SculkParseError::InvalidField(
-                        "minecraft:fire_resistant".into(),
-                    ))?;
-                    Component::FireResistant(true)
-                }
-                "minecraft:firework_explosion" => {
-                    let nbt = value
-                        .compound()
-                        .ok_or(SculkParseError::InvalidField("firework_explosion".into()))?;
-                    Component::FireworkExplosion(
-                        firework_explosion::FireworkExplosion::from_compound_nbt(&nbt)?,
-                    )
-                }
-                "minecraft:fireworks" => {
-                    let nbt = value
-                        .compound()
-                        .ok_or(SculkParseError::InvalidField("fireworks".into()))?;
-                    Component::Fireworks(fireworks::Fireworks::from_compound_nbt(&nbt)?)
-                }
-                "minecraft:food" => {
-                    let nbt = value
-                        .compound()
-                        .ok_or(SculkParseError::InvalidField("food".into()))?;
-                    Component::Food(food::Food::from_compound_nbt(&nbt)?)
-                }
-                "minecraft:hide_additional_tooltip" => {
-                    value.compound().ok_or(SculkParseError::InvalidField(
-                        "minecraft:hide_additional_tooltip".into(),
-                    ))?;
-                    Component::HideAdditionalTooltip(true)
-                }
-                "minecraft:hide_tooltip" => {
-                    value.compound().ok_or(SculkParseError::InvalidField(
-                        "minecraft:hide_tooltip".into(),
-                    ))?;
-                    Component::HideTooltip(true)
-                }
-                "minecraft:instrument" => Component::Instrument(
-                    instrument::Instrument::from_compound_nbt(&nbt_components)?,
-                ),
-                "minecraft:intangible_projectile" => {
-                    let value = value.byte().ok_or(SculkParseError::InvalidField(
-                        "minecraft:intangible_projectile".into(),
-                    ))?;
-                    Component::IntangibleProjectile(value != 0)
-                }
-                "minecraft:item_name" => {
-                    let value = value
-                        .string()
-                        .ok_or(SculkParseError::InvalidField("item_name".into()))?;
-                    Component::ItemName(value.to_string())
-                }
-                "minecraft:jukebox_playable" => {
-                    let nbt = value
-                        .compound()
-                        .ok_or(SculkParseError::InvalidField("jukebox_playable".into()))?;
-                    Component::JukeboxPlayable(
-                        jukebox_playable::JukeboxPlayable::from_compound_nbt(&nbt)?,
-                    )
-                }
-                "minecraft:lock" => {
-                    let value = value
-                        .string()
-                        .ok_or(SculkParseError::InvalidField("lock".into()))?;
-                    Component::Lock(value.to_string())
-                }
-                "minecraft:lodestone_tracker" => {
-                    let nbt = value
-                        .compound()
-                        .ok_or(SculkParseError::InvalidField("lodestone_tracker".into()))?;
-                    Component::LodestoneTracker(
-                        lodestone_tracker::LodestoneTracker::from_compound_nbt(&nbt)?,
-                    )
-                }
-                "minecraft:lore" => {
-                    let list = value
-                        .list()
-                        .ok_or(SculkParseError::InvalidField("lore".into()))?;
+            map.insert(key, component);
+        }
 
-                    let mut lore = vec![];
+        Ok(Components(map))
+    }
+}
 
-                    for item in list
-                        .strings()
-                        .ok_or(SculkParseError::InvalidField("lore".into()))?
-                    {
-                        lore.push((*item).to_string());
-                    }
+/// Writes the value of the `components` field, with the keys sorted so the output is stable.
+impl ToVersionedCompoundNbt for Components {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut components: Vec<_> = self.0.iter().collect();
+        components.sort_by_key(|(key, _)| *key);
 
-                    Component::Lore(lore)
-                }
-                "minecraft:map_color" => {
-                    let int = value
-                        .int()
-                        .ok_or(SculkParseError::InvalidField("map_color".into()))?;
-                    Component::MapColor(RGB::new(int))
-                }
-                "minecraft:map_decorations" => {
-                    let nbt = value
-                        .compound()
-                        .ok_or(SculkParseError::InvalidField("map_decorations".into()))?;
-                    Component::MapDecorations(map_decorations::MapDecorations::from_compound_nbt(
-                        &nbt,
-                    )?)
-                }
-                "minecraft:map_id" => {
-                    let value = value
-                        .int()
-                        .ok_or(SculkParseError::InvalidField("map_id".into()))?;
-                    Component::MapId(value)
-                }
-                "minecraft:max_damage" => {
-                    let value = value
-                        .int()
-                        .ok_or(SculkParseError::InvalidField("max_damage".into()))?;
-                    Component::MaxDamage(value)
-                }
-                "minecraft:max_stack_size" => {
-                    let value = value
-                        .int()
-                        .ok_or(SculkParseError::InvalidField("max_stack_size".into()))?;
-                    Component::MaxStackSize(value)
-                }
-                "minecraft:note_block_sound" => {
-                    let value = value
-                        .string()
-                        .ok_or(SculkParseError::InvalidField("note_block_sound".into()))?;
-                    Component::NoteBlockSound(value.to_string())
-                }
-                "minecraft:ominous_bottle_amplifier" => {
-                    let value = value.int().ok_or(SculkParseError::InvalidField(
-                        "ominous_bottle_amplifier".into(),
-                    ))?;
-                    Component::OminousBottleAmplifier(value)
-                }
-                "minecraft:pot_decorations" => {
-                    let list = value
-                        .list()
-                        .ok_or(SculkParseError::InvalidField("pot_decorations".into()))?;
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+        for (key, component) in components {
+            nbt.insert(key.as_str(), component.to_tag(data_version));
+        }
+
+        nbt
+    }
+}
 
-                    let mut decorations = vec![];
+impl Components {
+    /// Parses the components with the given [`ParseMode`], returns `None` if there's no `components` field.  
+    /// Lenient keeps components that fail as [`Component::Unknown`], strict fails on unknown component keys.
+    pub fn from_compound_nbt_with_mode(
+        nbt: &simdnbt::borrow::NbtCompound,
+        mode: ParseMode,
+        warnings: &mut Vec<ParseWarning>,
+    ) -> Result<Option<Self>, SculkParseError> {
+        let nbt_components = match nbt.compound("components") {
+            Some(components) => components,
+            None => return Ok(None),
+        };
+        let data_version = current_data_version();
+        let ids = LenientIdScope::enter(mode);
 
-                    for item in list
-                        .strings()
-                        .ok_or(SculkParseError::InvalidField("pot_decorations".into()))?
-                    {
-                        decorations.push((*item).to_string());
-                    }
+        let mut map: InternalMap = HashMap::new();
 
-                    Component::PotDecorations(decorations)
-                }
-                "minecraft:potion_contents" => Component::PotionContents(
-                    potion_contents::PotionContents::from_compound_nbt(&nbt_components)?,
-                ),
-                "minecraft:profile" => {
-                    Component::Profile(SkullProfile::from_component_compound_nbt(nbt)?)
-                }
-                "minecraft:rarity" => {
-                    let value = value
-                        .string()
-                        .ok_or(SculkParseError::InvalidField("rarity".into()))?;
-                    Component::Rarity(Rarity::from_str(value.to_str().as_ref())?)
+        for (key, value) in nbt_components.iter() {
+            let key = key.to_string();
+
+            let component = match parse_component(&key, &value, &nbt_components, data_version) {
+                Ok(Component::Unknown(_)) if mode == ParseMode::Strict => {
+                    Err(SculkParseError::UnknownField(key.clone()))
                 }
-                "minecraft:recipes" => {
-                    let list = value
-                        .list()
-                        .ok_or(SculkParseError::InvalidField("recipes".into()))?;
+                result => result,
+            };
+            let component = mode.recover(
+                component.map_err(|e| component_context(e, &key)),
+                warnings,
+                Substitute::Raw,
+                || Component::Unknown(value.to_owned()),
+            )?;
 
-                    let mut recipes = vec![];
+            map.insert(key, component);
+        }
+        ids.finish(warnings);
 
-                    for item in list
-                        .strings()
-                        .ok_or(SculkParseError::InvalidField("recipes".into()))?
-                    {
-                        recipes.push((*item).to_string());
-                    }
+        Ok(Some(Components(map)))
+    }
+}
 
-                    Component::Recipes(recipes)
-                }
-                "minecraft:repair_cost" => {
-                    let value = value
-                        .int()
-                        .ok_or(SculkParseError::InvalidField("repair_cost".into()))?;
-                    Component::RepairCost(value)
-                }
-                "minecraft:stored_enchantments" => {
-                    let nbt = value
-                        .compound()
-                        .ok_or(SculkParseError::InvalidField("stored_enchantments".into()))?;
-                    Component::StoredEnchantments(enchantments::Enchantments::from_compound_nbt(
-                        &nbt,
-                    )?)
-                }
-                "minecraft:suspicious_stew_effects" => {
-                    let effects = value.list().ok_or(SculkParseError::InvalidField(
-                        "suspicious_stew_effects".into(),
-                    ))?;
-
-                    let effects = get_t_list(
-                        &effects,
-                        "suspicious_stew_effects",
-                        SuspiciousStewEffects::from_compound_nbt,
-                    )?;
-
-                    Component::SuspiciousStewEffects(effects)
-                }
-                "minecraft:tool" => {
-                    let nbt = value
-                        .compound()
-                        .ok_or(SculkParseError::InvalidField("tool".into()))?;
-                    Component::Tool(tool::Tool::from_compound_nbt(&nbt)?)
-                }
-                "minecraft:trim" => {
-                    let nbt = value
-                        .compound()
-                        .ok_or(SculkParseError::InvalidField("trim".into()))?;
-                    Component::Trim(Trim::from_compound_nbt(&nbt)?)
-                }
-                "minecraft:unbreakable" => {
-                    if nbt.compound("minecraft:unbreakable").is_some() {
-                        let nbt = value
-                            .compound()
-                            .ok_or(SculkParseError::InvalidField("unbreakable".into()))?;
-
-                        Component::Unbreakable(unbreakable::Unbreakable::from_compound_nbt(&nbt)?)
-                    } else {
-                        return Err(SculkParseError::InvalidField("unbreakable".into()));
-                    }
-                }
-                "minecraft:writable_book_content" => {
-                    let nbt = value.compound().ok_or(SculkParseError::InvalidField(
-                        "writable_book_content".into(),
-                    ))?;
-                    Component::WritableBookContent(
-                        writable_book_content::WritableBookContent::from_compound_nbt(&nbt)?,
-                    )
+/// Adds `components.<key>` to the error path.  
+/// Components that are lists already start their path with the key.
+pub(crate) fn component_context(err: SculkParseError, key: &str) -> SculkParseError {
+    let err = match err.context().and_then(|context| context.path.first()) {
+        Some(PathSegment::Field(field)) if field == key => err,
+        _ => err.at_field(key),
+    };
+
+    err.at_field("components")
+}
+
+/// Parses a single component from its key and value.  
+/// Some components can be more than one tag type, so they read their value from `components` instead of `value`.  
+/// Components that changed format between versions are read based on `data_version`, see [`crate::version`].  
+/// Items, entities and block entities nested in the component are read with the same data version.
+pub(crate) fn parse_component(
+    key: &str,
+    value: &simdnbt::borrow::NbtTag,
+    nbt_components: &simdnbt::borrow::NbtCompound,
+    data_version: i32,
+) -> Result<Component, SculkParseError> {
+    let _data_version = DataVersionScope::enter(data_version);
+    let component = match key {
+        "minecraft:attribute_modifiers" => {
+            // since the root value is either list or compound, we need to pass the components compound.
+            Component::AttributeModifiers(AttributeModifier::from_compound_nbt(nbt_components)?)
+        }
+        "minecraft:banner_patterns" => {
+            let list = value.list().ok_or(SculkParseError::InvalidField(
+                "minecraft:banner_patterns".into(),
+            ))?;
+            let patterns = get_t_list(
+                &list,
+                "minecraft:banner_patterns",
+                BannerPattern::from_compound_nbt,
+            )?;
+
+            Component::BannerPatterns(patterns)
+        }
+        "minecraft:base_color" => {
+            Component::BaseColor(BaseColor::from_compound_nbt(nbt_components)?)
+        }
+        "minecraft:bees" => {
+            let list = value
+                .list()
+                .ok_or(SculkParseError::InvalidField("minecraft:bees".into()))?;
+            let bees = get_t_list::<Bee>(&list, "minecraft:bees", Bee::from_compound_nbt)?;
+
+            Component::Bees(bees)
+        }
+        "minecraft:blocks_attacks" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("blocks_attacks".into()))?;
+            Component::BlocksAttacks(blocks_attacks::BlocksAttacks::from_compound_nbt(&nbt)?)
+        }
+        "minecraft:block_entity_data" => {
+            let nbt = value.compound().ok_or(SculkParseError::InvalidField(
+                "minecraft:block_entity_data".into(),
+            ))?;
+            let mut base = NoCoordinatesBlockEntityBase::from_compound_nbt(&nbt)?;
+            // Items only store the fields that were set, like a sign with just `front_text`,
+            // so data that doesn't parse as a full block entity is kept raw.
+            let kind = BlockEntityKind::from_compound_nbt(&nbt)
+                .unwrap_or_else(|_| BlockEntityKind::Unknown(nbt.to_owned()));
+            base.extra = kind_extra(&nbt, &kind, NO_COORDINATES_BASE_FIELDS);
+
+            Component::BlockEntityData(Box::from(NoCoordinatesBlockEntity { base, kind }))
+        }
+        "minecraft:block_state" => {
+            let nbt = value.compound().ok_or(SculkParseError::InvalidField(
+                "minecraft:block_state".into(),
+            ))?;
+            Component::BlockState(block_state::BlockState::from_compound_nbt(&nbt)?)
+        }
+        "minecraft:break_sound" => {
+            Component::BreakSound(get_sound_event(nbt_components, "minecraft:break_sound")?)
+        }
+        "minecraft:bucket_entity_data" => {
+            let nbt = value.compound().ok_or(SculkParseError::InvalidField(
+                "minecraft:bucket_entity_data".into(),
+            ))?;
+            Component::BucketEntityData(bucket_entity_data::BucketEntityData::from_compound_nbt(
+                &nbt,
+            )?)
+        }
+        "minecraft:bundle_contents" => {
+            let list = value.list().ok_or(SculkParseError::InvalidField(
+                "minecraft:bundle_contents".into(),
+            ))?;
+            let items = get_t_list(
+                &list,
+                "minecraft:bundle_contents",
+                ItemWithNoSlot::from_compound_nbt,
+            )?;
+
+            Component::BundleContents(items)
+        }
+        "minecraft:can_break" => {
+            Component::CanBreak(can_break::CanBreak::from_tag(value, "minecraft:can_break")?)
+        }
+        "minecraft:can_place_on" => Component::CanPlaceOn(can_break::CanBreak::from_tag(
+            value,
+            "minecraft:can_place_on",
+        )?),
+        "minecraft:charged_projectiles" => {
+            let list = value.list().ok_or(SculkParseError::InvalidField(
+                "minecraft:charged_projectiles".into(),
+            ))?;
+            let items = get_t_list(
+                &list,
+                "minecraft:charged_projectiles",
+                ItemWithNoSlot::from_compound_nbt,
+            )?;
+
+            Component::ChargedProjectiles(items)
+        }
+        "minecraft:consumable" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("consumable".into()))?;
+            Component::Consumable(consumable::Consumable::from_compound_nbt(&nbt)?)
+        }
+        "minecraft:container" => {
+            let items = if let Some(list) = value.list() {
+                let list = list
+                    .compounds()
+                    .ok_or(SculkParseError::InvalidField("minecraft:container".into()))?;
+
+                let mut items = vec![];
+
+                for item_container in list {
+                    items.push(Container::from_compound_nbt(&item_container)?);
                 }
-                "minecraft:written_book_content" => {
-                    let nbt = value
-                        .compound()
-                        .ok_or(SculkParseError::InvalidField("written_book_content".into()))?;
-                    Component::WrittenBookContent(
-                        written_book_content::WrittenBookContent::from_compound_nbt(&nbt)?,
-                    )
+
+                items
+            } else {
+                vec![]
+            };
+            Component::Container(items)
+        }
+        "minecraft:container_loot" => {
+            let nbt = value.compound().ok_or(SculkParseError::InvalidField(
+                "minecraft:container_loot".into(),
+            ))?;
+            Component::ContainerLoot(container_loot::ContainerLoot::from_compound_nbt(&nbt)?)
+        }
+        "minecraft:creative_slot_lock" => {
+            value.compound().ok_or(SculkParseError::InvalidField(
+                "minecraft:creative_slot_lock".into(),
+            ))?;
+            Component::CreativeSlotLock(true)
+        }
+        "minecraft:custom_data" => {
+            Component::CustomData(custom_data::CustomData::from_compound_nbt(nbt_components)?)
+        }
+        "minecraft:custom_model_data" => {
+            Component::CustomModelData(custom_model_data::CustomModelData::from_tag(value)?)
+        }
+        "minecraft:custom_name" => {
+            let value = get_text_component(value, data_version)
+                .ok_or(SculkParseError::InvalidField("custom_name".into()))?;
+            Component::CustomName(value)
+        }
+        "minecraft:damage" => {
+            let value = value
+                .int()
+                .ok_or(SculkParseError::InvalidField("damage".into()))?;
+            Component::Damage(value)
+        }
+        "minecraft:damage_resistant" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("damage_resistant".into()))?;
+            Component::DamageResistant(get_owned_string(&nbt, "types")?)
+        }
+        "minecraft:death_protection" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("death_protection".into()))?;
+            Component::DeathProtection(get_t_compound_vec(
+                &nbt,
+                "death_effects",
+                ConsumeEffect::from_compound_nbt,
+            )?)
+        }
+        "minecraft:debug_stick_state" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("debug_stick_state".into()))?;
+            Component::DebugStickState(KVPair::from_compound_nbt(&nbt)?)
+        }
+        "minecraft:dyed_color" => {
+            Component::DyedColor(dyed_color::DyedColor::from_compound_nbt(nbt_components)?)
+        }
+        "minecraft:enchantable" => {
+            let value = value
+                .compound()
+                .and_then(|nbt| nbt.int("value"))
+                .ok_or(SculkParseError::InvalidField("enchantable".into()))?;
+            Component::Enchantable(value)
+        }
+        "minecraft:enchantment_glint_override" => {
+            let value = value.byte().ok_or(SculkParseError::InvalidField(
+                "enchantment_glint_override".into(),
+            ))?;
+            Component::EnchantmentGlintOverride(value != 0)
+        }
+        "minecraft:enchantments" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("enchantments".into()))?;
+            Component::Enchantments(enchantments::Enchantments::from_compound_nbt(&nbt)?)
+        }
+        "minecraft:entity_data" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("entity_data".into()))?;
+            Component::EntityData(Entity::from_compound_nbt(&nbt)?)
+        }
+        "minecraft:equippable" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("equippable".into()))?;
+            Component::Equippable(equippable::Equippable::from_compound_nbt(&nbt)?)
+        }
+        "minecraft:fire_resistant" => {
+            value.compound().ok_or(SculkParseError::InvalidField(
+                "minecraft:fire_resistant".into(),
+            ))?;
+            Component::FireResistant(true)
+        }
+        "minecraft:firework_explosion" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("firework_explosion".into()))?;
+            Component::FireworkExplosion(firework_explosion::FireworkExplosion::from_compound_nbt(
+                &nbt,
+            )?)
+        }
+        "minecraft:fireworks" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("fireworks".into()))?;
+            Component::Fireworks(fireworks::Fireworks::from_compound_nbt(&nbt)?)
+        }
+        "minecraft:food" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("food".into()))?;
+            Component::Food(food::Food::from_compound_nbt(&nbt)?)
+        }
+        "minecraft:glider" => {
+            value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("minecraft:glider".into()))?;
+            Component::Glider(true)
+        }
+        "minecraft:hide_additional_tooltip" => {
+            value.compound().ok_or(SculkParseError::InvalidField(
+                "minecraft:hide_additional_tooltip".into(),
+            ))?;
+            Component::HideAdditionalTooltip(true)
+        }
+        "minecraft:hide_tooltip" => {
+            value.compound().ok_or(SculkParseError::InvalidField(
+                "minecraft:hide_tooltip".into(),
+            ))?;
+            Component::HideTooltip(true)
+        }
+        "minecraft:instrument" => Component::Instrument(
+            instrument::Instrument::from_compound_nbt_with_version(nbt_components, data_version)?,
+        ),
+        "minecraft:intangible_projectile" => {
+            // Written as an empty compound, but older data may have it as a byte.
+            let value = match (value.byte(), value.compound()) {
+                (Some(byte), _) => byte != 0,
+                (None, Some(_)) => true,
+                _ => {
+                    return Err(SculkParseError::InvalidField(
+                        "minecraft:intangible_projectile".into(),
+                    ))
                 }
-                _ => Component::Unknown(value.to_owned()),
             };
+            Component::IntangibleProjectile(value)
+        }
+        "minecraft:item_model" => {
+            let value = value
+                .string()
+                .ok_or(SculkParseError::InvalidField("item_model".into()))?;
+            Component::ItemModel(value.to_string())
+        }
+        "minecraft:item_name" => {
+            let value = get_text_component(value, data_version)
+                .ok_or(SculkParseError::InvalidField("item_name".into()))?;
+            Component::ItemName(value)
+        }
+        "minecraft:jukebox_playable" => {
+            Component::JukeboxPlayable(jukebox_playable::JukeboxPlayable::from_tag(value)?)
+        }
+        "minecraft:lock" => Component::Lock(lock::Lock::from_tag(value)?),
+        "minecraft:lodestone_tracker" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("lodestone_tracker".into()))?;
+            Component::LodestoneTracker(lodestone_tracker::LodestoneTracker::from_compound_nbt(
+                &nbt,
+            )?)
+        }
+        "minecraft:lore" => {
+            let lore = get_text_components(value, data_version)
+                .ok_or(SculkParseError::InvalidField("lore".into()))?;
 
-            map.insert(key, component);
+            Component::Lore(lore)
+        }
+        "minecraft:map_color" => {
+            let int = value
+                .int()
+                .ok_or(SculkParseError::InvalidField("map_color".into()))?;
+            Component::MapColor(RGB::new(int))
         }
+        "minecraft:map_decorations" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("map_decorations".into()))?;
+            Component::MapDecorations(map_decorations::MapDecorations::from_compound_nbt(&nbt)?)
+        }
+        "minecraft:map_id" => {
+            let value = value
+                .int()
+                .ok_or(SculkParseError::InvalidField("map_id".into()))?;
+            Component::MapId(value)
+        }
+        "minecraft:map_post_processing" => {
+            let value = get_numeric(value).ok_or(SculkParseError::InvalidField(
+                "minecraft:map_post_processing".into(),
+            ))?;
+            Component::MapPostProcessing(map_post_processing::MapPostProcessing::from_i32(value)?)
+        }
+        "minecraft:max_damage" => {
+            let value = value
+                .int()
+                .ok_or(SculkParseError::InvalidField("max_damage".into()))?;
+            Component::MaxDamage(value)
+        }
+        "minecraft:max_stack_size" => {
+            let value = value
+                .int()
+                .ok_or(SculkParseError::InvalidField("max_stack_size".into()))?;
+            Component::MaxStackSize(value)
+        }
+        "minecraft:note_block_sound" => {
+            let value = value
+                .string()
+                .ok_or(SculkParseError::InvalidField("note_block_sound".into()))?;
+            Component::NoteBlockSound(value.to_string())
+        }
+        "minecraft:ominous_bottle_amplifier" => {
+            // Written as an int, but any number is accepted when read.
+            let value = get_numeric(value).ok_or(SculkParseError::InvalidField(
+                "ominous_bottle_amplifier".into(),
+            ))?;
+            Component::OminousBottleAmplifier(value)
+        }
+        "minecraft:pot_decorations" => {
+            let list = value
+                .list()
+                .ok_or(SculkParseError::InvalidField("pot_decorations".into()))?;
 
-        Ok(Components(map))
-    }
+            let mut decorations = vec![];
+
+            for item in list
+                .strings()
+                .ok_or(SculkParseError::InvalidField("pot_decorations".into()))?
+            {
+                decorations.push((*item).to_string());
+            }
+
+            Component::PotDecorations(decorations)
+        }
+        "minecraft:potion_contents" => Component::PotionContents(
+            potion_contents::PotionContents::from_compound_nbt(nbt_components)?,
+        ),
+        "minecraft:potion_duration_scale" => {
+            let value = value.float().ok_or(SculkParseError::InvalidField(
+                "potion_duration_scale".into(),
+            ))?;
+            Component::PotionDurationScale(value)
+        }
+        "minecraft:profile" => {
+            Component::Profile(SkullProfile::from_component_compound_nbt(nbt_components)?)
+        }
+        "minecraft:provides_banner_patterns" => {
+            let value = value.string().ok_or(SculkParseError::InvalidField(
+                "provides_banner_patterns".into(),
+            ))?;
+            Component::ProvidesBannerPatterns(value.to_string())
+        }
+        "minecraft:provides_trim_material" => {
+            let value = value.string().ok_or(SculkParseError::InvalidField(
+                "provides_trim_material".into(),
+            ))?;
+            Component::ProvidesTrimMaterial(value.to_string())
+        }
+        "minecraft:rarity" => {
+            let value = value
+                .string()
+                .ok_or(SculkParseError::InvalidField("rarity".into()))?;
+            Component::Rarity(Rarity::from_str(value.to_str().as_ref())?)
+        }
+        "minecraft:recipes" => {
+            let list = value
+                .list()
+                .ok_or(SculkParseError::InvalidField("recipes".into()))?;
+
+            let mut recipes = vec![];
+
+            for item in list
+                .strings()
+                .ok_or(SculkParseError::InvalidField("recipes".into()))?
+            {
+                recipes.push((*item).to_string());
+            }
+
+            Component::Recipes(recipes)
+        }
+        "minecraft:repairable" => {
+            let items = value
+                .compound()
+                .map(|nbt| get_id_set(&nbt, "items"))
+                .transpose()?
+                .flatten()
+                .ok_or(SculkParseError::InvalidField("repairable".into()))?;
+            Component::Repairable(items)
+        }
+        "minecraft:repair_cost" => {
+            let value = value
+                .int()
+                .ok_or(SculkParseError::InvalidField("repair_cost".into()))?;
+            Component::RepairCost(value)
+        }
+        "minecraft:stored_enchantments" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("stored_enchantments".into()))?;
+            Component::StoredEnchantments(enchantments::Enchantments::from_compound_nbt(&nbt)?)
+        }
+        "minecraft:suspicious_stew_effects" => {
+            let effects = value.list().ok_or(SculkParseError::InvalidField(
+                "suspicious_stew_effects".into(),
+            ))?;
+
+            let effects = get_t_list(
+                &effects,
+                "suspicious_stew_effects",
+                SuspiciousStewEffects::from_compound_nbt,
+            )?;
+
+            Component::SuspiciousStewEffects(effects)
+        }
+        "minecraft:tool" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("tool".into()))?;
+            Component::Tool(tool::Tool::from_compound_nbt(&nbt)?)
+        }
+        "minecraft:tooltip_display" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("tooltip_display".into()))?;
+            Component::TooltipDisplay(tooltip_display::TooltipDisplay::from_compound_nbt(&nbt)?)
+        }
+        "minecraft:tooltip_style" => {
+            let value = value
+                .string()
+                .ok_or(SculkParseError::InvalidField("tooltip_style".into()))?;
+            Component::TooltipStyle(value.to_string())
+        }
+        "minecraft:trim" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("trim".into()))?;
+            Component::Trim(Trim::from_compound_nbt(&nbt)?)
+        }
+        "minecraft:unbreakable" => {
+            if nbt_components.compound("minecraft:unbreakable").is_some() {
+                let nbt = value
+                    .compound()
+                    .ok_or(SculkParseError::InvalidField("unbreakable".into()))?;
+
+                Component::Unbreakable(unbreakable::Unbreakable::from_compound_nbt(&nbt)?)
+            } else {
+                return Err(SculkParseError::InvalidField("unbreakable".into()));
+            }
+        }
+        "minecraft:use_cooldown" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("use_cooldown".into()))?;
+            Component::UseCooldown(use_cooldown::UseCooldown::from_compound_nbt(&nbt)?)
+        }
+        "minecraft:use_remainder" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("use_remainder".into()))?;
+            Component::UseRemainder(Box::new(ItemWithNoSlot::from_compound_nbt(&nbt)?))
+        }
+        "minecraft:weapon" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("weapon".into()))?;
+            Component::Weapon(weapon::Weapon::from_compound_nbt(&nbt)?)
+        }
+        "minecraft:writable_book_content" => {
+            let nbt = value.compound().ok_or(SculkParseError::InvalidField(
+                "writable_book_content".into(),
+            ))?;
+            Component::WritableBookContent(
+                writable_book_content::WritableBookContent::from_compound_nbt(&nbt)?,
+            )
+        }
+        "minecraft:written_book_content" => {
+            let nbt = value
+                .compound()
+                .ok_or(SculkParseError::InvalidField("written_book_content".into()))?;
+            Component::WrittenBookContent(
+                written_book_content::WrittenBookContent::from_compound_nbt_with_version(
+                    &nbt,
+                    data_version,
+                )?,
+            )
+        }
+        // Markers that remove one of the item's default components, their value is an empty compound.
+        key if key.starts_with('!') => Component::Removed,
+        // Entity variants from 1.21.5, like `minecraft:wolf/variant` or `minecraft:fox/variant`.
+        key if key.contains('/') => match value.string() {
+            Some(variant) => Component::EntityVariant(variant.to_string()),
+            None => Component::Unknown(value.to_owned()),
+        },
+        _ => Component::Unknown(value.to_owned()),
+    };
+
+    Ok(component)
+}
+
+/// Reads any numeric tag as an int.
+fn get_numeric(value: &simdnbt::borrow::NbtTag) -> Option<i32> {
+    value
+        .int()
+        .or_else(|| value.byte().map(i32::from))
+        .or_else(|| value.short().map(i32::from))
+        .or_else(|| value.long().map(|long| long as i32))
 }
 
 /// Represents a component in a block entity.
@@ -543,6 +866,10 @@ pub enum Component {
     /// `minecraft:bees`
     Bees(Vec<bees::Bee>),
 
+    /// If set, this item blocks attacks while being used, like a shield. Added in 1.21.5.  
+    /// `minecraft:blocks_attacks`
+    BlocksAttacks(blocks_attacks::BlocksAttacks),
+
     /// [Block entity](https://minecraft.wiki/w/Block_entity) NBT applied when this block is placed.
     /// `minecraft:block_entity_data`
     BlockEntityData(Box<NoCoordinatesBlockEntity>),
@@ -551,6 +878,10 @@ pub enum Component {
     /// `minecraft:block_state`
     BlockState(block_state::BlockState),
 
+    /// The sound played when this item breaks. Added in 1.21.5.  
+    /// `minecraft:break_sound`
+    BreakSound(instrument::SoundEvent),
+
     /// NBT applied to an [entity](https://minecraft.wiki/w/Entity) when placed from this bucket. Only tags below are applied.
     /// `minecraft:bucket_entity_data`
     BucketEntityData(bucket_entity_data::BucketEntityData),
@@ -573,6 +904,10 @@ pub enum Component {
     /// `minecraft:charged_projectiles`
     ChargedProjectiles(Vec<ItemWithNoSlot>),
 
+    /// If set, this item can be consumed, with the effects applied when it is. Added in 1.21.2.  
+    /// `minecraft:consumable`
+    Consumable(consumable::Consumable),
+
     /// The items contained in this [container](https://minecraft.wiki/w/Container).  
     /// `minecraft:container`
     Container(Vec<container::Container>),
@@ -581,22 +916,35 @@ pub enum Component {
     /// `minecraft:container_loot`
     ContainerLoot(container_loot::ContainerLoot),
 
+    /// Prevents the item from being moved in the creative inventory. Only sent to clients, but kept if it was saved.  
+    /// `minecraft:creative_slot_lock`
+    CreativeSlotLock(bool),
+
     /// Contains key-value pairs of any custom data not used by the game, either as an object or a [SNBT](https://minecraft.wiki/w/SNBT) string.  
     /// `minecraft:custom_data`
     CustomData(custom_data::CustomData),
 
-    /// A value used in the "custom_model_data" [item tag](https://minecraft.wiki/w/Model#Item_models) in the overrides of item models.  
+    /// A value used in the "custom_model_data" [item tag](https://minecraft.wiki/w/Model#Item_models) in the overrides of item models. Lists of values since 1.21.4.  
     /// `minecraft:custom_model_data`
-    CustomModelData(i32),
+    CustomModelData(custom_model_data::CustomModelData),
 
     /// The JSON text component to use as this item's name. See [Raw JSON text](https://minecraft.wiki/w/Raw_JSON_text_format) format.  
-    /// `custom_name`
+    /// Stored as nbt since 1.21.5, which is converted to JSON.  
+    /// `minecraft:custom_name`
     CustomName(String),
 
     /// The number of uses consumed (not remaining) of the item's durability. Must be a non-negative integer, defaults to 0.  
     /// `minecraft:damage`
     Damage(i32),
 
+    /// The damage type tag this item is immune to as an entity, like `#minecraft:is_fire`. Added in 1.21.2.  
+    /// `minecraft:damage_resistant`
+    DamageResistant(String),
+
+    /// If set, this item protects the holder from dying, like a totem of undying. The effects are applied when it does. Added in 1.21.2.  
+    /// `minecraft:death_protection`
+    DeathProtection(Vec<consumable::ConsumeEffect>),
+
     /// The selected block state properties used by this debug stick.  
     /// `minecraft:debug_stick_state`
     DebugStickState(KVPair<String>),
@@ -605,6 +953,10 @@ pub enum Component {
     /// `minecraft:dyed_color`
     DyedColor(dyed_color::DyedColor),
 
+    /// How well this item can be enchanted in an enchanting table. Added in 1.21.2.  
+    /// `minecraft:enchantable`
+    Enchantable(i32),
+
     ///  Overrides the enchantment glint effect on this item. When true, this item will display a glint, even without enchantments. When false, this item will not display a glint, even with enchantments.  
     /// `minecraft:enchantment_glint_override`
     EnchantmentGlintOverride(bool),
@@ -617,6 +969,10 @@ pub enum Component {
     /// `minecraft:entity_data`
     EntityData(Entity),
 
+    /// If set, this item can be equipped in an equipment slot. Added in 1.21.2.  
+    /// `minecraft:equippable`
+    Equippable(equippable::Equippable),
+
     /// If set, this item will not burn in fire or lava.  
     /// `minecraft:fire_resistant`
     FireResistant(bool),
@@ -632,11 +988,15 @@ pub enum Component {
     /// `minecraft:food`
     Food(food::Food),
 
-    /// If set, it will hide additional info on this item's tooltip.  
+    /// If set, this item lets the wearer glide like an elytra when equipped. Added in 1.21.2.  
+    /// `minecraft:glider`
+    Glider(bool),
+
+    /// If set, it will hide additional info on this item's tooltip. Replaced by `tooltip_display` in 1.21.5.  
     /// `minecraft:hide_additional_tooltip`
     HideAdditionalTooltip(bool),
 
-    /// If set, it will completely hide this item's tooltip, including its name.  
+    /// If set, it will completely hide this item's tooltip, including its name. Replaced by `tooltip_display` in 1.21.5.  
     /// `minecraft:hide_tooltip`
     HideTooltip(bool),
 
@@ -648,6 +1008,10 @@ pub enum Component {
     /// `minecraft:intangible_projectile`
     IntangibleProjectile(bool),
 
+    /// The item model used to render this item. Added in 1.21.2.  
+    /// `minecraft:item_model`
+    ItemModel(String),
+
     /// The default name of this item, as a JSON text component. See [Raw JSON text format](https://minecraft.wiki/w/Raw_JSON_text_format). Unlike the [custom_name](https://minecraft.wiki/w/Data_component_format#custom_name) component, this name cannot be changed through an anvil, and does not show in some labels, such as banner markers and item frames.  
     /// `minecraft:item_name`
     ItemName(String),
@@ -656,15 +1020,15 @@ pub enum Component {
     /// `minecraft:jukebox_playable`
     JukeboxPlayable(jukebox_playable::JukeboxPlayable),
 
-    ///  The string value representing the "key" to open this container item. The key must be an item with the same value as its custom name.  
+    ///  The string value representing the "key" to open this container item. The key must be an item with the same value as its custom name. An item predicate since 1.21.2.  
     /// `minecraft:lock`
-    Lock(String),
+    Lock(lock::Lock),
 
     /// If specified, stores information about the lodestone this compass should point towards.  
     /// `minecraft:lodestone_tracker`
     LodestoneTracker(lodestone_tracker::LodestoneTracker),
 
-    /// List of additional lines to display in this item's tooltip. Has a maximum of 256 lines.   
+    /// List of additional lines to display in this item's tooltip, as JSON text components. Has a maximum of 256 lines.   
     /// `minecraft:lore`
     Lore(Vec<String>),
 
@@ -680,6 +1044,10 @@ pub enum Component {
     /// `minecraft:map_id`
     MapId(i32),
 
+    /// The operation to apply to a [filled map](https://minecraft.wiki/w/Filled_map) when it's copied, locked or scaled in a cartography table.  
+    /// `minecraft:map_post_processing`
+    MapPostProcessing(map_post_processing::MapPostProcessing),
+
     /// The maximum amount of damage that this item can take. If not set, this item cannot take damage. Must be a positive integer. Note that this component cannot be combined with [max_stack_size](https://minecraft.wiki/w/Data_component_format#max_stack_size).  
     /// `minecraft:max_damage`
     MaxDamage(i32),
@@ -707,10 +1075,22 @@ pub enum Component {
     /// `minecraft:potion_contents`
     PotionContents(potion_contents::PotionContents),
 
+    /// The multiplier for the duration of the effects of this potion. Added in 1.21.5.  
+    /// `minecraft:potion_duration_scale`
+    PotionDurationScale(f32),
+
     /// Information about the owner of this player head. If defined as a string, corresponds to name.  
     /// `minecraft:profile`
     Profile(skull::SkullProfile),
 
+    /// The banner pattern tag this item unlocks in a loom. Added in 1.21.5.  
+    /// `minecraft:provides_banner_patterns`
+    ProvidesBannerPatterns(String),
+
+    /// The trim material this item provides in a smithing table. Added in 1.21.5.  
+    /// `minecraft:provides_trim_material`
+    ProvidesTrimMaterial(String),
+
     /// Sets the rarity of this item, which affects the default color of its name.  
     /// `minecraft:rarity`
     Rarity(Rarity),
@@ -719,6 +1099,10 @@ pub enum Component {
     /// `minecraft:recipes`
     Recipes(Vec<String>),
 
+    /// The items that can repair this item in an anvil. Added in 1.21.2.  
+    /// `minecraft:repairable`
+    Repairable(id_set::IdSet),
+
     /// The number of experience levels to add to the base level cost when repairing, combining, or renaming this item with an anvil. Must be a non-negative integer, defaults to 0.  
     /// `minecraft:repair_cost`
     RepairCost(i32),
@@ -728,13 +1112,21 @@ pub enum Component {
     StoredEnchantments(enchantments::Enchantments),
 
     /// The effects applied when consuming this suspicious stew.  
-    /// `minecraft:suspicous_stew_effects`
+    /// `minecraft:suspicious_stew_effects`
     SuspiciousStewEffects(Vec<suspicious_stew_effects::SuspiciousStewEffects>),
 
     /// If set, this item is considered as a [tool](https://minecraft.wiki/w/Tool).  
     /// `minecraft:tool`
     Tool(tool::Tool),
 
+    /// Which parts of this item's tooltip are shown. Replaces `hide_tooltip` and the `show_in_tooltip` fields in 1.21.5.  
+    /// `minecraft:tooltip_display`
+    TooltipDisplay(tooltip_display::TooltipDisplay),
+
+    /// The tooltip sprites used for this item. Added in 1.21.2.  
+    /// `minecraft:tooltip_style`
+    TooltipStyle(String),
+
     /// Contains the trim applied to this [armor](https://minecraft.wiki/w/Armor) piece.  
     /// `minecraft:trim`
     Trim(trim::Trim),
@@ -743,6 +1135,18 @@ pub enum Component {
     /// `minecraft:unbreakable`
     Unbreakable(unbreakable::Unbreakable),
 
+    /// A cooldown applied after this item is used. Added in 1.21.2.  
+    /// `minecraft:use_cooldown`
+    UseCooldown(use_cooldown::UseCooldown),
+
+    /// The item this item turns into after being used, like a bowl after eating stew. Added in 1.21.2.  
+    /// `minecraft:use_remainder`
+    UseRemainder(Box<ItemWithNoSlot>),
+
+    /// If set, this item takes damage when attacking with it. Added in 1.21.5.  
+    /// `minecraft:weapon`
+    Weapon(weapon::Weapon),
+
     /// The contents of this [book and quill](https://minecraft.wiki/w/Book_and_quill).  
     /// `minecraft:writable_book_content`
     WritableBookContent(writable_book_content::WritableBookContent),
@@ -751,7 +1155,1359 @@ pub enum Component {
     /// `minecraft:written_book_content`
     WrittenBookContent(written_book_content::WrittenBookContent),
 
+    /// The variant of the entity this item spawns, like a wolf variant or a fox type. Added in 1.21.5.  
+    /// Keyed by the entity, like `minecraft:wolf/variant` or `minecraft:fox/variant`.
+    EntityVariant(String),
+
+    /// Removes a default component of the item.  
+    /// Keyed by the removed component with a `!` prefix, like `!minecraft:food`.
+    Removed,
+
     /// Unknown component.
     #[cfg_attr(feature = "serde", serde(skip))]
     Unknown(simdnbt::owned::NbtTag),
 }
+
+impl Component {
+    /// Writes the value of the component for the data version, the inverse of how it's parsed.
+    pub fn to_tag(&self, data_version: i32) -> simdnbt::owned::NbtTag {
+        use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+
+        let items = |items: &[ItemWithNoSlot]| {
+            NbtTag::List(write_compound_list(items, |item| {
+                item.to_compound_nbt_with_version(data_version)
+            }))
+        };
+        let strings = |strings: &[String]| {
+            NbtTag::List(NbtList::String(
+                strings
+                    .iter()
+                    .map(|string| string.as_str().into())
+                    .collect(),
+            ))
+        };
+        let unit = || NbtTag::Compound(NbtCompound::new());
+
+        match self {
+            Component::AttributeModifiers(modifiers) => modifiers.to_tag(),
+            Component::BannerPatterns(patterns) => NbtTag::List(write_compound_list(
+                patterns,
+                BannerPattern::to_compound_nbt,
+            )),
+            Component::BaseColor(color) => color.to_tag(),
+            Component::Bees(bees) => NbtTag::List(write_compound_list(bees, |bee| {
+                bee.to_compound_nbt_with_version(data_version)
+            })),
+            Component::BlocksAttacks(blocks_attacks) => {
+                NbtTag::Compound(blocks_attacks.to_compound_nbt())
+            }
+            Component::BlockEntityData(block_entity) => {
+                NbtTag::Compound(block_entity.to_compound_nbt_with_version(data_version))
+            }
+            Component::BlockState(block_state) => NbtTag::Compound(block_state.to_compound_nbt()),
+            Component::BreakSound(sound) => sound.to_tag(),
+            Component::BucketEntityData(data) => NbtTag::Compound(data.to_compound_nbt()),
+            Component::BundleContents(contents) => items(contents),
+            Component::CanBreak(predicate) | Component::CanPlaceOn(predicate) => {
+                predicate.to_tag(data_version)
+            }
+            Component::ChargedProjectiles(projectiles) => items(projectiles),
+            Component::Consumable(consumable) => NbtTag::Compound(consumable.to_compound_nbt()),
+            Component::Container(container) => {
+                NbtTag::List(write_compound_list(container, |slot| {
+                    slot.to_compound_nbt_with_version(data_version)
+                }))
+            }
+            Component::ContainerLoot(loot) => NbtTag::Compound(loot.to_compound_nbt()),
+            Component::CustomData(data) => data.to_tag(),
+            Component::CustomModelData(data) => data.to_tag(),
+            Component::CustomName(name) | Component::ItemName(name) => {
+                text_component_tag(name, data_version)
+            }
+            Component::Damage(value)
+            | Component::MapId(value)
+            | Component::MaxDamage(value)
+            | Component::MaxStackSize(value)
+            | Component::OminousBottleAmplifier(value)
+            | Component::RepairCost(value) => NbtTag::Int(*value),
+            Component::Enchantable(value) => {
+                let mut nbt = NbtCompound::new();
+                nbt.insert("value", *value);
+                NbtTag::Compound(nbt)
+            }
+            Component::DamageResistant(types) => {
+                let mut nbt = NbtCompound::new();
+                nbt.insert("types", types.as_str());
+                NbtTag::Compound(nbt)
+            }
+            Component::DeathProtection(effects) => {
+                let mut nbt = NbtCompound::new();
+                if !effects.is_empty() {
+                    nbt.insert(
+                        "death_effects",
+                        write_compound_list(effects, ConsumeEffect::to_compound_nbt),
+                    );
+                }
+                NbtTag::Compound(nbt)
+            }
+            Component::DebugStickState(state) => NbtTag::Compound(state.to_compound_nbt()),
+            Component::DyedColor(color) => color.to_tag(),
+            Component::EnchantmentGlintOverride(value) => NbtTag::Byte(*value as i8),
+            Component::Enchantments(enchantments) | Component::StoredEnchantments(enchantments) => {
+                enchantments.to_tag(data_version)
+            }
+            Component::EntityData(entity) => {
+                NbtTag::Compound(entity.to_compound_nbt_with_version(data_version))
+            }
+            Component::Equippable(equippable) => NbtTag::Compound(equippable.to_compound_nbt()),
+            Component::CreativeSlotLock(_)
+            | Component::FireResistant(_)
+            | Component::Glider(_)
+            | Component::HideAdditionalTooltip(_)
+            | Component::HideTooltip(_)
+            | Component::Removed => unit(),
+            Component::IntangibleProjectile(value) => match value {
+                true => unit(),
+                false => NbtTag::Byte(0),
+            },
+            Component::FireworkExplosion(explosion) => {
+                NbtTag::Compound(explosion.to_compound_nbt())
+            }
+            Component::Fireworks(fireworks) => NbtTag::Compound(fireworks.to_compound_nbt()),
+            Component::Food(food) => {
+                NbtTag::Compound(food.to_compound_nbt_with_version(data_version))
+            }
+            Component::Instrument(instrument) => instrument.to_tag(data_version),
+            Component::ItemModel(value)
+            | Component::NoteBlockSound(value)
+            | Component::ProvidesBannerPatterns(value)
+            | Component::ProvidesTrimMaterial(value)
+            | Component::TooltipStyle(value)
+            | Component::EntityVariant(value) => NbtTag::String(value.as_str().into()),
+            Component::JukeboxPlayable(playable) => playable.to_tag(data_version),
+            Component::Lock(lock) => lock.to_tag(),
+            Component::LodestoneTracker(tracker) => NbtTag::Compound(tracker.to_compound_nbt()),
+            Component::Lore(lore) => text_components_tag(lore, data_version),
+            Component::MapColor(color) => NbtTag::Int(i32::from(color.clone())),
+            Component::MapDecorations(decorations) => {
+                NbtTag::Compound(decorations.to_compound_nbt())
+            }
+            Component::MapPostProcessing(post_processing) => NbtTag::Int(post_processing.to_i32()),
+            Component::PotDecorations(decorations) => strings(decorations),
+            Component::PotionContents(contents) => contents.to_tag(),
+            Component::PotionDurationScale(scale) => NbtTag::Float(*scale),
+            Component::Profile(profile) => profile.to_tag(),
+            Component::Rarity(rarity) => NbtTag::String(rarity.to_str().into()),
+            Component::Recipes(recipes) => strings(recipes),
+            Component::Repairable(items) => {
+                let mut nbt = NbtCompound::new();
+                nbt.insert("items", items.to_tag());
+                NbtTag::Compound(nbt)
+            }
+            Component::SuspiciousStewEffects(effects) => NbtTag::List(write_compound_list(
+                effects,
+                SuspiciousStewEffects::to_compound_nbt,
+            )),
+            Component::Tool(tool) => NbtTag::Compound(tool.to_compound_nbt()),
+            Component::TooltipDisplay(display) => NbtTag::Compound(display.to_compound_nbt()),
+            Component::Trim(trim) => NbtTag::Compound(trim.to_compound_nbt()),
+            Component::Unbreakable(unbreakable) => NbtTag::Compound(unbreakable.to_compound_nbt()),
+            Component::UseCooldown(cooldown) => NbtTag::Compound(cooldown.to_compound_nbt()),
+            Component::UseRemainder(item) => {
+                NbtTag::Compound(item.to_compound_nbt_with_version(data_version))
+            }
+            Component::Weapon(weapon) => NbtTag::Compound(weapon.to_compound_nbt()),
+            Component::WritableBookContent(content) => NbtTag::Compound(content.to_compound_nbt()),
+            Component::WrittenBookContent(content) => {
+                NbtTag::Compound(content.to_compound_nbt_with_version(data_version))
+            }
+            Component::Unknown(tag) => tag.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+fn nbt_compound(fields: Vec<(&str, simdnbt::owned::NbtTag)>) -> simdnbt::owned::NbtTag {
+    let mut nbt = simdnbt::owned::NbtCompound::new();
+    for (key, value) in fields {
+        nbt.insert(key, value);
+    }
+    simdnbt::owned::NbtTag::Compound(nbt)
+}
+
+#[cfg(test)]
+fn nbt_string(value: &str) -> simdnbt::owned::NbtTag {
+    simdnbt::owned::NbtTag::String(value.into())
+}
+
+#[cfg(test)]
+fn nbt_strings(values: &[&str]) -> simdnbt::owned::NbtTag {
+    simdnbt::owned::NbtTag::List(simdnbt::owned::NbtList::String(
+        values.iter().map(|value| (*value).into()).collect(),
+    ))
+}
+
+#[cfg(test)]
+fn nbt_compounds(values: Vec<simdnbt::owned::NbtTag>) -> simdnbt::owned::NbtTag {
+    simdnbt::owned::NbtTag::List(simdnbt::owned::NbtList::Compound(
+        values
+            .into_iter()
+            .map(|value| match value {
+                simdnbt::owned::NbtTag::Compound(compound) => compound,
+                _ => unreachable!(),
+            })
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+fn nbt_unit() -> simdnbt::owned::NbtTag {
+    nbt_compound(vec![])
+}
+
+#[cfg(test)]
+fn nbt_item(id: &str) -> simdnbt::owned::NbtTag {
+    nbt_compound(vec![
+        ("id", nbt_string(id)),
+        ("count", simdnbt::owned::NbtTag::Int(1)),
+    ])
+}
+
+#[cfg(test)]
+fn fixture_item(id: &str) -> ItemWithNoSlot {
+    ItemWithNoSlot {
+        id: id.parse().unwrap(),
+        count: 1,
+        components: None,
+        extra: simdnbt::owned::NbtCompound::new(),
+    }
+}
+
+#[cfg(test)]
+fn fixture_levels(
+    levels: Vec<(&str, i32)>,
+) -> HashMap<crate::resource_location::ResourceLocation, i32> {
+    levels
+        .into_iter()
+        .map(|(id, level)| (id.parse().unwrap(), level))
+        .collect()
+}
+
+#[cfg(test)]
+fn fixture_pairs<T>(pairs: Vec<(&str, T)>) -> KVPair<T> {
+    KVPair::new(
+        pairs
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect(),
+    )
+}
+
+/// Parses `value` as the component `key` of an item saved with `data_version`.
+#[cfg(test)]
+fn parse_fixture(key: &str, value: simdnbt::owned::NbtTag, data_version: i32) -> Component {
+    let mut components = simdnbt::owned::NbtCompound::new();
+    components.insert(key, value);
+    let mut item = simdnbt::owned::NbtCompound::new();
+    item.insert("id", "minecraft:stone");
+    item.insert("count", 1);
+    item.insert("components", simdnbt::owned::NbtTag::Compound(components));
+
+    let bytes = crate::util::write_root_compound(&item);
+    let nbt = simdnbt::borrow::read(&mut std::io::Cursor::new(bytes.as_slice()))
+        .unwrap()
+        .unwrap();
+
+    let _data_version = crate::version::DataVersionScope::enter(data_version);
+    Components::from_compound_nbt(&nbt.as_compound())
+        .unwrap_or_else(|e| panic!("{key}: {e}"))
+        .remove(key)
+        .unwrap()
+}
+
+/// Parses a fixture, checks its key maps back to its type and that it reads
+/// back the same after being written.
+#[cfg(test)]
+fn check_fixture(key: &str, value: simdnbt::owned::NbtTag, data_version: i32) -> Component {
+    let component = parse_fixture(key, value, data_version);
+    if let Some(component_type) = component.component_type() {
+        assert_eq!(component_type.key(), key);
+        assert_eq!(key.parse::<ComponentType>().unwrap(), component_type);
+    }
+    let written = component.to_tag(data_version);
+    assert_eq!(
+        parse_fixture(key, written, data_version),
+        component,
+        "{key} round trip"
+    );
+    component
+}
+
+#[cfg(test)]
+#[test]
+fn component_fixtures_1_21() {
+    use crate::{
+        block_entities::skull::Profile, color::Color, resource_location::IdOrTag,
+        util::MC_DATA_VERSION, uuid::Uuid,
+    };
+    use attribute_modifiers::{AttributeModifiers, Modifier, Operation, SlotType};
+    use banner_patterns::{Pattern, ResourceName};
+    use can_break::{Blocks, CanBreak, Predicate};
+    use firework_explosion::{FireworkExplosion, FireworkShape};
+    use simdnbt::owned::NbtTag;
+    use writable_book_content::{BookTextData, PageType};
+
+    // Laid out the way 1.21 writes each component.
+    let fixtures = vec![
+        (
+            "minecraft:attribute_modifiers",
+            nbt_compound(vec![(
+                "modifiers",
+                nbt_compounds(vec![nbt_compound(vec![
+                    ("type", nbt_string("minecraft:generic.attack_damage")),
+                    ("id", nbt_string("minecraft:base_attack_damage")),
+                    ("amount", NbtTag::Double(5.0)),
+                    ("operation", nbt_string("add_value")),
+                    ("slot", nbt_string("mainhand")),
+                ])]),
+            )]),
+            Component::AttributeModifiers(AttributeModifier::Compound(AttributeModifiers {
+                show_in_tooltip: true,
+                modifiers: vec![Modifier {
+                    r#type: "minecraft:generic.attack_damage".into(),
+                    slot: SlotType::MainHand,
+                    id: "minecraft:base_attack_damage".into(),
+                    amount: 5.0,
+                    operation: Operation::AddValue,
+                }],
+            })),
+        ),
+        (
+            "minecraft:banner_patterns",
+            nbt_compounds(vec![nbt_compound(vec![
+                ("pattern", nbt_string("minecraft:creeper")),
+                ("color", nbt_string("red")),
+            ])]),
+            Component::BannerPatterns(vec![BannerPattern {
+                color: Color::Red,
+                pattern: Pattern::ID(ResourceName::Creeper),
+            }]),
+        ),
+        (
+            "minecraft:bucket_entity_data",
+            nbt_compound(vec![
+                ("NoAI", NbtTag::Byte(0)),
+                ("Silent", NbtTag::Byte(0)),
+                ("NoGravity", NbtTag::Byte(0)),
+                ("Glowing", NbtTag::Byte(0)),
+                ("Invulnerable", NbtTag::Byte(0)),
+                ("Health", NbtTag::Float(3.0)),
+                ("BucketVariantTag", NbtTag::Int(65536)),
+            ]),
+            Component::BucketEntityData(bucket_entity_data::BucketEntityData {
+                no_ai: Some(false),
+                silent: Some(false),
+                no_gravity: Some(false),
+                glowing: Some(false),
+                invulnerable: Some(false),
+                health: Some(3.0),
+                age: None,
+                variant: None,
+                hunting_cooldown: None,
+                bucket_variant_tag: Some(65536),
+            }),
+        ),
+        (
+            "minecraft:bundle_contents",
+            nbt_compounds(vec![nbt_item("minecraft:diamond")]),
+            Component::BundleContents(vec![fixture_item("minecraft:diamond")]),
+        ),
+        (
+            "minecraft:can_break",
+            nbt_compound(vec![(
+                "predicates",
+                nbt_compounds(vec![nbt_compound(vec![(
+                    "blocks",
+                    nbt_strings(&["minecraft:stone", "minecraft:dirt"]),
+                )])]),
+            )]),
+            Component::CanBreak(CanBreak::List {
+                predicates: vec![Predicate {
+                    blocks: Blocks::Blocks(vec![
+                        "minecraft:stone".parse::<IdOrTag>().unwrap(),
+                        "minecraft:dirt".parse::<IdOrTag>().unwrap(),
+                    ]),
+                    nbt: None,
+                    state: None,
+                }],
+                show_in_tooltip: true,
+            }),
+        ),
+        (
+            "minecraft:can_place_on",
+            nbt_compound(vec![
+                (
+                    "predicates",
+                    nbt_compounds(vec![nbt_compound(vec![(
+                        "blocks",
+                        nbt_string("minecraft:stone"),
+                    )])]),
+                ),
+                ("show_in_tooltip", NbtTag::Byte(0)),
+            ]),
+            Component::CanPlaceOn(CanBreak::List {
+                predicates: vec![Predicate {
+                    blocks: Blocks::Block("minecraft:stone".parse().unwrap()),
+                    nbt: None,
+                    state: None,
+                }],
+                show_in_tooltip: false,
+            }),
+        ),
+        (
+            "minecraft:charged_projectiles",
+            nbt_compounds(vec![nbt_item("minecraft:arrow")]),
+            Component::ChargedProjectiles(vec![fixture_item("minecraft:arrow")]),
+        ),
+        (
+            "minecraft:container",
+            nbt_compounds(vec![nbt_compound(vec![
+                ("slot", NbtTag::Int(0)),
+                ("item", nbt_item("minecraft:stone")),
+            ])]),
+            Component::Container(vec![Container {
+                item: fixture_item("minecraft:stone"),
+                slot: 0,
+            }]),
+        ),
+        (
+            "minecraft:container_loot",
+            nbt_compound(vec![
+                ("loot_table", nbt_string("minecraft:chests/simple_dungeon")),
+                ("seed", NbtTag::Long(1)),
+            ]),
+            Component::ContainerLoot(container_loot::ContainerLoot {
+                loot_table: "minecraft:chests/simple_dungeon".into(),
+                seed: Some(1),
+            }),
+        ),
+        (
+            "minecraft:creative_slot_lock",
+            nbt_unit(),
+            Component::CreativeSlotLock(true),
+        ),
+        (
+            "minecraft:custom_data",
+            nbt_compound(vec![("foo", nbt_string("bar"))]),
+            Component::CustomData(custom_data::CustomData::KeyValues(fixture_pairs(vec![(
+                "foo",
+                "bar".to_string(),
+            )]))),
+        ),
+        (
+            "minecraft:custom_model_data",
+            NbtTag::Int(1),
+            Component::CustomModelData(custom_model_data::CustomModelData::Int(1)),
+        ),
+        (
+            "minecraft:custom_name",
+            nbt_string(r#"{"text":"Name"}"#),
+            Component::CustomName(r#"{"text":"Name"}"#.into()),
+        ),
+        ("minecraft:damage", NbtTag::Int(5), Component::Damage(5)),
+        (
+            "minecraft:debug_stick_state",
+            nbt_compound(vec![("minecraft:oak_stairs", nbt_string("facing"))]),
+            Component::DebugStickState(fixture_pairs(vec![(
+                "minecraft:oak_stairs",
+                "facing".to_string(),
+            )])),
+        ),
+        (
+            "minecraft:dyed_color",
+            nbt_compound(vec![("rgb", NbtTag::Int(0xff0000))]),
+            Component::DyedColor(dyed_color::DyedColor::Compound {
+                rgb: RGB::new(0xff0000),
+                show_in_tooltip: true,
+            }),
+        ),
+        (
+            "minecraft:enchantment_glint_override",
+            NbtTag::Byte(1),
+            Component::EnchantmentGlintOverride(true),
+        ),
+        (
+            "minecraft:enchantments",
+            nbt_compound(vec![(
+                "levels",
+                nbt_compound(vec![("minecraft:sharpness", NbtTag::Int(5))]),
+            )]),
+            Component::Enchantments(enchantments::Enchantments {
+                levels: fixture_levels(vec![("minecraft:sharpness", 5)]),
+                show_in_tooltip: true,
+            }),
+        ),
+        (
+            "minecraft:fire_resistant",
+            nbt_unit(),
+            Component::FireResistant(true),
+        ),
+        (
+            "minecraft:firework_explosion",
+            nbt_compound(vec![
+                ("shape", nbt_string("star")),
+                ("colors", NbtTag::IntArray(vec![0xff0000])),
+                ("has_trail", NbtTag::Byte(1)),
+            ]),
+            Component::FireworkExplosion(FireworkExplosion {
+                shape: FireworkShape::Star,
+                colors: vec![0xff0000],
+                fade_colors: vec![],
+                has_trail: true,
+                has_twinkle: false,
+            }),
+        ),
+        (
+            "minecraft:fireworks",
+            nbt_compound(vec![
+                ("flight_duration", NbtTag::Byte(2)),
+                (
+                    "explosions",
+                    nbt_compounds(vec![nbt_compound(vec![("shape", nbt_string("burst"))])]),
+                ),
+            ]),
+            Component::Fireworks(fireworks::Fireworks {
+                explosions: vec![FireworkExplosion {
+                    shape: FireworkShape::Burst,
+                    colors: vec![],
+                    fade_colors: vec![],
+                    has_trail: false,
+                    has_twinkle: false,
+                }],
+                flight_duration: 2,
+            }),
+        ),
+        (
+            "minecraft:food",
+            nbt_compound(vec![
+                ("nutrition", NbtTag::Int(4)),
+                ("saturation", NbtTag::Float(2.4)),
+            ]),
+            Component::Food(food::Food {
+                nutrition: 4,
+                saturation: 2.4,
+                can_always_eat: false,
+                eat_seconds: 1.6,
+                using_converts_to: None,
+                effects: vec![],
+            }),
+        ),
+        (
+            "minecraft:hide_additional_tooltip",
+            nbt_unit(),
+            Component::HideAdditionalTooltip(true),
+        ),
+        (
+            "minecraft:hide_tooltip",
+            nbt_unit(),
+            Component::HideTooltip(true),
+        ),
+        (
+            "minecraft:instrument",
+            nbt_string("minecraft:ponder_goat_horn"),
+            Component::Instrument(instrument::Instrument::ID(
+                "minecraft:ponder_goat_horn".into(),
+            )),
+        ),
+        (
+            "minecraft:intangible_projectile",
+            nbt_unit(),
+            Component::IntangibleProjectile(true),
+        ),
+        (
+            "minecraft:item_name",
+            nbt_string(r#""Name""#),
+            Component::ItemName(r#""Name""#.into()),
+        ),
+        (
+            "minecraft:jukebox_playable",
+            nbt_compound(vec![("song", nbt_string("minecraft:cat"))]),
+            Component::JukeboxPlayable(jukebox_playable::JukeboxPlayable {
+                song: jukebox_playable::JukeboxSong::ID("minecraft:cat".into()),
+                show_in_tooltip: true,
+            }),
+        ),
+        (
+            "minecraft:lodestone_tracker",
+            nbt_compound(vec![
+                (
+                    "target",
+                    nbt_compound(vec![
+                        ("pos", NbtTag::IntArray(vec![1, 64, 3])),
+                        ("dimension", nbt_string("minecraft:overworld")),
+                    ]),
+                ),
+                ("tracked", NbtTag::Byte(1)),
+            ]),
+            Component::LodestoneTracker(lodestone_tracker::LodestoneTracker {
+                target: Some(lodestone_tracker::LodestoneTarget {
+                    pos: [1, 64, 3],
+                    dimension: "minecraft:overworld".parse().unwrap(),
+                }),
+                tracked: true,
+            }),
+        ),
+        (
+            "minecraft:lock",
+            nbt_string("key"),
+            Component::Lock(lock::Lock::Name("key".into())),
+        ),
+        (
+            "minecraft:lore",
+            nbt_strings(&[r#""line""#]),
+            Component::Lore(vec![r#""line""#.into()]),
+        ),
+        (
+            "minecraft:map_color",
+            NbtTag::Int(0x46402e),
+            Component::MapColor(RGB::new(0x46402e)),
+        ),
+        ("minecraft:map_id", NbtTag::Int(0), Component::MapId(0)),
+        (
+            "minecraft:map_post_processing",
+            NbtTag::Int(1),
+            Component::MapPostProcessing(map_post_processing::MapPostProcessing::Scale),
+        ),
+        (
+            "minecraft:max_damage",
+            NbtTag::Int(100),
+            Component::MaxDamage(100),
+        ),
+        (
+            "minecraft:max_stack_size",
+            NbtTag::Int(16),
+            Component::MaxStackSize(16),
+        ),
+        (
+            "minecraft:note_block_sound",
+            nbt_string("minecraft:block.note_block.harp"),
+            Component::NoteBlockSound("minecraft:block.note_block.harp".into()),
+        ),
+        (
+            "minecraft:ominous_bottle_amplifier",
+            NbtTag::Int(2),
+            Component::OminousBottleAmplifier(2),
+        ),
+        (
+            "minecraft:pot_decorations",
+            nbt_strings(&[
+                "minecraft:brick",
+                "minecraft:angler_pottery_sherd",
+                "minecraft:brick",
+                "minecraft:brick",
+            ]),
+            Component::PotDecorations(vec![
+                "minecraft:brick".into(),
+                "minecraft:angler_pottery_sherd".into(),
+                "minecraft:brick".into(),
+                "minecraft:brick".into(),
+            ]),
+        ),
+        (
+            "minecraft:potion_contents",
+            nbt_compound(vec![("potion", nbt_string("minecraft:swiftness"))]),
+            Component::PotionContents(potion_contents::PotionContents::Compound(
+                potion_contents::PotionData {
+                    potion: Some("minecraft:swiftness".into()),
+                    custom_color: None,
+                    custom_effects: vec![],
+                },
+            )),
+        ),
+        (
+            "minecraft:profile",
+            nbt_compound(vec![
+                ("name", nbt_string("Notch")),
+                ("id", NbtTag::IntArray(vec![1, 2, 3, 4])),
+                ("properties", NbtTag::List(simdnbt::owned::NbtList::Empty)),
+            ]),
+            Component::Profile(SkullProfile::Profile(Profile {
+                name: Some("Notch".into()),
+                id: Some(Uuid([1, 2, 3, 4])),
+                properties: Some(vec![]),
+            })),
+        ),
+        (
+            "minecraft:rarity",
+            nbt_string("epic"),
+            Component::Rarity(Rarity::Epic),
+        ),
+        (
+            "minecraft:recipes",
+            nbt_strings(&["minecraft:stone_bricks"]),
+            Component::Recipes(vec!["minecraft:stone_bricks".into()]),
+        ),
+        (
+            "minecraft:repair_cost",
+            NbtTag::Int(1),
+            Component::RepairCost(1),
+        ),
+        (
+            "minecraft:stored_enchantments",
+            nbt_compound(vec![(
+                "levels",
+                nbt_compound(vec![("minecraft:mending", NbtTag::Int(1))]),
+            )]),
+            Component::StoredEnchantments(enchantments::Enchantments {
+                levels: fixture_levels(vec![("minecraft:mending", 1)]),
+                show_in_tooltip: true,
+            }),
+        ),
+        (
+            "minecraft:suspicious_stew_effects",
+            nbt_compounds(vec![nbt_compound(vec![
+                ("id", nbt_string("minecraft:night_vision")),
+                ("duration", NbtTag::Int(100)),
+            ])]),
+            Component::SuspiciousStewEffects(vec![SuspiciousStewEffects {
+                id: "minecraft:night_vision".into(),
+                duration: 100,
+            }]),
+        ),
+        (
+            "minecraft:tool",
+            nbt_compound(vec![
+                (
+                    "rules",
+                    nbt_compounds(vec![nbt_compound(vec![
+                        ("blocks", nbt_string("#minecraft:mineable/pickaxe")),
+                        ("speed", NbtTag::Float(8.0)),
+                        ("correct_for_drops", NbtTag::Byte(1)),
+                    ])]),
+                ),
+                ("damage_per_block", NbtTag::Int(1)),
+            ]),
+            Component::Tool(tool::Tool {
+                default_mining_speed: 1.0,
+                damage_per_block: 1,
+                rules: vec![tool::ToolRules {
+                    blocks: tool::ToolRulesBlocks::Multiple(vec![
+                        "#minecraft:mineable/pickaxe".into()
+                    ]),
+                    speed: Some(8.0),
+                    correct_for_drops: Some(true),
+                }],
+            }),
+        ),
+        (
+            "minecraft:trim",
+            nbt_compound(vec![
+                ("pattern", nbt_string("minecraft:coast")),
+                ("material", nbt_string("minecraft:iron")),
+            ]),
+            Component::Trim(Trim {
+                pattern: "minecraft:coast".into(),
+                material: "minecraft:iron".into(),
+                show_in_tooltip: true,
+            }),
+        ),
+        (
+            "minecraft:unbreakable",
+            nbt_unit(),
+            Component::Unbreakable(unbreakable::Unbreakable {
+                show_in_tooltip: true,
+            }),
+        ),
+        (
+            "minecraft:writable_book_content",
+            nbt_compound(vec![(
+                "pages",
+                nbt_compounds(vec![nbt_compound(vec![("raw", nbt_string("hello"))])]),
+            )]),
+            Component::WritableBookContent(writable_book_content::WritableBookContent {
+                pages: PageType::Multiple(vec![BookTextData {
+                    raw: "hello".into(),
+                    filtered: None,
+                }]),
+            }),
+        ),
+        (
+            "minecraft:written_book_content",
+            nbt_compound(vec![
+                ("title", nbt_compound(vec![("raw", nbt_string("Book"))])),
+                ("author", nbt_string("Steve")),
+                (
+                    "pages",
+                    nbt_compounds(vec![nbt_compound(vec![("raw", nbt_string(r#""page""#))])]),
+                ),
+                ("resolved", NbtTag::Byte(1)),
+            ]),
+            Component::WrittenBookContent(written_book_content::WrittenBookContent {
+                pages: PageType::Multiple(vec![BookTextData {
+                    raw: r#""page""#.into(),
+                    filtered: None,
+                }]),
+                title: BookTextData {
+                    raw: "Book".into(),
+                    filtered: None,
+                },
+                author: "Steve".into(),
+                generation: 0,
+                resolved: true,
+            }),
+        ),
+    ];
+
+    for (key, value, expected) in fixtures {
+        assert_eq!(
+            check_fixture(key, value, MC_DATA_VERSION),
+            expected,
+            "{key}"
+        );
+    }
+
+    // Components whose values are only reachable through their nbt.
+    assert!(matches!(
+        check_fixture("minecraft:base_color", nbt_string("red"), MC_DATA_VERSION),
+        Component::BaseColor(color) if color.to_tag() == nbt_string("red")
+    ));
+    assert!(matches!(
+        check_fixture(
+            "minecraft:block_state",
+            nbt_compound(vec![("facing", nbt_string("north"))]),
+            MC_DATA_VERSION
+        ),
+        Component::BlockState(state)
+            if NbtTag::Compound(state.to_compound_nbt())
+                == nbt_compound(vec![("facing", nbt_string("north"))])
+    ));
+    let decorations = check_fixture(
+        "minecraft:map_decorations",
+        nbt_compound(vec![(
+            "+",
+            nbt_compound(vec![
+                ("type", nbt_string("minecraft:red_x")),
+                ("x", NbtTag::Double(1.0)),
+                ("z", NbtTag::Double(2.0)),
+                ("rotation", NbtTag::Float(180.0)),
+            ]),
+        )]),
+        MC_DATA_VERSION,
+    );
+    let icon = map_decorations::MapIcon {
+        r#type: map_decorations::MapIconType::RedX,
+        x: 1.0,
+        z: 2.0,
+        rotation: 180.0,
+    };
+    assert_eq!(
+        format!("{decorations:?}"),
+        format!(
+            "MapDecorations(MapDecorations({:?}))",
+            fixture_pairs(vec![("+", icon)])
+        )
+    );
+    assert!(matches!(
+        check_fixture(
+            "minecraft:bees",
+            nbt_compounds(vec![nbt_compound(vec![
+                (
+                    "entity_data",
+                    nbt_compound(vec![("id", nbt_string("minecraft:bee"))]),
+                ),
+                ("ticks_in_hive", NbtTag::Int(0)),
+                ("min_ticks_in_hive", NbtTag::Int(600)),
+            ])]),
+            MC_DATA_VERSION
+        ),
+        Component::Bees(bees) if matches!(
+            bees.as_slice(),
+            [Bee { entity_data, min_ticks_in_hive: 600, ticks_in_hive: 0 }]
+                if entity_data.id == "minecraft:bee"
+        )
+    ));
+    assert!(matches!(
+        check_fixture(
+            "minecraft:entity_data",
+            nbt_compound(vec![("id", nbt_string("minecraft:zombie"))]),
+            MC_DATA_VERSION
+        ),
+        Component::EntityData(entity) if entity.id == "minecraft:zombie"
+    ));
+    assert!(matches!(
+        check_fixture(
+            "minecraft:block_entity_data",
+            nbt_compound(vec![
+                ("id", nbt_string("minecraft:sign")),
+                ("is_waxed", NbtTag::Byte(1)),
+            ]),
+            MC_DATA_VERSION
+        ),
+        Component::BlockEntityData(data)
+            if data.base.id == "minecraft:sign"
+                && matches!(&data.kind, BlockEntityKind::Unknown(raw) if raw.byte("is_waxed") == Some(1))
+    ));
+    assert_eq!(
+        parse_fixture("!minecraft:food", nbt_unit(), MC_DATA_VERSION),
+        Component::Removed
+    );
+
+    // Alternative forms and edge cases.
+    let song = nbt_compound(vec![(
+        "song",
+        nbt_compound(vec![
+            ("sound_event", nbt_string("minecraft:music_disc.cat")),
+            (
+                "description",
+                nbt_compound(vec![(
+                    "translate",
+                    nbt_string("jukebox_song.minecraft.cat"),
+                )]),
+            ),
+            ("length_in_seconds", NbtTag::Float(185.0)),
+            ("comparator_output", NbtTag::Int(2)),
+        ]),
+    )]);
+    assert!(matches!(
+        parse_fixture("minecraft:jukebox_playable", song, MC_DATA_VERSION),
+        Component::JukeboxPlayable(jukebox_playable::JukeboxPlayable {
+            song: jukebox_playable::JukeboxSong::Inline(jukebox_playable::JukeboxSongData {
+                length_in_seconds: 185.0,
+                comparator_output: 2,
+                ..
+            }),
+            show_in_tooltip: true,
+        })
+    ));
+    assert_eq!(
+        parse_fixture(
+            "minecraft:ominous_bottle_amplifier",
+            NbtTag::Byte(3),
+            MC_DATA_VERSION
+        ),
+        Component::OminousBottleAmplifier(3)
+    );
+    assert!(matches!(
+        parse_fixture(
+            "minecraft:block_entity_data",
+            nbt_compound(vec![("id", nbt_string("minecraft:mod_block"))]),
+            MC_DATA_VERSION
+        ),
+        Component::BlockEntityData(data) if matches!(data.kind, BlockEntityKind::Unknown(_))
+    ));
+    assert_eq!(
+        parse_fixture(
+            "minecraft:writable_book_content",
+            nbt_unit(),
+            MC_DATA_VERSION
+        ),
+        Component::WritableBookContent(writable_book_content::WritableBookContent {
+            pages: PageType::Multiple(vec![]),
+        })
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn component_fixtures_1_21_5() {
+    use crate::version::V1_21_5;
+    use attribute_modifiers::{Modifier, Operation, SlotType};
+    use blocks_attacks::{BlocksAttacks, DamageReduction, ItemDamage};
+    use consumable::{Consumable, ConsumeEffect};
+    use id_set::IdSet;
+    use instrument::SoundEvent;
+    use simdnbt::owned::{NbtList, NbtTag};
+    use writable_book_content::{BookTextData, PageType};
+
+    // Components added or changed from 1.21.2 to 1.21.5, laid out the way 1.21.5 writes them.
+    let fixtures = vec![
+        (
+            "minecraft:attribute_modifiers",
+            nbt_compounds(vec![nbt_compound(vec![
+                ("type", nbt_string("minecraft:armor")),
+                ("id", nbt_string("minecraft:armor.chestplate")),
+                ("amount", NbtTag::Double(8.0)),
+                ("operation", nbt_string("add_value")),
+                ("slot", nbt_string("chest")),
+            ])]),
+            Component::AttributeModifiers(AttributeModifier::ModifierList(vec![Modifier {
+                r#type: "minecraft:armor".into(),
+                slot: SlotType::Chest,
+                id: "minecraft:armor.chestplate".into(),
+                amount: 8.0,
+                operation: Operation::AddValue,
+            }])),
+        ),
+        (
+            "minecraft:blocks_attacks",
+            nbt_compound(vec![
+                ("block_delay_seconds", NbtTag::Float(0.25)),
+                ("bypassed_by", nbt_string("#minecraft:bypasses_shield")),
+                (
+                    "item_damage",
+                    nbt_compound(vec![
+                        ("threshold", NbtTag::Float(3.0)),
+                        ("base", NbtTag::Float(1.0)),
+                        ("factor", NbtTag::Float(1.0)),
+                    ]),
+                ),
+                ("block_sound", nbt_string("minecraft:item.shield.block")),
+            ]),
+            Component::BlocksAttacks(BlocksAttacks {
+                block_delay_seconds: 0.25,
+                disable_cooldown_scale: 1.0,
+                damage_reductions: vec![DamageReduction {
+                    horizontal_blocking_angle: 90.0,
+                    r#type: None,
+                    base: 0.0,
+                    factor: 1.0,
+                }],
+                item_damage: ItemDamage {
+                    threshold: 3.0,
+                    base: 1.0,
+                    factor: 1.0,
+                },
+                bypassed_by: Some("#minecraft:bypasses_shield".into()),
+                block_sound: Some(SoundEvent::ID("minecraft:item.shield.block".into())),
+                disabled_sound: None,
+            }),
+        ),
+        (
+            "minecraft:break_sound",
+            nbt_string("minecraft:entity.item.break"),
+            Component::BreakSound(SoundEvent::ID("minecraft:entity.item.break".into())),
+        ),
+        (
+            "minecraft:can_break",
+            nbt_compounds(vec![nbt_compound(vec![(
+                "blocks",
+                nbt_string("minecraft:stone"),
+            )])]),
+            Component::CanBreak(can_break::CanBreak::List {
+                predicates: vec![can_break::Predicate {
+                    blocks: can_break::Blocks::Block("minecraft:stone".parse().unwrap()),
+                    nbt: None,
+                    state: None,
+                }],
+                show_in_tooltip: true,
+            }),
+        ),
+        (
+            "minecraft:consumable",
+            nbt_compound(vec![
+                ("consume_seconds", NbtTag::Float(0.8)),
+                ("animation", nbt_string("drink")),
+                ("sound", nbt_string("minecraft:entity.generic.drink")),
+                ("has_consume_particles", NbtTag::Byte(0)),
+                (
+                    "on_consume_effects",
+                    nbt_compounds(vec![
+                        nbt_compound(vec![("type", nbt_string("minecraft:clear_all_effects"))]),
+                        nbt_compound(vec![
+                            ("type", nbt_string("minecraft:apply_effects")),
+                            (
+                                "effects",
+                                nbt_compounds(vec![nbt_compound(vec![
+                                    ("id", nbt_string("minecraft:regeneration")),
+                                    ("duration", NbtTag::Int(100)),
+                                ])]),
+                            ),
+                        ]),
+                    ]),
+                ),
+            ]),
+            Component::Consumable(Consumable {
+                consume_seconds: 0.8,
+                animation: "drink".into(),
+                sound: SoundEvent::ID("minecraft:entity.generic.drink".into()),
+                has_consume_particles: false,
+                on_consume_effects: vec![
+                    ConsumeEffect::ClearAllEffects,
+                    ConsumeEffect::ApplyEffects {
+                        effects: vec![food::EffectDetails {
+                            id: "minecraft:regeneration".into(),
+                            amplifier: None,
+                            duration: Some(100),
+                            ambient: None,
+                            show_particles: None,
+                            show_icon: None,
+                        }],
+                        probability: 1.0,
+                    },
+                ],
+            }),
+        ),
+        (
+            "minecraft:custom_model_data",
+            nbt_compound(vec![
+                ("floats", NbtTag::List(NbtList::Float(vec![1.0]))),
+                ("flags", NbtTag::List(NbtList::Byte(vec![1]))),
+                ("colors", NbtTag::List(NbtList::Int(vec![0xff0000]))),
+            ]),
+            Component::CustomModelData(custom_model_data::CustomModelData::Lists {
+                floats: vec![1.0],
+                flags: vec![true],
+                strings: vec![],
+                colors: vec![RGB::new(0xff0000)],
+            }),
+        ),
+        (
+            "minecraft:custom_name",
+            nbt_compound(vec![
+                ("text", nbt_string("Name")),
+                ("italic", NbtTag::Byte(0)),
+            ]),
+            Component::CustomName(r#"{"text":"Name","italic":false}"#.into()),
+        ),
+        (
+            "minecraft:damage_resistant",
+            nbt_compound(vec![("types", nbt_string("#minecraft:is_fire"))]),
+            Component::DamageResistant("#minecraft:is_fire".into()),
+        ),
+        (
+            "minecraft:death_protection",
+            nbt_compound(vec![(
+                "death_effects",
+                nbt_compounds(vec![nbt_compound(vec![(
+                    "type",
+                    nbt_string("minecraft:clear_all_effects"),
+                )])]),
+            )]),
+            Component::DeathProtection(vec![ConsumeEffect::ClearAllEffects]),
+        ),
+        (
+            "minecraft:dyed_color",
+            NbtTag::Int(0xff0000),
+            Component::DyedColor(dyed_color::DyedColor::Int(RGB::new(0xff0000))),
+        ),
+        (
+            "minecraft:enchantable",
+            nbt_compound(vec![("value", NbtTag::Int(15))]),
+            Component::Enchantable(15),
+        ),
+        (
+            "minecraft:enchantments",
+            nbt_compound(vec![("minecraft:sharpness", NbtTag::Int(5))]),
+            Component::Enchantments(enchantments::Enchantments {
+                levels: fixture_levels(vec![("minecraft:sharpness", 5)]),
+                show_in_tooltip: true,
+            }),
+        ),
+        (
+            "minecraft:equippable",
+            nbt_compound(vec![
+                ("slot", nbt_string("head")),
+                ("asset_id", nbt_string("minecraft:diamond")),
+                ("allowed_entities", nbt_strings(&["minecraft:player"])),
+                ("equip_on_interact", NbtTag::Byte(1)),
+            ]),
+            Component::Equippable(equippable::Equippable {
+                slot: "head".into(),
+                equip_sound: SoundEvent::ID("minecraft:item.armor.equip_generic".into()),
+                asset_id: Some("minecraft:diamond".into()),
+                allowed_entities: Some(IdSet::Multiple(vec!["minecraft:player".into()])),
+                dispensable: true,
+                swappable: true,
+                damage_on_hurt: true,
+                equip_on_interact: true,
+                camera_overlay: None,
+            }),
+        ),
+        ("minecraft:glider", nbt_unit(), Component::Glider(true)),
+        (
+            "minecraft:item_model",
+            nbt_string("minecraft:diamond_sword"),
+            Component::ItemModel("minecraft:diamond_sword".into()),
+        ),
+        (
+            "minecraft:item_name",
+            nbt_string("Name"),
+            Component::ItemName(r#""Name""#.into()),
+        ),
+        (
+            "minecraft:jukebox_playable",
+            nbt_string("minecraft:cat"),
+            Component::JukeboxPlayable(jukebox_playable::JukeboxPlayable {
+                song: jukebox_playable::JukeboxSong::ID("minecraft:cat".into()),
+                show_in_tooltip: true,
+            }),
+        ),
+        (
+            "minecraft:lore",
+            nbt_compounds(vec![nbt_compound(vec![("text", nbt_string("line"))])]),
+            Component::Lore(vec![r#"{"text":"line"}"#.into()]),
+        ),
+        (
+            "minecraft:potion_duration_scale",
+            NbtTag::Float(0.25),
+            Component::PotionDurationScale(0.25),
+        ),
+        (
+            "minecraft:provides_banner_patterns",
+            nbt_string("#minecraft:pattern_item/flower"),
+            Component::ProvidesBannerPatterns("#minecraft:pattern_item/flower".into()),
+        ),
+        (
+            "minecraft:provides_trim_material",
+            nbt_string("minecraft:iron"),
+            Component::ProvidesTrimMaterial("minecraft:iron".into()),
+        ),
+        (
+            "minecraft:repairable",
+            nbt_compound(vec![(
+                "items",
+                nbt_string("#minecraft:repairs_diamond_armor"),
+            )]),
+            Component::Repairable(IdSet::Single("#minecraft:repairs_diamond_armor".into())),
+        ),
+        (
+            "minecraft:tooltip_display",
+            nbt_compound(vec![
+                ("hide_tooltip", NbtTag::Byte(0)),
+                (
+                    "hidden_components",
+                    nbt_strings(&["minecraft:enchantments"]),
+                ),
+            ]),
+            Component::TooltipDisplay(tooltip_display::TooltipDisplay {
+                hide_tooltip: false,
+                hidden_components: vec!["minecraft:enchantments".into()],
+            }),
+        ),
+        (
+            "minecraft:tooltip_style",
+            nbt_string("minecraft:custom"),
+            Component::TooltipStyle("minecraft:custom".into()),
+        ),
+        (
+            "minecraft:unbreakable",
+            nbt_unit(),
+            Component::Unbreakable(unbreakable::Unbreakable {
+                show_in_tooltip: true,
+            }),
+        ),
+        (
+            "minecraft:use_cooldown",
+            nbt_compound(vec![
+                ("seconds", NbtTag::Float(1.5)),
+                ("cooldown_group", nbt_string("minecraft:ender_pearl")),
+            ]),
+            Component::UseCooldown(use_cooldown::UseCooldown {
+                seconds: 1.5,
+                cooldown_group: Some("minecraft:ender_pearl".into()),
+            }),
+        ),
+        (
+            "minecraft:use_remainder",
+            nbt_item("minecraft:bowl"),
+            Component::UseRemainder(Box::new(fixture_item("minecraft:bowl"))),
+        ),
+        (
+            "minecraft:weapon",
+            nbt_compound(vec![("item_damage_per_attack", NbtTag::Int(2))]),
+            Component::Weapon(weapon::Weapon {
+                item_damage_per_attack: 2,
+                disable_blocking_for_seconds: 0.0,
+            }),
+        ),
+        (
+            "minecraft:wolf/variant",
+            nbt_string("minecraft:ashen"),
+            Component::EntityVariant("minecraft:ashen".into()),
+        ),
+        (
+            "minecraft:written_book_content",
+            nbt_compound(vec![
+                ("title", nbt_compound(vec![("raw", nbt_string("Book"))])),
+                ("author", nbt_string("Steve")),
+                (
+                    "pages",
+                    nbt_compounds(vec![nbt_compound(vec![("raw", nbt_string("page"))])]),
+                ),
+            ]),
+            // Plain string pages are literal text since 1.21.5.
+            Component::WrittenBookContent(written_book_content::WrittenBookContent {
+                pages: PageType::Multiple(vec![BookTextData {
+                    raw: r#""page""#.into(),
+                    filtered: None,
+                }]),
+                title: BookTextData {
+                    raw: "Book".into(),
+                    filtered: None,
+                },
+                author: "Steve".into(),
+                generation: 0,
+                resolved: false,
+            }),
+        ),
+    ];
+
+    for (key, value, expected) in fixtures {
+        assert_eq!(check_fixture(key, value, V1_21_5), expected, "{key}");
+    }
+
+    let key_predicate = nbt_compound(vec![(
+        "components",
+        nbt_compound(vec![("minecraft:custom_name", nbt_string("key"))]),
+    )]);
+    assert!(matches!(
+        check_fixture("minecraft:lock", key_predicate.clone(), V1_21_5),
+        Component::Lock(lock::Lock::Predicate(predicate))
+            if NbtTag::Compound(predicate.clone()) == key_predicate
+    ));
+    assert_eq!(
+        parse_fixture("!minecraft:food", nbt_unit(), V1_21_5),
+        Component::Removed
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn typed_components() {
+    let mut components = Components::default();
+    components.mark_removed(ComponentType::Damage);
+    assert!(components.is_removed(ComponentType::Damage));
+
+    assert_eq!(components.insert(Component::Damage(3)).unwrap(), None);
+    assert_eq!(
+        components
+            .insert(Component::CustomName(r#""Name""#.into()))
+            .unwrap(),
+        None
+    );
+
+    assert!(!components.is_removed(ComponentType::Damage));
+    assert_eq!(components.damage(), Some(3));
+    assert_eq!(
+        components.custom_name().map(String::as_str),
+        Some(r#""Name""#)
+    );
+    assert_eq!(components.enchantments(), None);
+    assert_eq!(
+        components.remove_type(ComponentType::Damage),
+        Some(Component::Damage(3))
+    );
+    assert_eq!(
+        "damage".parse::<ComponentType>().unwrap(),
+        ComponentType::Damage
+    );
+
+    // Components without a fixed key are inserted with their key.
+    let variant = Component::EntityVariant("minecraft:ashen".into());
+    assert_eq!(
+        components.insert(variant.clone()),
+        Err(KeylessComponent(Box::new(variant.clone())))
+    );
+    assert_eq!(
+        components.insert_with_key("minecraft:wolf/variant", variant.clone()),
+        None
+    );
+    assert_eq!(components.get("minecraft:wolf/variant"), Some(&variant));
+
+    components.insert_with_key("minecraft:wolf/variant", Component::Removed);
+    assert_eq!(components.get("minecraft:wolf/variant"), None);
+    assert_eq!(
+        components.removed().collect::<Vec<_>>(),
+        vec!["minecraft:wolf/variant"]
+    );
+}
@@ -2,8 +2,8 @@
 
 use crate::{
     color::RGB,
-    traits::FromCompoundNbt,
-    util::{get_owned_optional_string, get_t_compound_vec},
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::{get_owned_optional_string, get_t_compound_vec, set_optional_tag, write_compound_list},
 };
 
 use super::food::EffectDetails;
@@ -76,3 +76,38 @@ impl FromCompoundNbt for PotionData {
         })
     }
 }
+
+impl PotionContents {
+    /// The value of the component, the inverse of [`PotionContents::from_compound_nbt`].
+    pub fn to_tag(&self) -> simdnbt::owned::NbtTag {
+        match self {
+            PotionContents::String(potion) => {
+                simdnbt::owned::NbtTag::String(potion.as_str().into())
+            }
+            PotionContents::Compound(data) => {
+                simdnbt::owned::NbtTag::Compound(data.to_compound_nbt())
+            }
+        }
+    }
+}
+
+impl ToCompoundNbt for PotionData {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_optional_tag(&mut nbt, "potion", self.potion.as_deref());
+        set_optional_tag(
+            &mut nbt,
+            "custom_color",
+            self.custom_color.clone().map(i32::from),
+        );
+        if !self.custom_effects.is_empty() {
+            nbt.insert(
+                "custom_effects",
+                write_compound_list(&self.custom_effects, EffectDetails::to_compound_nbt),
+            );
+        }
+
+        nbt
+    }
+}
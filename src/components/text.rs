@@ -0,0 +1,491 @@
+//! Text components, used by `custom_name`, `item_name`, `lore` and book pages.
+//!
+//! Before 1.21.5 they're stored as JSON strings, from 1.21.5 they're stored as nbt
+//! where a plain string is literal text. Both are read as JSON so they stay comparable.
+
+use crate::version::V1_21_5;
+use simdnbt::borrow::{NbtCompound, NbtList, NbtTag};
+
+/// Reads a text component as a JSON string, based on the data version it was saved with.
+pub(crate) fn get_text_component(value: &NbtTag, data_version: i32) -> Option<String> {
+    if data_version < V1_21_5 {
+        return value.string().map(|string| string.to_string());
+    }
+
+    let mut json = String::new();
+    write_tag(&mut json, value)?;
+    Some(json)
+}
+
+/// A text component that's stored as a plain string, like the pages of a written book.
+pub(crate) fn string_text_component(string: &str, data_version: i32) -> String {
+    if data_version < V1_21_5 {
+        return string.to_string();
+    }
+
+    let mut json = String::new();
+    write_string(&mut json, string);
+    json
+}
+
+/// Reads a list of text components, like `lore`.
+pub(crate) fn get_text_components(value: &NbtTag, data_version: i32) -> Option<Vec<String>> {
+    let list = value.list()?;
+
+    if data_version < V1_21_5 {
+        return list
+            .strings()
+            .map(|strings| strings.iter().map(|string| string.to_string()).collect());
+    }
+
+    list_elements(&list)
+}
+
+fn write_tag(json: &mut String, tag: &NbtTag) -> Option<()> {
+    if let Some(string) = tag.string() {
+        write_string(json, &string.to_str());
+    } else if let Some(compound) = tag.compound() {
+        write_compound(json, &compound)?;
+    } else if let Some(list) = tag.list() {
+        json.push('[');
+        json.push_str(&list_elements(&list)?.join(","));
+        json.push(']');
+    } else if let Some(byte) = tag.byte() {
+        // Text components only use bytes for flags like `bold` and `italic`.
+        json.push_str(if byte != 0 { "true" } else { "false" });
+    } else if let Some(int) = tag.int() {
+        json.push_str(&int.to_string());
+    } else if let Some(short) = tag.short() {
+        json.push_str(&short.to_string());
+    } else if let Some(long) = tag.long() {
+        json.push_str(&long.to_string());
+    } else if let Some(float) = tag.float() {
+        json.push_str(&float.to_string());
+    } else {
+        json.push_str(&tag.double()?.to_string());
+    }
+
+    Some(())
+}
+
+fn write_compound(json: &mut String, compound: &NbtCompound) -> Option<()> {
+    json.push('{');
+    for (index, (key, value)) in compound.iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        write_string(json, &key.to_str());
+        json.push(':');
+        write_tag(json, &value)?;
+    }
+    json.push('}');
+
+    Some(())
+}
+
+/// Each element of the list as JSON, text components only use lists of strings, compounds and lists.
+fn list_elements(list: &NbtList) -> Option<Vec<String>> {
+    if list.empty() {
+        return Some(vec![]);
+    }
+
+    let mut elements = vec![];
+
+    if let Some(strings) = list.strings() {
+        for string in strings {
+            let mut json = String::new();
+            write_string(&mut json, &string.to_str());
+            elements.push(json);
+        }
+    } else if let Some(compounds) = list.compounds() {
+        for compound in compounds {
+            let mut json = String::new();
+            write_compound(&mut json, &compound)?;
+            elements.push(json);
+        }
+    } else {
+        for list in list.lists()? {
+            elements.push(format!("[{}]", list_elements(&list)?.join(",")));
+        }
+    }
+
+    Some(elements)
+}
+
+fn write_string(json: &mut String, string: &str) {
+    json.push('"');
+    for char in string.chars() {
+        match char {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            char if (char as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", char as u32)),
+            char => json.push(char),
+        }
+    }
+    json.push('"');
+}
+
+/// Writes a text component in the format of the data version, the inverse of [`get_text_component`].  
+/// JSON that can't be read back as nbt is written as literal text.
+pub(crate) fn text_component_tag(json: &str, data_version: i32) -> simdnbt::owned::NbtTag {
+    if data_version < V1_21_5 {
+        return simdnbt::owned::NbtTag::String(json.into());
+    }
+
+    let mut reader = JsonReader {
+        json: json.as_bytes(),
+        pos: 0,
+    };
+    match reader.read_value() {
+        Some(tag) if reader.at_end() => tag,
+        _ => simdnbt::owned::NbtTag::String(json.into()),
+    }
+}
+
+/// Writes a list of text components, the inverse of [`get_text_components`].
+pub(crate) fn text_components_tag(jsons: &[String], data_version: i32) -> simdnbt::owned::NbtTag {
+    if data_version < V1_21_5 {
+        return simdnbt::owned::NbtTag::List(simdnbt::owned::NbtList::String(
+            jsons.iter().map(|json| json.as_str().into()).collect(),
+        ));
+    }
+
+    let tags = jsons
+        .iter()
+        .map(|json| text_component_tag(json, data_version))
+        .collect();
+    simdnbt::owned::NbtTag::List(list_of(tags))
+}
+
+/// The inverse of [`string_text_component`], for text components stored as plain strings.
+pub(crate) fn string_text_component_tag(json: &str, data_version: i32) -> String {
+    match text_component_tag(json, data_version) {
+        simdnbt::owned::NbtTag::String(string) => string.to_string(),
+        _ => json.to_string(),
+    }
+}
+
+/// Reads a text component that's kept as is when it's a string, like the `CustomName` of block entities.  
+/// Only nbt text components from 1.21.5 are converted to JSON.
+pub(crate) fn get_plain_or_text_component(value: &NbtTag) -> Option<String> {
+    match value.string() {
+        Some(string) => Some(string.to_string()),
+        None => get_text_component(value, V1_21_5),
+    }
+}
+
+/// Reads a list of text components like [`get_plain_or_text_component`], like the messages of a sign.
+pub(crate) fn get_plain_or_text_components(value: &NbtTag) -> Option<Vec<String>> {
+    let list = value.list()?;
+    match list.strings() {
+        Some(strings) => Some(strings.iter().map(|string| string.to_string()).collect()),
+        None => get_text_components(value, V1_21_5),
+    }
+}
+
+/// The inverse of [`get_plain_or_text_component`].  
+/// From 1.21.5 JSON objects and arrays are written as nbt, anything else as a plain string.
+pub(crate) fn plain_or_text_component_tag(text: &str, data_version: i32) -> simdnbt::owned::NbtTag {
+    if data_version >= V1_21_5 && text.starts_with(['{', '[']) {
+        text_component_tag(text, data_version)
+    } else {
+        simdnbt::owned::NbtTag::String(text.into())
+    }
+}
+
+/// The inverse of [`get_plain_or_text_components`].
+pub(crate) fn plain_or_text_components_tag(
+    texts: &[String],
+    data_version: i32,
+) -> simdnbt::owned::NbtList {
+    list_of(
+        texts
+            .iter()
+            .map(|text| plain_or_text_component_tag(text, data_version))
+            .collect(),
+    )
+}
+
+/// The text a text component shows, without its formatting, like `Duped Sword` for `{"text":"Duped ","extra":["Sword"]}`.  
+/// Translated text shows its `fallback` if it has one, otherwise its translation key.
+/// JSON that can't be read is taken as literal text.
+pub(crate) fn plain_text(json: &str) -> String {
+    let mut reader = JsonReader {
+        json: json.as_bytes(),
+        pos: 0,
+    };
+    match reader.read_value() {
+        Some(tag) if reader.at_end() => {
+            let mut text = String::new();
+            push_plain_text(&mut text, &tag);
+            text
+        }
+        _ => json.to_string(),
+    }
+}
+
+fn push_plain_text(text: &mut String, tag: &simdnbt::owned::NbtTag) {
+    use simdnbt::owned::NbtTag;
+
+    match tag {
+        NbtTag::String(string) => text.push_str(&string.to_str()),
+        NbtTag::Int(int) => text.push_str(&int.to_string()),
+        NbtTag::Double(double) => text.push_str(&double.to_string()),
+        NbtTag::List(list) => {
+            for tag in list.as_nbt_tags() {
+                push_plain_text(text, &tag);
+            }
+        }
+        NbtTag::Compound(compound) => {
+            if let Some(string) = compound.string("text") {
+                text.push_str(&string.to_str());
+            } else if let Some(translate) = compound.string("translate") {
+                let fallback = compound.string("fallback").unwrap_or(translate);
+                text.push_str(&fallback.to_str());
+            }
+            if let Some(extra) = compound.get("extra") {
+                push_plain_text(text, extra);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Nbt lists can only hold one type, text components mixing strings and compounds
+/// have their strings written as `{"text": ...}`.
+fn list_of(tags: Vec<simdnbt::owned::NbtTag>) -> simdnbt::owned::NbtList {
+    use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+
+    if tags.is_empty() {
+        return NbtList::Empty;
+    }
+    if tags.iter().all(|tag| matches!(tag, NbtTag::String(_))) {
+        return NbtList::String(
+            tags.into_iter()
+                .filter_map(|tag| match tag {
+                    NbtTag::String(string) => Some(string),
+                    _ => None,
+                })
+                .collect(),
+        );
+    }
+    if tags.iter().all(|tag| matches!(tag, NbtTag::List(_))) {
+        return NbtList::List(
+            tags.into_iter()
+                .filter_map(|tag| match tag {
+                    NbtTag::List(list) => Some(list),
+                    _ => None,
+                })
+                .collect(),
+        );
+    }
+
+    NbtList::Compound(
+        tags.into_iter()
+            .map(|tag| match tag {
+                NbtTag::Compound(compound) => compound,
+                tag => {
+                    let mut compound = NbtCompound::new();
+                    compound.insert("text", tag);
+                    compound
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Reads the JSON written by [`write_tag`] back into nbt.
+struct JsonReader<'a> {
+    json: &'a [u8],
+    pos: usize,
+}
+
+impl JsonReader<'_> {
+    fn at_end(&mut self) -> bool {
+        self.skip_whitespace();
+        self.pos == self.json.len()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.json.get(self.pos).is_some_and(u8::is_ascii_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        if self.json.get(self.pos) == Some(&byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_literal(&mut self, literal: &str) -> bool {
+        if self.json[self.pos..].starts_with(literal.as_bytes()) {
+            self.pos += literal.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn read_value(&mut self) -> Option<simdnbt::owned::NbtTag> {
+        use simdnbt::owned::{NbtCompound, NbtTag};
+
+        self.skip_whitespace();
+        let tag = match *self.json.get(self.pos)? {
+            b'"' => NbtTag::String(self.read_string()?.into()),
+            b'{' => {
+                self.pos += 1;
+                let mut compound = NbtCompound::new();
+                if !self.eat(b'}') {
+                    loop {
+                        self.skip_whitespace();
+                        let key = self.read_string()?;
+                        if !self.eat(b':') {
+                            return None;
+                        }
+                        compound.insert(key, self.read_value()?);
+                        if self.eat(b'}') {
+                            break;
+                        }
+                        if !self.eat(b',') {
+                            return None;
+                        }
+                    }
+                }
+                NbtTag::Compound(compound)
+            }
+            b'[' => {
+                self.pos += 1;
+                let mut tags = vec![];
+                if !self.eat(b']') {
+                    loop {
+                        tags.push(self.read_value()?);
+                        if self.eat(b']') {
+                            break;
+                        }
+                        if !self.eat(b',') {
+                            return None;
+                        }
+                    }
+                }
+                NbtTag::List(list_of(tags))
+            }
+            _ if self.eat_literal("true") => NbtTag::Byte(1),
+            _ if self.eat_literal("false") => NbtTag::Byte(0),
+            _ => self.read_number()?,
+        };
+
+        Some(tag)
+    }
+
+    fn read_number(&mut self) -> Option<simdnbt::owned::NbtTag> {
+        let start = self.pos;
+        while self
+            .json
+            .get(self.pos)
+            .is_some_and(|byte| byte.is_ascii_digit() || b"+-.eE".contains(byte))
+        {
+            self.pos += 1;
+        }
+        let number = std::str::from_utf8(&self.json[start..self.pos]).ok()?;
+
+        match number.parse::<i32>() {
+            Ok(int) => Some(simdnbt::owned::NbtTag::Int(int)),
+            Err(_) => number.parse().ok().map(simdnbt::owned::NbtTag::Double),
+        }
+    }
+
+    fn read_string(&mut self) -> Option<String> {
+        if self.json.get(self.pos) != Some(&b'"') {
+            return None;
+        }
+        self.pos += 1;
+
+        let mut bytes = vec![];
+        loop {
+            let byte = *self.json.get(self.pos)?;
+            self.pos += 1;
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    let escaped = *self.json.get(self.pos)?;
+                    self.pos += 1;
+                    let char = match escaped {
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'u' => {
+                            let hex =
+                                std::str::from_utf8(self.json.get(self.pos..self.pos + 4)?).ok()?;
+                            self.pos += 4;
+                            char::from_u32(u32::from_str_radix(hex, 16).ok()?)?
+                        }
+                        byte => byte as char,
+                    };
+                    let mut buf = [0; 4];
+                    bytes.extend_from_slice(char.encode_utf8(&mut buf).as_bytes());
+                }
+                byte => bytes.push(byte),
+            }
+        }
+
+        String::from_utf8(bytes).ok()
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn text_component_round_trip() {
+    use crate::version::V1_21_4;
+
+    let json = r#"{"text":"Hi \"there\"","bold":true,"extra":[{"text":"a"},"b"],"color":"red"}"#;
+    let tag = text_component_tag(json, V1_21_5);
+    let bytes = crate::util::write_root_compound(&{
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+        nbt.insert("name", tag);
+        nbt
+    });
+    let nbt = simdnbt::borrow::read(&mut std::io::Cursor::new(bytes.as_slice()))
+        .unwrap()
+        .unwrap();
+    let read = get_text_component(&nbt.as_compound().get("name").unwrap(), V1_21_5).unwrap();
+    // The mixed list has its string written as a compound.
+    assert_eq!(
+        read,
+        r#"{"text":"Hi \"there\"","bold":true,"extra":[{"text":"a"},{"text":"b"}],"color":"red"}"#
+    );
+
+    assert_eq!(
+        text_component_tag(json, V1_21_4),
+        simdnbt::owned::NbtTag::String(json.into())
+    );
+    assert_eq!(string_text_component_tag(r#""page""#, V1_21_5), "page");
+}
+
+#[cfg(test)]
+#[test]
+fn text_component_plain_text() {
+    assert_eq!(plain_text(r#"{"text":"Duped Sword"}"#), "Duped Sword");
+    assert_eq!(plain_text(r#""Duped Sword""#), "Duped Sword");
+    assert_eq!(
+        plain_text(r#"{"text":"Duped ","color":"red","extra":[{"text":"Sw","bold":true},"ord"]}"#),
+        "Duped Sword"
+    );
+    assert_eq!(plain_text(r#"["",{"text":"A"},"B"]"#), "AB");
+    assert_eq!(
+        plain_text(r#"{"translate":"item.minecraft.diamond_sword","fallback":"Sword"}"#),
+        "Sword"
+    );
+    // Keys and formatting aren't part of the text.
+    assert_eq!(plain_text(r#"{"text":"Hi","color":"gold"}"#), "Hi");
+    assert_eq!(plain_text("not json"), "not json");
+}
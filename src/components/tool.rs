@@ -1,6 +1,10 @@
 //! Tool component.
 
-use crate::{error::SculkParseError, traits::FromCompoundNbt};
+use crate::{
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::{set_optional_tag, write_compound_list},
+};
 
 #[cfg(feature = "serde")]
 fn default_f32() -> f32 {
@@ -124,3 +128,38 @@ impl FromCompoundNbt for ToolRules {
         })
     }
 }
+
+impl ToCompoundNbt for Tool {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("default_mining_speed", self.default_mining_speed);
+        nbt.insert("damage_per_block", self.damage_per_block);
+        nbt.insert(
+            "rules",
+            write_compound_list(&self.rules, ToolRules::to_compound_nbt),
+        );
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for ToolRules {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        match &self.blocks {
+            ToolRulesBlocks::Single(block) => nbt.insert("blocks", block.as_str()),
+            ToolRulesBlocks::Multiple(blocks) => nbt.insert(
+                "blocks",
+                simdnbt::owned::NbtList::String(
+                    blocks.iter().map(|block| block.as_str().into()).collect(),
+                ),
+            ),
+        }
+        set_optional_tag(&mut nbt, "speed", self.speed);
+        set_optional_tag(&mut nbt, "correct_for_drops", self.correct_for_drops);
+
+        nbt
+    }
+}
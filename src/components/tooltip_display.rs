@@ -0,0 +1,75 @@
+//! Tooltip display component, added in 1.21.5.
+//! Replaces `hide_tooltip`, `hide_additional_tooltip` and the `show_in_tooltip` fields of other components.
+
+use crate::{
+    error::{NbtType, SculkParseError},
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::invalid_list_type,
+};
+
+/// Which parts of the tooltip are shown.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TooltipDisplay {
+    /// If `true`, the tooltip is hidden entirely. Defaults to `false`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hide_tooltip: bool,
+
+    /// The components that aren't shown in the tooltip.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hidden_components: Vec<String>,
+}
+
+impl TooltipDisplay {
+    /// Whether the component is shown in the tooltip.
+    pub fn shows(&self, component: &str) -> bool {
+        !self.hide_tooltip && !self.hidden_components.iter().any(|key| key == component)
+    }
+}
+
+impl FromCompoundNbt for TooltipDisplay {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        let hide_tooltip = nbt.byte("hide_tooltip").map(|b| b != 0).unwrap_or(false);
+
+        let hidden_components = match nbt.list("hidden_components") {
+            Some(list) if !list.empty() => list
+                .strings()
+                .ok_or_else(|| invalid_list_type(&list, "hidden_components", NbtType::String))?
+                .iter()
+                .map(|key| key.to_string())
+                .collect(),
+            _ => vec![],
+        };
+
+        Ok(TooltipDisplay {
+            hide_tooltip,
+            hidden_components,
+        })
+    }
+}
+
+impl ToCompoundNbt for TooltipDisplay {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        if self.hide_tooltip {
+            nbt.insert("hide_tooltip", true);
+        }
+        if !self.hidden_components.is_empty() {
+            nbt.insert(
+                "hidden_components",
+                simdnbt::owned::NbtList::String(
+                    self.hidden_components
+                        .iter()
+                        .map(|key| key.as_str().into())
+                        .collect(),
+                ),
+            );
+        }
+
+        nbt
+    }
+}
@@ -0,0 +1,46 @@
+//! Use cooldown component, added in 1.21.2.
+
+use crate::{
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::{get_owned_optional_string, set_optional_tag},
+};
+
+/// A cooldown applied to the item after it's used.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UseCooldown {
+    /// The cooldown in seconds.
+    pub seconds: f32,
+
+    /// The group of items that share the cooldown. Optional, defaults to the item id.
+    pub cooldown_group: Option<String>,
+}
+
+impl FromCompoundNbt for UseCooldown {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        let seconds = nbt
+            .float("seconds")
+            .ok_or(SculkParseError::MissingField("seconds".into()))?;
+        let cooldown_group = get_owned_optional_string(nbt, "cooldown_group");
+
+        Ok(UseCooldown {
+            seconds,
+            cooldown_group,
+        })
+    }
+}
+
+impl ToCompoundNbt for UseCooldown {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("seconds", self.seconds);
+        set_optional_tag(&mut nbt, "cooldown_group", self.cooldown_group.as_deref());
+
+        nbt
+    }
+}
@@ -0,0 +1,50 @@
+//! Weapon component, added in 1.21.5.
+
+use crate::{
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+};
+
+#[cfg(feature = "serde")]
+fn default_item_damage_per_attack() -> i32 {
+    1
+}
+
+/// An item that takes damage when attacking with it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Weapon {
+    /// The durability removed each time the item attacks. Defaults to 1.
+    #[cfg_attr(feature = "serde", serde(default = "default_item_damage_per_attack"))]
+    pub item_damage_per_attack: i32,
+
+    /// How long a shield hit by this item is disabled for, in seconds. Defaults to 0.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub disable_blocking_for_seconds: f32,
+}
+
+impl FromCompoundNbt for Weapon {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        Ok(Weapon {
+            item_damage_per_attack: nbt.int("item_damage_per_attack").unwrap_or(1),
+            disable_blocking_for_seconds: nbt.float("disable_blocking_for_seconds").unwrap_or(0.0),
+        })
+    }
+}
+
+impl ToCompoundNbt for Weapon {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("item_damage_per_attack", self.item_damage_per_attack);
+        nbt.insert(
+            "disable_blocking_for_seconds",
+            self.disable_blocking_for_seconds,
+        );
+
+        nbt
+    }
+}
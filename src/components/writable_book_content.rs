@@ -1,9 +1,12 @@
 //! A component that represents the content of a writable book.
 
+use super::text::{
+    get_text_component, string_text_component, string_text_component_tag, text_component_tag,
+};
 use crate::{
-    error::SculkParseError,
-    traits::FromCompoundNbt,
-    util::{get_owned_optional_string, get_owned_string},
+    error::{NbtType, SculkParseError},
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::{get_owned_optional_string, get_owned_string, invalid_type, write_compound_list},
 };
 
 /// A book that can be written in-game.
@@ -55,22 +58,60 @@ impl FromCompoundNbt for PageType {
     where
         Self: Sized,
     {
-        if let Some(single) = nbt.string("pages") {
-            Ok(PageType::Single(single.to_string()))
-        } else if let Some(multiple) = nbt.list("pages") {
-            let mut pages = Vec::new();
+        get_pages(nbt, None)
+    }
+}
 
+impl PageType {
+    /// Reads pages that are text components, like the pages of a written book.
+    pub(crate) fn from_text_compound_nbt(
+        nbt: &simdnbt::borrow::NbtCompound,
+        data_version: i32,
+    ) -> Result<Self, SculkParseError> {
+        get_pages(nbt, Some(data_version))
+    }
+}
+
+/// Reads the pages as plain text, or as JSON text components if there's a data version.
+fn get_pages(
+    nbt: &simdnbt::borrow::NbtCompound,
+    text_data_version: Option<i32>,
+) -> Result<PageType, SculkParseError> {
+    let text = |string: &str| match text_data_version {
+        Some(data_version) => string_text_component(string, data_version),
+        None => string.to_string(),
+    };
+
+    if let Some(single) = nbt.string("pages") {
+        Ok(PageType::Single(text(&single.to_str())))
+    } else if let Some(multiple) = nbt.list("pages") {
+        let mut pages = Vec::new();
+
+        if let Some(strings) = multiple.strings() {
+            for page in strings {
+                pages.push(BookTextData {
+                    raw: text(&page.to_str()),
+                    filtered: None,
+                });
+            }
+        } else if !multiple.empty() {
             for page in multiple
                 .compounds()
                 .ok_or(SculkParseError::InvalidField("pages".into()))?
             {
-                pages.push(BookTextData::from_compound_nbt(&page)?);
+                pages.push(match text_data_version {
+                    Some(data_version) => {
+                        BookTextData::from_text_compound_nbt(&page, data_version)?
+                    }
+                    None => BookTextData::from_compound_nbt(&page)?,
+                });
             }
-
-            return Ok(PageType::Multiple(pages));
-        } else {
-            return Err(SculkParseError::MissingField("pages".into()));
         }
+
+        Ok(PageType::Multiple(pages))
+    } else {
+        // Empty books don't write their pages.
+        Ok(PageType::Multiple(vec![]))
     }
 }
 
@@ -87,3 +128,88 @@ impl FromCompoundNbt for BookTextData {
         Ok(BookTextData { raw, filtered })
     }
 }
+
+impl BookTextData {
+    /// Reads a page where `raw` and `filtered` are text components, which are stored as nbt since 1.21.5.
+    pub(crate) fn from_text_compound_nbt(
+        nbt: &simdnbt::borrow::NbtCompound,
+        data_version: i32,
+    ) -> Result<Self, SculkParseError> {
+        let raw = nbt
+            .get("raw")
+            .and_then(|raw| get_text_component(&raw, data_version))
+            .ok_or_else(|| invalid_type(nbt, "raw", NbtType::String))?;
+        let filtered = nbt
+            .get("filtered")
+            .and_then(|filtered| get_text_component(&filtered, data_version));
+
+        Ok(BookTextData { raw, filtered })
+    }
+}
+
+impl ToCompoundNbt for WritableBookContent {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        if let Some(pages) = write_pages(&self.pages, None) {
+            nbt.insert("pages", pages);
+        }
+
+        nbt
+    }
+}
+
+impl PageType {
+    /// Writes pages of text components, the inverse of [`PageType::from_text_compound_nbt`].  
+    /// Returns `None` for a book without pages, which vanilla doesn't write.
+    pub(crate) fn to_text_tag(&self, data_version: i32) -> Option<simdnbt::owned::NbtTag> {
+        write_pages(self, Some(data_version))
+    }
+}
+
+fn write_pages(pages: &PageType, text_data_version: Option<i32>) -> Option<simdnbt::owned::NbtTag> {
+    match pages {
+        PageType::Single(page) => Some(simdnbt::owned::NbtTag::String(
+            match text_data_version {
+                Some(data_version) => string_text_component_tag(page, data_version),
+                None => page.clone(),
+            }
+            .into(),
+        )),
+        PageType::Multiple(pages) if pages.is_empty() => None,
+        PageType::Multiple(pages) => Some(simdnbt::owned::NbtTag::List(write_compound_list(
+            pages,
+            |page| match text_data_version {
+                Some(data_version) => page.to_text_compound_nbt(data_version),
+                None => page.to_compound_nbt(),
+            },
+        ))),
+    }
+}
+
+impl ToCompoundNbt for BookTextData {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("raw", self.raw.as_str());
+        if let Some(filtered) = &self.filtered {
+            nbt.insert("filtered", filtered.as_str());
+        }
+
+        nbt
+    }
+}
+
+impl BookTextData {
+    /// The inverse of [`BookTextData::from_text_compound_nbt`].
+    pub(crate) fn to_text_compound_nbt(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("raw", text_component_tag(&self.raw, data_version));
+        if let Some(filtered) = &self.filtered {
+            nbt.insert("filtered", text_component_tag(filtered, data_version));
+        }
+
+        nbt
+    }
+}
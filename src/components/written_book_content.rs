@@ -1,13 +1,17 @@
 //! Written book content component.
 
 use super::writable_book_content::{BookTextData, PageType};
-use crate::{error::SculkParseError, traits::FromCompoundNbt, util::get_owned_string};
+use crate::{
+    error::SculkParseError,
+    traits::{FromCompoundNbt, FromVersionedCompoundNbt, ToCompoundNbt, ToVersionedCompoundNbt},
+    util::get_owned_string,
+};
 
 /// The content of a written book.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WrittenBookContent {
-    /// A list of the pages in the book.
+    /// A list of the pages in the book, as JSON text components.
     pub pages: PageType,
 
     ///  The title of this written book.
@@ -25,14 +29,15 @@ pub struct WrittenBookContent {
     pub resolved: bool,
 }
 
-impl FromCompoundNbt for WrittenBookContent {
-    fn from_compound_nbt(
+impl FromVersionedCompoundNbt for WrittenBookContent {
+    fn from_compound_nbt_with_version(
         nbt: &simdnbt::borrow::NbtCompound,
+        data_version: i32,
     ) -> Result<Self, crate::error::SculkParseError>
     where
         Self: Sized,
     {
-        let pages = PageType::from_compound_nbt(nbt)?;
+        let pages = PageType::from_text_compound_nbt(nbt, data_version)?;
 
         let tile = nbt
             .compound("title")
@@ -53,3 +58,23 @@ impl FromCompoundNbt for WrittenBookContent {
         })
     }
 }
+
+impl ToVersionedCompoundNbt for WrittenBookContent {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        if let Some(pages) = self.pages.to_text_tag(data_version) {
+            nbt.insert("pages", pages);
+        }
+        nbt.insert("title", self.title.to_compound_nbt());
+        nbt.insert("author", self.author.as_str());
+        if self.generation != 0 {
+            nbt.insert("generation", self.generation);
+        }
+        if self.resolved {
+            nbt.insert("resolved", true);
+        }
+
+        nbt
+    }
+}
@@ -1,7 +1,12 @@
 use crate::{
     error::SculkParseError,
-    traits::FromCompoundNbt,
-    util::{get_optional_name, get_owned_optional_string, get_owned_string, get_t_compound_vec},
+    resource_location::ResourceLocation,
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+    util::{
+        check_known_fields, get_extra, get_optional_name, get_owned_optional_string,
+        get_resource_location, get_t_compound_vec, merge_compound, set_optional_tag, set_tag,
+        write_compound_list, write_optional_name,
+    },
     uuid::Uuid,
 };
 
@@ -47,7 +52,7 @@ pub struct Entity {
     pub has_visual_fire: Option<bool>,
 
     /// String representation of the entity's ID. Does not exist for the Player entity.
-    pub id: String,
+    pub id: ResourceLocation,
 
     ///  if true, the entity should not take damage. This applies to living and nonliving entities alike: mobs should not take damage from any source (including potion effects), and cannot be moved by fishing rods, attacks, explosions, or projectiles, and objects such as vehicles and item frames cannot be destroyed unless their supports are removed. Invulnerable player entities are also ignored by any hostile mobs. Note that these entities can be damaged by players in Creative mode.
     ///
@@ -111,10 +116,310 @@ pub struct Entity {
     /// This entity's Universally Unique IDentifier.
     /// `UUID`
     pub uuid: Option<Uuid>,
+
+    /// Every other field, including all the entity specific data.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extra: simdnbt::owned::NbtCompound,
     //
     // TODO: Add entity specific data field like block entites, low priority as it allows very specific narrow block entity -> entity data handling
 }
 
+/// Fields of [`Entity`], the rest is kept in [`Entity::extra`].
+const ENTITY_FIELDS: &[&str] = &[
+    "Air",
+    "CustomName",
+    "CustomNameVisible",
+    "FallDistance",
+    "Fire",
+    "Glowing",
+    "HasVisualFire",
+    "id",
+    "Invulnerable",
+    "Motion",
+    "NoGravity",
+    "OnGround",
+    "passengers",
+    "PortalCooldown",
+    "Pos",
+    "Rotation",
+    "Silent",
+    "Tags",
+    "TicksFrozen",
+    "UUID",
+];
+
+/// Vanilla fields of the entity types, kept in [`Entity::extra`], that [`crate::error::ParseMode::Strict`] still accepts.
+const UNDECODED_ENTITY_FIELDS: &[&str] = &[
+    "Passengers",
+    "AbsorptionAmount",
+    "active_effects",
+    "attributes",
+    "Brain",
+    "DeathTime",
+    "FallFlying",
+    "Health",
+    "HurtByTimestamp",
+    "HurtTime",
+    "SleepingX",
+    "SleepingY",
+    "SleepingZ",
+    "Team",
+    "ArmorDropChances",
+    "ArmorItems",
+    "body_armor_drop_chance",
+    "body_armor_item",
+    "CanPickUpLoot",
+    "DeathLootTable",
+    "DeathLootTableSeed",
+    "HandDropChances",
+    "HandItems",
+    "LeftHanded",
+    "leash",
+    "NoAI",
+    "PersistenceRequired",
+    "Age",
+    "ForcedAge",
+    "InLove",
+    "LoveCause",
+    "Owner",
+    "Sitting",
+    "variant",
+    "Variant",
+    "Type",
+    "CollarColor",
+    "Color",
+    "Sheared",
+    "EggLayTime",
+    "IsChickenJockey",
+    "Saddle",
+    "AngerTime",
+    "AngryAt",
+    "Bred",
+    "EatingHaystack",
+    "Tame",
+    "Temper",
+    "SaddleItem",
+    "ArmorItem",
+    "ChestedHorse",
+    "Items",
+    "Strength",
+    "DecorItem",
+    "VillagerData",
+    "Offers",
+    "Gossips",
+    "Xp",
+    "FoodLevel",
+    "Inventory",
+    "LastRestock",
+    "LastGossipDecay",
+    "RestocksToday",
+    "Willing",
+    "ConversionTime",
+    "ConversionPlayer",
+    "DespawnDelay",
+    "wander_target",
+    "IsBaby",
+    "CanBreakDoors",
+    "DrownedConversionTime",
+    "InWaterTime",
+    "StrayConversionTime",
+    "ExplosionRadius",
+    "Fuse",
+    "ignited",
+    "powered",
+    "carriedBlockState",
+    "Size",
+    "wasOnGround",
+    "HivePos",
+    "flower_pos",
+    "HasNectar",
+    "HasStung",
+    "TicksSincePollination",
+    "CannotEnterHiveTicks",
+    "CropsGrownSincePollination",
+    "Trusted",
+    "Crouching",
+    "Sleeping",
+    "FromBucket",
+    "BucketVariantTag",
+    "HasLeftHorn",
+    "HasRightHorn",
+    "IsScreamingGoat",
+    "MainGene",
+    "HiddenGene",
+    "MoreCarrotTicks",
+    "RabbitType",
+    "AttachFace",
+    "Peek",
+    "HasEgg",
+    "HomePosX",
+    "HomePosY",
+    "HomePosZ",
+    "TravelPosX",
+    "TravelPosY",
+    "TravelPosZ",
+    "IsImmuneToZombification",
+    "CannotHunt",
+    "TimeInOverworld",
+    "CannotBeHunted",
+    "CanJoinRaid",
+    "PatrolLeader",
+    "Patrolling",
+    "patrol_target",
+    "RaidId",
+    "Wave",
+    "SpellTicks",
+    "BoundX",
+    "BoundY",
+    "BoundZ",
+    "LifeTicks",
+    "AX",
+    "AY",
+    "AZ",
+    "ExplosionPower",
+    "Invul",
+    "DragonPhase",
+    "PlayerCreated",
+    "Pumpkin",
+    "DuplicationCooldown",
+    "CanDuplicate",
+    "listener",
+    "GotFish",
+    "TreasurePosX",
+    "TreasurePosY",
+    "TreasurePosZ",
+    "Moistness",
+    "state",
+    "scute_time",
+    "anger",
+    "sheared",
+    "Item",
+    "PickupDelay",
+    "Thrower",
+    "Count",
+    "Value",
+    "LeftOwner",
+    "HasBeenShot",
+    "inGround",
+    "inBlockState",
+    "life",
+    "damage",
+    "pickup",
+    "crit",
+    "PierceLevel",
+    "ShotFromCrossbow",
+    "SoundEvent",
+    "item",
+    "weapon",
+    "shake",
+    "power",
+    "acceleration_power",
+    "Life",
+    "LifeTime",
+    "FireworksItem",
+    "ShotAtAngle",
+    "DealtDamage",
+    "facing",
+    "Facing",
+    "TileX",
+    "TileY",
+    "TileZ",
+    "block_pos",
+    "ItemDropChance",
+    "ItemRotation",
+    "Fixed",
+    "Invisible",
+    "DisabledSlots",
+    "Marker",
+    "NoBasePlate",
+    "Pose",
+    "ShowArms",
+    "Small",
+    "CustomDisplayTile",
+    "DisplayOffset",
+    "DisplayState",
+    "LootTable",
+    "LootTableSeed",
+    "Fuel",
+    "PushX",
+    "PushZ",
+    "TransferCooldown",
+    "Enabled",
+    "Command",
+    "CommandStats",
+    "LastOutput",
+    "SuccessCount",
+    "TrackOutput",
+    "TNTFuse",
+    "BlockState",
+    "CancelDrop",
+    "DropItem",
+    "FallHurtAmount",
+    "FallHurtMax",
+    "HurtEntities",
+    "TileEntityData",
+    "Time",
+    "fuse",
+    "block_state",
+    "explosion_power",
+    "Duration",
+    "ReapplicationDelay",
+    "WaitTime",
+    "DurationOnUse",
+    "Radius",
+    "RadiusOnUse",
+    "RadiusPerTick",
+    "Particle",
+    "potion_contents",
+    "data",
+    "transformation",
+    "billboard",
+    "brightness",
+    "view_range",
+    "shadow_radius",
+    "shadow_strength",
+    "width",
+    "height",
+    "glow_color_override",
+    "interpolation_duration",
+    "start_interpolation",
+    "teleport_duration",
+    "item_display",
+    "text",
+    "background",
+    "default_background",
+    "line_width",
+    "see_through",
+    "shadow",
+    "text_opacity",
+    "alignment",
+    "response",
+    "attack",
+    "interaction",
+    "BeamTarget",
+    "ShowBottom",
+    "Warmup",
+    "spawn_item_after_ticks",
+];
+
+/// Fails with [`SculkParseError::UnknownField`] on a key that isn't a vanilla entity field,
+/// also checking the entity's passengers.
+pub(crate) fn check_entity_fields(
+    nbt: &simdnbt::borrow::NbtCompound,
+) -> Result<(), SculkParseError> {
+    check_known_fields(nbt, &[ENTITY_FIELDS, UNDECODED_ENTITY_FIELDS].concat())?;
+
+    for key in ["passengers", "Passengers"] {
+        if let Some(passengers) = nbt.list(key).and_then(|list| list.compounds()) {
+            for (index, passenger) in passengers.into_iter().enumerate() {
+                check_entity_fields(&passenger).map_err(|e| e.at_index(key, index))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// A maybe entity.  
 /// All fields are optional.
 #[derive(Debug, Clone, PartialEq)]
@@ -220,6 +525,10 @@ pub struct MaybeEntity {
     /// This entity's Universally Unique IDentifier.
     /// `UUID`
     pub uuid: Option<Uuid>,
+
+    /// Every other field, including all the entity specific data.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extra: simdnbt::owned::NbtCompound,
 }
 
 impl FromCompoundNbt for Entity {
@@ -237,7 +546,7 @@ impl FromCompoundNbt for Entity {
         let glowing = nbt.byte("Glowing").map(|b| b != 0);
         let has_visual_fire = nbt.byte("HasVisualFire").map(|b| b != 0);
 
-        let id = get_owned_string(nbt, "id")?;
+        let id = get_resource_location(nbt, "id")?;
 
         let invulnerable = nbt.byte("Invulnerable").map(|b| b != 0);
 
@@ -333,6 +642,7 @@ impl FromCompoundNbt for Entity {
             tags,
             ticks_frozen,
             uuid,
+            extra: get_extra(nbt, ENTITY_FIELDS),
         })
     }
 }
@@ -375,7 +685,7 @@ impl FromCompoundNbt for MaybeEntity {
         let passengers: Option<Vec<Entity>> =
             match get_t_compound_vec(nbt, "passengers", Entity::from_compound_nbt) {
                 Ok(passengers) => Some(passengers),
-                Err(SculkParseError::MissingField(_)) => None,
+                Err(e) if matches!(e.root_cause(), SculkParseError::MissingField(_)) => None,
                 Err(e) => return Err(e),
             };
 
@@ -451,10 +761,101 @@ impl FromCompoundNbt for MaybeEntity {
             tags,
             ticks_frozen,
             uuid,
+            extra: get_extra(nbt, ENTITY_FIELDS),
         })
     }
 }
 
+/// Writes the fields [`Entity`] and [`MaybeEntity`] share, which are optional in both.
+macro_rules! write_optional_entity_fields {
+    ($nbt:expr, $entity:expr, $data_version:expr) => {
+        set_optional_tag($nbt, "Air", $entity.air);
+        write_optional_name($nbt, $entity.custom_name.as_ref(), $data_version);
+        set_optional_tag($nbt, "CustomNameVisible", $entity.custom_name_visible);
+        set_optional_tag($nbt, "FallDistance", $entity.fall_distance);
+        set_optional_tag($nbt, "Fire", $entity.fire);
+        set_optional_tag($nbt, "Glowing", $entity.glowing);
+        set_optional_tag($nbt, "HasVisualFire", $entity.has_visual_fire);
+        set_optional_tag($nbt, "Invulnerable", $entity.invulnerable);
+        set_optional_tag(
+            $nbt,
+            "Motion",
+            $entity.motion.map(|motion| doubles(&motion)),
+        );
+        set_optional_tag($nbt, "NoGravity", $entity.no_gravity);
+        set_optional_tag($nbt, "OnGround", $entity.on_ground);
+        set_optional_tag($nbt, "PortalCooldown", $entity.portal_cooldown);
+        set_optional_tag($nbt, "Pos", $entity.pos.map(|pos| doubles(&pos)));
+        set_optional_tag(
+            $nbt,
+            "Rotation",
+            $entity
+                .rotation
+                .map(|rotation| simdnbt::owned::NbtList::Float(rotation.to_vec())),
+        );
+        set_optional_tag($nbt, "Silent", $entity.silent);
+        set_optional_tag($nbt, "TicksFrozen", $entity.ticks_frozen);
+        set_optional_tag($nbt, "UUID", $entity.uuid.as_ref().map(Uuid::to_tag));
+    };
+}
+
+fn doubles(values: &[f64]) -> simdnbt::owned::NbtList {
+    simdnbt::owned::NbtList::Double(values.to_vec())
+}
+
+pub(crate) fn write_passengers(
+    passengers: &[Entity],
+    data_version: i32,
+) -> simdnbt::owned::NbtList {
+    write_compound_list(passengers, |passenger| {
+        passenger.to_compound_nbt_with_version(data_version)
+    })
+}
+
+impl ToVersionedCompoundNbt for Entity {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_tag(&mut nbt, "id", self.id.to_string());
+        write_optional_entity_fields!(&mut nbt, self, data_version);
+        if !self.passengers.is_empty() {
+            set_tag(
+                &mut nbt,
+                "passengers",
+                write_passengers(&self.passengers, data_version),
+            );
+        }
+        if !self.tags.is_empty() {
+            set_tag(&mut nbt, "Tags", self.tags.clone());
+        }
+
+        let mut entity = self.extra.clone();
+        merge_compound(&mut entity, nbt);
+        entity
+    }
+}
+
+impl ToVersionedCompoundNbt for MaybeEntity {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_optional_tag(&mut nbt, "id", self.id.as_deref());
+        write_optional_entity_fields!(&mut nbt, self, data_version);
+        if let Some(passengers) = &self.passengers {
+            set_tag(
+                &mut nbt,
+                "passengers",
+                write_passengers(passengers, data_version),
+            );
+        }
+        set_optional_tag(&mut nbt, "Tags", self.tags.clone());
+
+        let mut entity = self.extra.clone();
+        merge_compound(&mut entity, nbt);
+        entity
+    }
+}
+
 // FUTURE ENTITY PLAN
 
 // Entity Enum
@@ -1,3 +1,4 @@
+use std::fmt;
 use thiserror::Error;
 
 /// Any error that can occur while parsing Nbt data.
@@ -19,6 +20,10 @@ pub enum SculkParseError {
     #[error("Deserialize error: {0}")]
     DeserializeError(#[from] simdnbt::DeserializeError),
 
+    /// Error when a resource location has characters that aren't allowed.
+    #[error("Invalid resource location: {0}")]
+    InvalidResourceLocation(String),
+
     /// Error when the root nbt is none.
     #[error("Missing Nbt data")]
     NoNbt,
@@ -26,4 +31,301 @@ pub enum SculkParseError {
     /// Error when the block entity is unsupported.
     #[error("Unsupported block entity: {0}")]
     UnsupportedBlockEntity(String),
+
+    /// Error when a chunk in a region file uses a compression that isn't supported.
+    #[error("Unsupported chunk compression: {0}")]
+    UnsupportedCompression(u8),
+
+    /// Error when reading or writing files.
+    #[error("Io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Error when a field has a different Nbt type than expected.  
+    /// For lists, the types are of the list elements.
+    #[error("Unexpected type for {field}: expected {expected}, found {found}")]
+    UnexpectedType {
+        field: String,
+        expected: NbtType,
+        found: NbtType,
+    },
+
+    /// Error when a field isn't known, only returned with [`ParseMode::Strict`].
+    #[error("Unknown field: {0}")]
+    UnknownField(String),
+
+    /// Another error, with where in the data it happened.  
+    /// The context is added as the error bubbles up, use [`SculkParseError::root_cause`] to get the original error.
+    #[error("{source} ({context})")]
+    WithContext {
+        context: Box<ErrorContext>,
+        source: Box<SculkParseError>,
+    },
+}
+
+/// How parsing deals with data that doesn't match what's expected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParseMode {
+    /// Fails on malformed data, unknown fields are ignored.
+    #[default]
+    Default,
+
+    /// Records malformed data as a [`ParseWarning`] and uses raw Nbt or a default in its place.  
+    /// Only fails if the data can't be used at all, like a chunk without its position.
+    Lenient,
+
+    /// Fails on malformed data and on any unknown field.
+    Strict,
+}
+
+/// A non-fatal issue found while parsing with [`ParseMode::Lenient`].
+#[derive(Debug)]
+pub struct ParseWarning {
+    /// What went wrong, with where it happened.
+    pub error: SculkParseError,
+
+    /// What was used in place of the malformed data.
+    pub substitute: Substitute,
+}
+
+/// What [`ParseMode::Lenient`] used in place of malformed data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Substitute {
+    /// The data was left out, like an element of a list.
+    Skipped,
+    /// The raw Nbt was kept, like [`crate::components::Component::Unknown`].
+    Raw,
+    /// An empty or default value was used.
+    Default,
+}
+
+/// Where in the data an error happened.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ErrorContext {
+    /// Path to the field the error happened in, from the outermost parsed compound.
+    pub path: Vec<PathSegment>,
+
+    /// Id of the outermost block entity, entity or item the error happened in.
+    pub id: Option<String>,
+
+    /// Chunk coordinates `[x, z]` of the chunk the error happened in.
+    pub chunk: Option<[i32; 2]>,
+}
+
+/// A part of an [`ErrorContext`] path.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathSegment {
+    /// A field in a compound.
+    Field(String),
+    /// An index in a list.
+    Index(usize),
+}
+
+/// The types an Nbt tag can have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NbtType {
+    End,
+    Byte,
+    Short,
+    Int,
+    Long,
+    Float,
+    Double,
+    ByteArray,
+    String,
+    List,
+    Compound,
+    IntArray,
+    LongArray,
+}
+
+impl SculkParseError {
+    /// Adds a field to the start of the error path.
+    pub fn at_field(self, field: impl Into<String>) -> Self {
+        self.map_context(|context| context.path.insert(0, PathSegment::Field(field.into())))
+    }
+
+    /// Adds a list field and the index in it to the start of the error path.
+    pub fn at_index(self, field: impl Into<String>, index: usize) -> Self {
+        self.map_context(|context| {
+            context.path.insert(0, PathSegment::Index(index));
+            context.path.insert(0, PathSegment::Field(field.into()));
+        })
+    }
+
+    /// Sets the id of the block entity, entity or item the error happened in.  
+    /// Replaces any id set further in, so the outermost one is kept.
+    pub fn with_id(self, id: impl Into<String>) -> Self {
+        self.map_context(|context| context.id = Some(id.into()))
+    }
+
+    /// Sets the chunk coordinates the error happened in.
+    pub fn in_chunk(self, x: i32, z: i32) -> Self {
+        self.map_context(|context| context.chunk = Some([x, z]))
+    }
+
+    /// Where in the data the error happened, if known.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            SculkParseError::WithContext { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// The original error, without any context.
+    pub fn root_cause(&self) -> &SculkParseError {
+        match self {
+            SculkParseError::WithContext { source, .. } => source.root_cause(),
+            err => err,
+        }
+    }
+
+    fn map_context(self, f: impl FnOnce(&mut ErrorContext)) -> Self {
+        let (mut context, source) = match self {
+            SculkParseError::WithContext { context, source } => (context, source),
+            err => (Box::default(), Box::new(err)),
+        };
+        f(&mut context);
+
+        SculkParseError::WithContext { context, source }
+    }
+}
+
+impl ParseMode {
+    /// In lenient mode, records the error as a warning and returns the substitute.
+    /// Otherwise the error is returned.
+    pub(crate) fn recover<T>(
+        &self,
+        result: Result<T, SculkParseError>,
+        warnings: &mut Vec<ParseWarning>,
+        substitute: Substitute,
+        fallback: impl FnOnce() -> T,
+    ) -> Result<T, SculkParseError> {
+        match result {
+            Err(error) if *self == ParseMode::Lenient => {
+                warnings.push(ParseWarning { error, substitute });
+                Ok(fallback())
+            }
+            result => result,
+        }
+    }
+}
+
+impl ParseWarning {
+    /// Adds context to the errors of the warnings, used for warnings recorded further in.
+    pub(crate) fn add_context(
+        warnings: &mut [ParseWarning],
+        f: impl Fn(SculkParseError) -> SculkParseError,
+    ) {
+        for warning in warnings {
+            let error = std::mem::replace(&mut warning.error, SculkParseError::NoNbt);
+            warning.error = f(error);
+        }
+    }
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, used {:?}", self.error, self.substitute)
+    }
+}
+
+impl ErrorContext {
+    /// The path formatted like `block_entities[12].Items[3].components.minecraft:container`.
+    pub fn path_string(&self) -> String {
+        let mut path = String::new();
+
+        for segment in &self.path {
+            match segment {
+                PathSegment::Field(field) => {
+                    if !path.is_empty() {
+                        path.push('.');
+                    }
+                    path.push_str(field);
+                }
+                PathSegment::Index(index) => path.push_str(&format!("[{index}]")),
+            }
+        }
+
+        path
+    }
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = vec![];
+
+        if !self.path.is_empty() {
+            parts.push(format!("at {}", self.path_string()));
+        }
+        if let Some(id) = &self.id {
+            parts.push(format!("in {id}"));
+        }
+        if let Some([x, z]) = self.chunk {
+            parts.push(format!("in chunk {x}, {z}"));
+        }
+
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+impl NbtType {
+    /// Gets the type from its Nbt tag id.
+    pub fn from_id(id: u8) -> Option<Self> {
+        Some(match id {
+            0 => NbtType::End,
+            1 => NbtType::Byte,
+            2 => NbtType::Short,
+            3 => NbtType::Int,
+            4 => NbtType::Long,
+            5 => NbtType::Float,
+            6 => NbtType::Double,
+            7 => NbtType::ByteArray,
+            8 => NbtType::String,
+            9 => NbtType::List,
+            10 => NbtType::Compound,
+            11 => NbtType::IntArray,
+            12 => NbtType::LongArray,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for NbtType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            NbtType::End => "end",
+            NbtType::Byte => "byte",
+            NbtType::Short => "short",
+            NbtType::Int => "int",
+            NbtType::Long => "long",
+            NbtType::Float => "float",
+            NbtType::Double => "double",
+            NbtType::ByteArray => "byte array",
+            NbtType::String => "string",
+            NbtType::List => "list",
+            NbtType::Compound => "compound",
+            NbtType::IntArray => "int array",
+            NbtType::LongArray => "long array",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn error_context() {
+    let err = SculkParseError::MissingField("Slot".into())
+        .at_index("Items", 3)
+        .with_id("minecraft:chest")
+        .at_index("block_entities", 12)
+        .in_chunk(3, -5);
+
+    assert!(matches!(err.root_cause(), SculkParseError::MissingField(_)));
+    assert_eq!(
+        err.to_string(),
+        "Missing field: Slot (at block_entities[12].Items[3], in minecraft:chest, in chunk 3, -5)"
+    );
 }
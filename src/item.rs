@@ -1,9 +1,20 @@
 use crate::{
     components::Components,
-    traits::FromCompoundNbt,
-    util::{get_optional_components, get_owned_string},
+    resource_location::ResourceLocation,
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+    util::{
+        check_known_fields, get_extra, get_optional_components, get_resource_location,
+        merge_compound, set_tag,
+    },
+    version::V1_20_5,
 };
 
+/// Fields of [`Item`], the rest is kept in [`Item::extra`].
+pub(crate) const ITEM_FIELDS: &[&str] = &["Slot", "id", "count", "Count", "components"];
+
+/// Vanilla fields of an item that aren't decoded, but that [`crate::error::ParseMode::Strict`] still accepts.
+const UNDECODED_ITEM_FIELDS: &[&str] = &["tag"];
+
 /// Represents an item in an inventory slot.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -13,7 +24,7 @@ pub struct Item {
     pub slot: i8,
 
     /// The resource location of the item. Must not be `air`.
-    pub id: String,
+    pub id: ResourceLocation,
 
     /// Number of items stacked in this inventory slot. Any item can be stacked, even if unstackable through normal means. Defaults to 1.  
     ///
@@ -22,6 +33,11 @@ pub struct Item {
 
     /// Optional map of data components. Additional information about the item.
     pub components: Option<Components>,
+
+    /// Fields that aren't modelled, like the `tag` of items from before 1.20.5.
+    /// The `tag` is also converted into [`Item::components`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extra: simdnbt::owned::NbtCompound,
 }
 
 /// Represents an item in an inventory slot, without the slot number.  
@@ -30,7 +46,7 @@ pub struct Item {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ItemWithNoSlot {
     /// The resource location of the item. Must not be `air`.
-    pub id: String,
+    pub id: ResourceLocation,
 
     /// Number of items stacked in this inventory slot. Any item can be stacked, even if unstackable through normal means. Defaults to 1.  
     /// Actual name: `count`
@@ -38,6 +54,28 @@ pub struct ItemWithNoSlot {
 
     /// Optional map of data components. Additional information about the item.
     pub components: Option<Components>,
+
+    /// Fields that aren't modelled, like the `tag` of items from before 1.20.5.
+    /// The `tag` is also converted into [`ItemWithNoSlot::components`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extra: simdnbt::owned::NbtCompound,
+}
+
+/// The components of an item, converted from its `tag` if it's from before 1.20.5.
+pub(crate) fn get_item_components(
+    nbt: &simdnbt::borrow::NbtCompound,
+) -> Result<Option<Components>, crate::error::SculkParseError> {
+    match get_optional_components(nbt)? {
+        Some(components) => Ok(Some(components)),
+        None => Components::from_legacy_item_nbt(nbt),
+    }
+}
+
+/// Fails with [`crate::error::SculkParseError::UnknownField`] on a key that isn't a vanilla item field.
+pub(crate) fn check_item_fields(
+    nbt: &simdnbt::borrow::NbtCompound,
+) -> Result<(), crate::error::SculkParseError> {
+    check_known_fields(nbt, &[ITEM_FIELDS, UNDECODED_ITEM_FIELDS].concat())
 }
 
 impl FromCompoundNbt for Item {
@@ -51,19 +89,20 @@ impl FromCompoundNbt for Item {
             .byte("Slot")
             .ok_or(crate::error::SculkParseError::MissingField("Slot".into()))?;
 
-        let id = get_owned_string(nbt, "id")?;
+        let id = get_resource_location(nbt, "id")?;
 
         let count = nbt
             .int("count")
             .unwrap_or_else(|| nbt.byte("Count").unwrap_or(1) as i32);
 
-        let components = get_optional_components(nbt)?;
+        let components = get_item_components(nbt)?;
 
         Ok(Item {
             slot,
             id,
             count,
             components,
+            extra: get_extra(nbt, ITEM_FIELDS),
         })
     }
 }
@@ -75,18 +114,137 @@ impl FromCompoundNbt for ItemWithNoSlot {
     where
         Self: Sized,
     {
-        let id = get_owned_string(nbt, "id")?;
+        let id = get_resource_location(nbt, "id")?;
 
         let count = nbt
             .int("count")
             .unwrap_or_else(|| nbt.byte("Count").unwrap_or(1) as i32);
 
-        let components = get_optional_components(nbt)?;
+        let components = get_item_components(nbt)?;
 
         Ok(ItemWithNoSlot {
             id,
             count,
             components,
+            extra: get_extra(nbt, ITEM_FIELDS),
         })
     }
 }
+
+/// Writes an item over its unmodelled fields, in the format of the data version.  
+/// Before 1.20.5 the count is a `Count` byte and the original `tag` is kept instead of the components,
+/// from 1.20.5 the `tag` is left out since it was converted into the components.
+fn write_item(
+    fields: simdnbt::owned::NbtCompound,
+    id: &ResourceLocation,
+    count: i32,
+    components: Option<&Components>,
+    extra: &simdnbt::owned::NbtCompound,
+    data_version: i32,
+) -> simdnbt::owned::NbtCompound {
+    let mut nbt = fields;
+    set_tag(&mut nbt, "id", id.to_string());
+
+    let mut item = extra.clone();
+    if data_version < V1_20_5 {
+        set_tag(&mut nbt, "Count", count.clamp(0, i8::MAX as i32) as i8);
+    } else {
+        item.remove("tag");
+        set_tag(&mut nbt, "count", count);
+        if let Some(components) = components {
+            set_tag(
+                &mut nbt,
+                "components",
+                components.to_compound_nbt_with_version(data_version),
+            );
+        }
+    }
+
+    merge_compound(&mut item, nbt);
+    item
+}
+
+impl ToVersionedCompoundNbt for Item {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut slot = simdnbt::owned::NbtCompound::new();
+        set_tag(&mut slot, "Slot", self.slot);
+
+        write_item(
+            slot,
+            &self.id,
+            self.count,
+            self.components.as_ref(),
+            &self.extra,
+            data_version,
+        )
+    }
+}
+
+impl ToVersionedCompoundNbt for ItemWithNoSlot {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        write_item(
+            simdnbt::owned::NbtCompound::new(),
+            &self.id,
+            self.count,
+            self.components.as_ref(),
+            &self.extra,
+            data_version,
+        )
+    }
+}
+
+impl ItemWithNoSlot {
+    /// The components the item ends up with, its saved components applied over the 1.21 defaults of its item type.
+    #[cfg(feature = "default-components")]
+    pub fn effective_components(&self) -> Components {
+        effective_components(&self.id, self.components.as_ref())
+    }
+}
+
+#[cfg(feature = "default-components")]
+fn effective_components(id: &ResourceLocation, components: Option<&Components>) -> Components {
+    let defaults = crate::components::defaults::default_components(id);
+
+    match components {
+        Some(components) => components.apply_to(&defaults),
+        None => defaults,
+    }
+}
+
+#[cfg(all(test, feature = "default-components"))]
+#[test]
+fn effective_item_components() {
+    use crate::{
+        components::{Component, ComponentType},
+        rarity::Rarity,
+    };
+
+    let mut components = Components::default();
+    components.insert(Component::Damage(1000)).unwrap();
+    components.mark_removed(ComponentType::Rarity);
+
+    let item = ItemWithNoSlot {
+        id: "minecraft:diamond_pickaxe".parse().unwrap(),
+        count: 1,
+        components: Some(components),
+        extra: simdnbt::owned::NbtCompound::new(),
+    };
+    let effective = item.effective_components();
+    assert_eq!(effective.max_damage(), Some(1561));
+    assert_eq!(effective.max_stack_size(), Some(1));
+    assert_eq!(effective.damage(), Some(1000));
+    assert_eq!(effective.rarity(), None);
+    assert!(!effective.is_removed(ComponentType::Rarity));
+
+    let item = ItemWithNoSlot {
+        id: "minecraft:nether_star".parse().unwrap(),
+        count: 1,
+        components: None,
+        extra: simdnbt::owned::NbtCompound::new(),
+    };
+    assert_eq!(
+        item.effective_components().rarity(),
+        Some(&Rarity::Uncommon)
+    );
+    assert_eq!(item.effective_components().max_stack_size(), Some(64));
+}
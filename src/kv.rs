@@ -1,14 +1,24 @@
-use crate::{error::SculkParseError, traits::FromCompoundNbt};
+use crate::{
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+};
 use simdnbt::borrow::NbtCompound;
 use std::{
     collections::HashMap,
     ops::{Deref, DerefMut},
 };
 
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct KVPair<T>(HashMap<String, T>);
 
+// Derived Default would require `T: Default`.
+impl<T> Default for KVPair<T> {
+    fn default() -> Self {
+        KVPair(HashMap::new())
+    }
+}
+
 impl<T> KVPair<T> {
     pub fn new(map: HashMap<String, T>) -> Self {
         KVPair(map)
@@ -92,3 +102,18 @@ impl FromCompoundNbt for KVPair<simdnbt::owned::NbtCompound> {
         Ok(KVPair::new(map))
     }
 }
+
+impl<T: Clone + simdnbt::ToNbtTag> ToCompoundNbt for KVPair<T> {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        // Sorted so the same pairs are always written the same way.
+        let mut keys: Vec<&String> = self.0.keys().collect();
+        keys.sort();
+        for key in keys {
+            nbt.insert(key.as_str(), self.0[key].clone());
+        }
+
+        nbt
+    }
+}
@@ -3,7 +3,7 @@
 use crate::{
     error::SculkParseError,
     kv::KVPair,
-    traits::FromCompoundNbt,
+    traits::{FromCompoundNbt, ToCompoundNbt},
     util::{get_bool, get_owned_string},
     uuid::Uuid,
 };
@@ -87,6 +87,19 @@ impl From<&str> for BossEventOverlay {
     }
 }
 
+impl From<BossEventOverlay> for String {
+    fn from(value: BossEventOverlay) -> Self {
+        match value {
+            BossEventOverlay::Progress => String::from("progress"),
+            BossEventOverlay::Notched6 => String::from("notched_6"),
+            BossEventOverlay::Notched10 => String::from("notched_10"),
+            BossEventOverlay::Notched12 => String::from("notched_12"),
+            BossEventOverlay::Notched20 => String::from("notched_20"),
+            BossEventOverlay::Unknown(value) => value,
+        }
+    }
+}
+
 impl FromCompoundNbt for KVPair<CustomBossEvent> {
     fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
     where
@@ -166,3 +179,36 @@ impl FromCompoundNbt for CustomBossEvent {
         })
     }
 }
+
+impl ToCompoundNbt for KVPair<CustomBossEvent> {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        let mut keys: Vec<&String> = self.keys().collect();
+        keys.sort();
+        for key in keys {
+            nbt.insert(key.as_str(), self[key].to_compound_nbt());
+        }
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for CustomBossEvent {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("Players", Uuid::to_nbt_list(&self.players));
+        nbt.insert("Color", self.color.as_str());
+        nbt.insert("CreateWorldFog", self.create_world_fog);
+        nbt.insert("DarkenScreen", self.darken_screen);
+        nbt.insert("Max", self.max);
+        nbt.insert("Value", self.value);
+        nbt.insert("Name", self.name.as_str());
+        nbt.insert("Overlay", String::from(self.overlay.clone()));
+        nbt.insert("PlayBossMusic", self.play_boss_music);
+        nbt.insert("Visible", self.visible);
+
+        nbt
+    }
+}
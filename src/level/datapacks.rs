@@ -1,7 +1,7 @@
 //! Lists what datapacks are enabled and disabled in a world.  
 //! You may think at first this is the actual datapacks but nah nah.
 
-use crate::traits::FromCompoundNbt;
+use crate::traits::{FromCompoundNbt, ToCompoundNbt};
 
 /// What datapacks are enabled and disabled in a world.
 #[derive(Debug, Clone, PartialEq)]
@@ -54,3 +54,12 @@ impl FromCompoundNbt for Datapacks {
         Ok(Self { disabled, enabled })
     }
 }
+
+impl ToCompoundNbt for Datapacks {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+        nbt.insert("Disabled", self.disabled.clone());
+        nbt.insert("Enabled", self.enabled.clone());
+        nbt
+    }
+}
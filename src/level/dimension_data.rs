@@ -1,6 +1,10 @@
 //! Dimension specific data for a world.  
 
-use crate::{error::SculkParseError, traits::FromCompoundNbt, util::get_bool};
+use crate::{
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::{get_bool, set_optional_tag},
+};
 
 /// Data for a dimension.
 #[derive(Debug, Clone, PartialEq)]
@@ -153,3 +157,53 @@ impl FromCompoundNbt for ExitPortalLocation {
         Ok(Self { x, y, z })
     }
 }
+
+impl ToCompoundNbt for DimensionData {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+        nbt.insert("1", self.end.to_compound_nbt());
+        nbt
+    }
+}
+
+impl ToCompoundNbt for EndData {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+        nbt.insert("DragonFight", self.dragon_fight.to_compound_nbt());
+        nbt
+    }
+}
+
+impl ToCompoundNbt for DragonFight {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_optional_tag(
+            &mut nbt,
+            "ExitPortalLocation",
+            self.exit_portal_location
+                .as_ref()
+                .map(ExitPortalLocation::to_compound_nbt),
+        );
+        nbt.insert(
+            "Gateways",
+            simdnbt::owned::NbtTag::IntArray(self.gateways.clone()),
+        );
+        nbt.insert("DragonKilled", self.dragon_killed);
+        set_optional_tag(&mut nbt, "DragonUUIDLeast", self.dragon_uuid_least);
+        set_optional_tag(&mut nbt, "DragonUUIDMost", self.dragon_uuid_most);
+        nbt.insert("PreviouslyKilled", self.previously_killed);
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for ExitPortalLocation {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+        nbt.insert("X", self.x);
+        nbt.insert("Y", self.y);
+        nbt.insert("Z", self.z);
+        nbt
+    }
+}
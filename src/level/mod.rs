@@ -7,9 +7,10 @@ use crate::{
     error::SculkParseError,
     kv::KVPair,
     player::{game_type::GameType, Player},
-    traits::FromCompoundNbt,
-    util::{get_bool, get_owned_string},
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::{get_bool, get_extra, get_owned_string, merge_compound, set_optional_tag},
     uuid::Uuid,
+    version::DataVersionScope,
 };
 
 pub mod boss_event;
@@ -202,8 +203,60 @@ pub struct Level {
     ///  true if the world was opened in a modified version.  
     /// `WasModded`
     pub was_modded: bool,
+
+    /// Fields of `Data` that aren't modelled, like `ScheduledEvents` or data added by mods.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extra: simdnbt::owned::NbtCompound,
 }
 
+/// Fields of `Data` that are decoded, the rest is kept in [`Level::extra`].
+const LEVEL_FIELDS: &[&str] = &[
+    "allowCommands",
+    "BorderCenterX",
+    "BorderCenterZ",
+    "BorderDamagePerBlock",
+    "BorderSize",
+    "BorderSafeZone",
+    "BorderSizeLerpTarget",
+    "BorderSizeLerpTime",
+    "BorderWarningBlocks",
+    "BorderWarningTime",
+    "clearWeatherTime",
+    "CustomBossEvents",
+    "DataPacks",
+    "DataVersion",
+    "DayTime",
+    "Difficulty",
+    "DifficultyLocked",
+    "DragonFight",
+    "enabled_features",
+    "GameRules",
+    "WorldGenSettings",
+    "GameType",
+    "hardcore",
+    "initialized",
+    "LastPlayed",
+    "LevelName",
+    "MapFeatures",
+    "Player",
+    "raining",
+    "rainTime",
+    "RandomSeed",
+    "SizeOnDisk",
+    "SpawnX",
+    "SpawnY",
+    "SpawnZ",
+    "thundering",
+    "thunderTime",
+    "Time",
+    "version",
+    "Version",
+    "WanderingTraderId",
+    "WanderingTraderSpawnChance",
+    "WanderingTraderSpawnDelay",
+    "WasModded",
+];
+
 /// More detailed information about the Minecraft version the world was saved in.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -273,6 +326,17 @@ impl Difficulty {
             _ => Difficulty::Unknown(b),
         }
     }
+
+    /// Converts a Difficulty back to an i8.
+    pub fn to_i8(&self) -> i8 {
+        match self {
+            Difficulty::Peaceful => 0,
+            Difficulty::Easy => 1,
+            Difficulty::Normal => 2,
+            Difficulty::Hard => 3,
+            Difficulty::Unknown(b) => *b,
+        }
+    }
 }
 
 impl FromCompoundNbt for Level {
@@ -287,6 +351,12 @@ impl FromCompoundNbt for Level {
             .compound("Data")
             .ok_or(SculkParseError::MissingField("Data".into()))?;
 
+        // The player in `Data` has items, so the version is needed before anything else.
+        let data_version = nbt
+            .int("DataVersion")
+            .ok_or(SculkParseError::MissingField("DataVersion".into()))?;
+        let _data_version = DataVersionScope::enter(data_version);
+
         let allow_commands = get_bool(&nbt, "allowCommands");
 
         let border_center_x = nbt.double("BorderCenterX").unwrap_or(0.0);
@@ -313,10 +383,6 @@ impl FromCompoundNbt for Level {
             .map(|nbt| Datapacks::from_compound_nbt(&nbt))
             .ok_or(SculkParseError::MissingField("DataPacks".into()))??;
 
-        let data_version = nbt
-            .int("DataVersion")
-            .ok_or(SculkParseError::MissingField("DataVersion".into()))?;
-
         let day_time = nbt
             .long("DayTime")
             .ok_or(SculkParseError::MissingField("DayTime".into()))?;
@@ -476,6 +542,7 @@ impl FromCompoundNbt for Level {
             wandering_trader_spawn_chance,
             wandering_trader_spawn_delay,
             was_modded,
+            extra: get_extra(&nbt, LEVEL_FIELDS),
         })
     }
 }
@@ -493,6 +560,96 @@ impl Level {
     }
 }
 
+impl ToCompoundNbt for VersionData {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+        nbt.insert("Id", self.id);
+        nbt.insert("Name", self.name.as_str());
+        nbt.insert("Series", self.series.as_str());
+        nbt.insert("Snapshot", self.snapshot);
+        nbt
+    }
+}
+
+impl ToCompoundNbt for Level {
+    /// Writes the root of the level.dat file, with the fields in a `Data` compound.
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("allowCommands", self.allow_commands);
+        nbt.insert("BorderCenterX", self.border_center_x);
+        nbt.insert("BorderCenterZ", self.border_center_z);
+        nbt.insert("BorderDamagePerBlock", self.border_damage_per_block);
+        nbt.insert("BorderSize", self.border_size);
+        nbt.insert("BorderSafeZone", self.border_safe_zone);
+        nbt.insert("BorderSizeLerpTarget", self.border_size_lerp_target);
+        nbt.insert("BorderSizeLerpTime", self.border_size_lerp_time);
+        nbt.insert("BorderWarningBlocks", self.border_warning_blocks);
+        nbt.insert("BorderWarningTime", self.border_warning_time);
+        nbt.insert("clearWeatherTime", self.clear_weather_time);
+        nbt.insert(
+            "CustomBossEvents",
+            self.custom_boss_events.to_compound_nbt(),
+        );
+        nbt.insert("DataPacks", self.datapacks.to_compound_nbt());
+        nbt.insert("DataVersion", self.data_version);
+        nbt.insert("DayTime", self.day_time);
+        nbt.insert("Difficulty", self.difficulty.to_i8());
+        nbt.insert("DifficultyLocked", self.difficulty_locked);
+        nbt.insert("DragonFight", self.dimension_data.to_compound_nbt());
+        nbt.insert("enabled_features", self.enabled_features.clone());
+        nbt.insert("GameRules", self.game_rules.to_compound_nbt());
+        nbt.insert(
+            "WorldGenSettings",
+            self.world_gen_settings.to_compound_nbt(),
+        );
+        nbt.insert("GameType", self.game_type.to_i32());
+        nbt.insert("hardcore", self.hardcore);
+        nbt.insert("initialized", self.initialized);
+        nbt.insert("LastPlayed", self.last_played);
+        nbt.insert("LevelName", self.level_name.as_str());
+        nbt.insert("MapFeatures", self.map_features);
+        set_optional_tag(
+            &mut nbt,
+            "Player",
+            self.player.as_ref().map(Player::to_compound_nbt),
+        );
+        nbt.insert("raining", self.raining);
+        nbt.insert("rainTime", self.rain_time);
+        set_optional_tag(&mut nbt, "RandomSeed", self.random_seed);
+        set_optional_tag(&mut nbt, "SizeOnDisk", self.size_on_disk);
+        nbt.insert("SpawnX", self.spawn_x);
+        nbt.insert("SpawnY", self.spawn_y);
+        nbt.insert("SpawnZ", self.spawn_z);
+        nbt.insert("thundering", self.thundering);
+        nbt.insert("thunderTime", self.thunder_time);
+        nbt.insert("Time", self.time);
+        nbt.insert("version", self.version);
+        nbt.insert("Version", self.version_data.to_compound_nbt());
+        set_optional_tag(
+            &mut nbt,
+            "WanderingTraderId",
+            self.wandering_trader_id.as_ref().map(Uuid::to_tag),
+        );
+        nbt.insert(
+            "WanderingTraderSpawnChance",
+            self.wandering_trader_spawn_chance,
+        );
+        nbt.insert(
+            "WanderingTraderSpawnDelay",
+            self.wandering_trader_spawn_delay,
+        );
+        nbt.insert("WasModded", self.was_modded);
+
+        let mut data = self.extra.clone();
+        merge_compound(&mut data, nbt);
+
+        let mut root = simdnbt::owned::NbtCompound::new();
+        root.insert("Data", data);
+        root
+    }
+}
+
 #[cfg(test)]
 #[test]
 fn simple_level_test() {
@@ -516,3 +673,27 @@ fn simple_level_test() {
 
     let _ = Level::from_compound_nbt(&nbt).unwrap();
 }
+
+#[cfg(test)]
+#[test]
+fn level_round_trip() {
+    use crate::util::{maybe_gunzip, write_root_compound};
+    use std::io::Cursor;
+
+    for bytes in [
+        include_bytes!("../../test_data/level.dat").as_slice(),
+        include_bytes!("../../test_data/void_level.dat").as_slice(),
+    ] {
+        let bytes = maybe_gunzip(bytes).unwrap();
+        let nbt = simdnbt::borrow::read(&mut Cursor::new(&bytes[..]))
+            .unwrap()
+            .unwrap();
+        let level = Level::from_compound_nbt(&nbt.as_compound()).unwrap();
+
+        let bytes = write_root_compound(&level.to_compound_nbt());
+        let nbt = simdnbt::borrow::read(&mut Cursor::new(bytes.as_slice()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(Level::from_compound_nbt(&nbt.as_compound()).unwrap(), level);
+    }
+}
@@ -1,6 +1,11 @@
 //! Settings used when generating the world.  
 
-use crate::{error::SculkParseError, kv::KVPair, traits::FromCompoundNbt};
+use crate::{
+    error::SculkParseError,
+    kv::KVPair,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::set_optional_tag,
+};
 
 /// Settings about the world generation.
 #[derive(Debug, Clone, PartialEq)]
@@ -54,3 +59,16 @@ impl FromCompoundNbt for WorldGenSettings {
         })
     }
 }
+
+impl ToCompoundNbt for WorldGenSettings {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        set_optional_tag(&mut nbt, "bonus_chest", self.bonus_chest);
+        nbt.insert("seed", self.seed);
+        nbt.insert("generate_features", self.generate_features);
+        nbt.insert("dimensions", self.dimensions.to_compound_nbt());
+
+        nbt
+    }
+}
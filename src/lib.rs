@@ -15,6 +15,7 @@
 // Pub mod declarations.
 pub mod block_entities;
 pub mod block_entity;
+pub mod borrowed;
 pub mod chunk;
 pub mod color;
 pub mod components;
@@ -22,11 +23,21 @@ pub mod entity;
 pub mod error;
 pub mod item;
 pub mod level;
+pub mod litematic;
 pub mod map;
 pub mod player;
+pub mod query;
 pub mod rarity;
+pub mod region;
+pub mod report;
+pub mod resource_location;
+pub mod schematic;
+pub mod structure_template;
 pub mod traits;
 pub mod uuid;
+pub mod version;
+pub mod walk;
+pub mod world;
 
 // Internal modules.
 mod kv;
@@ -36,4 +47,4 @@ mod util;
 mod statistics;
 
 // Re-export the modules.
-pub use util::MC_VERSION;
+pub use util::{LootTableData, MC_DATA_VERSION, MC_VERSION};
@@ -0,0 +1,647 @@
+//! Litematica schematic files (`.litematic`).
+//! [Litematica](https://github.com/maruohon/litematica)
+
+use crate::{
+    block_entity::BlockEntity,
+    chunk::{
+        section::{bits_for_palette, pack_spanning_indices, unpack_spanning_indices, Palette},
+        tile_tick::TileTick,
+    },
+    entity::Entity,
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToCompoundNbt, ToVersionedCompoundNbt},
+    util::{
+        get_owned_string, get_resource_location, get_t_compound_vec, gzip, maybe_gunzip,
+        write_root_compound, MC_DATA_VERSION,
+    },
+    version::DataVersionScope,
+};
+use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+use std::io::Cursor;
+
+/// A Litematica schematic, made up of one or more named regions.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Litematic {
+    /// Version of the litematic format.
+    /// `Version`
+    pub version: i32,
+
+    /// Minor version of the litematic format, added in version 6.
+    /// `SubVersion`
+    pub sub_version: Option<i32>,
+
+    /// The data version of the blocks and entities in the litematic.
+    /// `MinecraftDataVersion`
+    pub minecraft_data_version: i32,
+
+    /// Information about the litematic.
+    /// `Metadata`
+    pub metadata: LitematicMetadata,
+
+    /// The regions of the litematic, in the order they are stored.
+    /// `Regions`
+    pub regions: Vec<LitematicRegion>,
+}
+
+/// Information about a litematic.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LitematicMetadata {
+    /// `Name`
+    pub name: String,
+
+    /// `Author`
+    pub author: String,
+
+    /// `Description`
+    pub description: String,
+
+    /// Amount of regions.
+    /// `RegionCount`
+    pub region_count: i32,
+
+    /// Volume of all regions combined.
+    /// `TotalVolume`
+    pub total_volume: i32,
+
+    /// Amount of non-air blocks in all regions combined.
+    /// `TotalBlocks`
+    pub total_blocks: i32,
+
+    /// Creation time in milliseconds since the unix epoch.
+    /// `TimeCreated`
+    pub time_created: i64,
+
+    /// Last modification time in milliseconds since the unix epoch.
+    /// `TimeModified`
+    pub time_modified: i64,
+
+    /// Size of the box enclosing all regions.
+    /// `EnclosingSize`
+    pub enclosing_size: [i32; 3],
+
+    /// ARGB pixels of the preview image.
+    /// `PreviewImageData`
+    pub preview_image_data: Option<Vec<i32>>,
+}
+
+/// A region in a litematic.
+/// Blocks are stored with the index `(y * size_z + z) * size_x + x`, using the absolute size.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LitematicRegion {
+    /// Name of the region, the key in `Regions`.
+    pub name: String,
+
+    /// Position of the region relative to the litematic origin.
+    /// `Position`
+    pub position: [i32; 3],
+
+    /// Size of the region, each axis may be negative when the region extends towards negative coordinates.
+    /// `Size`
+    pub size: [i32; 3],
+
+    /// The block states used in the region, the position in the list is the index used in `block_states`.
+    /// `BlockStatePalette`
+    pub block_state_palette: Vec<Palette>,
+
+    /// Palette index of every block in the region, unpacked from the `BlockStates` long array.
+    /// `BlockStates`
+    pub block_states: Vec<u32>,
+
+    /// Block entities in the region, with their `x`, `y` and `z` relative to the region.
+    /// `TileEntities`
+    pub tile_entities: Vec<BlockEntity>,
+
+    /// Entities in the region, with their `Pos` relative to the region.
+    /// `Entities`
+    pub entities: Vec<Entity>,
+
+    /// Scheduled block ticks, positions are relative to the region.
+    /// `PendingBlockTicks`
+    pub pending_block_ticks: Vec<TileTick>,
+
+    /// Scheduled fluid ticks, positions are relative to the region.
+    /// `PendingFluidTicks`
+    pub pending_fluid_ticks: Vec<TileTick>,
+}
+
+impl FromCompoundNbt for Litematic {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        let version = nbt
+            .int("Version")
+            .ok_or(SculkParseError::MissingField("Version".into()))?;
+        let sub_version = nbt.int("SubVersion");
+        let minecraft_data_version = nbt
+            .int("MinecraftDataVersion")
+            .ok_or(SculkParseError::MissingField("MinecraftDataVersion".into()))?;
+        let _data_version = DataVersionScope::enter(minecraft_data_version);
+
+        let metadata = nbt
+            .compound("Metadata")
+            .map(|nbt| LitematicMetadata::from_compound_nbt(&nbt))
+            .ok_or(SculkParseError::MissingField("Metadata".into()))??;
+
+        let regions = nbt
+            .compound("Regions")
+            .ok_or(SculkParseError::MissingField("Regions".into()))?
+            .iter()
+            .map(|(name, region)| {
+                let region = region
+                    .compound()
+                    .ok_or(SculkParseError::InvalidField("Regions".into()))?;
+                LitematicRegion::from_named_compound_nbt(name.to_string(), &region)
+            })
+            .collect::<Result<Vec<LitematicRegion>, SculkParseError>>()?;
+
+        Ok(Litematic {
+            version,
+            sub_version,
+            minecraft_data_version,
+            metadata,
+            regions,
+        })
+    }
+}
+
+impl FromCompoundNbt for LitematicMetadata {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        Ok(LitematicMetadata {
+            name: get_owned_string(nbt, "Name")?,
+            author: get_owned_string(nbt, "Author")?,
+            description: get_owned_string(nbt, "Description")?,
+            region_count: nbt.int("RegionCount").unwrap_or(0),
+            total_volume: nbt.int("TotalVolume").unwrap_or(0),
+            total_blocks: nbt.int("TotalBlocks").unwrap_or(0),
+            time_created: nbt.long("TimeCreated").unwrap_or(0),
+            time_modified: nbt.long("TimeModified").unwrap_or(0),
+            enclosing_size: get_xyz(nbt, "EnclosingSize")?,
+            preview_image_data: nbt.int_array("PreviewImageData"),
+        })
+    }
+}
+
+impl LitematicRegion {
+    fn from_named_compound_nbt(
+        name: String,
+        nbt: &simdnbt::borrow::NbtCompound,
+    ) -> Result<Self, SculkParseError> {
+        let position = get_xyz(nbt, "Position")?;
+        let size = get_xyz(nbt, "Size")?;
+
+        let block_state_palette = nbt
+            .list("BlockStatePalette")
+            .and_then(|list| list.compounds())
+            .ok_or(SculkParseError::MissingField("BlockStatePalette".into()))?
+            .into_iter()
+            .map(|nbt| Palette::from_compound_nbt(&nbt))
+            .collect::<Result<Vec<Palette>, SculkParseError>>()?;
+
+        let volume = size.iter().map(|s| s.unsigned_abs() as usize).product();
+        let block_states = unpack_spanning_indices(
+            &nbt.long_array("BlockStates")
+                .ok_or(SculkParseError::MissingField("BlockStates".into()))?,
+            bits_for_palette(block_state_palette.len(), 2),
+            volume,
+        );
+
+        let tile_entities =
+            get_t_compound_vec(nbt, "TileEntities", BlockEntity::from_compound_nbt)?;
+        let entities = get_t_compound_vec(nbt, "Entities", Entity::from_compound_nbt)?;
+
+        let pending_block_ticks = get_ticks(nbt, "PendingBlockTicks", "Block")?;
+        let pending_fluid_ticks = get_ticks(nbt, "PendingFluidTicks", "Fluid")?;
+
+        Ok(LitematicRegion {
+            name,
+            position,
+            size,
+            block_state_palette,
+            block_states,
+            tile_entities,
+            entities,
+            pending_block_ticks,
+            pending_fluid_ticks,
+        })
+    }
+}
+
+impl ToCompoundNbt for Litematic {
+    fn to_compound_nbt(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+
+        nbt.insert("Version", self.version);
+        if let Some(sub_version) = self.sub_version {
+            nbt.insert("SubVersion", sub_version);
+        }
+        nbt.insert("MinecraftDataVersion", self.minecraft_data_version);
+        nbt.insert(
+            "Metadata",
+            NbtTag::Compound(self.metadata.to_compound_nbt()),
+        );
+
+        let mut regions = NbtCompound::new();
+        for region in &self.regions {
+            regions.insert(
+                region.name.as_str(),
+                NbtTag::Compound(region.to_compound_nbt_with_version(self.minecraft_data_version)),
+            );
+        }
+        nbt.insert("Regions", NbtTag::Compound(regions));
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for LitematicMetadata {
+    fn to_compound_nbt(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+
+        nbt.insert("Name", self.name.as_str());
+        nbt.insert("Author", self.author.as_str());
+        nbt.insert("Description", self.description.as_str());
+        nbt.insert("RegionCount", self.region_count);
+        nbt.insert("TotalVolume", self.total_volume);
+        nbt.insert("TotalBlocks", self.total_blocks);
+        nbt.insert("TimeCreated", self.time_created);
+        nbt.insert("TimeModified", self.time_modified);
+        nbt.insert("EnclosingSize", NbtTag::Compound(xyz(self.enclosing_size)));
+        if let Some(preview) = &self.preview_image_data {
+            nbt.insert("PreviewImageData", NbtTag::IntArray(preview.clone()));
+        }
+
+        nbt
+    }
+}
+
+impl ToVersionedCompoundNbt for LitematicRegion {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+
+        nbt.insert("Position", NbtTag::Compound(xyz(self.position)));
+        nbt.insert("Size", NbtTag::Compound(xyz(self.size)));
+
+        let palette = self
+            .block_state_palette
+            .iter()
+            .map(|p| p.to_compound_nbt())
+            .collect();
+        nbt.insert("BlockStatePalette", NbtList::Compound(palette));
+        nbt.insert(
+            "BlockStates",
+            NbtTag::LongArray(pack_spanning_indices(
+                &self.block_states,
+                bits_for_palette(self.block_state_palette.len(), 2),
+            )),
+        );
+
+        let tile_entities = self
+            .tile_entities
+            .iter()
+            .map(|block_entity| block_entity.to_compound_nbt_with_version(data_version))
+            .collect();
+        nbt.insert("TileEntities", NbtList::Compound(tile_entities));
+        let entities = self
+            .entities
+            .iter()
+            .map(|entity| entity.to_compound_nbt_with_version(data_version))
+            .collect();
+        nbt.insert("Entities", NbtList::Compound(entities));
+        nbt.insert(
+            "PendingBlockTicks",
+            write_ticks(&self.pending_block_ticks, "Block"),
+        );
+        nbt.insert(
+            "PendingFluidTicks",
+            write_ticks(&self.pending_fluid_ticks, "Fluid"),
+        );
+
+        nbt
+    }
+}
+
+impl Litematic {
+    /// Creates an empty litematic with the given name and no regions.
+    pub fn new(name: impl Into<String>) -> Self {
+        Litematic {
+            version: 6,
+            sub_version: Some(1),
+            minecraft_data_version: MC_DATA_VERSION,
+            metadata: LitematicMetadata {
+                name: name.into(),
+                author: String::new(),
+                description: String::new(),
+                region_count: 0,
+                total_volume: 0,
+                total_blocks: 0,
+                time_created: 0,
+                time_modified: 0,
+                enclosing_size: [0, 0, 0],
+                preview_image_data: None,
+            },
+            regions: vec![],
+        }
+    }
+
+    /// Converts from bytes to a litematic.
+    /// The bytes can either be raw nbt or gzip compressed, as `.litematic` files are saved.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SculkParseError> {
+        let bytes = maybe_gunzip(bytes)?;
+        let nbt = simdnbt::borrow::read(&mut Cursor::new(&bytes))?;
+
+        let nbt = match nbt {
+            simdnbt::borrow::Nbt::Some(nbt) => nbt,
+            simdnbt::borrow::Nbt::None => return Err(SculkParseError::NoNbt),
+        };
+
+        Litematic::from_compound_nbt(&nbt.as_compound())
+    }
+
+    /// Converts the litematic to uncompressed nbt bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        write_root_compound(&self.to_compound_nbt())
+    }
+
+    /// Converts the litematic to gzip compressed nbt bytes, the same way `.litematic` files are saved.
+    pub fn to_gzip_bytes(&self) -> Vec<u8> {
+        gzip(&self.to_bytes())
+    }
+
+    /// Gets a region by name.
+    pub fn region(&self, name: &str) -> Option<&LitematicRegion> {
+        self.regions.iter().find(|region| region.name == name)
+    }
+
+    /// Recomputes the region count, volume, block count and enclosing size in the metadata.
+    pub fn update_metadata(&mut self) {
+        self.metadata.region_count = self.regions.len() as i32;
+        self.metadata.total_volume = self.regions.iter().map(|r| r.volume() as i32).sum();
+        self.metadata.total_blocks = self.regions.iter().map(|r| r.block_count() as i32).sum();
+
+        let mut min = [i32::MAX; 3];
+        let mut max = [i32::MIN; 3];
+        for region in &self.regions {
+            let (region_min, region_max) = region.bounds();
+            for axis in 0..3 {
+                min[axis] = min[axis].min(region_min[axis]);
+                max[axis] = max[axis].max(region_max[axis]);
+            }
+        }
+
+        self.metadata.enclosing_size = if self.regions.is_empty() {
+            [0, 0, 0]
+        } else {
+            [0, 1, 2].map(|axis| max[axis] - min[axis] + 1)
+        };
+    }
+}
+
+impl LitematicRegion {
+    /// Creates a region of the given size filled with air.
+    pub fn new(name: impl Into<String>, position: [i32; 3], size: [i32; 3]) -> Self {
+        let volume = size.iter().map(|s| s.unsigned_abs() as usize).product();
+
+        LitematicRegion {
+            name: name.into(),
+            position,
+            size,
+            block_state_palette: vec![Palette::air()],
+            block_states: vec![0; volume],
+            tile_entities: vec![],
+            entities: vec![],
+            pending_block_ticks: vec![],
+            pending_fluid_ticks: vec![],
+        }
+    }
+
+    /// The absolute size of the region on each axis.
+    pub fn abs_size(&self) -> [usize; 3] {
+        self.size.map(|s| s.unsigned_abs() as usize)
+    }
+
+    /// Amount of blocks the region spans.
+    pub fn volume(&self) -> usize {
+        self.abs_size().iter().product()
+    }
+
+    /// Amount of blocks that are not air.
+    pub fn block_count(&self) -> usize {
+        self.block_states
+            .iter()
+            .filter(|state| {
+                self.block_state_palette
+                    .get(**state as usize)
+                    .is_some_and(|p| p.name != "minecraft:air")
+            })
+            .count()
+    }
+
+    /// The minimum and maximum corner of the region, relative to the litematic origin.
+    pub fn bounds(&self) -> ([i32; 3], [i32; 3]) {
+        let corner = [0, 1, 2].map(|axis| {
+            let size = self.size[axis];
+            self.position[axis] + if size < 0 { size + 1 } else { size - 1 }
+        });
+
+        (
+            [0, 1, 2].map(|axis| self.position[axis].min(corner[axis])),
+            [0, 1, 2].map(|axis| self.position[axis].max(corner[axis])),
+        )
+    }
+
+    /// Gets the index into `block_states` for the given region relative position.
+    pub fn index(&self, x: usize, y: usize, z: usize) -> usize {
+        let [size_x, _, size_z] = self.abs_size();
+        (y * size_z + z) * size_x + x
+    }
+
+    /// Gets the block state at the given region relative position.
+    pub fn get_block(&self, x: usize, y: usize, z: usize) -> Option<&Palette> {
+        let [size_x, size_y, size_z] = self.abs_size();
+        if x >= size_x || y >= size_y || z >= size_z {
+            return None;
+        }
+
+        let state = *self.block_states.get(self.index(x, y, z))?;
+        self.block_state_palette.get(state as usize)
+    }
+
+    /// Sets the block state at the given region relative position, inserting it in the palette if it's new.  
+    /// Returns `None` without changing anything if the position is outside of the region.
+    pub fn set_block(&mut self, x: usize, y: usize, z: usize, state: Palette) -> Option<()> {
+        let [size_x, size_y, size_z] = self.abs_size();
+        if x >= size_x || y >= size_y || z >= size_z {
+            return None;
+        }
+        let index = self.index(x, y, z);
+        if index >= self.block_states.len() {
+            return None;
+        }
+
+        let state = match self.block_state_palette.iter().position(|p| *p == state) {
+            Some(state) => state,
+            None => {
+                self.block_state_palette.push(state);
+                self.block_state_palette.len() - 1
+            }
+        };
+
+        self.block_states[index] = state as u32;
+        Some(())
+    }
+}
+
+fn get_xyz(
+    nbt: &simdnbt::borrow::NbtCompound,
+    key: &'static str,
+) -> Result<[i32; 3], SculkParseError> {
+    let pos = nbt
+        .compound(key)
+        .ok_or(SculkParseError::MissingField(key.into()))?;
+
+    match (pos.int("x"), pos.int("y"), pos.int("z")) {
+        (Some(x), Some(y), Some(z)) => Ok([x, y, z]),
+        _ => Err(SculkParseError::InvalidField(key.into())),
+    }
+}
+
+fn xyz(pos: [i32; 3]) -> NbtCompound {
+    let mut nbt = NbtCompound::new();
+    nbt.insert("x", pos[0]);
+    nbt.insert("y", pos[1]);
+    nbt.insert("z", pos[2]);
+    nbt
+}
+
+/// Litematica stores ticks with their own field names, these are mapped onto [`TileTick`].
+fn get_ticks(
+    nbt: &simdnbt::borrow::NbtCompound,
+    key: &'static str,
+    id_key: &'static str,
+) -> Result<Vec<TileTick>, SculkParseError> {
+    let list = match nbt.list(key).and_then(|list| list.compounds()) {
+        Some(list) => list,
+        None => return Ok(vec![]),
+    };
+
+    list.into_iter()
+        .map(|tick| {
+            let [x, y, z] = match (tick.int("x"), tick.int("y"), tick.int("z")) {
+                (Some(x), Some(y), Some(z)) => [x, y, z],
+                _ => return Err(SculkParseError::InvalidField(key.into())),
+            };
+
+            Ok(TileTick {
+                i: get_resource_location(&tick, id_key)?,
+                p: tick.int("Priority").unwrap_or(0),
+                t: tick
+                    .int("Time")
+                    .ok_or(SculkParseError::MissingField("Time".into()))?,
+                x,
+                y,
+                z,
+            })
+        })
+        .collect()
+}
+
+/// Sub ticks are not kept, so they are written in list order.
+fn write_ticks(ticks: &[TileTick], id_key: &'static str) -> NbtList {
+    NbtList::Compound(
+        ticks
+            .iter()
+            .enumerate()
+            .map(|(sub_tick, tick)| {
+                let mut nbt = NbtCompound::new();
+                nbt.insert(id_key, tick.i.to_string());
+                nbt.insert("Priority", tick.p);
+                nbt.insert("SubTick", sub_tick as i64);
+                nbt.insert("Time", tick.t);
+                nbt.insert("x", tick.x);
+                nbt.insert("y", tick.y);
+                nbt.insert("z", tick.z);
+                nbt
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+#[test]
+fn litematic_round_trip() {
+    use crate::util::from_owned_compound;
+    use std::str::FromStr;
+
+    let mut litematic = Litematic::new("test");
+    let mut region = LitematicRegion::new("main", [0, 0, 0], [-5, 3, 7]);
+
+    // 5 states need 3 bits, which makes some indices span two longs.
+    for i in 0..region.volume() {
+        region.block_states[i] = (i % 5) as u32;
+    }
+    for i in 1..5 {
+        region
+            .block_state_palette
+            .push(Palette::from_str(&format!("minecraft:test_{i}")).unwrap());
+    }
+    region
+        .set_block(
+            4,
+            2,
+            6,
+            Palette::from_str("minecraft:oak_log[axis=z]").unwrap(),
+        )
+        .unwrap();
+    assert!(region.set_block(5, 0, 0, Palette::air()).is_none());
+
+    let mut chest = NbtCompound::new();
+    chest.insert("id", "minecraft:chest");
+    chest.insert("x", 4);
+    chest.insert("y", 2);
+    chest.insert("z", 6);
+    chest.insert("Items", NbtList::Empty);
+    chest.insert("mod_data", 1);
+    region
+        .tile_entities
+        .push(from_owned_compound(&chest, BlockEntity::from_compound_nbt).unwrap());
+    let mut pig = NbtCompound::new();
+    pig.insert("id", "minecraft:pig");
+    pig.insert("Pos", NbtList::Double(vec![0.5, 1.0, 0.5]));
+    region
+        .entities
+        .push(from_owned_compound(&pig, Entity::from_compound_nbt).unwrap());
+
+    region.pending_block_ticks.push(TileTick {
+        i: "minecraft:repeater".parse().unwrap(),
+        p: -1,
+        t: 2,
+        x: 1,
+        y: 0,
+        z: 3,
+    });
+
+    litematic.regions.push(region);
+    litematic.update_metadata();
+    assert_eq!(litematic.metadata.enclosing_size, [5, 3, 7]);
+    assert_eq!(litematic.metadata.total_volume, 105);
+
+    let parsed = Litematic::from_bytes(&litematic.to_gzip_bytes()).unwrap();
+    assert_eq!(parsed, litematic);
+
+    let region = parsed.region("main").unwrap();
+    assert_eq!(region.get_block(3, 0, 0).unwrap().name, "minecraft:test_3");
+    assert_eq!(
+        region.get_block(4, 2, 6).unwrap().to_string(),
+        "minecraft:oak_log[axis=z]"
+    );
+    assert_eq!(region.tile_entities[0].base.z, 6);
+    assert_eq!(region.tile_entities[0].base.extra.int("mod_data"), Some(1));
+    assert_eq!(region.entities[0].id, "minecraft:pig");
+}
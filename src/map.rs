@@ -1,8 +1,9 @@
 use crate::{
     color::Color,
     error::SculkParseError,
+    resource_location::ResourceLocation,
     traits::FromCompoundNbt,
-    util::{get_bool, get_owned_optional_string, get_owned_string, get_t_compound_vec},
+    util::{get_bool, get_owned_optional_string, get_resource_location, get_t_compound_vec},
 };
 use std::str::FromStr;
 
@@ -14,7 +15,7 @@ pub struct Map {
     pub scale: i8,
 
     /// Resource location for a dimension.  
-    pub dimension: String,
+    pub dimension: ResourceLocation,
 
     /// true (default) indicates that a positional arrow should be shown when the map is near its center coords. false indicates that the position arrow should never be shown.  
     /// `trackingPosition`
@@ -184,7 +185,7 @@ impl FromCompoundNbt for Map {
             .byte("scale")
             .ok_or(SculkParseError::MissingField("scale".into()))?;
 
-        let dimension = get_owned_string(&nbt, "dimension")?;
+        let dimension = get_resource_location(&nbt, "dimension")?;
         let tracking_position = nbt.byte("trackingPosition").map(|b| b != 0).unwrap_or(true);
         let unlimited_tracking = get_bool(&nbt, "unlimitedTracking");
 
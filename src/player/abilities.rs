@@ -1,7 +1,10 @@
 //! Players specific abilities.  
 //! Like if they can fly, if they are invulnerable, etc.
 
-use crate::{traits::FromCompoundNbt, util::get_bool};
+use crate::{
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::get_bool,
+};
 
 /// A player's abilities.
 #[derive(Debug, Clone, PartialEq)]
@@ -60,3 +63,19 @@ impl FromCompoundNbt for Abilities {
         })
     }
 }
+
+impl ToCompoundNbt for Abilities {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("flying", self.flying);
+        nbt.insert("flySpeed", self.fly_speed);
+        nbt.insert("instabuild", self.insta_build);
+        nbt.insert("invulnerable", self.invulnerable);
+        nbt.insert("mayBuild", self.may_build);
+        nbt.insert("mayfly", self.may_fly);
+        nbt.insert("walkSpeed", self.walk_speed);
+
+        nbt
+    }
+}
@@ -27,4 +27,15 @@ impl GameType {
             _ => GameType::Unknown(i),
         }
     }
+
+    /// Converts a `GameType` back to its `i32`.
+    pub fn to_i32(&self) -> i32 {
+        match self {
+            GameType::Survival => 0,
+            GameType::Creative => 1,
+            GameType::Adventure => 2,
+            GameType::Spectator => 3,
+            GameType::Unknown(i) => *i,
+        }
+    }
 }
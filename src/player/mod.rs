@@ -1,11 +1,16 @@
 use crate::{
     components::food::EffectDetails,
-    entity::Entity,
+    entity::{write_passengers, Entity},
     error::SculkParseError,
     item::Item,
-    traits::FromCompoundNbt,
-    util::{get_bool, get_owned_optional_string, get_owned_string, get_t_compound_vec},
+    resource_location::ResourceLocation,
+    traits::{FromCompoundNbt, ToCompoundNbt, ToVersionedCompoundNbt},
+    util::{
+        get_bool, get_extra, get_owned_optional_string, get_resource_location, get_t_compound_vec,
+        merge_compound, set_optional_tag, write_compound_list,
+    },
     uuid::Uuid,
+    version::DataVersionScope,
 };
 use abilities::Abilities;
 use game_type::GameType;
@@ -35,7 +40,7 @@ pub struct Player {
 
     /// The ID of the dimension the player is in. Used to store the players last known location along with Pos.  
     /// `Dimension`
-    pub dimension: String,
+    pub dimension: ResourceLocation,
 
     ///  Each compound tag in this list is an item in the player's 27-slot ender chest inventory. When empty, list type may have unexpected value.  
     /// `EnderItems`
@@ -146,8 +151,72 @@ pub struct Player {
     /// The total amount of experience the player has collected over time; used for the score upon death.  
     /// `XpTotal`
     pub xp_total: i32,
+
+    /// Fields that aren't modelled by [`Player`] or [`PlayerEntity`], like `Brain` or `Attributes`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extra: simdnbt::owned::NbtCompound,
 }
 
+/// Fields of [`Player`] and [`PlayerEntity`], the rest is kept in [`Player::extra`].
+const PLAYER_FIELDS: &[&str] = &[
+    // PlayerEntity
+    "AbsorptionAmount",
+    "ActiveEffects",
+    "Air",
+    "DeathTime",
+    "FallDistance",
+    "FallFlying",
+    "Fire",
+    "HasVisualFire",
+    "Health",
+    "HurtByTimestamp",
+    "Invulnerable",
+    "LeftHanded",
+    "Motion",
+    "NoGravity",
+    "OnGround",
+    "passengers",
+    "PortalCooldown",
+    "Pos",
+    "Rotation",
+    "Silent",
+    "Tags",
+    "TicksFrozen",
+    "UUID",
+    // Player
+    "abilities",
+    "DataVersion",
+    "Dimension",
+    "EnderItems",
+    "enteredNetherPosition",
+    "foodExhaustionLevel",
+    "foodLevel",
+    "foodSaturationLevel",
+    "foodTickTimer",
+    "Inventory",
+    "LastDeathLocation",
+    "playerGameType",
+    "previousPlayerGameType",
+    "recipeBook",
+    "RootVehicle",
+    "Score",
+    "seenCredits",
+    "SelectedItemSlot",
+    "ShoulderEntityLeft",
+    "ShoulderEntityRight",
+    "SleepTimer",
+    "SpawnDimension",
+    "SpawnForced",
+    "SpawnX",
+    "SpawnY",
+    "SpawnZ",
+    "warden_spawn_tracker",
+    "XpLevel",
+    "XpP",
+    "XpSeed",
+    "XpTotal",
+];
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PlayerEntity {
@@ -297,7 +366,7 @@ pub struct Vechile {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeathLocation {
     /// Dimension of last death.
-    pub dimension: String,
+    pub dimension: ResourceLocation,
 
     /// Coordinates of last death.
     pub pos: Vec<i32>,
@@ -451,6 +520,11 @@ impl FromCompoundNbt for Player {
     where
         Self: Sized,
     {
+        let data_version = nbt
+            .int("DataVersion")
+            .ok_or(SculkParseError::MissingField("DataVersion".into()))?;
+        let _data_version = DataVersionScope::enter(data_version);
+
         let player_entity = PlayerEntity::from_compound_nbt(nbt)?;
 
         let abilities = nbt
@@ -458,11 +532,7 @@ impl FromCompoundNbt for Player {
             .map(|nbt| Abilities::from_compound_nbt(&nbt))
             .ok_or(SculkParseError::MissingField("abilities".into()))??;
 
-        let data_version = nbt
-            .int("DataVersion")
-            .ok_or(SculkParseError::MissingField("DataVersion".into()))?;
-
-        let dimension = get_owned_string(nbt, "Dimension")?;
+        let dimension = get_resource_location(nbt, "Dimension")?;
         let ender_items = get_t_compound_vec(nbt, "EnderItems", Item::from_compound_nbt)?;
 
         let entered_nether_position = if let Some(nbt) = nbt.compound("enteredNetherPosition") {
@@ -584,6 +654,7 @@ impl FromCompoundNbt for Player {
             xp_p,
             xp_seed,
             xp_total,
+            extra: get_extra(nbt, PLAYER_FIELDS),
         })
     }
 }
@@ -645,7 +716,7 @@ impl FromCompoundNbt for DeathLocation {
     where
         Self: Sized,
     {
-        let dimension = get_owned_string(nbt, "dimension")?;
+        let dimension = get_resource_location(nbt, "dimension")?;
         let pos = nbt
             .int_array("pos")
             .ok_or(SculkParseError::MissingField("pos".into()))?;
@@ -675,6 +746,188 @@ impl FromCompoundNbt for NetherPosition {
     }
 }
 
+impl ToVersionedCompoundNbt for PlayerEntity {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("Air", self.air);
+        nbt.insert("FallDistance", self.fall_distance);
+        nbt.insert("Fire", self.fire);
+        nbt.insert("HasVisualFire", self.has_visual_fire);
+        nbt.insert("Invulnerable", self.invulnerable);
+        nbt.insert(
+            "Motion",
+            simdnbt::owned::NbtList::Double(self.motion.to_vec()),
+        );
+        nbt.insert("NoGravity", self.no_gravity);
+        nbt.insert("OnGround", self.on_ground);
+        if !self.passengers.is_empty() {
+            nbt.insert(
+                "passengers",
+                write_passengers(&self.passengers, data_version),
+            );
+        }
+        nbt.insert("PortalCooldown", self.portal_cooldown);
+        nbt.insert("Pos", simdnbt::owned::NbtList::Double(self.pos.to_vec()));
+        nbt.insert(
+            "Rotation",
+            simdnbt::owned::NbtList::Float(self.rotation.to_vec()),
+        );
+        set_optional_tag(&mut nbt, "Silent", self.silent);
+        if !self.tags.is_empty() {
+            nbt.insert("Tags", self.tags.clone());
+        }
+        set_optional_tag(&mut nbt, "TicksFrozen", self.ticks_frozen);
+        nbt.insert("UUID", self.uuid.to_tag());
+        set_optional_tag(&mut nbt, "AbsorptionAmount", self.absorption_amount);
+        if !self.active_effects.is_empty() {
+            nbt.insert(
+                "ActiveEffects",
+                write_compound_list(&self.active_effects, EffectDetails::to_compound_nbt),
+            );
+        }
+        nbt.insert("DeathTime", self.death_time);
+        nbt.insert("FallFlying", self.fall_flying);
+        nbt.insert("Health", self.health);
+        nbt.insert("HurtByTimestamp", self.hurt_by_timestamp);
+        nbt.insert("LeftHanded", self.left_handed);
+
+        nbt
+    }
+}
+
+impl ToCompoundNbt for Player {
+    /// Writes the player with its own [`Player::data_version`].
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let data_version = self.data_version;
+        let items = |items: &[Item]| {
+            write_compound_list(items, |item| {
+                item.to_compound_nbt_with_version(data_version)
+            })
+        };
+        let mut nbt = self.entity.to_compound_nbt_with_version(data_version);
+
+        nbt.insert("abilities", self.abilities.to_compound_nbt());
+        nbt.insert("DataVersion", data_version);
+        nbt.insert("Dimension", self.dimension.to_string());
+        nbt.insert("EnderItems", items(&self.ender_items));
+        set_optional_tag(
+            &mut nbt,
+            "enteredNetherPosition",
+            self.entered_nether_position
+                .as_ref()
+                .map(NetherPosition::to_compound_nbt),
+        );
+        nbt.insert("foodExhaustionLevel", self.food_exhaustion_level);
+        nbt.insert("foodLevel", self.food_level);
+        nbt.insert("foodSaturationLevel", self.food_saturation_level);
+        nbt.insert("foodTickTimer", self.food_tick_timer);
+        nbt.insert("Inventory", items(&self.inventory));
+        set_optional_tag(
+            &mut nbt,
+            "LastDeathLocation",
+            self.last_death_location
+                .as_ref()
+                .map(DeathLocation::to_compound_nbt),
+        );
+        nbt.insert("playerGameType", self.player_game_type.to_i32());
+        set_optional_tag(
+            &mut nbt,
+            "previousPlayerGameType",
+            self.previous_player_game_type
+                .as_ref()
+                .map(GameType::to_i32),
+        );
+        nbt.insert("recipeBook", self.recipe_book.to_compound_nbt());
+        set_optional_tag(
+            &mut nbt,
+            "RootVehicle",
+            self.root_vechile
+                .as_ref()
+                .map(|vehicle| vehicle.to_compound_nbt_with_version(data_version)),
+        );
+        nbt.insert("Score", self.score);
+        nbt.insert("seenCredits", self.seen_credits);
+        nbt.insert("SelectedItemSlot", self.selected_item_slot);
+        set_optional_tag(
+            &mut nbt,
+            "ShoulderEntityLeft",
+            self.shoulder_entity_left
+                .as_ref()
+                .map(|entity| entity.to_compound_nbt_with_version(data_version)),
+        );
+        set_optional_tag(
+            &mut nbt,
+            "ShoulderEntityRight",
+            self.shoulder_entity_right
+                .as_ref()
+                .map(|entity| entity.to_compound_nbt_with_version(data_version)),
+        );
+        nbt.insert("SleepTimer", self.sleep_timer);
+        set_optional_tag(&mut nbt, "SpawnDimension", self.spawn_dimension.clone());
+        set_optional_tag(&mut nbt, "SpawnForced", self.spawn_forced);
+        set_optional_tag(&mut nbt, "SpawnX", self.spawn_x);
+        set_optional_tag(&mut nbt, "SpawnY", self.spawn_y);
+        set_optional_tag(&mut nbt, "SpawnZ", self.spawn_z);
+        nbt.insert(
+            "warden_spawn_tracker",
+            self.warden_spawn_tracker.to_compound_nbt(),
+        );
+        nbt.insert("XpLevel", self.xp_level);
+        nbt.insert("XpP", self.xp_p);
+        nbt.insert("XpSeed", self.xp_seed);
+        nbt.insert("XpTotal", self.xp_total);
+
+        let mut player = self.extra.clone();
+        merge_compound(&mut player, nbt);
+        player
+    }
+}
+
+impl ToCompoundNbt for WardenTracker {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+        nbt.insert("warning_level", self.warning_level);
+        nbt.insert("cooldown_ticks", self.cooldown_ticks);
+        nbt.insert("ticks_since_last_warning", self.ticks_since_last_warning);
+        nbt
+    }
+}
+
+impl ToVersionedCompoundNbt for Vechile {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+        set_optional_tag(&mut nbt, "attach", self.attach.as_ref().map(Uuid::to_tag));
+        set_optional_tag(
+            &mut nbt,
+            "Entity",
+            self.entity
+                .as_ref()
+                .map(|entity| entity.to_compound_nbt_with_version(data_version)),
+        );
+        nbt
+    }
+}
+
+impl ToCompoundNbt for DeathLocation {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+        nbt.insert("dimension", self.dimension.to_string());
+        nbt.insert("pos", simdnbt::owned::NbtTag::IntArray(self.pos.clone()));
+        nbt
+    }
+}
+
+impl ToCompoundNbt for NetherPosition {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+        nbt.insert("x", self.x);
+        nbt.insert("y", self.y);
+        nbt.insert("z", self.z);
+        nbt
+    }
+}
+
 #[cfg(test)]
 #[test]
 fn complex_player_dat() {
@@ -698,3 +951,30 @@ fn complex_player_dat() {
 
     let _ = Player::from_compound_nbt(&nbt).unwrap();
 }
+
+#[cfg(test)]
+#[test]
+fn player_round_trip() {
+    use crate::util::{maybe_gunzip, write_root_compound};
+    use std::io::Cursor;
+
+    for bytes in [
+        include_bytes!("../../test_data/player_data.dat").as_slice(),
+        include_bytes!("../../test_data/player_data_with_shulkerbox.dat").as_slice(),
+    ] {
+        let bytes = maybe_gunzip(bytes).unwrap();
+        let nbt = simdnbt::borrow::read(&mut Cursor::new(&bytes[..]))
+            .unwrap()
+            .unwrap();
+        let player = Player::from_compound_nbt(&nbt.as_compound()).unwrap();
+
+        let bytes = write_root_compound(&player.to_compound_nbt());
+        let nbt = simdnbt::borrow::read(&mut Cursor::new(bytes.as_slice()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            Player::from_compound_nbt(&nbt.as_compound()).unwrap(),
+            player
+        );
+    }
+}
@@ -0,0 +1,408 @@
+//! Queries for finding items, block entities and blocks in parsed chunks and players.
+//!
+//! A query is a list of conditions that all have to match.
+//! Queries only hold data and `fn` pointers, so the same query can be shared across threads,
+//! like when running it on every chunk from [`crate::world::World::par_chunks`].
+//!
+//! ```rust
+//! use sculk::query::ItemQuery;
+//!
+//! let query = ItemQuery::new()
+//!     .id("minecraft:diamond_sword")
+//!     .enchantment("minecraft:sharpness", 6);
+//! ```
+
+use crate::{
+    block_entities::BlockEntityKind,
+    block_entity::BlockEntity,
+    chunk::{section::Palette, Chunk},
+    components::{text::plain_text, ComponentType},
+    entity::Entity,
+    item::ItemWithNoSlot,
+    player::Player,
+    resource_location::{parse_or_raw, ResourceLocation},
+    walk::{FoundItem, WalkError},
+};
+
+/// A condition an item has to match.
+#[derive(Debug, Clone)]
+pub enum ItemCondition {
+    /// The item has this id.
+    Id(ResourceLocation),
+
+    /// The stack has at least this many items.
+    CountAtLeast(i32),
+
+    /// The item has the enchantment at this level or higher, applied or stored in an enchanted book.
+    Enchantment { id: String, min_level: i32 },
+
+    /// The custom name contains this text.
+    /// Only the text of the name is matched, not the JSON keys or formatting around it.
+    CustomNameContains(String),
+
+    /// The item has the component set.
+    HasComponent(ComponentType),
+
+    /// The function returns true for the item.
+    Custom(fn(&ItemWithNoSlot) -> bool),
+}
+
+/// Finds items that match every condition.
+#[derive(Debug, Clone, Default)]
+pub struct ItemQuery {
+    pub conditions: Vec<ItemCondition>,
+}
+
+impl ItemQuery {
+    /// A query without conditions, which matches every item.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a condition to the query.
+    pub fn condition(mut self, condition: ItemCondition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Items with this id, the `minecraft:` namespace is optional.
+    pub fn id(self, id: &str) -> Self {
+        self.condition(ItemCondition::Id(parse_or_raw(id)))
+    }
+
+    /// Stacks with at least this many items.
+    pub fn count_at_least(self, count: i32) -> Self {
+        self.condition(ItemCondition::CountAtLeast(count))
+    }
+
+    /// Items with the enchantment at this level or higher.
+    pub fn enchantment(self, id: impl Into<String>, min_level: i32) -> Self {
+        self.condition(ItemCondition::Enchantment {
+            id: id.into(),
+            min_level,
+        })
+    }
+
+    /// Items with a custom name containing this text.
+    pub fn custom_name_contains(self, text: impl Into<String>) -> Self {
+        self.condition(ItemCondition::CustomNameContains(text.into()))
+    }
+
+    /// Items that have the component set.
+    pub fn has_component(self, component_type: ComponentType) -> Self {
+        self.condition(ItemCondition::HasComponent(component_type))
+    }
+
+    /// Items the function returns true for.
+    pub fn filter(self, filter: fn(&ItemWithNoSlot) -> bool) -> Self {
+        self.condition(ItemCondition::Custom(filter))
+    }
+
+    /// Whether the item matches every condition.
+    pub fn matches(&self, item: &ItemWithNoSlot) -> bool {
+        self.conditions
+            .iter()
+            .all(|condition| item_matches(condition, item))
+    }
+
+    /// Matching items in a chunk's block entities and entities, including nested items.
+    /// Fails with the first entity item that couldn't be parsed.
+    pub fn in_chunk(&self, chunk: &Chunk) -> Result<Vec<FoundItem>, WalkError> {
+        self.filter_found(chunk.walk_items())
+    }
+
+    /// Matching items in a player's inventory, ender chest and equipment, including nested items.
+    /// Fails with the first entity item that couldn't be parsed.
+    pub fn in_player(&self, player: &Player) -> Result<Vec<FoundItem>, WalkError> {
+        self.filter_found(player.walk_items())
+    }
+
+    /// Matching items in a block entity, including nested items.
+    /// Entity items are parsed with `data_version`, see [`BlockEntity::walk_items`].
+    /// Fails with the first entity item that couldn't be parsed.
+    pub fn in_block_entity(
+        &self,
+        block_entity: &BlockEntity,
+        data_version: i32,
+    ) -> Result<Vec<FoundItem>, WalkError> {
+        self.filter_found(block_entity.walk_items(data_version))
+    }
+
+    /// Matching items held by an entity, including nested items.
+    /// Entity items are parsed with `data_version`, see [`Entity::walk_items`].
+    /// Fails with the first entity item that couldn't be parsed.
+    pub fn in_entity(
+        &self,
+        entity: &Entity,
+        data_version: i32,
+    ) -> Result<Vec<FoundItem>, WalkError> {
+        self.filter_found(entity.walk_items(data_version))
+    }
+
+    fn filter_found(
+        &self,
+        walked: impl Iterator<Item = Result<FoundItem, WalkError>>,
+    ) -> Result<Vec<FoundItem>, WalkError> {
+        walked
+            .filter(|found| {
+                found
+                    .as_ref()
+                    .map_or(true, |found| self.matches(&found.item))
+            })
+            .collect()
+    }
+}
+
+fn item_matches(condition: &ItemCondition, item: &ItemWithNoSlot) -> bool {
+    match condition {
+        ItemCondition::Id(id) => item.id == *id,
+        ItemCondition::CountAtLeast(count) => item.count >= *count,
+        ItemCondition::Enchantment { id, min_level } => {
+            let components = match &item.components {
+                Some(components) => components,
+                None => return false,
+            };
+
+            [components.enchantments(), components.stored_enchantments()]
+                .into_iter()
+                .flatten()
+                .any(|enchantments| {
+                    enchantments
+                        .level(id)
+                        .is_some_and(|level| level >= *min_level)
+                })
+        }
+        ItemCondition::CustomNameContains(text) => item
+            .components
+            .as_ref()
+            .and_then(|components| components.custom_name())
+            .is_some_and(|name| plain_text(name).contains(text.as_str())),
+        ItemCondition::HasComponent(component_type) => item
+            .components
+            .as_ref()
+            .is_some_and(|components| components.contains_type(*component_type)),
+        ItemCondition::Custom(filter) => filter(item),
+    }
+}
+
+/// A condition a block entity has to match.
+#[derive(Debug, Clone)]
+pub enum BlockEntityCondition {
+    /// The block entity has this id.
+    Id(ResourceLocation),
+
+    /// The spawner spawns this entity, either next or as one of its potential spawns.
+    Spawns(String),
+
+    /// The function returns true for the block entity.
+    Custom(fn(&BlockEntity) -> bool),
+}
+
+/// Finds block entities that match every condition.
+#[derive(Debug, Clone, Default)]
+pub struct BlockEntityQuery {
+    pub conditions: Vec<BlockEntityCondition>,
+}
+
+impl BlockEntityQuery {
+    /// A query without conditions, which matches every block entity.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a condition to the query.
+    pub fn condition(mut self, condition: BlockEntityCondition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Block entities with this id, the `minecraft:` namespace is optional.
+    pub fn id(self, id: &str) -> Self {
+        self.condition(BlockEntityCondition::Id(parse_or_raw(id)))
+    }
+
+    /// Spawners that spawn this entity.
+    pub fn spawns(self, entity: impl Into<String>) -> Self {
+        self.condition(BlockEntityCondition::Spawns(entity.into()))
+    }
+
+    /// Block entities the function returns true for.
+    pub fn filter(self, filter: fn(&BlockEntity) -> bool) -> Self {
+        self.condition(BlockEntityCondition::Custom(filter))
+    }
+
+    /// Whether the block entity matches every condition.
+    pub fn matches(&self, block_entity: &BlockEntity) -> bool {
+        self.conditions
+            .iter()
+            .all(|condition| block_entity_matches(condition, block_entity))
+    }
+
+    /// Matching block entities in a chunk, their position is in [`BlockEntity::base`].
+    pub fn in_chunk<'a>(&self, chunk: &'a Chunk) -> Vec<&'a BlockEntity> {
+        chunk
+            .block_entities
+            .iter()
+            .filter(|block_entity| self.matches(block_entity))
+            .collect()
+    }
+}
+
+fn block_entity_matches(condition: &BlockEntityCondition, block_entity: &BlockEntity) -> bool {
+    match condition {
+        BlockEntityCondition::Id(id) => block_entity.base.id == *id,
+        BlockEntityCondition::Spawns(entity) => {
+            let spawner = match &block_entity.kind {
+                BlockEntityKind::MobSpawner(spawner) => spawner,
+                _ => return false,
+            };
+
+            std::iter::once(&spawner.spawn_data)
+                .chain(
+                    spawner
+                        .spawn_potentials
+                        .iter()
+                        .flatten()
+                        .map(|potential| &potential.data),
+                )
+                .filter_map(|spawn_data| spawn_data.entity.id.as_deref())
+                .any(|id| same_id(id, entity))
+        }
+        BlockEntityCondition::Custom(filter) => filter(block_entity),
+    }
+}
+
+/// Finds blocks by their state in the chunk palette.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockQuery {
+    /// The block name, the `minecraft:` namespace is optional.
+    pub name: String,
+
+    /// Block state properties that have to match, others can be anything.
+    pub properties: Vec<(String, String)>,
+}
+
+/// A block found by a [`BlockQuery`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FoundBlock<'a> {
+    /// World coordinates of the block, `[x, y, z]`.
+    pub pos: [i32; 3],
+
+    /// The block state.
+    pub state: &'a Palette,
+}
+
+impl BlockQuery {
+    /// Finds every state of the block.
+    pub fn new(name: impl Into<String>) -> Self {
+        BlockQuery {
+            name: name.into(),
+            properties: vec![],
+        }
+    }
+
+    /// Only finds states where the property has this value.
+    pub fn property(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.properties.push((key.into(), value.into()));
+        self
+    }
+
+    /// Whether the block state matches.
+    pub fn matches(&self, state: &Palette) -> bool {
+        state.name == self.name
+            && self
+                .properties
+                .iter()
+                .all(|(key, value)| state.properties.get(key) == Some(value))
+    }
+
+    /// Matching blocks in a chunk.
+    /// Sections are only unpacked if their palette has a matching state.
+    pub fn in_chunk<'a>(&self, chunk: &'a Chunk) -> Vec<FoundBlock<'a>> {
+        let mut found = vec![];
+
+        for section in &chunk.sections {
+            let block_states = match &section.block_states {
+                Some(block_states) => block_states,
+                None => continue,
+            };
+            let matching: Vec<bool> = block_states
+                .palette
+                .iter()
+                .map(|state| self.matches(state))
+                .collect();
+            if !matching.contains(&true) {
+                continue;
+            }
+
+            for (index, state) in block_states.indices().into_iter().enumerate() {
+                if !matching.get(state as usize).copied().unwrap_or(false) {
+                    continue;
+                }
+
+                // The index of a block is `(y * 16 + z) * 16 + x`.
+                let index = index as i32;
+                found.push(FoundBlock {
+                    pos: [
+                        chunk.x_pos * 16 + index % 16,
+                        section.y as i32 * 16 + index / 256,
+                        chunk.z_pos * 16 + (index / 16) % 16,
+                    ],
+                    state: &block_states.palette[state as usize],
+                });
+            }
+        }
+
+        found
+    }
+}
+
+/// Compares ids, where either can leave out the `minecraft:` namespace.
+fn same_id(id: &str, other: &str) -> bool {
+    id.strip_prefix("minecraft:").unwrap_or(id) == other.strip_prefix("minecraft:").unwrap_or(other)
+}
+
+#[cfg(test)]
+#[test]
+fn item_query() {
+    use crate::components::{enchantments::Enchantments, Component, Components};
+    use std::collections::HashMap;
+
+    let mut components = Components::default();
+    components
+        .insert(Component::Enchantments(Enchantments {
+            levels: HashMap::from([("minecraft:sharpness".parse().unwrap(), 6)]),
+            show_in_tooltip: true,
+        }))
+        .unwrap();
+    components
+        .insert(Component::CustomName(r#"{"text":"Duped Sword"}"#.into()))
+        .unwrap();
+    let sword = ItemWithNoSlot {
+        id: "minecraft:diamond_sword".parse().unwrap(),
+        count: 1,
+        components: Some(components),
+        extra: simdnbt::owned::NbtCompound::new(),
+    };
+
+    assert!(ItemQuery::new()
+        .id("diamond_sword")
+        .enchantment("sharpness", 6)
+        .custom_name_contains("Duped")
+        .matches(&sword));
+    // The JSON around the name isn't matched.
+    assert!(!ItemQuery::new()
+        .custom_name_contains("text")
+        .matches(&sword));
+    assert!(!ItemQuery::new()
+        .custom_name_contains("\"Duped")
+        .matches(&sword));
+    assert!(!ItemQuery::new()
+        .enchantment("minecraft:sharpness", 7)
+        .matches(&sword));
+    assert!(!ItemQuery::new().count_at_least(2).matches(&sword));
+    assert!(ItemQuery::new()
+        .has_component(ComponentType::CustomName)
+        .filter(|item| item.id.path().ends_with("_sword"))
+        .matches(&sword));
+}
@@ -30,3 +30,15 @@ impl FromStr for Rarity {
         }
     }
 }
+
+impl Rarity {
+    /// The name of the rarity, as it's written in nbt.
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Rarity::Common => "common",
+            Rarity::Uncommon => "uncommon",
+            Rarity::Rare => "rare",
+            Rarity::Epic => "epic",
+        }
+    }
+}
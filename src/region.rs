@@ -0,0 +1,377 @@
+//! Region files (`.mca`), each holding up to 32x32 chunks.
+//! [Minecraft Wiki](https://minecraft.wiki/w/Region_file_format)
+
+use crate::{
+    chunk::{Chunk, ChunkParseOptions},
+    error::SculkParseError,
+    traits::FromCompoundNbt,
+    util::{from_owned_compound, write_root_compound},
+};
+use simdnbt::owned::NbtCompound;
+use std::{
+    io::{Cursor, Read, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Size of a sector in a region file, in bytes.
+const SECTOR_SIZE: usize = 4096;
+
+/// Amount of chunks in a region file.
+pub const REGION_CHUNKS: usize = 1024;
+
+/// A region file.
+/// Chunks are kept compressed as they're stored in the file, and are only decompressed and parsed when asked for,
+/// so a broken chunk only fails on its own.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Region {
+    /// X position of the region, in regions.
+    pub x: i32,
+
+    /// Z position of the region, in regions.
+    pub z: i32,
+
+    /// All 1024 chunk slots of the region, indexed by `x + z * 32` in region relative chunk coordinates.
+    pub chunks: Vec<Option<RegionChunk>>,
+}
+
+/// A chunk stored in a region file.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegionChunk {
+    /// Last time the chunk was modified, in seconds since the unix epoch.
+    pub timestamp: u32,
+
+    /// The compression used when the chunk was read, and used again when it's written.
+    pub compression: ChunkCompression,
+
+    /// The chunk data as stored in the region file, compressed with `compression`.  
+    /// Use [`RegionChunk::decompress`] to get the nbt bytes.
+    pub data: Vec<u8>,
+}
+
+/// Compression schemes a chunk can be stored with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChunkCompression {
+    /// GZip (RFC1952), unused in practice.
+    GZip,
+    /// Zlib (RFC1950), the default.
+    #[default]
+    Zlib,
+    /// Uncompressed, since 1.15.1.
+    Uncompressed,
+    /// Any other id, like LZ4 (4), custom compressions (127) and chunks stored in external `.mcc` files (128+).  
+    /// These chunks are written back unchanged, but fail to decompress.
+    /// Chunks whose data lies outside of the region file also end up here, with id 0.
+    Unsupported(u8),
+}
+
+impl ChunkCompression {
+    /// The id stored in front of the chunk data.
+    pub fn id(&self) -> u8 {
+        match self {
+            ChunkCompression::GZip => 1,
+            ChunkCompression::Zlib => 2,
+            ChunkCompression::Uncompressed => 3,
+            ChunkCompression::Unsupported(id) => *id,
+        }
+    }
+}
+
+impl From<u8> for ChunkCompression {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => ChunkCompression::GZip,
+            2 => ChunkCompression::Zlib,
+            3 => ChunkCompression::Uncompressed,
+            _ => ChunkCompression::Unsupported(value),
+        }
+    }
+}
+
+impl RegionChunk {
+    /// Creates a zlib compressed chunk from uncompressed nbt bytes, with the current time as timestamp.
+    pub fn new(data: Vec<u8>) -> Self {
+        RegionChunk::with_compression(ChunkCompression::Zlib, &data)
+    }
+
+    /// Creates a chunk from uncompressed nbt bytes, compressed with the given compression.
+    /// Unsupported compressions fall back to zlib.
+    pub fn with_compression(compression: ChunkCompression, data: &[u8]) -> Self {
+        let compression = match compression {
+            ChunkCompression::Unsupported(_) => ChunkCompression::Zlib,
+            compression => compression,
+        };
+
+        RegionChunk {
+            timestamp: now(),
+            compression,
+            data: compress(compression, data),
+        }
+    }
+
+    /// Decompresses the chunk data into nbt bytes.
+    pub fn decompress(&self) -> Result<Vec<u8>, SculkParseError> {
+        let mut data = Vec::new();
+
+        match self.compression {
+            ChunkCompression::GZip => {
+                flate2::read::GzDecoder::new(self.data.as_slice()).read_to_end(&mut data)?;
+            }
+            ChunkCompression::Zlib => {
+                flate2::read::ZlibDecoder::new(self.data.as_slice()).read_to_end(&mut data)?;
+            }
+            ChunkCompression::Uncompressed => data.extend_from_slice(&self.data),
+            ChunkCompression::Unsupported(id) => {
+                return Err(SculkParseError::UnsupportedCompression(id))
+            }
+        };
+
+        Ok(data)
+    }
+
+    /// Parses the chunk data as a [`Chunk`].
+    /// Chunks in `entities` and `poi` region files have a different layout, use [`RegionChunk::nbt`] for those.
+    pub fn chunk(&self) -> Result<Chunk, SculkParseError> {
+        Chunk::from_bytes(&self.decompress()?)
+    }
+
+    /// Parses the chunk data as a [`Chunk`], only decoding the fields selected in the options.
+    pub fn chunk_with_options(
+        &self,
+        options: &ChunkParseOptions,
+    ) -> Result<Chunk, SculkParseError> {
+        Chunk::from_bytes_with_options(&self.decompress()?, options)
+    }
+
+    /// Parses the chunk data as an owned compound.
+    pub fn nbt(&self) -> Result<NbtCompound, SculkParseError> {
+        let data = self.decompress()?;
+        let nbt = simdnbt::borrow::read(&mut Cursor::new(data.as_slice()))?;
+
+        match nbt {
+            simdnbt::borrow::Nbt::Some(nbt) => Ok(nbt.as_compound().to_owned()),
+            simdnbt::borrow::Nbt::None => Err(SculkParseError::NoNbt),
+        }
+    }
+
+    /// Parses the chunk data with any [`FromCompoundNbt`] type.
+    pub fn parse<T: FromCompoundNbt>(&self) -> Result<T, SculkParseError> {
+        from_owned_compound(&self.nbt()?, T::from_compound_nbt)
+    }
+}
+
+impl Region {
+    /// Creates an empty region.
+    pub fn new(x: i32, z: i32) -> Self {
+        Region {
+            x,
+            z,
+            chunks: vec![None; REGION_CHUNKS],
+        }
+    }
+
+    /// The file name of the region at the given region coordinates, `r.x.z.mca`.
+    pub fn file_name(x: i32, z: i32) -> String {
+        format!("r.{x}.{z}.mca")
+    }
+
+    /// Reads a region from the bytes of a `.mca` file.
+    /// Empty files are valid and give an empty region.
+    pub fn from_bytes(x: i32, z: i32, bytes: &[u8]) -> Result<Self, SculkParseError> {
+        let mut region = Region::new(x, z);
+        if bytes.is_empty() {
+            return Ok(region);
+        }
+        if bytes.len() < SECTOR_SIZE * 2 {
+            return Err(SculkParseError::InvalidField("region header".into()));
+        }
+
+        for index in 0..REGION_CHUNKS {
+            let location = read_u32(bytes, index * 4);
+            let offset = (location >> 8) as usize * SECTOR_SIZE;
+            if location == 0 {
+                continue;
+            }
+
+            let timestamp = read_u32(bytes, SECTOR_SIZE + index * 4);
+
+            // Broken chunks are kept with whatever data is there, they fail once they're decompressed.
+            let (compression, data) = match bytes.get(offset..offset + 5) {
+                Some(header) => {
+                    let length = read_u32(header, 0) as usize;
+                    let end = (offset + 4 + length).clamp(offset + 5, bytes.len());
+                    (ChunkCompression::from(header[4]), &bytes[offset + 5..end])
+                }
+                None => (ChunkCompression::Unsupported(0), &[][..]),
+            };
+
+            region.chunks[index] = Some(RegionChunk {
+                timestamp,
+                compression,
+                data: data.to_vec(),
+            });
+        }
+
+        Ok(region)
+    }
+
+    /// Writes the region to the bytes of a `.mca` file.
+    /// Errors if a chunk is too large to fit in a region file, which the game would move to an external `.mcc` file.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SculkParseError> {
+        let mut bytes = vec![0u8; SECTOR_SIZE * 2];
+
+        for (index, chunk) in self.chunks.iter().enumerate() {
+            let chunk = match chunk {
+                Some(chunk) => chunk,
+                None => continue,
+            };
+
+            let data = &chunk.data;
+            let sector = bytes.len() / SECTOR_SIZE;
+            let sectors = (data.len() + 5).div_ceil(SECTOR_SIZE);
+            if sectors > u8::MAX as usize {
+                return Err(SculkParseError::InvalidField(format!(
+                    "chunk {index} is too large for a region file"
+                )));
+            }
+
+            bytes[index * 4..index * 4 + 4]
+                .copy_from_slice(&((sector as u32) << 8 | sectors as u32).to_be_bytes());
+            bytes[SECTOR_SIZE + index * 4..SECTOR_SIZE + index * 4 + 4]
+                .copy_from_slice(&chunk.timestamp.to_be_bytes());
+
+            bytes.extend_from_slice(&(data.len() as u32 + 1).to_be_bytes());
+            bytes.push(chunk.compression.id());
+            bytes.extend_from_slice(data);
+            bytes.resize((sector + sectors) * SECTOR_SIZE, 0);
+        }
+
+        Ok(bytes)
+    }
+
+    /// Index of a chunk in [`Region::chunks`], the chunk coordinates can be absolute or region relative.
+    pub fn chunk_index(x: i32, z: i32) -> usize {
+        ((x & 31) + (z & 31) * 32) as usize
+    }
+
+    /// Gets the chunk at the given chunk coordinates, absolute or region relative.
+    pub fn get(&self, x: i32, z: i32) -> Option<&RegionChunk> {
+        self.chunks[Self::chunk_index(x, z)].as_ref()
+    }
+
+    /// Parses the chunk at the given chunk coordinates, absolute or region relative.
+    pub fn chunk(&self, x: i32, z: i32) -> Result<Option<Chunk>, SculkParseError> {
+        self.get(x, z).map(|chunk| chunk.chunk()).transpose()
+    }
+
+    /// Gets the raw nbt of the chunk at the given chunk coordinates, absolute or region relative.
+    pub fn chunk_nbt(&self, x: i32, z: i32) -> Result<Option<NbtCompound>, SculkParseError> {
+        self.get(x, z).map(|chunk| chunk.nbt()).transpose()
+    }
+
+    /// Sets the chunk at the given chunk coordinates, keeping its compression and updating its timestamp.
+    pub fn set_chunk_nbt(&mut self, x: i32, z: i32, nbt: &NbtCompound) {
+        let data = write_root_compound(nbt);
+        let slot = &mut self.chunks[Self::chunk_index(x, z)];
+        let compression = slot
+            .as_ref()
+            .map(|chunk| chunk.compression)
+            .unwrap_or_default();
+
+        *slot = Some(RegionChunk::with_compression(compression, &data));
+    }
+
+    /// Removes the chunk at the given chunk coordinates, returning it if there was one.
+    pub fn remove_chunk(&mut self, x: i32, z: i32) -> Option<RegionChunk> {
+        self.chunks[Self::chunk_index(x, z)].take()
+    }
+
+    /// Iterates over all chunks in the region, parsed as [`Chunk`].
+    pub fn chunks(&self) -> impl Iterator<Item = Result<Chunk, SculkParseError>> + '_ {
+        self.chunks.iter().flatten().map(|chunk| chunk.chunk())
+    }
+
+    /// Iterates over all chunks in the region, only decoding the fields selected in the options.
+    /// Useful for region-wide scans that don't need every chunk section.
+    pub fn chunks_with_options<'a>(
+        &'a self,
+        options: &'a ChunkParseOptions,
+    ) -> impl Iterator<Item = Result<Chunk, SculkParseError>> + 'a {
+        self.chunks
+            .iter()
+            .flatten()
+            .map(move |chunk| chunk.chunk_with_options(options))
+    }
+
+    /// Parses all chunks in the region across threads.
+    /// Each chunk gets its own result, so one bad chunk doesn't stop the rest.
+    #[cfg(feature = "parallel")]
+    pub fn par_chunks(
+        &self,
+    ) -> impl rayon::iter::ParallelIterator<Item = Result<Chunk, SculkParseError>> + '_ {
+        use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+        self.chunks.par_iter().flatten().map(|chunk| chunk.chunk())
+    }
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
+
+fn compress(compression: ChunkCompression, data: &[u8]) -> Vec<u8> {
+    // Writing into a Vec can't fail.
+    match compression {
+        ChunkCompression::GZip => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(data).unwrap();
+            encoder.finish().unwrap()
+        }
+        ChunkCompression::Zlib => {
+            let mut encoder =
+                flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(data).unwrap();
+            encoder.finish().unwrap()
+        }
+        ChunkCompression::Uncompressed | ChunkCompression::Unsupported(_) => data.to_vec(),
+    }
+}
+
+fn now() -> u32 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as u32)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+#[test]
+fn broken_chunks() {
+    let mut region = Region::new(0, 0);
+    region.set_chunk_nbt(0, 0, &NbtCompound::new());
+    region.chunks[Region::chunk_index(1, 0)] = Some(RegionChunk {
+        timestamp: 0,
+        compression: ChunkCompression::Unsupported(0x82),
+        data: vec![],
+    });
+    let bytes = region.to_bytes().unwrap();
+
+    // External chunks are kept as they are.
+    let read = Region::from_bytes(0, 0, &bytes).unwrap();
+    assert_eq!(read, region);
+    assert!(read.chunk_nbt(1, 0).is_err());
+    assert_eq!(read.to_bytes().unwrap(), bytes);
+
+    // A chunk cut off from the end of the file only fails on its own.
+    let read = Region::from_bytes(0, 0, &bytes[..bytes.len() - SECTOR_SIZE]).unwrap();
+    assert_eq!(read.chunk_nbt(0, 0).unwrap(), Some(NbtCompound::new()));
+    assert!(read.chunk_nbt(1, 0).is_err());
+}
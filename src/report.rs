@@ -0,0 +1,353 @@
+//! Item totals of players and regions, and the difference between two snapshots of a world.
+//!
+//! Totals include the items nested in containers, like the contents of a shulker box in a chest,
+//! so items moved between containers don't show up as a difference.
+
+use crate::{
+    block_entity::BlockEntity,
+    chunk::Chunk,
+    item::ItemWithNoSlot,
+    player::Player,
+    resource_location::{parse_or_raw, ResourceLocation},
+    walk::{FoundItem, WalkError},
+};
+use std::collections::HashMap;
+
+/// Total item counts by item id.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ItemTotals(pub HashMap<ResourceLocation, i64>);
+
+impl ItemTotals {
+    /// Totals without any items.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Totals of every item in the player's inventory, ender chest and equipment.
+    /// Fails with the first entity item that couldn't be parsed.
+    pub fn from_player(player: &Player) -> Result<Self, WalkError> {
+        Self::from_walk(player.walk_items())
+    }
+
+    /// Totals of every item in the chunk's block entities and entities.
+    /// Fails with the first entity item that couldn't be parsed.
+    pub fn from_chunk(chunk: &Chunk) -> Result<Self, WalkError> {
+        Self::from_walk(chunk.walk_items())
+    }
+
+    /// Totals of every item in the block entity.
+    /// Entity items are parsed with `data_version`, see [`BlockEntity::walk_items`].
+    /// Fails with the first entity item that couldn't be parsed.
+    pub fn from_block_entity(
+        block_entity: &BlockEntity,
+        data_version: i32,
+    ) -> Result<Self, WalkError> {
+        Self::from_walk(block_entity.walk_items(data_version))
+    }
+
+    /// Totals of the items found by `walk_items()`, see [`crate::walk`].
+    fn from_walk(
+        walked: impl Iterator<Item = Result<FoundItem, WalkError>>,
+    ) -> Result<Self, WalkError> {
+        let mut totals = Self::new();
+        for found in walked {
+            totals.add_item(&found?.item);
+        }
+        Ok(totals)
+    }
+
+    /// Adds `count` of the item, the `minecraft:` namespace is optional.
+    pub fn add(&mut self, id: &str, count: i64) {
+        self.add_id(parse_or_raw(id), count);
+    }
+
+    /// Adds the stack, without the items nested in it.
+    pub fn add_item(&mut self, item: &ItemWithNoSlot) {
+        self.add_id(item.id.clone(), item.count as i64);
+    }
+
+    fn add_id(&mut self, id: ResourceLocation, count: i64) {
+        *self.0.entry(id).or_insert(0) += count;
+    }
+
+    /// Adds the totals of `other`.
+    pub fn merge(&mut self, other: &ItemTotals) {
+        for (id, count) in &other.0 {
+            self.add_id(id.clone(), *count);
+        }
+    }
+
+    /// The total count of an item, 0 if there is none. The `minecraft:` namespace is optional.
+    pub fn get(&self, id: &str) -> i64 {
+        self.count(&parse_or_raw(id))
+    }
+
+    fn count(&self, id: &ResourceLocation) -> i64 {
+        self.0.get(id).copied().unwrap_or(0)
+    }
+
+    /// How the totals changed from `self` to `newer`.
+    pub fn diff(&self, newer: &ItemTotals) -> ItemDiff {
+        let mut changes = HashMap::new();
+
+        for id in self.0.keys().chain(newer.0.keys()) {
+            let change = newer.count(id) - self.count(id);
+            if change != 0 {
+                changes.insert(id.clone(), change);
+            }
+        }
+
+        ItemDiff(changes)
+    }
+}
+
+/// Change in item counts by item id, only holding items whose count changed.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ItemDiff(pub HashMap<ResourceLocation, i64>);
+
+impl ItemDiff {
+    /// Whether no counts changed.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Items that there are more of, with how many more.
+    pub fn appeared(&self) -> impl Iterator<Item = (&ResourceLocation, i64)> {
+        self.0
+            .iter()
+            .filter(|(_, change)| **change > 0)
+            .map(|(id, change)| (id, *change))
+    }
+
+    /// Items that there are fewer of, with how many fewer.
+    pub fn disappeared(&self) -> impl Iterator<Item = (&ResourceLocation, i64)> {
+        self.0
+            .iter()
+            .filter(|(_, change)| **change < 0)
+            .map(|(id, change)| (id, -change))
+    }
+}
+
+/// Item totals of a world, per player and per region.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InventoryReport {
+    /// Totals per player, keyed by what the player was added with, like the UUID from the playerdata file name.
+    pub players: HashMap<String, ItemTotals>,
+
+    /// Totals per region, keyed by region coordinates `[x, z]`.
+    pub regions: HashMap<[i32; 2], ItemTotals>,
+}
+
+impl InventoryReport {
+    /// A report without any items.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the items of a player.
+    /// Nothing is added if an entity item of the player couldn't be parsed.
+    pub fn add_player(&mut self, key: impl Into<String>, player: &Player) -> Result<(), WalkError> {
+        let totals = ItemTotals::from_player(player)?;
+        self.players.entry(key.into()).or_default().merge(&totals);
+        Ok(())
+    }
+
+    /// Adds the items of a chunk to its region.
+    /// Nothing is added if an entity item in the chunk couldn't be parsed.
+    pub fn add_chunk(&mut self, chunk: &Chunk) -> Result<(), WalkError> {
+        let totals = ItemTotals::from_chunk(chunk)?;
+        self.regions
+            .entry([chunk.x_pos >> 5, chunk.z_pos >> 5])
+            .or_default()
+            .merge(&totals);
+        Ok(())
+    }
+
+    /// Totals of every player and region together.
+    pub fn total(&self) -> ItemTotals {
+        let mut total = ItemTotals::new();
+        for totals in self.players.values().chain(self.regions.values()) {
+            total.merge(totals);
+        }
+        total
+    }
+
+    /// How the totals changed from `self` to `newer`, per player and per region.
+    /// Players and regions that are only in one of the reports are compared to having no items.
+    pub fn diff(&self, newer: &InventoryReport) -> ReportDiff {
+        ReportDiff {
+            players: diff_keyed(&self.players, &newer.players),
+            regions: diff_keyed(&self.regions, &newer.regions),
+        }
+    }
+}
+
+/// How the totals changed between two [`InventoryReport`]s.
+/// Only players and regions with changes are included.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReportDiff {
+    /// Changes per player.
+    pub players: HashMap<String, ItemDiff>,
+
+    /// Changes per region, keyed by region coordinates `[x, z]`.
+    pub regions: HashMap<[i32; 2], ItemDiff>,
+}
+
+impl ReportDiff {
+    /// The change over the whole world, items moved between players and regions cancel out.
+    pub fn total(&self) -> ItemDiff {
+        let mut total = ItemTotals::new();
+        for diff in self.players.values().chain(self.regions.values()) {
+            for (id, change) in &diff.0 {
+                total.add_id(id.clone(), *change);
+            }
+        }
+        total.0.retain(|_, change| *change != 0);
+
+        ItemDiff(total.0)
+    }
+}
+
+fn diff_keyed<K: Clone + Eq + std::hash::Hash>(
+    older: &HashMap<K, ItemTotals>,
+    newer: &HashMap<K, ItemTotals>,
+) -> HashMap<K, ItemDiff> {
+    let empty = ItemTotals::new();
+    let mut diffs = HashMap::new();
+
+    for key in older.keys().chain(newer.keys()) {
+        let diff = older
+            .get(key)
+            .unwrap_or(&empty)
+            .diff(newer.get(key).unwrap_or(&empty));
+        if !diff.is_empty() {
+            diffs.insert(key.clone(), diff);
+        }
+    }
+
+    diffs
+}
+
+#[cfg(test)]
+#[test]
+fn item_totals_diff() {
+    let mut before = ItemTotals::new();
+    before.add("minecraft:diamond", 64);
+    before.add("minecraft:shulker_box", 1);
+
+    let mut after = before.clone();
+    // The namespace is optional, both are the same item.
+    after.add("diamond", 64);
+    after.add("minecraft:shulker_box", -1);
+    after.add("minecraft:elytra", 1);
+    assert_eq!(after.get("minecraft:diamond"), 128);
+    assert_eq!(after.0.len(), 3);
+
+    let diff = before.diff(&after);
+    let mut appeared: Vec<_> = diff
+        .appeared()
+        .map(|(id, change)| (id.to_string(), change))
+        .collect();
+    appeared.sort();
+    assert_eq!(
+        appeared,
+        vec![
+            ("minecraft:diamond".to_string(), 64),
+            ("minecraft:elytra".to_string(), 1)
+        ]
+    );
+    let disappeared: Vec<_> = diff.disappeared().collect();
+    assert_eq!(disappeared.len(), 1);
+    assert_eq!(*disappeared[0].0, "shulker_box");
+    assert_eq!(disappeared[0].1, 1);
+
+    let mut older = InventoryReport::new();
+    older.players.insert("steve".into(), before.clone());
+    let mut newer = InventoryReport::new();
+    newer.players.insert("steve".into(), before.clone());
+    newer.regions.insert([0, 0], after);
+
+    let report_diff = older.diff(&newer);
+    assert!(!report_diff.players.contains_key("steve"));
+    let diamond = "minecraft:diamond".parse().unwrap();
+    assert_eq!(report_diff.regions[&[0, 0]].0[&diamond], 128);
+    assert_eq!(report_diff.total().0.len(), 2);
+}
+
+#[cfg(test)]
+#[test]
+fn item_totals_nested() {
+    use crate::{item::Item, traits::FromCompoundNbt, util::from_owned_compound, version::V1_21};
+    use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+
+    // A shulker box in slot 4 holding a diamond.
+    let mut diamond = NbtCompound::new();
+    diamond.insert("id", "minecraft:diamond");
+    diamond.insert("count", 1);
+    let mut slot = NbtCompound::new();
+    slot.insert("slot", 12);
+    slot.insert("item", NbtTag::Compound(diamond));
+    let mut components = NbtCompound::new();
+    components.insert(
+        "minecraft:container",
+        NbtTag::List(NbtList::Compound(vec![slot])),
+    );
+    let mut shulker_box = NbtCompound::new();
+    shulker_box.insert("id", "minecraft:shulker_box");
+    shulker_box.insert("count", 1);
+    shulker_box.insert("Slot", NbtTag::Byte(4));
+    shulker_box.insert("components", NbtTag::Compound(components));
+
+    let mut chest = NbtCompound::new();
+    chest.insert("id", "minecraft:chest");
+    chest.insert("x", 0);
+    chest.insert("y", 64);
+    chest.insert("z", 0);
+    chest.insert(
+        "Items",
+        NbtTag::List(NbtList::Compound(vec![shulker_box.clone()])),
+    );
+
+    let mut heightmaps = NbtCompound::new();
+    heightmaps.insert("WORLD_SURFACE", NbtTag::LongArray(vec![0; 37]));
+    let mut chunk = NbtCompound::new();
+    chunk.insert("DataVersion", V1_21);
+    chunk.insert("xPos", 0);
+    chunk.insert("zPos", 0);
+    chunk.insert("yPos", -4);
+    chunk.insert("Status", "minecraft:full");
+    chunk.insert("LastUpdate", 0i64);
+    chunk.insert("InhabitedTime", 0i64);
+    chunk.insert("Heightmaps", heightmaps);
+    chunk.insert("block_entities", NbtList::Compound(vec![chest]));
+
+    let chunk = from_owned_compound(&chunk, Chunk::from_compound_nbt).unwrap();
+    let totals = ItemTotals::from_chunk(&chunk).unwrap();
+    assert_eq!(totals.get("minecraft:shulker_box"), 1);
+    assert_eq!(totals.get("minecraft:diamond"), 1);
+    assert_eq!(totals.0.len(), 2);
+
+    // The same shulker box added to a player's inventory.
+    let bytes = crate::util::maybe_gunzip(include_bytes!("../test_data/player_data.dat")).unwrap();
+    let nbt = simdnbt::borrow::read(&mut std::io::Cursor::new(&bytes[..]))
+        .unwrap()
+        .unwrap();
+    let mut player = Player::from_compound_nbt(&nbt.as_compound()).unwrap();
+    let before = ItemTotals::from_player(&player).unwrap();
+    player
+        .inventory
+        .push(from_owned_compound(&shulker_box, Item::from_compound_nbt).unwrap());
+    let after = ItemTotals::from_player(&player).unwrap();
+    assert_eq!(
+        after.get("minecraft:shulker_box"),
+        before.get("minecraft:shulker_box") + 1
+    );
+    assert_eq!(
+        after.get("minecraft:diamond"),
+        before.get("minecraft:diamond") + 1
+    );
+}
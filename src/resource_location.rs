@@ -0,0 +1,373 @@
+//! Resource locations, the namespaced ids of blocks, items, entities, dimensions and everything else in the game.
+//!
+//! ```rust
+//! use sculk::resource_location::ResourceLocation;
+//!
+//! let stone: ResourceLocation = "stone".parse().unwrap();
+//! assert_eq!(stone.to_string(), "minecraft:stone");
+//! assert_eq!(stone, "minecraft:stone");
+//! assert_eq!(stone, "stone");
+//! ```
+
+use crate::error::{ParseMode, ParseWarning, SculkParseError, Substitute};
+use std::{cell::RefCell, collections::HashSet, fmt, str::FromStr, sync::Arc};
+
+/// The namespace used when a resource location leaves it out.
+pub const DEFAULT_NAMESPACE: &str = "minecraft";
+
+/// A namespaced id, like `minecraft:stone`.
+/// The namespace and path are interned, so cloning is cheap and the same ids read on a thread share one allocation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ResourceLocation {
+    namespace: Arc<str>,
+    path: Arc<str>,
+}
+
+impl ResourceLocation {
+    /// Creates a resource location, failing if the namespace or path has characters that aren't allowed.
+    /// Namespaces can have `a-z`, `0-9`, `_`, `-` and `.`, paths can also have `/`.
+    pub fn new(namespace: &str, path: &str) -> Result<Self, SculkParseError> {
+        let valid_namespace = namespace.chars().all(is_namespace_char);
+        let valid_path = !path.is_empty() && path.chars().all(|c| is_namespace_char(c) || c == '/');
+        if !valid_namespace || !valid_path {
+            return Err(SculkParseError::InvalidResourceLocation(format!(
+                "{namespace}:{path}"
+            )));
+        }
+
+        let namespace = if namespace.is_empty() {
+            DEFAULT_NAMESPACE
+        } else {
+            namespace
+        };
+
+        Ok(ResourceLocation {
+            namespace: intern(namespace),
+            path: intern(path),
+        })
+    }
+
+    /// Creates a resource location in the `minecraft` namespace.
+    pub fn minecraft(path: &str) -> Result<Self, SculkParseError> {
+        Self::new(DEFAULT_NAMESPACE, path)
+    }
+
+    /// The namespace, `minecraft` for everything in vanilla.
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// The path, like `stone` in `minecraft:stone`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Whether this is in the `minecraft` namespace.
+    pub fn is_minecraft(&self) -> bool {
+        &*self.namespace == DEFAULT_NAMESPACE
+    }
+
+    /// Keeps an id without checking its characters, splitting it like [`ResourceLocation::from_str`].  
+    /// [`ParseMode::Lenient`] uses this for ids that aren't valid, so one bad id doesn't fail the data it's in.
+    pub fn raw(id: &str) -> Self {
+        let (namespace, path) = split(id);
+        ResourceLocation {
+            namespace: intern(namespace),
+            path: intern(path),
+        }
+    }
+
+    /// Whether the namespace and path only have allowed characters, which is only false for [`ResourceLocation::raw`] ids.
+    pub fn is_valid(&self) -> bool {
+        self.namespace.chars().all(is_namespace_char)
+            && !self.path.is_empty()
+            && self.path.chars().all(|c| is_namespace_char(c) || c == '/')
+    }
+}
+
+fn is_namespace_char(c: char) -> bool {
+    matches!(c, 'a'..='z' | '0'..='9' | '_' | '-' | '.')
+}
+
+/// Splits an id into its namespace and path, using the default namespace if there is none.
+fn split(id: &str) -> (&str, &str) {
+    match id.split_once(':') {
+        Some(("", path)) => (DEFAULT_NAMESPACE, path),
+        Some((namespace, path)) => (namespace, path),
+        None => (DEFAULT_NAMESPACE, id),
+    }
+}
+
+thread_local! {
+    static INTERNED: RefCell<HashSet<Arc<str>>> = RefCell::new(HashSet::new());
+
+    /// Warnings for the ids kept raw, `None` outside of a [`LenientIdScope`].
+    static RAW_IDS: RefCell<Option<Vec<ParseWarning>>> = const { RefCell::new(None) };
+}
+
+/// Returns the shared copy of the string, storing it if it's new.
+/// Each thread interns its own strings so parsing on many threads doesn't wait on a lock,
+/// the same id parsed on two threads is just two allocations.
+/// Interned strings are never freed, which is fine for ids as there are only so many of them.
+fn intern(s: &str) -> Arc<str> {
+    INTERNED.with(|interned| {
+        let mut interned = interned.borrow_mut();
+        if let Some(s) = interned.get(s) {
+            return s.clone();
+        }
+
+        let s: Arc<str> = Arc::from(s);
+        interned.insert(s.clone());
+        s
+    })
+}
+
+/// Parses an id read from `field`.  
+/// Inside a [`LenientIdScope`] an id that isn't valid is kept with [`ResourceLocation::raw`] and recorded as a warning.
+pub(crate) fn parse_id(id: &str, field: &str) -> Result<ResourceLocation, SculkParseError> {
+    let error = match id.parse() {
+        Ok(location) => return Ok(location),
+        Err(err) => err.at_field(field),
+    };
+
+    RAW_IDS.with(|raw_ids| match raw_ids.borrow_mut().as_mut() {
+        Some(warnings) => {
+            warnings.push(ParseWarning {
+                error,
+                substitute: Substitute::Raw,
+            });
+            Ok(ResourceLocation::raw(id))
+        }
+        None => Err(error),
+    })
+}
+
+/// Parses an id given by the caller, like a query or a count to add, keeping it with [`ResourceLocation::raw`] if it isn't valid.  
+/// Such an id can't be read from valid data, so it just doesn't match anything.
+pub(crate) fn parse_or_raw(id: &str) -> ResourceLocation {
+    id.parse().unwrap_or_else(|_| ResourceLocation::raw(id))
+}
+
+/// Keeps ids that aren't valid as raw strings while it's alive, when entered with [`ParseMode::Lenient`].  
+/// Ids are read by parsers the mode isn't passed to, like the items in a chest, so the mode is set for the thread instead.
+/// Entering with another mode turns it off for the nested data.
+#[must_use = "ids are only kept raw until the scope is dropped"]
+pub(crate) struct LenientIdScope {
+    previous: Option<Vec<ParseWarning>>,
+}
+
+impl LenientIdScope {
+    pub(crate) fn enter(mode: ParseMode) -> Self {
+        let raw_ids = (mode == ParseMode::Lenient).then(Vec::new);
+        let previous = RAW_IDS.with(|ids| ids.replace(raw_ids));
+        LenientIdScope { previous }
+    }
+
+    /// Ends the scope, adding a warning for every id that was kept raw.
+    pub(crate) fn finish(self, warnings: &mut Vec<ParseWarning>) {
+        let raw_ids = RAW_IDS.with(|ids| ids.borrow_mut().take());
+        warnings.extend(raw_ids.into_iter().flatten());
+    }
+}
+
+impl Drop for LenientIdScope {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        RAW_IDS.with(|ids| ids.replace(previous));
+    }
+}
+
+impl FromStr for ResourceLocation {
+    type Err = SculkParseError;
+
+    /// Parses `namespace:path`, or just `path` for the `minecraft` namespace.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (namespace, path) = split(s);
+        Self::new(namespace, path)
+    }
+}
+
+impl TryFrom<&str> for ResourceLocation {
+    type Error = SculkParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for ResourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.namespace, self.path)
+    }
+}
+
+/// Compares with an id, where the id can leave out the `minecraft:` namespace.
+impl PartialEq<str> for ResourceLocation {
+    fn eq(&self, other: &str) -> bool {
+        let (namespace, path) = split(other);
+        *self.namespace == *namespace && *self.path == *path
+    }
+}
+
+impl PartialEq<&str> for ResourceLocation {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<String> for ResourceLocation {
+    fn eq(&self, other: &String) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq<ResourceLocation> for str {
+    fn eq(&self, other: &ResourceLocation) -> bool {
+        other == self
+    }
+}
+
+impl PartialEq<ResourceLocation> for &str {
+    fn eq(&self, other: &ResourceLocation) -> bool {
+        other == *self
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ResourceLocation {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ResourceLocation {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// A resource location or a tag of them, written with a `#` like `#minecraft:logs`.
+/// Used where a predicate can match either, like the blocks of [`crate::components::can_break::Predicate`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IdOrTag {
+    /// A single id.
+    Id(ResourceLocation),
+
+    /// A tag, without the `#`.
+    Tag(ResourceLocation),
+}
+
+impl IdOrTag {
+    /// Whether this is a tag.
+    pub fn is_tag(&self) -> bool {
+        matches!(self, IdOrTag::Tag(_))
+    }
+
+    /// The id or the tag's id.
+    pub fn location(&self) -> &ResourceLocation {
+        match self {
+            IdOrTag::Id(id) | IdOrTag::Tag(id) => id,
+        }
+    }
+
+    /// Whether the id matches.
+    /// Tags aren't bundled with the crate, so `in_tag` is asked whether the id is in a tag.
+    pub fn matches(
+        &self,
+        id: &ResourceLocation,
+        in_tag: impl FnOnce(&ResourceLocation, &ResourceLocation) -> bool,
+    ) -> bool {
+        match self {
+            IdOrTag::Id(location) => location == id,
+            IdOrTag::Tag(tag) => in_tag(tag, id),
+        }
+    }
+}
+
+impl FromStr for IdOrTag {
+    type Err = SculkParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix('#') {
+            Some(tag) => Ok(IdOrTag::Tag(tag.parse()?)),
+            None => Ok(IdOrTag::Id(s.parse()?)),
+        }
+    }
+}
+
+impl fmt::Display for IdOrTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdOrTag::Id(id) => write!(f, "{id}"),
+            IdOrTag::Tag(tag) => write!(f, "#{tag}"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for IdOrTag {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for IdOrTag {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn resource_locations() {
+    let stone: ResourceLocation = "stone".parse().unwrap();
+    assert_eq!(
+        stone,
+        "minecraft:stone".parse::<ResourceLocation>().unwrap()
+    );
+    assert_eq!(stone, ":stone");
+    assert!(stone.is_minecraft());
+    assert_ne!(stone, "create:stone");
+
+    let custom: ResourceLocation = "create:large_cogwheel/top".parse().unwrap();
+    assert_eq!(custom.namespace(), "create");
+    assert_eq!(custom.path(), "large_cogwheel/top");
+    assert_eq!(custom.to_string(), "create:large_cogwheel/top");
+
+    assert!("Stone".parse::<ResourceLocation>().is_err());
+    assert!("minecraft:".parse::<ResourceLocation>().is_err());
+    assert!("my/mod:stone".parse::<ResourceLocation>().is_err());
+
+    let logs: IdOrTag = "#logs".parse().unwrap();
+    assert!(logs.is_tag());
+    assert_eq!(logs.to_string(), "#minecraft:logs");
+    let oak_log: ResourceLocation = "oak_log".parse().unwrap();
+    assert!(logs.matches(&oak_log, |tag, id| tag == "logs"
+        && id.path().ends_with("_log")));
+}
+
+#[cfg(test)]
+#[test]
+fn lenient_ids() {
+    assert!(parse_id("Minecraft:Stone", "id").is_err());
+
+    let mut warnings = vec![];
+    let scope = LenientIdScope::enter(ParseMode::Lenient);
+    let stone = parse_id("Minecraft:Stone", "id").unwrap();
+    assert!(parse_id("minecraft:dirt", "id").unwrap().is_valid());
+    {
+        let _default = LenientIdScope::enter(ParseMode::Default);
+        assert!(parse_id("Stone", "id").is_err());
+    }
+    scope.finish(&mut warnings);
+
+    assert!(!stone.is_valid());
+    assert_eq!(stone.to_string(), "Minecraft:Stone");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].substitute, Substitute::Raw);
+    assert!(parse_id("Minecraft:Stone", "id").is_err());
+}
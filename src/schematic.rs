@@ -0,0 +1,837 @@
+//! Sponge schematic files (`.schem`), version 2 and 3.
+//! Used by WorldEdit and most other building tools.
+//! [Specification](https://github.com/SpongePowered/Schematic-Specification)
+
+use crate::{
+    block_entity::{BlockEntity, NoCoordinatesBlockEntity},
+    chunk::section::{BlockStates, Palette},
+    entity::Entity,
+    error::SculkParseError,
+    resource_location::{parse_id, ResourceLocation},
+    structure_template::{StructureTemplate, StructureTemplateEntity},
+    traits::{FromCompoundNbt, ToCompoundNbt, ToVersionedCompoundNbt},
+    util::{from_owned_compound, get_resource_location, gzip, maybe_gunzip, write_root_compound},
+    version::DataVersionScope,
+    MC_DATA_VERSION,
+};
+use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+use std::{collections::HashMap, io::Cursor, str::FromStr};
+
+/// A Sponge schematic.
+/// Blocks are stored with the index `(y * length + z) * width + x`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Schematic {
+    /// The version of the schematic specification, either 2 or 3.
+    /// Decides the layout used when writing.
+    /// `Version`
+    pub version: i32,
+
+    /// The data version of the blocks and entities in the schematic.
+    /// `DataVersion`
+    pub data_version: i32,
+
+    /// Extra information about the schematic, like `Name`, `Author` and `Date`.
+    /// `Metadata`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub metadata: Option<NbtCompound>,
+
+    /// Size along the x axis.
+    /// `Width`
+    pub width: u16,
+
+    /// Size along the y axis.
+    /// `Height`
+    pub height: u16,
+
+    /// Size along the z axis.
+    /// `Length`
+    pub length: u16,
+
+    /// Relative offset of the schematic from the position it was copied at.
+    /// `Offset`
+    pub offset: [i32; 3],
+
+    /// The block states used in the schematic, the position in the list is the index used in `block_data`.
+    /// `Palette`
+    pub palette: Vec<Palette>,
+
+    /// Palette index of every block in the schematic, decoded from the varint `BlockData`.
+    /// `BlockData`
+    pub block_data: Vec<u32>,
+
+    /// Block entities in the schematic.
+    /// `BlockEntities`
+    pub block_entities: Vec<SchematicBlockEntity>,
+
+    /// Entities in the schematic.
+    /// `Entities`
+    pub entities: Vec<SchematicEntity>,
+
+    /// Biomes in the schematic.
+    /// `Biomes`, or `BiomePalette` and `BiomeData` in version 2
+    pub biomes: Option<SchematicBiomes>,
+}
+
+/// A block entity in a schematic.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SchematicBlockEntity {
+    /// Position of the block entity, relative to the schematic origin.
+    /// `Pos`
+    pub pos: [i32; 3],
+
+    /// ID of the block entity.
+    /// `Id`
+    pub id: ResourceLocation,
+
+    /// The block entity data, without `id`, `x`, `y` and `z`.
+    /// `Data`, or the remaining fields in version 2
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub data: NbtCompound,
+}
+
+/// An entity in a schematic.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SchematicEntity {
+    /// Position of the entity, relative to the schematic origin.
+    /// `Pos`
+    pub pos: [f64; 3],
+
+    /// ID of the entity.
+    /// `Id`
+    pub id: ResourceLocation,
+
+    /// The entity data, without `id` and `Pos`.
+    /// `Data`, or the remaining fields in version 2
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub data: NbtCompound,
+}
+
+/// Biomes in a schematic.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SchematicBiomes {
+    /// The biomes used in the schematic, the position in the list is the index used in `data`.
+    pub palette: Vec<ResourceLocation>,
+
+    /// Palette index of every biome.
+    /// Version 3 stores one biome per block, version 2 one per column (`z * width + x`).
+    pub data: Vec<u32>,
+}
+
+impl FromCompoundNbt for Schematic {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        // Version 3 wraps everything in a `Schematic` compound.
+        let (nbt, version) = match nbt.compound("Schematic") {
+            Some(nbt) => (nbt, 3),
+            None => (*nbt, 2),
+        };
+        let version = nbt.int("Version").unwrap_or(version);
+
+        let data_version = nbt
+            .int("DataVersion")
+            .ok_or(SculkParseError::MissingField("DataVersion".into()))?;
+        let _data_version = DataVersionScope::enter(data_version);
+        let metadata = nbt.compound("Metadata").map(|nbt| nbt.to_owned());
+
+        let width = get_u16(&nbt, "Width")?;
+        let height = get_u16(&nbt, "Height")?;
+        let length = get_u16(&nbt, "Length")?;
+        let volume = width as usize * height as usize * length as usize;
+
+        let offset = nbt
+            .int_array("Offset")
+            .and_then(|arr| <[i32; 3]>::try_from(arr).ok())
+            .unwrap_or_default();
+
+        let blocks = if version >= 3 {
+            nbt.compound("Blocks")
+        } else {
+            Some(nbt)
+        };
+
+        let (palette, block_data, block_entities) = match blocks {
+            Some(blocks) => {
+                let palette = read_palette(&blocks, "Palette")?
+                    .iter()
+                    .map(|s| Palette::from_str(s))
+                    .collect::<Result<Vec<Palette>, SculkParseError>>()?;
+
+                let data_key = if version >= 3 { "Data" } else { "BlockData" };
+                let block_data = read_varints(
+                    blocks
+                        .byte_array(data_key)
+                        .ok_or(SculkParseError::MissingField(data_key.into()))?,
+                    volume,
+                )?;
+
+                let block_entities = match blocks.list("BlockEntities") {
+                    Some(list) => list
+                        .compounds()
+                        .ok_or(SculkParseError::InvalidField("BlockEntities".into()))?
+                        .into_iter()
+                        .map(|nbt| SchematicBlockEntity::from_compound_nbt(&nbt))
+                        .collect::<Result<Vec<_>, SculkParseError>>()?,
+                    None => vec![],
+                };
+
+                (palette, block_data, block_entities)
+            }
+            None => (vec![Palette::air()], vec![0; volume], vec![]),
+        };
+
+        let entities = match nbt.list("Entities") {
+            Some(list) => list
+                .compounds()
+                .ok_or(SculkParseError::InvalidField("Entities".into()))?
+                .into_iter()
+                .map(|nbt| SchematicEntity::from_compound_nbt(&nbt))
+                .collect::<Result<Vec<_>, SculkParseError>>()?,
+            None => vec![],
+        };
+
+        let biomes = if version >= 3 {
+            match nbt.compound("Biomes") {
+                Some(biomes) => Some(SchematicBiomes {
+                    palette: read_biome_palette(&biomes, "Palette")?,
+                    data: read_varints(
+                        biomes
+                            .byte_array("Data")
+                            .ok_or(SculkParseError::MissingField("Data".into()))?,
+                        volume,
+                    )?,
+                }),
+                None => None,
+            }
+        } else {
+            match nbt.byte_array("BiomeData") {
+                Some(data) => Some(SchematicBiomes {
+                    palette: read_biome_palette(&nbt, "BiomePalette")?,
+                    data: read_varints(data, width as usize * length as usize)?,
+                }),
+                None => None,
+            }
+        };
+
+        Ok(Schematic {
+            version,
+            data_version,
+            metadata,
+            width,
+            height,
+            length,
+            offset,
+            palette,
+            block_data,
+            block_entities,
+            entities,
+            biomes,
+        })
+    }
+}
+
+impl FromCompoundNbt for SchematicBlockEntity {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        let pos = nbt
+            .int_array("Pos")
+            .and_then(|arr| <[i32; 3]>::try_from(arr).ok())
+            .ok_or(SculkParseError::InvalidField("Pos".into()))?;
+        let id = get_resource_location(nbt, "Id")?;
+
+        let data = match nbt.compound("Data") {
+            Some(data) => data.to_owned(),
+            None => without_keys(nbt, &["Pos", "Id"]),
+        };
+
+        Ok(SchematicBlockEntity { pos, id, data })
+    }
+}
+
+impl FromCompoundNbt for SchematicEntity {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        let pos = nbt
+            .list("Pos")
+            .and_then(|list| list.doubles())
+            .and_then(|doubles| <[f64; 3]>::try_from(doubles).ok())
+            .ok_or(SculkParseError::InvalidField("Pos".into()))?;
+        let id = get_resource_location(nbt, "Id")?;
+
+        let data = match nbt.compound("Data") {
+            Some(data) => data.to_owned(),
+            None => without_keys(nbt, &["Pos", "Id"]),
+        };
+
+        Ok(SchematicEntity { pos, id, data })
+    }
+}
+
+impl ToCompoundNbt for Schematic {
+    fn to_compound_nbt(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+
+        nbt.insert("Version", self.version);
+        nbt.insert("DataVersion", self.data_version);
+        if let Some(metadata) = &self.metadata {
+            nbt.insert("Metadata", NbtTag::Compound(metadata.clone()));
+        }
+        nbt.insert("Width", self.width as i16);
+        nbt.insert("Height", self.height as i16);
+        nbt.insert("Length", self.length as i16);
+        nbt.insert("Offset", NbtTag::IntArray(self.offset.to_vec()));
+
+        let palette = write_palette(self.palette.iter().map(|p| p.to_string()));
+        let block_data = write_varints(&self.block_data);
+
+        if self.version >= 3 {
+            let mut blocks = NbtCompound::new();
+            blocks.insert("Palette", NbtTag::Compound(palette));
+            blocks.insert("Data", NbtTag::ByteArray(block_data));
+            let block_entities = self
+                .block_entities
+                .iter()
+                .map(|block_entity| {
+                    let mut nbt = NbtCompound::new();
+                    nbt.insert("Pos", NbtTag::IntArray(block_entity.pos.to_vec()));
+                    nbt.insert("Id", block_entity.id.to_string());
+                    nbt.insert("Data", NbtTag::Compound(block_entity.data.clone()));
+                    nbt
+                })
+                .collect();
+            blocks.insert("BlockEntities", NbtList::Compound(block_entities));
+            nbt.insert("Blocks", NbtTag::Compound(blocks));
+
+            if let Some(biomes) = &self.biomes {
+                let mut biome_nbt = NbtCompound::new();
+                biome_nbt.insert(
+                    "Palette",
+                    NbtTag::Compound(write_palette(biomes.palette.iter().map(|b| b.to_string()))),
+                );
+                biome_nbt.insert(
+                    "Data",
+                    NbtTag::ByteArray(write_varints(&self.biomes_3d(biomes))),
+                );
+                nbt.insert("Biomes", NbtTag::Compound(biome_nbt));
+            }
+
+            let entities = self
+                .entities
+                .iter()
+                .map(|entity| {
+                    let mut nbt = NbtCompound::new();
+                    nbt.insert("Pos", NbtList::Double(entity.pos.to_vec()));
+                    nbt.insert("Id", entity.id.to_string());
+                    nbt.insert("Data", NbtTag::Compound(entity.data.clone()));
+                    nbt
+                })
+                .collect();
+            nbt.insert("Entities", NbtList::Compound(entities));
+
+            let mut root = NbtCompound::new();
+            root.insert("Schematic", NbtTag::Compound(nbt));
+            root
+        } else {
+            nbt.insert("PaletteMax", self.palette.len() as i32);
+            nbt.insert("Palette", NbtTag::Compound(palette));
+            nbt.insert("BlockData", NbtTag::ByteArray(block_data));
+
+            let block_entities = self
+                .block_entities
+                .iter()
+                .map(|block_entity| {
+                    let mut nbt = block_entity.data.clone();
+                    nbt.insert("Pos", NbtTag::IntArray(block_entity.pos.to_vec()));
+                    nbt.insert("Id", block_entity.id.to_string());
+                    nbt
+                })
+                .collect();
+            nbt.insert("BlockEntities", NbtList::Compound(block_entities));
+
+            if let Some(biomes) = &self.biomes {
+                nbt.insert("BiomePaletteMax", biomes.palette.len() as i32);
+                nbt.insert(
+                    "BiomePalette",
+                    NbtTag::Compound(write_palette(biomes.palette.iter().map(|b| b.to_string()))),
+                );
+                nbt.insert(
+                    "BiomeData",
+                    NbtTag::ByteArray(write_varints(&self.biomes_2d(biomes))),
+                );
+            }
+
+            let entities = self
+                .entities
+                .iter()
+                .map(|entity| {
+                    let mut nbt = entity.data.clone();
+                    nbt.insert("Pos", NbtList::Double(entity.pos.to_vec()));
+                    nbt.insert("Id", entity.id.to_string());
+                    nbt
+                })
+                .collect();
+            nbt.insert("Entities", NbtList::Compound(entities));
+
+            nbt
+        }
+    }
+}
+
+impl SchematicBlockEntity {
+    /// Parses the typed block entity, with its position relative to the schematic origin.
+    pub fn block_entity(&self) -> Result<BlockEntity, SculkParseError> {
+        let mut nbt = self.data.clone();
+        nbt.insert("id", self.id.to_string());
+        nbt.insert("x", self.pos[0]);
+        nbt.insert("y", self.pos[1]);
+        nbt.insert("z", self.pos[2]);
+
+        from_owned_compound(&nbt, BlockEntity::from_compound_nbt)
+    }
+}
+
+impl SchematicEntity {
+    /// Parses the typed entity.
+    pub fn entity(&self) -> Result<Entity, SculkParseError> {
+        let mut nbt = self.data.clone();
+        nbt.insert("id", self.id.to_string());
+        nbt.insert("Pos", NbtList::Double(self.pos.to_vec()));
+
+        from_owned_compound(&nbt, Entity::from_compound_nbt)
+    }
+}
+
+impl Schematic {
+    /// Creates a version 3 schematic of the given size filled with air.
+    pub fn new(width: u16, height: u16, length: u16) -> Self {
+        let volume = width as usize * height as usize * length as usize;
+
+        Schematic {
+            version: 3,
+            data_version: MC_DATA_VERSION,
+            metadata: None,
+            width,
+            height,
+            length,
+            offset: [0, 0, 0],
+            palette: vec![Palette::air()],
+            block_data: vec![0; volume],
+            block_entities: vec![],
+            entities: vec![],
+            biomes: None,
+        }
+    }
+
+    /// Converts from bytes to a schematic.
+    /// The bytes can either be raw nbt or gzip compressed, as `.schem` files are saved.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SculkParseError> {
+        let bytes = maybe_gunzip(bytes)?;
+        let nbt = simdnbt::borrow::read(&mut Cursor::new(&bytes))?;
+
+        let nbt = match nbt {
+            simdnbt::borrow::Nbt::Some(nbt) => nbt,
+            simdnbt::borrow::Nbt::None => return Err(SculkParseError::NoNbt),
+        };
+
+        Schematic::from_compound_nbt(&nbt.as_compound())
+    }
+
+    /// Converts the schematic to uncompressed nbt bytes, in the layout of its `version`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = write_root_compound(&self.to_compound_nbt());
+
+        // Version 2 names its root tag `Schematic`, version 3 uses an empty name.
+        if self.version < 3 {
+            bytes.splice(1..3, [0, 9].into_iter().chain(*b"Schematic"));
+        }
+
+        bytes
+    }
+
+    /// Converts the schematic to gzip compressed nbt bytes, the same way `.schem` files are saved.
+    pub fn to_gzip_bytes(&self) -> Vec<u8> {
+        gzip(&self.to_bytes())
+    }
+
+    /// Gets the index into `block_data` for the given position.
+    pub fn index(&self, x: usize, y: usize, z: usize) -> usize {
+        (y * self.length as usize + z) * self.width as usize + x
+    }
+
+    /// Gets the block state at the given position.
+    pub fn get_block(&self, x: usize, y: usize, z: usize) -> Option<&Palette> {
+        if x >= self.width as usize || y >= self.height as usize || z >= self.length as usize {
+            return None;
+        }
+
+        let state = *self.block_data.get(self.index(x, y, z))?;
+        self.palette.get(state as usize)
+    }
+
+    /// Sets the block state at the given position, inserting it in the palette if it's new.  
+    /// Returns `None` without changing anything if the position is outside of the schematic.
+    pub fn set_block(&mut self, x: usize, y: usize, z: usize, state: Palette) -> Option<()> {
+        if x >= self.width as usize || y >= self.height as usize || z >= self.length as usize {
+            return None;
+        }
+        let index = self.index(x, y, z);
+        if index >= self.block_data.len() {
+            return None;
+        }
+
+        let state = match self.palette.iter().position(|p| *p == state) {
+            Some(state) => state,
+            None => {
+                self.palette.push(state);
+                self.palette.len() - 1
+            }
+        };
+
+        self.block_data[index] = state as u32;
+        Some(())
+    }
+
+    /// Converts a structure template to a schematic, using the palette at the given index.
+    /// Positions without a block in the template, which are structure voids, become air.  
+    /// Fails if the template is larger than a schematic can be, or if a block is outside of it.
+    pub fn from_structure_template(
+        template: &StructureTemplate,
+        palette: usize,
+    ) -> Result<Self, SculkParseError> {
+        let [width, height, length] = template.size.map(|axis| {
+            u16::try_from(axis).map_err(|_| SculkParseError::InvalidField("size".into()))
+        });
+        let mut schematic = Schematic::new(width?, height?, length?);
+        schematic.data_version = template.data_version;
+
+        let template_palette = template
+            .get_palette(palette)
+            .ok_or(SculkParseError::InvalidField("palette".into()))?;
+
+        for block in &template.blocks {
+            let state = template_palette
+                .get(block.state as usize)
+                .ok_or(SculkParseError::InvalidField("state".into()))?;
+            let [x, y, z] = block
+                .pos
+                .map(|axis| usize::try_from(axis).unwrap_or(usize::MAX));
+            schematic
+                .set_block(x, y, z, state.clone())
+                .ok_or(SculkParseError::InvalidField("pos".into()))?;
+
+            if let Some(block_entity) = &block.block_entity {
+                let mut data = block_entity.to_compound_nbt_with_version(template.data_version);
+                data.remove("id");
+
+                schematic.block_entities.push(SchematicBlockEntity {
+                    pos: block.pos,
+                    id: block_entity.base.id.clone(),
+                    data,
+                });
+            }
+        }
+
+        for entity in &template.entities {
+            let mut data = entity
+                .entity
+                .to_compound_nbt_with_version(template.data_version);
+            data.remove("id");
+
+            schematic.entities.push(SchematicEntity {
+                pos: entity.pos,
+                id: entity.entity.id.clone(),
+                data,
+            });
+        }
+
+        Ok(schematic)
+    }
+
+    /// Converts the schematic to a structure template.
+    /// `minecraft:structure_void` blocks are left out, as the template uses missing blocks for them.  
+    /// Fails if a block entity or entity can't be parsed.
+    pub fn to_structure_template(&self) -> Result<StructureTemplate, SculkParseError> {
+        let mut template =
+            StructureTemplate::new([self.width as i32, self.height as i32, self.length as i32]);
+        template.data_version = self.data_version;
+        let _data_version = DataVersionScope::enter(self.data_version);
+
+        // If several block entities share a position, the first one is used.
+        let mut block_entities = HashMap::new();
+        for block_entity in &self.block_entities {
+            block_entities
+                .entry(block_entity.pos)
+                .or_insert(block_entity);
+        }
+
+        for y in 0..self.height as usize {
+            for z in 0..self.length as usize {
+                for x in 0..self.width as usize {
+                    let state = match self.get_block(x, y, z) {
+                        Some(state) if state.name != "minecraft:structure_void" => state,
+                        _ => continue,
+                    };
+
+                    let pos = [x as i32, y as i32, z as i32];
+                    let block_entity = block_entities
+                        .get(&pos)
+                        .map(|block_entity| {
+                            let mut nbt = block_entity.data.clone();
+                            nbt.insert("id", block_entity.id.to_string());
+                            from_owned_compound(&nbt, NoCoordinatesBlockEntity::from_compound_nbt)
+                        })
+                        .transpose()?;
+
+                    template.push_block(pos, state.clone(), block_entity);
+                }
+            }
+        }
+
+        for entity in &self.entities {
+            let mut nbt = entity.data.clone();
+            nbt.insert("id", entity.id.to_string());
+
+            template.entities.push(StructureTemplateEntity {
+                pos: entity.pos,
+                block_pos: entity.pos.map(|p| p.floor() as i32),
+                entity: from_owned_compound(&nbt, Entity::from_compound_nbt)?,
+            });
+        }
+
+        Ok(template)
+    }
+
+    /// Creates a 16x16x16 schematic from the block states of a chunk section.
+    pub fn from_block_states(block_states: &BlockStates) -> Self {
+        let mut schematic = Schematic::new(16, 16, 16);
+        schematic.palette = block_states.palette.clone();
+        schematic.block_data = block_states.indices();
+        schematic
+    }
+
+    /// Gets the chunk section block states of the 16x16x16 area starting at `origin`.
+    /// Anything outside of the schematic becomes air.  
+    /// Fails if `block_data` is shorter than the schematic or uses a state that isn't in the palette.
+    pub fn to_block_states(&self, origin: [i32; 3]) -> Result<BlockStates, SculkParseError> {
+        let mut palette = self.palette.clone();
+        let air = match palette.iter().position(|p| p.name == "minecraft:air") {
+            Some(air) => air,
+            None => {
+                palette.push(Palette::air());
+                palette.len() - 1
+            }
+        } as u32;
+
+        let mut indices = Vec::with_capacity(BlockStates::SIZE);
+        for y in 0..16 {
+            for z in 0..16 {
+                for x in 0..16 {
+                    let [sx, sy, sz] = [origin[0] + x, origin[1] + y, origin[2] + z];
+                    let inside = sx >= 0
+                        && sy >= 0
+                        && sz >= 0
+                        && sx < self.width as i32
+                        && sy < self.height as i32
+                        && sz < self.length as i32;
+
+                    indices.push(if inside {
+                        *self
+                            .block_data
+                            .get(self.index(sx as usize, sy as usize, sz as usize))
+                            .ok_or(SculkParseError::InvalidField("BlockData".into()))?
+                    } else {
+                        air
+                    });
+                }
+            }
+        }
+
+        BlockStates::from_indices(&palette, &indices)
+    }
+
+    fn biomes_3d(&self, biomes: &SchematicBiomes) -> Vec<u32> {
+        let columns = self.width as usize * self.length as usize;
+        if biomes.data.len() == columns {
+            biomes.data.repeat(self.height as usize)
+        } else {
+            biomes.data.clone()
+        }
+    }
+
+    fn biomes_2d(&self, biomes: &SchematicBiomes) -> Vec<u32> {
+        let columns = self.width as usize * self.length as usize;
+        biomes.data.iter().take(columns).copied().collect()
+    }
+}
+
+fn get_u16(nbt: &simdnbt::borrow::NbtCompound, key: &'static str) -> Result<u16, SculkParseError> {
+    nbt.short(key)
+        .map(|s| s as u16)
+        .ok_or(SculkParseError::MissingField(key.into()))
+}
+
+/// Reads a biome palette, failing on a biome id that isn't a valid resource location.
+fn read_biome_palette(
+    nbt: &simdnbt::borrow::NbtCompound,
+    key: &'static str,
+) -> Result<Vec<ResourceLocation>, SculkParseError> {
+    read_palette(nbt, key)?
+        .iter()
+        .map(|biome| parse_id(biome, key))
+        .collect()
+}
+
+/// Reads a `{ "name": index }` palette compound into a list ordered by index.
+fn read_palette(
+    nbt: &simdnbt::borrow::NbtCompound,
+    key: &'static str,
+) -> Result<Vec<String>, SculkParseError> {
+    let palette = nbt
+        .compound(key)
+        .ok_or(SculkParseError::MissingField(key.into()))?;
+
+    let mut entries = vec![];
+    for (name, index) in palette.iter() {
+        let index = index
+            .int()
+            .ok_or(SculkParseError::InvalidField(key.into()))?;
+        entries.push((index, name.to_string()));
+    }
+    entries.sort();
+
+    Ok(entries.into_iter().map(|(_, name)| name).collect())
+}
+
+fn write_palette(palette: impl Iterator<Item = String>) -> NbtCompound {
+    let mut nbt = NbtCompound::new();
+    for (index, name) in palette.enumerate() {
+        nbt.insert(name, index as i32);
+    }
+    nbt
+}
+
+fn read_varints(bytes: &[u8], len: usize) -> Result<Vec<u32>, SculkParseError> {
+    let mut values = Vec::with_capacity(len);
+    let mut value: u32 = 0;
+    let mut shift = 0;
+
+    for byte in bytes {
+        value |= ((byte & 0x7f) as u32) << shift;
+
+        if byte & 0x80 == 0 {
+            values.push(value);
+            value = 0;
+            shift = 0;
+        } else {
+            shift += 7;
+            if shift > 28 {
+                return Err(SculkParseError::InvalidField("varint too long".into()));
+            }
+        }
+    }
+
+    if values.len() != len {
+        return Err(SculkParseError::InvalidField(format!(
+            "expected {len} varints, found {}",
+            values.len()
+        )));
+    }
+
+    Ok(values)
+}
+
+fn write_varints(values: &[u32]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(values.len());
+
+    for value in values {
+        let mut value = *value;
+        while value >= 0x80 {
+            bytes.push((value as u8 & 0x7f) | 0x80);
+            value >>= 7;
+        }
+        bytes.push(value as u8);
+    }
+
+    bytes
+}
+
+fn without_keys(nbt: &simdnbt::borrow::NbtCompound, keys: &[&str]) -> NbtCompound {
+    let mut owned = nbt.to_owned();
+    for key in keys {
+        owned.remove(key);
+    }
+    owned
+}
+
+#[cfg(test)]
+#[test]
+fn schematic_round_trip() {
+    let mut schematic = Schematic::new(3, 2, 200);
+    schematic
+        .set_block(
+            1,
+            1,
+            199,
+            Palette::from_str("minecraft:oak_log[axis=x]").unwrap(),
+        )
+        .unwrap();
+    for i in 0..150 {
+        schematic
+            .set_block(0, 0, i, format!("minecraft:test_{i}").parse().unwrap())
+            .unwrap();
+    }
+    assert!(schematic.set_block(3, 0, 0, Palette::air()).is_none());
+
+    let mut chest = NbtCompound::new();
+    chest.insert("Items", NbtList::Compound(vec![]));
+    schematic
+        .set_block(2, 0, 0, "minecraft:chest".parse().unwrap())
+        .unwrap();
+    schematic.block_entities.push(SchematicBlockEntity {
+        pos: [2, 0, 0],
+        id: "minecraft:chest".parse().unwrap(),
+        data: chest,
+    });
+
+    for version in [2, 3] {
+        schematic.version = version;
+        let parsed = Schematic::from_bytes(&schematic.to_gzip_bytes()).unwrap();
+        assert_eq!(parsed, schematic);
+    }
+
+    assert_eq!(
+        schematic.get_block(1, 1, 199).unwrap().to_string(),
+        "minecraft:oak_log[axis=x]"
+    );
+    let block_entity = schematic.block_entities[0].block_entity().unwrap();
+    assert_eq!(block_entity.base.x, 2);
+
+    let template = schematic.to_structure_template().unwrap();
+    let back = Schematic::from_structure_template(&template, 0).unwrap();
+    assert_eq!(back.get_block(1, 1, 199), schematic.get_block(1, 1, 199));
+
+    let mut outside = template.clone();
+    outside.blocks[0].pos = [-1, 0, 0];
+    assert!(Schematic::from_structure_template(&outside, 0).is_err());
+    let mut too_large = template.clone();
+    too_large.size[0] = 70_000;
+    assert!(Schematic::from_structure_template(&too_large, 0).is_err());
+
+    let block_states = schematic.to_block_states([0, 0, 0]).unwrap();
+    assert_eq!(
+        block_states.get(0, 0, 5).unwrap().name,
+        "minecraft:test_5".to_string()
+    );
+    assert!(BlockStates::from_indices(&[Palette::air()], &[0, 1]).is_err());
+    let section = Schematic::from_block_states(&block_states);
+    assert_eq!(section.get_block(2, 0, 0).unwrap().name, "minecraft:chest");
+}
@@ -0,0 +1,453 @@
+//! Structure template files, the `.nbt` files saved by structure blocks.
+//! Found in `generated/<namespace>/structures/` or inside datapacks under `data/<namespace>/structure/`.
+
+use crate::{
+    block_entities::structure_block::{StructureBlockMirror, StructureBlockRotation},
+    block_entity::NoCoordinatesBlockEntity,
+    chunk::section::Palette,
+    entity::Entity,
+    error::SculkParseError,
+    traits::{FromCompoundNbt, ToCompoundNbt, ToVersionedCompoundNbt},
+    util::{get_t_compound_vec, get_t_list, gzip, maybe_gunzip, write_root_compound},
+    version::DataVersionScope,
+    MC_DATA_VERSION,
+};
+use simdnbt::owned::{NbtList, NbtTag};
+use std::io::Cursor;
+
+/// A structure template.
+/// [Minecraft Wiki](https://minecraft.wiki/w/Structure_file)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructureTemplate {
+    /// Version of the structure NBT structure.
+    /// `DataVersion`
+    pub data_version: i32,
+
+    /// Size of the structure, as the x, y and z length.
+    pub size: [i32; 3],
+
+    /// Set of different block states used in the structure.
+    /// Empty if the structure uses `palettes` instead.
+    pub palette: Vec<Palette>,
+
+    /// Sets of different block states used in the structure, a random palette gets selected based on coordinates.
+    /// Used in vanilla by shipwrecks. Empty if the structure uses `palette` instead.
+    pub palettes: Vec<Vec<Palette>>,
+
+    /// List of individual blocks in the structure.
+    pub blocks: Vec<StructureTemplateBlock>,
+
+    /// List of entities in the structure.
+    pub entities: Vec<StructureTemplateEntity>,
+}
+
+/// A single block in a structure template.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructureTemplateBlock {
+    /// Index of the block in the palette.
+    pub state: i32,
+
+    /// The position of the block, relative to the structure origin.
+    pub pos: [i32; 3],
+
+    /// The block entity of the block, without the x, y and z coordinates.
+    /// `nbt`
+    pub block_entity: Option<NoCoordinatesBlockEntity>,
+}
+
+/// A single entity in a structure template.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructureTemplateEntity {
+    /// The exact position of the entity, relative to the structure origin.
+    pub pos: [f64; 3],
+
+    /// The block position of the entity, relative to the structure origin.
+    /// `blockPos`
+    pub block_pos: [i32; 3],
+
+    /// The entity, usually without the `UUID` tag.
+    /// `nbt`
+    pub entity: Entity,
+}
+
+/// A block of a structure template after rotation and mirroring has been applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlacedBlock<'a> {
+    /// The transformed position, relative to the structure origin.
+    pub pos: [i32; 3],
+
+    /// The transformed block state.
+    pub state: Palette,
+
+    /// The block entity of the block, if any.
+    pub block_entity: Option<&'a NoCoordinatesBlockEntity>,
+}
+
+impl FromCompoundNbt for StructureTemplate {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        let data_version = nbt
+            .int("DataVersion")
+            .ok_or(SculkParseError::MissingField("DataVersion".into()))?;
+        let _data_version = DataVersionScope::enter(data_version);
+
+        let size = get_int_vec3(nbt, "size")?;
+
+        let palette = get_t_compound_vec(nbt, "palette", Palette::from_compound_nbt)?;
+
+        let palettes = if let Some(list) = nbt.list("palettes") {
+            let mut palettes = vec![];
+            for palette in list
+                .lists()
+                .ok_or(SculkParseError::InvalidField("palettes".into()))?
+            {
+                palettes.push(get_t_list(
+                    &palette,
+                    "palettes",
+                    Palette::from_compound_nbt,
+                )?);
+            }
+            palettes
+        } else {
+            vec![]
+        };
+
+        if palette.is_empty() && palettes.is_empty() {
+            return Err(SculkParseError::MissingField("palette".into()));
+        }
+
+        let blocks = get_t_compound_vec(nbt, "blocks", StructureTemplateBlock::from_compound_nbt)?;
+        let entities =
+            get_t_compound_vec(nbt, "entities", StructureTemplateEntity::from_compound_nbt)?;
+
+        Ok(StructureTemplate {
+            data_version,
+            size,
+            palette,
+            palettes,
+            blocks,
+            entities,
+        })
+    }
+}
+
+impl FromCompoundNbt for StructureTemplateBlock {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        let state = nbt
+            .int("state")
+            .ok_or(SculkParseError::MissingField("state".into()))?;
+        let pos = get_int_vec3(nbt, "pos")?;
+        let block_entity = match nbt.compound("nbt") {
+            Some(nbt) => Some(NoCoordinatesBlockEntity::from_compound_nbt(&nbt)?),
+            None => None,
+        };
+
+        Ok(StructureTemplateBlock {
+            state,
+            pos,
+            block_entity,
+        })
+    }
+}
+
+impl FromCompoundNbt for StructureTemplateEntity {
+    fn from_compound_nbt(nbt: &simdnbt::borrow::NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        let pos = nbt
+            .list("pos")
+            .and_then(|list| list.doubles())
+            .and_then(|doubles| <[f64; 3]>::try_from(doubles).ok())
+            .ok_or(SculkParseError::InvalidField("pos".into()))?;
+        let block_pos = get_int_vec3(nbt, "blockPos")?;
+        let entity = nbt
+            .compound("nbt")
+            .ok_or(SculkParseError::MissingField("nbt".into()))?;
+        let entity = Entity::from_compound_nbt(&entity)?;
+
+        Ok(StructureTemplateEntity {
+            pos,
+            block_pos,
+            entity,
+        })
+    }
+}
+
+impl ToCompoundNbt for StructureTemplate {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("DataVersion", self.data_version);
+        nbt.insert("size", NbtList::Int(self.size.to_vec()));
+
+        if !self.palettes.is_empty() {
+            let palettes = self
+                .palettes
+                .iter()
+                .map(|palette| {
+                    NbtList::Compound(palette.iter().map(|p| p.to_compound_nbt()).collect())
+                })
+                .collect::<Vec<NbtList>>();
+            nbt.insert("palettes", NbtList::List(palettes));
+        } else {
+            let palette = self.palette.iter().map(|p| p.to_compound_nbt()).collect();
+            nbt.insert("palette", NbtList::Compound(palette));
+        }
+
+        let blocks = self
+            .blocks
+            .iter()
+            .map(|b| b.to_compound_nbt_with_version(self.data_version))
+            .collect();
+        nbt.insert("blocks", NbtList::Compound(blocks));
+
+        let entities = self
+            .entities
+            .iter()
+            .map(|e| e.to_compound_nbt_with_version(self.data_version))
+            .collect();
+        nbt.insert("entities", NbtList::Compound(entities));
+
+        nbt
+    }
+}
+
+impl ToVersionedCompoundNbt for StructureTemplateBlock {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("state", self.state);
+        nbt.insert("pos", NbtList::Int(self.pos.to_vec()));
+        if let Some(block_entity) = &self.block_entity {
+            nbt.insert(
+                "nbt",
+                NbtTag::Compound(block_entity.to_compound_nbt_with_version(data_version)),
+            );
+        }
+
+        nbt
+    }
+}
+
+impl ToVersionedCompoundNbt for StructureTemplateEntity {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound {
+        let mut nbt = simdnbt::owned::NbtCompound::new();
+
+        nbt.insert("pos", NbtList::Double(self.pos.to_vec()));
+        nbt.insert("blockPos", NbtList::Int(self.block_pos.to_vec()));
+        nbt.insert(
+            "nbt",
+            NbtTag::Compound(self.entity.to_compound_nbt_with_version(data_version)),
+        );
+
+        nbt
+    }
+}
+
+impl StructureTemplate {
+    /// Creates an empty structure template with the given size.
+    /// Uses the data version of the supported Minecraft version.
+    pub fn new(size: [i32; 3]) -> Self {
+        StructureTemplate {
+            data_version: MC_DATA_VERSION,
+            size,
+            palette: vec![],
+            palettes: vec![],
+            blocks: vec![],
+            entities: vec![],
+        }
+    }
+
+    /// Converts from bytes to a structure template.
+    /// The bytes can either be raw nbt or gzip compressed, as structure files are saved.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SculkParseError> {
+        let bytes = maybe_gunzip(bytes)?;
+        let nbt = simdnbt::borrow::read(&mut Cursor::new(&bytes))?;
+
+        let nbt = match nbt {
+            simdnbt::borrow::Nbt::Some(nbt) => nbt,
+            simdnbt::borrow::Nbt::None => return Err(SculkParseError::NoNbt),
+        };
+
+        StructureTemplate::from_compound_nbt(&nbt.as_compound())
+    }
+
+    /// Converts the structure template to uncompressed nbt bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        write_root_compound(&self.to_compound_nbt())
+    }
+
+    /// Converts the structure template to gzip compressed nbt bytes, the same way the game saves them.
+    pub fn to_gzip_bytes(&self) -> Vec<u8> {
+        gzip(&self.to_bytes())
+    }
+
+    /// Gets the palette at the given index.
+    /// Structures with a single `palette` only have index 0.
+    pub fn get_palette(&self, index: usize) -> Option<&[Palette]> {
+        if self.palettes.is_empty() {
+            (index == 0).then_some(self.palette.as_slice())
+        } else {
+            self.palettes.get(index).map(|p| p.as_slice())
+        }
+    }
+
+    /// Adds a block to the structure, inserting the block state in the palette if it's new.
+    /// Only works on structures using a single `palette`.
+    pub fn push_block(
+        &mut self,
+        pos: [i32; 3],
+        state: Palette,
+        block_entity: Option<NoCoordinatesBlockEntity>,
+    ) {
+        let index = match self.palette.iter().position(|p| *p == state) {
+            Some(index) => index,
+            None => {
+                self.palette.push(state);
+                self.palette.len() - 1
+            }
+        };
+
+        self.blocks.push(StructureTemplateBlock {
+            state: index as i32,
+            pos,
+            block_entity,
+        });
+    }
+
+    /// Iterates every block in the structure with the given palette, after mirroring and then rotating it.
+    /// This follows the game, positions are mirrored first and then rotated around the structure origin.
+    ///
+    /// Blocks whose state index is out of bounds for the palette are skipped.
+    pub fn placed_blocks<'a>(
+        &'a self,
+        palette: usize,
+        rotation: &'a StructureBlockRotation,
+        mirror: &'a StructureBlockMirror,
+    ) -> impl Iterator<Item = PlacedBlock<'a>> + 'a {
+        let palette = self.get_palette(palette).unwrap_or_default();
+
+        self.blocks.iter().filter_map(move |block| {
+            let state = palette.get(block.state as usize)?;
+
+            Some(PlacedBlock {
+                pos: transform_pos(block.pos, rotation, mirror),
+                state: state.mirror(mirror).rotate(rotation),
+                block_entity: block.block_entity.as_ref(),
+            })
+        })
+    }
+
+    /// Gets the size of the structure after the rotation has been applied.
+    pub fn rotated_size(&self, rotation: &StructureBlockRotation) -> [i32; 3] {
+        match rotation {
+            StructureBlockRotation::Clockwise90 | StructureBlockRotation::CounterClockwise90 => {
+                [self.size[2], self.size[1], self.size[0]]
+            }
+            _ => self.size,
+        }
+    }
+}
+
+/// Mirrors and then rotates a position around the origin.
+pub fn transform_pos(
+    pos: [i32; 3],
+    rotation: &StructureBlockRotation,
+    mirror: &StructureBlockMirror,
+) -> [i32; 3] {
+    let [mut x, y, mut z] = pos;
+
+    match mirror {
+        StructureBlockMirror::LeftRight => z = -z,
+        StructureBlockMirror::FrontBack => x = -x,
+        StructureBlockMirror::None => {}
+    }
+
+    match rotation {
+        StructureBlockRotation::None => [x, y, z],
+        StructureBlockRotation::Clockwise90 => [-z, y, x],
+        StructureBlockRotation::Clockwise180 => [-x, y, -z],
+        StructureBlockRotation::CounterClockwise90 => [z, y, -x],
+    }
+}
+
+fn get_int_vec3(
+    nbt: &simdnbt::borrow::NbtCompound,
+    key: &'static str,
+) -> Result<[i32; 3], SculkParseError> {
+    nbt.list(key)
+        .and_then(|list| list.ints())
+        .and_then(|ints| <[i32; 3]>::try_from(ints).ok())
+        .ok_or(SculkParseError::InvalidField(key.into()))
+}
+
+#[cfg(test)]
+#[test]
+fn structure_template_round_trip() {
+    use crate::{kv::KVPair, util::from_owned_compound};
+
+    let mut template = StructureTemplate::new([2, 1, 1]);
+
+    let mut properties = KVPair::default();
+    properties.insert("facing".to_string(), "north".to_string());
+    let chest = Palette {
+        name: "minecraft:chest".parse().unwrap(),
+        properties,
+    };
+
+    let mut chest_nbt = simdnbt::owned::NbtCompound::new();
+    chest_nbt.insert("id", "minecraft:chest");
+    chest_nbt.insert("Items", NbtList::Compound(vec![]));
+    chest_nbt.insert("mod_data", 1);
+    let chest_entity =
+        from_owned_compound(&chest_nbt, NoCoordinatesBlockEntity::from_compound_nbt).unwrap();
+
+    template.push_block([0, 0, 0], chest, Some(chest_entity));
+    template.push_block(
+        [1, 0, 0],
+        Palette {
+            name: "minecraft:stone".parse().unwrap(),
+            properties: KVPair::default(),
+        },
+        None,
+    );
+
+    let mut pig = simdnbt::owned::NbtCompound::new();
+    pig.insert("id", "minecraft:pig");
+    pig.insert("Saddle", 1i8);
+    template.entities.push(StructureTemplateEntity {
+        pos: [0.5, 1.0, 0.5],
+        block_pos: [0, 1, 0],
+        entity: from_owned_compound(&pig, Entity::from_compound_nbt).unwrap(),
+    });
+
+    let bytes = template.to_gzip_bytes();
+    let parsed = StructureTemplate::from_bytes(&bytes).unwrap();
+    assert_eq!(parsed, template);
+
+    let block_entity = parsed.blocks[0].block_entity.as_ref().unwrap();
+    assert_eq!(block_entity.base.id, "minecraft:chest");
+    // Data the block entity doesn't model is kept.
+    assert_eq!(block_entity.base.extra.int("mod_data"), Some(1));
+    assert_eq!(parsed.entities[0].entity.id, "minecraft:pig");
+    assert_eq!(parsed.entities[0].entity.extra.byte("Saddle"), Some(1));
+
+    let placed = parsed
+        .placed_blocks(
+            0,
+            &StructureBlockRotation::Clockwise90,
+            &StructureBlockMirror::None,
+        )
+        .collect::<Vec<_>>();
+    assert_eq!(placed[0].state.properties.get("facing").unwrap(), "east");
+    assert_eq!(placed[1].pos, [0, 0, 1]);
+}
@@ -22,3 +22,48 @@ pub trait FromCompoundNbt<Output = Self> {
         unimplemented!()
     }
 }
+
+/// Used on any struct that holds item components, whose format depends on the data version they were saved with.  
+/// Also implements [`FromCompoundNbt`], reading with [`crate::version::current_data_version`].
+pub trait FromVersionedCompoundNbt {
+    fn from_compound_nbt_with_version(
+        nbt: &NbtCompound,
+        data_version: i32,
+    ) -> Result<Self, SculkParseError>
+    where
+        Self: Sized;
+}
+
+impl<T: FromVersionedCompoundNbt> FromCompoundNbt for T {
+    fn from_compound_nbt(nbt: &NbtCompound) -> Result<Self, SculkParseError>
+    where
+        Self: Sized,
+    {
+        T::from_compound_nbt_with_version(nbt, crate::version::current_data_version())
+    }
+}
+
+/// Used on any struct that can be serialized back into an owned Nbt compound.  
+/// The output mirrors the layout that [`FromCompoundNbt`] reads.
+pub trait ToCompoundNbt {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound;
+}
+
+/// Used on any struct that holds item components, whose format depends on the data version they're written for.  
+/// Also implements [`ToCompoundNbt`], writing for [`crate::MC_DATA_VERSION`].
+pub trait ToVersionedCompoundNbt {
+    fn to_compound_nbt_with_version(&self, data_version: i32) -> simdnbt::owned::NbtCompound;
+}
+
+impl<T: ToVersionedCompoundNbt> ToCompoundNbt for T {
+    fn to_compound_nbt(&self) -> simdnbt::owned::NbtCompound {
+        self.to_compound_nbt_with_version(crate::MC_DATA_VERSION)
+    }
+}
+
+/// Used on the borrowed variants in [`crate::borrowed`], which keep strings tied to the Nbt buffer instead of copying them.
+pub trait FromBorrowedNbt<'a> {
+    fn from_borrowed_nbt(nbt: &NbtCompound<'a, 'a>) -> Result<Self, SculkParseError>
+    where
+        Self: Sized;
+}
@@ -1,13 +1,26 @@
-use crate::{components::Components, error::SculkParseError, traits::FromCompoundNbt};
+use crate::{
+    components::Components,
+    error::{NbtType, ParseMode, ParseWarning, SculkParseError, Substitute},
+    item::Item,
+    resource_location::{parse_id, ResourceLocation},
+    traits::{FromCompoundNbt, ToVersionedCompoundNbt},
+};
 use simdnbt::borrow::{NbtCompound, NbtList};
+use std::{borrow::Cow, io::Cursor};
 
 /// The version of Minecraft that this library is designed to work with.  
 /// Formatted exactly as minecraft versions are.
 #[allow(dead_code)]
 pub const MC_VERSION: &str = "1.21";
 
+/// The data version of [`MC_VERSION`], used as the `DataVersion` of newly created data.
+pub const MC_DATA_VERSION: i32 = 3953;
+
+/// The loot table of a container and the seed to generate it with.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LootTableData {
-    pub loot_table: Option<String>,
+    pub loot_table: Option<ResourceLocation>,
     pub loot_table_seed: Option<i64>,
 }
 
@@ -16,15 +29,18 @@ pub fn default_true() -> bool {
     true
 }
 
-pub fn get_loot_table_data(nbt: &NbtCompound) -> LootTableData {
-    let loot_table = get_owned_optional_string(nbt, "LootTable");
+pub fn get_loot_table_data(nbt: &NbtCompound) -> Result<LootTableData, SculkParseError> {
+    let loot_table = match nbt.get("LootTable") {
+        Some(_) => Some(get_resource_location(nbt, "LootTable")?),
+        None => None,
+    };
 
     let loot_table_seed = nbt.long("LootTableSeed");
 
-    LootTableData {
+    Ok(LootTableData {
         loot_table,
         loot_table_seed,
-    }
+    })
 }
 
 // TODO: convert get_owned_mutf8str to borrowed Cow
@@ -33,26 +49,145 @@ pub fn get_loot_table_data(nbt: &NbtCompound) -> LootTableData {
 pub fn get_owned_string(nbt: &NbtCompound, key: &'static str) -> Result<String, SculkParseError> {
     nbt.string(key)
         .map(|s| s.to_string())
-        .ok_or(SculkParseError::InvalidField(key.into()))
+        .ok_or_else(|| invalid_type(nbt, key, NbtType::String))
+}
+
+/// Gets a resource location, the error is at the field if it's invalid.  
+/// When parsing with [`crate::error::ParseMode::Lenient`], an invalid id is kept raw with a warning instead.
+pub fn get_resource_location(
+    nbt: &NbtCompound,
+    key: &'static str,
+) -> Result<ResourceLocation, SculkParseError> {
+    let id = nbt
+        .string(key)
+        .ok_or_else(|| invalid_type(nbt, key, NbtType::String))?;
+
+    parse_id(&id.to_str(), key)
 }
 
 pub fn get_owned_optional_string(nbt: &NbtCompound, key: &'static str) -> Option<String> {
     nbt.string(key).map(|s| s.to_string())
 }
 
-pub fn get_optional_lock(nbt: &NbtCompound) -> Option<String> {
-    nbt.string("Lock").map(|s| s.to_string())
+/// Gets a string that borrows from the nbt buffer, only allocating if it isn't valid utf-8 as is.
+pub fn get_cow_string<'a>(
+    nbt: &NbtCompound<'a, '_>,
+    key: &'static str,
+) -> Result<Cow<'a, str>, SculkParseError> {
+    nbt.string(key)
+        .map(|s| s.to_str())
+        .ok_or_else(|| invalid_type(nbt, key, NbtType::String))
+}
+
+/// The error for a field that couldn't be read as the expected type.  
+/// [`SculkParseError::UnexpectedType`] if the field exists, otherwise [`SculkParseError::InvalidField`].
+pub fn invalid_type(nbt: &NbtCompound, key: &str, expected: NbtType) -> SculkParseError {
+    match nbt.get(key).and_then(|tag| NbtType::from_id(tag.id())) {
+        Some(found) => SculkParseError::UnexpectedType {
+            field: key.into(),
+            expected,
+            found,
+        },
+        None => SculkParseError::InvalidField(key.into()),
+    }
+}
+
+/// The error for a list whose elements aren't the expected type.
+pub fn invalid_list_type(list: &NbtList, key: &str, expected: NbtType) -> SculkParseError {
+    match NbtType::from_id(list.id()) {
+        Some(found) => SculkParseError::UnexpectedType {
+            field: key.into(),
+            expected,
+            found,
+        },
+        None => SculkParseError::InvalidField(key.into()),
+    }
+}
+
+/// Adds the index in the list, and the id of the element if it has one, to the error context.
+pub fn element_context(
+    err: SculkParseError,
+    nbt: &NbtCompound,
+    key: &str,
+    index: usize,
+) -> SculkParseError {
+    let err = match nbt.string("id") {
+        Some(id) => err.with_id(id.to_str()),
+        None => err,
+    };
+
+    err.at_index(key, index)
+}
+
+/// Gets the key needed to open a container, a `Lock` name before 1.21.2 and a `lock` item predicate after.
+pub fn get_optional_lock(
+    nbt: &NbtCompound,
+) -> Result<Option<crate::components::lock::Lock>, SculkParseError> {
+    nbt.get("lock")
+        .or_else(|| nbt.get("Lock"))
+        .map(|tag| crate::components::lock::Lock::from_tag(&tag))
+        .transpose()
 }
 
 pub fn get_optional_name(nbt: &NbtCompound) -> Option<String> {
-    nbt.string("CustomName").map(|s| s.to_string())
+    get_optional_text(nbt, "CustomName")
+}
+
+/// Gets a text component that's a JSON string before 1.21.5 and nbt after, which is read as JSON.  
+/// Plain strings are kept as is, since it isn't known which version they were written with.
+pub fn get_optional_text(nbt: &NbtCompound, key: &str) -> Option<String> {
+    crate::components::text::get_plain_or_text_component(&nbt.get(key)?)
+}
+
+/// Sets the `CustomName`, the inverse of [`get_optional_name`].
+pub fn write_optional_name(
+    nbt: &mut simdnbt::owned::NbtCompound,
+    name: Option<&String>,
+    data_version: i32,
+) {
+    write_optional_text(nbt, "CustomName", name, data_version);
+}
+
+/// Sets a text component, the inverse of [`get_optional_text`].
+pub fn write_optional_text(
+    nbt: &mut simdnbt::owned::NbtCompound,
+    key: &str,
+    text: Option<&String>,
+    data_version: i32,
+) {
+    if let Some(text) = text {
+        set_tag(
+            nbt,
+            key,
+            crate::components::text::plain_or_text_component_tag(text, data_version),
+        );
+    }
+}
+
+/// Sets the lock, the inverse of [`get_optional_lock`].
+pub fn write_optional_lock(
+    nbt: &mut simdnbt::owned::NbtCompound,
+    lock: Option<&crate::components::lock::Lock>,
+) {
+    match lock {
+        Some(lock @ crate::components::lock::Lock::Name(_)) => set_tag(nbt, "Lock", lock.to_tag()),
+        Some(lock @ crate::components::lock::Lock::Predicate(_)) => {
+            set_tag(nbt, "lock", lock.to_tag())
+        }
+        None => {}
+    }
 }
 
 pub fn get_bool(nbt: &NbtCompound, key: &'static str) -> bool {
     nbt.byte(key).map(|b| b != 0).unwrap_or(false)
 }
 
+/// Gets an int array, vanilla writes them as an int array tag but a list of ints is also accepted.
 pub fn get_int_array(nbt: &NbtCompound, key: &'static str) -> Result<Vec<i32>, SculkParseError> {
+    if let Some(array) = nbt.int_array(key) {
+        return Ok(array);
+    }
+
     let list = match nbt.list(key) {
         Some(list) => list,
         None => return Ok(vec![]),
@@ -62,7 +197,7 @@ pub fn get_int_array(nbt: &NbtCompound, key: &'static str) -> Result<Vec<i32>, S
 
     for item in list
         .ints()
-        .ok_or(SculkParseError::InvalidField(key.into()))?
+        .ok_or_else(|| invalid_list_type(&list, key, NbtType::Int))?
         .iter()
     {
         arr.push(*item);
@@ -84,7 +219,7 @@ pub fn get_doubles_array(
 
     for item in list
         .doubles()
-        .ok_or(SculkParseError::InvalidField(key.into()))?
+        .ok_or_else(|| invalid_list_type(&list, key, NbtType::Double))?
         .iter()
     {
         arr.push(*item);
@@ -109,16 +244,86 @@ pub fn get_t_compound_vec<T>(
 
     let mut vec = vec![];
 
-    for item in list
+    for (index, item) in list
         .compounds()
-        .ok_or(SculkParseError::InvalidField(key.into()))?
+        .ok_or_else(|| invalid_list_type(&list, key, NbtType::Compound))?
+        .into_iter()
+        .enumerate()
     {
-        vec.push(nbt_conversion(&item)?);
+        vec.push(nbt_conversion(&item).map_err(|err| element_context(err, &item, key, index))?);
     }
 
     Ok(vec)
 }
 
+/// Same as [`get_t_compound_vec`], but with [`ParseMode::Lenient`] elements that fail are skipped with a warning.
+pub fn get_t_compound_vec_with_mode<T>(
+    nbt: &NbtCompound,
+    key: &'static str,
+    nbt_conversion: fn(nbt: &NbtCompound) -> Result<T, SculkParseError>,
+    mode: ParseMode,
+    warnings: &mut Vec<ParseWarning>,
+) -> Result<Vec<T>, SculkParseError> {
+    if mode != ParseMode::Lenient {
+        return get_t_compound_vec(nbt, key, nbt_conversion);
+    }
+
+    let list = match nbt.list(key) {
+        Some(list) if !list.empty() => list,
+        _ => return Ok(vec![]),
+    };
+
+    let compounds = match list.compounds() {
+        Some(compounds) => compounds,
+        None => {
+            warnings.push(ParseWarning {
+                error: invalid_list_type(&list, key, NbtType::Compound),
+                substitute: Substitute::Default,
+            });
+            return Ok(vec![]);
+        }
+    };
+
+    let mut vec = vec![];
+
+    for (index, item) in compounds.into_iter().enumerate() {
+        match nbt_conversion(&item) {
+            Ok(item) => vec.push(item),
+            Err(err) => warnings.push(ParseWarning {
+                error: element_context(err, &item, key, index),
+                substitute: Substitute::Skipped,
+            }),
+        }
+    }
+
+    Ok(vec)
+}
+
+/// Collects the fields that aren't in `known`, so data that isn't modelled can be written back.
+pub fn get_extra(nbt: &NbtCompound, known: &[&str]) -> simdnbt::owned::NbtCompound {
+    let mut extra = simdnbt::owned::NbtCompound::new();
+
+    for (key, value) in nbt.iter() {
+        if !known.contains(&key.to_str().as_ref()) {
+            extra.insert(key.to_owned(), value.to_owned());
+        }
+    }
+
+    extra
+}
+
+/// Fails with [`SculkParseError::UnknownField`] on the first key that isn't in `known`.
+pub fn check_known_fields(nbt: &NbtCompound, known: &[&str]) -> Result<(), SculkParseError> {
+    for (key, _) in nbt.iter() {
+        let key = key.to_str();
+        if !known.contains(&key.as_ref()) {
+            return Err(SculkParseError::UnknownField(key.into_owned()));
+        }
+    }
+
+    Ok(())
+}
+
 pub fn get_t_list<T>(
     nbt: &NbtList,
     key: &'static str,
@@ -127,7 +332,10 @@ pub fn get_t_list<T>(
     Ok(if let Some(compounds) = nbt.compounds() {
         compounds
             .into_iter()
-            .map(|nbt| nbt_conversion(&nbt))
+            .enumerate()
+            .map(|(index, nbt)| {
+                nbt_conversion(&nbt).map_err(|err| element_context(err, &nbt, key, index))
+            })
             .collect::<Result<Vec<T>, SculkParseError>>()?
     } else {
         vec![]
@@ -138,12 +346,126 @@ pub fn get_optional_components(nbt: &NbtCompound) -> Result<Option<Components>,
     match Components::from_compound_nbt(nbt) {
         Ok(components) => Ok(Some(components)),
         // Only return None if the field is missing
-        Err(SculkParseError::MissingField(_)) => Ok(None),
+        Err(e) if matches!(e.root_cause(), SculkParseError::MissingField(_)) => Ok(None),
         // Return the error if it's anything else
         Err(e) => Err(e),
     }
 }
 
+/// Parses an owned compound by writing it out and reading it back as borrowed nbt.  
+/// Used where we keep raw nbt around and only want the typed data on demand.
+pub fn from_owned_compound<T>(
+    nbt: &simdnbt::owned::NbtCompound,
+    nbt_conversion: fn(nbt: &NbtCompound) -> Result<T, SculkParseError>,
+) -> Result<T, SculkParseError> {
+    let mut buf: Vec<u8> = vec![COMPOUND_ID, 0, 0];
+    nbt.write(&mut buf);
+
+    let nbt = match simdnbt::borrow::read(&mut Cursor::new(buf.as_slice()))? {
+        simdnbt::borrow::Nbt::Some(nbt) => nbt,
+        simdnbt::borrow::Nbt::None => return Err(SculkParseError::NoNbt),
+    };
+
+    nbt_conversion(&nbt.as_compound())
+}
+
+/// Writes an owned compound as a root nbt with an empty name.
+pub fn write_root_compound(nbt: &simdnbt::owned::NbtCompound) -> Vec<u8> {
+    let mut buf: Vec<u8> = vec![COMPOUND_ID, 0, 0];
+    nbt.write(&mut buf);
+    buf
+}
+
+/// Sets a tag in an owned compound, replacing it in place if it already exists.
+/// [`simdnbt::owned::NbtCompound::insert`] always appends, which would leave duplicate keys.
+pub fn set_tag(nbt: &mut simdnbt::owned::NbtCompound, key: &str, tag: impl simdnbt::ToNbtTag) {
+    match nbt.get_mut(key) {
+        Some(existing) => *existing = tag.to_nbt_tag(),
+        None => nbt.insert(key, tag),
+    }
+}
+
+/// Sets the tag if there's a value, optional fields are left out when they're `None`.
+pub fn set_optional_tag<T: simdnbt::ToNbtTag>(
+    nbt: &mut simdnbt::owned::NbtCompound,
+    key: &str,
+    tag: Option<T>,
+) {
+    if let Some(tag) = tag {
+        set_tag(nbt, key, tag);
+    }
+}
+
+/// Writes the loot table fields that [`get_loot_table_data`] reads.
+pub fn write_loot_table_data(
+    nbt: &mut simdnbt::owned::NbtCompound,
+    loot_table: Option<&ResourceLocation>,
+    loot_table_seed: Option<i64>,
+) {
+    set_optional_tag(nbt, "LootTable", loot_table.map(|id| id.to_string()));
+    set_optional_tag(nbt, "LootTableSeed", loot_table_seed);
+}
+
+/// Writes each element as a compound, the inverse of [`get_t_compound_vec`].
+pub fn write_compound_list<T>(
+    elements: &[T],
+    to_compound: impl Fn(&T) -> simdnbt::owned::NbtCompound,
+) -> simdnbt::owned::NbtList {
+    if elements.is_empty() {
+        return simdnbt::owned::NbtList::Empty;
+    }
+
+    simdnbt::owned::NbtList::Compound(elements.iter().map(to_compound).collect())
+}
+
+/// Writes the `Items` of a block entity, the inverse of reading them with [`get_t_compound_vec`].
+pub fn write_items(nbt: &mut simdnbt::owned::NbtCompound, items: &[Item], data_version: i32) {
+    set_tag(
+        nbt,
+        "Items",
+        write_compound_list(items, |item| {
+            item.to_compound_nbt_with_version(data_version)
+        }),
+    );
+}
+
+/// Merges the fields into the compound, replacing the ones it already has.  
+/// Used to write the modelled fields over [`get_extra`].
+pub fn merge_compound(nbt: &mut simdnbt::owned::NbtCompound, fields: simdnbt::owned::NbtCompound) {
+    for (key, value) in fields {
+        set_tag(nbt, &key.to_str(), value);
+    }
+}
+
+/// Decompresses the bytes if they start with the gzip magic, otherwise returns them as is.
+pub fn maybe_gunzip(bytes: &[u8]) -> Result<std::borrow::Cow<'_, [u8]>, SculkParseError> {
+    use std::io::Read;
+
+    if bytes.starts_with(&[0x1f, 0x8b]) {
+        let mut decoder = flate2::read::GzDecoder::new(bytes);
+        let mut out = Vec::new();
+        decoder
+            .read_to_end(&mut out)
+            .map_err(|e| SculkParseError::InvalidField(format!("gzip: {e}")))?;
+
+        Ok(std::borrow::Cow::Owned(out))
+    } else {
+        Ok(std::borrow::Cow::Borrowed(bytes))
+    }
+}
+
+/// Gzip compresses the bytes, used for files like `level.dat` and structure templates.
+pub fn gzip(bytes: &[u8]) -> Vec<u8> {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    // Writing into a Vec can't fail.
+    encoder.write_all(bytes).unwrap();
+    encoder.finish().unwrap()
+}
+
+const COMPOUND_ID: u8 = 10;
+
 #[allow(dead_code)]
 pub fn dump_nbt(nbt: &NbtCompound) {
     for (key, value) in nbt.iter() {
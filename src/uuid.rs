@@ -40,6 +40,20 @@ impl From<[i32; 4]> for Uuid {
 }
 
 impl Uuid {
+    /// Writes the `Uuid` as an int array, like it's stored in nbt.
+    pub fn to_tag(&self) -> simdnbt::owned::NbtTag {
+        simdnbt::owned::NbtTag::IntArray(self.0.to_vec())
+    }
+
+    /// Writes a list of `Uuid`s, the inverse of [`Uuid::from_nbt_to_vec`].
+    pub fn to_nbt_list(uuids: &[Uuid]) -> simdnbt::owned::NbtList {
+        if uuids.is_empty() {
+            return simdnbt::owned::NbtList::Empty;
+        }
+
+        simdnbt::owned::NbtList::IntArray(uuids.iter().map(|uuid| uuid.0.to_vec()).collect())
+    }
+
     /// Converts a list of int arrays to a list of `Uuid`s.
     pub fn from_nbt_to_vec(nbt: &NbtCompound, key: &'static str) -> Vec<Self> {
         nbt.list(key)
@@ -0,0 +1,75 @@
+//! Data versions of the Minecraft releases sculk knows about.
+//! Some data changes format between releases, so it's read based on the `DataVersion` of the file it's in.
+
+use crate::util::MC_DATA_VERSION;
+use std::cell::Cell;
+
+/// `1.20.5`, items store `components` instead of a `tag`, and `count` as an int instead of a `Count` byte.
+pub const V1_20_5: i32 = 3837;
+/// `1.21`
+pub const V1_21: i32 = 3953;
+/// `1.21.1`
+pub const V1_21_1: i32 = 3955;
+/// `1.21.2`, adds components like `consumable`, `equippable` and `use_cooldown`.
+pub const V1_21_2: i32 = 4080;
+/// `1.21.3`
+pub const V1_21_3: i32 = 4082;
+/// `1.21.4`, `custom_model_data` becomes a compound of lists.
+pub const V1_21_4: i32 = 4189;
+/// `1.21.5`, text components are stored as nbt instead of JSON and `tooltip_display` replaces the `show_in_tooltip` flags.
+pub const V1_21_5: i32 = 4325;
+
+thread_local! {
+    static DATA_VERSION: Cell<Option<i32>> = const { Cell::new(None) };
+}
+
+/// The data version that's currently being read.
+/// Set by files with a `DataVersion`, like chunks and `level.dat`, defaults to [`MC_DATA_VERSION`].
+pub fn current_data_version() -> i32 {
+    DATA_VERSION.with(|version| version.get().unwrap_or(MC_DATA_VERSION))
+}
+
+/// Sets the data version used while parsing, until it's dropped.
+/// Used for data that doesn't store its own version, like item components.
+///
+/// ```rust
+/// use sculk::version::{current_data_version, DataVersionScope, V1_21_5};
+///
+/// let scope = DataVersionScope::enter(V1_21_5);
+/// assert_eq!(current_data_version(), V1_21_5);
+/// drop(scope);
+/// ```
+#[must_use = "the data version is only set until the scope is dropped"]
+pub struct DataVersionScope {
+    previous: Option<i32>,
+}
+
+impl DataVersionScope {
+    /// Sets the data version for the current thread.
+    pub fn enter(data_version: i32) -> Self {
+        let previous = DATA_VERSION.with(|version| version.replace(Some(data_version)));
+        DataVersionScope { previous }
+    }
+}
+
+impl Drop for DataVersionScope {
+    fn drop(&mut self) {
+        DATA_VERSION.with(|version| version.set(self.previous));
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn data_version_scope() {
+    assert_eq!(current_data_version(), MC_DATA_VERSION);
+
+    let outer = DataVersionScope::enter(V1_21_2);
+    {
+        let _inner = DataVersionScope::enter(V1_21_5);
+        assert_eq!(current_data_version(), V1_21_5);
+    }
+    assert_eq!(current_data_version(), V1_21_2);
+
+    drop(outer);
+    assert_eq!(current_data_version(), MC_DATA_VERSION);
+}
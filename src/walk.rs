@@ -0,0 +1,664 @@
+//! Walks every item inside chunks, block entities, entities, players and other items.
+//!
+//! Items nest through `minecraft:container`, `minecraft:bundle_contents`, `minecraft:block_entity_data`,
+//! entity inventories like chest boats and item frames, and the player's ender chest.
+//! Each item is yielded with the path to it, like `chunk (3,-7) > chest at (52,64,-110) slot 4 > shulker box slot 12`.
+
+use crate::{
+    block_entities::BlockEntityKind,
+    block_entity::BlockEntity,
+    chunk::Chunk,
+    components::{Component, Components},
+    entity::Entity,
+    error::SculkParseError,
+    item::{Item, ItemWithNoSlot},
+    player::Player,
+    resource_location::ResourceLocation,
+    traits::FromCompoundNbt,
+    util::from_owned_compound,
+    version::DataVersionScope,
+};
+use std::fmt;
+use thiserror::Error;
+
+/// A step in the path to an item.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ItemPathSegment {
+    /// A chunk, by its chunk coordinates.
+    Chunk { x: i32, z: i32 },
+
+    /// A block entity, by its id and block position.
+    BlockEntity {
+        id: ResourceLocation,
+        x: i32,
+        y: i32,
+        z: i32,
+    },
+
+    /// An entity, by its id and block position if it has one.
+    Entity {
+        id: ResourceLocation,
+        pos: Option<[i32; 3]>,
+    },
+
+    /// A player.
+    Player,
+
+    /// An item that holds the items after it, by its id.
+    Item(ResourceLocation),
+
+    /// A slot, or the index in a list without slots.
+    Slot(i32),
+
+    /// A named field, like `ender items`, `mainhand` or `record`.
+    Field(String),
+}
+
+/// The path to an item, from the outermost holder inwards.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ItemPath(pub Vec<ItemPathSegment>);
+
+/// An item found by `walk_items()`, with the path to it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FoundItem {
+    /// Where the item is.
+    pub path: ItemPath,
+
+    /// The item, without its slot since that's part of the path.
+    pub item: ItemWithNoSlot,
+}
+
+/// An item in the data of an entity that couldn't be parsed, with the path to it.
+#[derive(Error, Debug)]
+#[error("{error} (at {path})")]
+pub struct WalkError {
+    /// Where the item is.
+    pub path: ItemPath,
+
+    /// Why the item couldn't be parsed.
+    #[source]
+    pub error: SculkParseError,
+}
+
+impl ItemPath {
+    /// How many items this item is nested in.
+    pub fn depth(&self) -> usize {
+        self.0
+            .iter()
+            .filter(|segment| matches!(segment, ItemPathSegment::Item(_)))
+            .count()
+    }
+}
+
+impl fmt::Display for ItemPathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ItemPathSegment::Chunk { x, z } => write!(f, "chunk ({x},{z})"),
+            ItemPathSegment::BlockEntity { id, x, y, z } => {
+                write!(f, "{} at ({x},{y},{z})", display_name(id))
+            }
+            ItemPathSegment::Entity { id, pos } => match pos {
+                Some([x, y, z]) => write!(f, "{} at ({x},{y},{z})", display_name(id)),
+                None => f.write_str(&display_name(id)),
+            },
+            ItemPathSegment::Player => f.write_str("player"),
+            ItemPathSegment::Item(id) => f.write_str(&display_name(id)),
+            ItemPathSegment::Slot(slot) => write!(f, "slot {slot}"),
+            ItemPathSegment::Field(field) => f.write_str(field),
+        }
+    }
+}
+
+impl fmt::Display for ItemPath {
+    /// Holders are separated by ` > `, and followed by the slot or field the item is in.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, segment) in self.0.iter().enumerate() {
+            if index > 0 {
+                match segment {
+                    ItemPathSegment::Slot(_) | ItemPathSegment::Field(_) => f.write_str(" ")?,
+                    _ => f.write_str(" > ")?,
+                }
+            }
+            write!(f, "{segment}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// `minecraft:shulker_box` as `shulker box`.
+fn display_name(id: &ResourceLocation) -> String {
+    if id.is_minecraft() {
+        id.path().replace('_', " ")
+    } else {
+        id.to_string().replace('_', " ")
+    }
+}
+
+impl Chunk {
+    /// Every item in the chunk's block entities and entities, including the items nested in them.
+    /// Entity items are parsed with the chunk's [`Chunk::data_version`], those that fail to parse are yielded as errors.
+    pub fn walk_items(&self) -> impl Iterator<Item = Result<FoundItem, WalkError>> {
+        let root = ItemPathSegment::Chunk {
+            x: self.x_pos,
+            z: self.z_pos,
+        };
+        let mut walker = Walker::new(vec![root], self.data_version);
+
+        for block_entity in &self.block_entities {
+            walker.block_entity(block_entity);
+        }
+        for entity in self.entities.iter().flatten() {
+            walker.entity(entity);
+        }
+
+        walker.found.into_iter()
+    }
+}
+
+impl BlockEntity {
+    /// Every item in the block entity, including the items nested in them.
+    /// Entity items are parsed with `data_version`, the version of the file the block entity is from.
+    /// Those that fail to parse are yielded as errors.
+    pub fn walk_items(
+        &self,
+        data_version: i32,
+    ) -> impl Iterator<Item = Result<FoundItem, WalkError>> {
+        let mut walker = Walker::new(vec![], data_version);
+        walker.block_entity(self);
+        walker.found.into_iter()
+    }
+}
+
+impl Entity {
+    /// Every item the entity holds, including its passengers and the items nested in them.
+    /// Entity items are read from [`Entity::extra`] and parsed with `data_version`, the version of the file the entity is from.
+    /// Those that fail to parse are yielded as errors.
+    pub fn walk_items(
+        &self,
+        data_version: i32,
+    ) -> impl Iterator<Item = Result<FoundItem, WalkError>> {
+        let mut walker = Walker::new(vec![], data_version);
+        walker.entity(self);
+        walker.found.into_iter()
+    }
+}
+
+impl Player {
+    /// Every item in the player's inventory, ender chest and equipment, including the items nested in them.
+    /// Entity items are parsed with the player's [`Player::data_version`], those that fail to parse are yielded as errors.
+    pub fn walk_items(&self) -> impl Iterator<Item = Result<FoundItem, WalkError>> {
+        let mut walker = Walker::new(vec![ItemPathSegment::Player], self.data_version);
+
+        walker.with(ItemPathSegment::Field("inventory".into()), |walker| {
+            walker.slotted_items(&self.inventory)
+        });
+        walker.with(ItemPathSegment::Field("ender items".into()), |walker| {
+            walker.slotted_items(&self.ender_items)
+        });
+        walker.extra_items(&self.extra);
+
+        for entity in [&self.shoulder_entity_left, &self.shoulder_entity_right]
+            .into_iter()
+            .flatten()
+        {
+            walker.entity(entity);
+        }
+        if let Some(entity) = self
+            .root_vechile
+            .as_ref()
+            .and_then(|vehicle| vehicle.entity.as_ref())
+        {
+            walker.entity(entity);
+        }
+
+        walker.found.into_iter()
+    }
+}
+
+impl Item {
+    /// The item itself, followed by every item nested in it.
+    /// Items in entity data are parsed with `data_version`, the version of the file the item is from.
+    pub fn walk_items(
+        &self,
+        data_version: i32,
+    ) -> impl Iterator<Item = Result<FoundItem, WalkError>> {
+        let root = ItemPathSegment::Slot(self.slot as i32);
+        let mut walker = Walker::new(vec![root], data_version);
+        walker.item(&self.id, self.count, self.components.as_ref(), &self.extra);
+        walker.found.into_iter()
+    }
+}
+
+impl ItemWithNoSlot {
+    /// The item itself, followed by every item nested in it.
+    /// Items in entity data are parsed with `data_version`, the version of the file the item is from.
+    pub fn walk_items(
+        &self,
+        data_version: i32,
+    ) -> impl Iterator<Item = Result<FoundItem, WalkError>> {
+        let mut walker = Walker::new(vec![], data_version);
+        walker.item_with_no_slot(self);
+        walker.found.into_iter()
+    }
+}
+
+/// Collects the items while keeping track of the path to them.
+struct Walker {
+    path: ItemPath,
+    data_version: i32,
+    found: Vec<Result<FoundItem, WalkError>>,
+}
+
+impl Walker {
+    fn new(path: Vec<ItemPathSegment>, data_version: i32) -> Self {
+        Walker {
+            path: ItemPath(path),
+            data_version,
+            found: vec![],
+        }
+    }
+
+    /// Runs `walk` with `segment` added to the path.
+    fn with(&mut self, segment: ItemPathSegment, walk: impl FnOnce(&mut Self)) {
+        self.path.0.push(segment);
+        walk(self);
+        self.path.0.pop();
+    }
+
+    /// Adds the item at the current path, then walks the items nested in its components.
+    fn item(
+        &mut self,
+        id: &ResourceLocation,
+        count: i32,
+        components: Option<&Components>,
+        extra: &simdnbt::owned::NbtCompound,
+    ) {
+        self.found.push(Ok(FoundItem {
+            path: self.path.clone(),
+            item: ItemWithNoSlot {
+                id: id.clone(),
+                count,
+                components: components.cloned(),
+                extra: extra.clone(),
+            },
+        }));
+
+        if let Some(components) = components {
+            self.with(ItemPathSegment::Item(id.clone()), |walker| {
+                walker.components(components)
+            });
+        }
+    }
+    fn item_with_no_slot(&mut self, item: &ItemWithNoSlot) {
+        self.item(&item.id, item.count, item.components.as_ref(), &item.extra);
+    }
+    fn slotted_item(&mut self, item: &Item) {
+        self.item(&item.id, item.count, item.components.as_ref(), &item.extra);
+    }
+
+    fn slotted_items(&mut self, items: &[Item]) {
+        for item in items {
+            self.with(ItemPathSegment::Slot(item.slot as i32), |walker| {
+                walker.slotted_item(item)
+            });
+        }
+    }
+    fn listed_items(&mut self, items: &[ItemWithNoSlot]) {
+        for (index, item) in items.iter().enumerate() {
+            self.with(ItemPathSegment::Slot(index as i32), |walker| {
+                walker.item_with_no_slot(item)
+            });
+        }
+    }
+
+    fn field_item(&mut self, field: &str, item: &ItemWithNoSlot) {
+        self.with(ItemPathSegment::Field(field.into()), |walker| {
+            walker.item_with_no_slot(item)
+        });
+    }
+
+    fn components(&mut self, components: &Components) {
+        for component in components.values() {
+            match component {
+                Component::Container(slots) => {
+                    for slot in slots {
+                        self.with(ItemPathSegment::Slot(slot.slot), |walker| {
+                            walker.item_with_no_slot(&slot.item)
+                        });
+                    }
+                }
+                Component::BundleContents(items) | Component::ChargedProjectiles(items) => {
+                    self.listed_items(items)
+                }
+                Component::BlockEntityData(block_entity) => {
+                    self.block_entity_kind(&block_entity.kind)
+                }
+                Component::EntityData(entity) => self.entity_contents(entity),
+                _ => {}
+            }
+        }
+    }
+
+    fn block_entity(&mut self, block_entity: &BlockEntity) {
+        let base = &block_entity.base;
+        let segment = ItemPathSegment::BlockEntity {
+            id: base.id.clone(),
+            x: base.x,
+            y: base.y,
+            z: base.z,
+        };
+
+        self.with(segment, |walker| {
+            walker.block_entity_kind(&block_entity.kind);
+            if let Some(components) = &base.components {
+                walker.components(components);
+            }
+        });
+    }
+
+    fn block_entity_kind(&mut self, kind: &BlockEntityKind) {
+        match kind {
+            BlockEntityKind::Barrel(barrel) => self.slotted_items(&barrel.items),
+            BlockEntityKind::BlastFurnace(furnace)
+            | BlockEntityKind::Furnace(furnace)
+            | BlockEntityKind::Smoker(furnace) => self.slotted_items(&furnace.items),
+            BlockEntityKind::BrewingStand(brewing_stand) => {
+                self.slotted_items(&brewing_stand.items)
+            }
+            BlockEntityKind::BrushableBlock(block)
+            | BlockEntityKind::SuspiciousGravel(block)
+            | BlockEntityKind::SuspiciousSand(block) => {
+                if let Some(item) = &block.item {
+                    self.with(ItemPathSegment::Field("item".into()), |walker| {
+                        walker.slotted_item(item)
+                    });
+                }
+            }
+            BlockEntityKind::Campfire(campfire) | BlockEntityKind::SoulCampfire(campfire) => {
+                self.slotted_items(&campfire.items)
+            }
+            BlockEntityKind::ChiseledBookshelf(bookshelf) => self.slotted_items(&bookshelf.items),
+            BlockEntityKind::Chest(chest) | BlockEntityKind::TrappedChest(chest) => {
+                self.slotted_items(&chest.items)
+            }
+            BlockEntityKind::Crafter(crafter) => self.slotted_items(&crafter.items),
+            BlockEntityKind::DecoratedPot(pot) => {
+                if let Some(item) = &pot.item {
+                    self.field_item("item", item);
+                }
+            }
+            BlockEntityKind::Dispenser(dispenser) => self.slotted_items(&dispenser.items),
+            BlockEntityKind::Dropper(dropper) => self.slotted_items(&dropper.items),
+            BlockEntityKind::Hopper(hopper) => self.slotted_items(&hopper.items),
+            BlockEntityKind::Jukebox(jukebox) => {
+                if let Some(item) = &jukebox.record_item {
+                    self.field_item("record", item);
+                }
+            }
+            BlockEntityKind::Lectern(lectern) => {
+                if let Some(book) = &lectern.book {
+                    self.field_item("book", book);
+                }
+            }
+            BlockEntityKind::ShulkerBox(shulker_box) => self.slotted_items(&shulker_box.items),
+            BlockEntityKind::Vault(vault) => {
+                self.field_item("key item", &vault.config.key_item);
+                self.with(ItemPathSegment::Field("items to eject".into()), |walker| {
+                    walker.slotted_items(&vault.server_data.items_to_eject)
+                });
+                if let Some(item) = &vault.shared_data.display_item {
+                    self.with(ItemPathSegment::Field("display item".into()), |walker| {
+                        walker.slotted_item(item)
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn entity(&mut self, entity: &Entity) {
+        let segment = ItemPathSegment::Entity {
+            id: entity.id.clone(),
+            pos: entity
+                .pos
+                .map(|pos| pos.map(|coordinate| coordinate.floor() as i32)),
+        };
+
+        self.with(segment, |walker| walker.entity_contents(entity));
+    }
+
+    fn entity_contents(&mut self, entity: &Entity) {
+        self.extra_items(&entity.extra);
+        for passenger in &entity.passengers {
+            self.entity(passenger);
+        }
+    }
+
+    /// Items in entity data that isn't modelled, like an item frame's item or a chest boat's inventory.
+    /// Only the item compounds are parsed, the rest of the data is left as is.
+    fn extra_items(&mut self, extra: &simdnbt::owned::NbtCompound) {
+        // Components like `custom_name` change format between versions.
+        let _data_version = DataVersionScope::enter(self.data_version);
+
+        for (segment, item_nbt) in entity_items(extra) {
+            match from_owned_compound(item_nbt, ItemWithNoSlot::from_compound_nbt) {
+                Ok(item) => self.with(segment, |walker| walker.item_with_no_slot(&item)),
+                Err(error) => {
+                    let mut path = self.path.clone();
+                    path.0.push(segment);
+                    self.found.push(Err(WalkError { path, error }));
+                }
+            }
+        }
+    }
+}
+
+/// Fields holding a single item, with the name used in the path.
+const ENTITY_ITEM_FIELDS: &[(&str, &str)] = &[
+    ("Item", "item"),
+    ("item", "item"),
+    ("SaddleItem", "saddle"),
+    ("ArmorItem", "armor"),
+    ("DecorItem", "decor"),
+    ("body_armor_item", "body"),
+];
+
+/// The items in the entity specific data of an entity, and where they are.
+/// Empty slots, which are compounds without an id, are left out.
+fn entity_items(
+    nbt: &simdnbt::owned::NbtCompound,
+) -> Vec<(ItemPathSegment, &simdnbt::owned::NbtCompound)> {
+    let mut items = vec![];
+    let is_item = |item: &simdnbt::owned::NbtCompound| item.contains("id");
+
+    for (key, name) in ENTITY_ITEM_FIELDS {
+        if let Some(item) = nbt.compound(key).filter(|item| is_item(item)) {
+            items.push((ItemPathSegment::Field((*name).into()), item));
+        }
+    }
+
+    // Chest boats, minecarts and donkeys store a slot, mob inventories don't.
+    for key in ["Items", "Inventory"] {
+        let list = match nbt.list(key).and_then(|list| list.compounds()) {
+            Some(list) => list,
+            None => continue,
+        };
+        for (index, item) in list.iter().enumerate().filter(|(_, item)| is_item(item)) {
+            let slot = item.byte("Slot").map(i32::from).unwrap_or(index as i32);
+            items.push((ItemPathSegment::Slot(slot), item));
+        }
+    }
+
+    // Equipment before 1.21.5, empty slots are empty compounds.
+    for (key, slots) in [
+        ("HandItems", &["mainhand", "offhand"][..]),
+        ("ArmorItems", &["feet", "legs", "chest", "head"][..]),
+    ] {
+        let list = match nbt.list(key).and_then(|list| list.compounds()) {
+            Some(list) => list,
+            None => continue,
+        };
+        for (item, slot) in list.iter().zip(slots).filter(|(item, _)| is_item(item)) {
+            items.push((ItemPathSegment::Field((*slot).into()), item));
+        }
+    }
+
+    if let Some(equipment) = nbt.compound("equipment") {
+        for (slot, item) in equipment.iter() {
+            if let Some(item) = item.compound().filter(|item| is_item(item)) {
+                items.push((ItemPathSegment::Field(slot.to_string()), item));
+            }
+        }
+    }
+
+    items
+}
+#[cfg(test)]
+#[test]
+fn walk_nested_items() {
+    use crate::version::V1_21;
+    use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+
+    fn item(id: &str, fields: Vec<(&str, NbtTag)>) -> NbtCompound {
+        let mut item = NbtCompound::new();
+        item.insert("id", id);
+        item.insert("count", 1);
+        for (key, value) in fields {
+            item.insert(key, value);
+        }
+        item
+    }
+
+    let mut slot = NbtCompound::new();
+    slot.insert("slot", 12);
+    slot.insert("item", NbtTag::Compound(item("minecraft:diamond", vec![])));
+    let mut components = NbtCompound::new();
+    components.insert(
+        "minecraft:container",
+        NbtTag::List(NbtList::Compound(vec![slot])),
+    );
+    let shulker_box = item(
+        "minecraft:shulker_box",
+        vec![
+            ("Slot", NbtTag::Byte(4)),
+            ("components", NbtTag::Compound(components)),
+        ],
+    );
+
+    let mut chest = NbtCompound::new();
+    chest.insert("id", "minecraft:chest");
+    chest.insert("x", 52);
+    chest.insert("y", 64);
+    chest.insert("z", -110);
+    chest.insert("Items", NbtTag::List(NbtList::Compound(vec![shulker_box])));
+
+    let chest = from_owned_compound(&chest, BlockEntity::from_compound_nbt).unwrap();
+    let items: Vec<_> = chest
+        .walk_items(V1_21)
+        .map(|found| {
+            let found = found.unwrap();
+            (found.path.to_string(), found.item.id.to_string())
+        })
+        .collect();
+    assert_eq!(
+        items,
+        vec![
+            (
+                "chest at (52,64,-110) slot 4".to_string(),
+                "minecraft:shulker_box".to_string()
+            ),
+            (
+                "chest at (52,64,-110) slot 4 > shulker box slot 12".to_string(),
+                "minecraft:diamond".to_string()
+            ),
+        ]
+    );
+
+    let mut item_frame = NbtCompound::new();
+    item_frame.insert("id", "minecraft:item_frame");
+    item_frame.insert("Pos", NbtTag::List(NbtList::Double(vec![1.5, 64.0, -2.5])));
+    item_frame.insert("Item", NbtTag::Compound(item("minecraft:elytra", vec![])));
+
+    let item_frame = from_owned_compound(&item_frame, Entity::from_compound_nbt).unwrap();
+    let walked: Vec<_> = item_frame.walk_items(V1_21).map(Result::unwrap).collect();
+    assert_eq!(walked.len(), 1);
+    assert_eq!(walked[0].path.to_string(), "item frame at (1,64,-3) item");
+    assert_eq!(walked[0].item.id, "minecraft:elytra");
+
+    // An item that fails to parse is yielded as an error, the items after it are still walked.
+    let mut bad_item = NbtCompound::new();
+    bad_item.insert("id", 5);
+    let mut chest_boat = NbtCompound::new();
+    chest_boat.insert("id", "minecraft:oak_chest_boat");
+    chest_boat.insert(
+        "Items",
+        NbtTag::List(NbtList::Compound(vec![
+            bad_item,
+            NbtCompound::new(),
+            item("minecraft:diamond", vec![("Slot", NbtTag::Byte(2))]),
+        ])),
+    );
+
+    let chest_boat = from_owned_compound(&chest_boat, Entity::from_compound_nbt).unwrap();
+    let walked: Vec<_> = chest_boat
+        .walk_items(V1_21)
+        .map(|found| found.map(|found| found.path.to_string()))
+        .collect();
+    assert_eq!(walked.len(), 2);
+    let err = walked[0].as_ref().unwrap_err();
+    assert_eq!(err.path.to_string(), "oak chest boat slot 0");
+    assert_eq!(walked[1].as_ref().unwrap(), "oak chest boat slot 2");
+}
+
+#[cfg(test)]
+#[test]
+fn walk_with_data_version() {
+    use crate::version::{V1_21, V1_21_5};
+    use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+
+    // Since 1.21.5 the custom name is a text component compound rather than JSON.
+    let mut name = NbtCompound::new();
+    name.insert("text", "Named");
+    let mut components = NbtCompound::new();
+    components.insert("minecraft:custom_name", name);
+    let mut item = NbtCompound::new();
+    item.insert("id", "minecraft:elytra");
+    item.insert("count", 1);
+    item.insert("components", components);
+    let mut item_frame = NbtCompound::new();
+    item_frame.insert("id", "minecraft:item_frame");
+    item_frame.insert("Pos", NbtList::Double(vec![1.5, 64.0, -2.5]));
+    item_frame.insert("Item", item);
+
+    let mut heightmaps = NbtCompound::new();
+    heightmaps.insert("WORLD_SURFACE", NbtTag::LongArray(vec![0; 37]));
+    let mut chunk = NbtCompound::new();
+    chunk.insert("DataVersion", V1_21_5);
+    chunk.insert("xPos", 0);
+    chunk.insert("zPos", -1);
+    chunk.insert("yPos", -4);
+    chunk.insert("Status", "minecraft:full");
+    chunk.insert("LastUpdate", 0i64);
+    chunk.insert("InhabitedTime", 0i64);
+    chunk.insert("Heightmaps", heightmaps);
+    chunk.insert("Entities", NbtList::Compound(vec![item_frame]));
+
+    let chunk = from_owned_compound(&chunk, Chunk::from_compound_nbt).unwrap();
+    let walked: Vec<_> = chunk.walk_items().map(Result::unwrap).collect();
+    assert_eq!(walked.len(), 1);
+    assert_eq!(
+        walked[0].path.to_string(),
+        "chunk (0,-1) > item frame at (1,64,-3) item"
+    );
+    let components = walked[0].item.components.as_ref().unwrap();
+    assert!(components.custom_name().is_some());
+
+    // The same entity read as 1.21 data doesn't have a valid name.
+    let item_frame = &chunk.entities.as_ref().unwrap()[0];
+    assert!(item_frame.walk_items(V1_21_5).all(|found| found.is_ok()));
+    assert!(item_frame.walk_items(V1_21).all(|found| found.is_err()));
+}
@@ -0,0 +1,507 @@
+use crate::{
+    chunk::Chunk,
+    error::SculkParseError,
+    region::{Region, RegionChunk},
+    resource_location::ResourceLocation,
+};
+use simdnbt::owned::NbtCompound;
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+mod prune;
+mod volume;
+
+pub use prune::*;
+pub use volume::*;
+
+/// A world save folder, read and written through its region files.
+/// Regions are loaded from disk the first time they're used and kept in memory,
+/// changes are only written back to disk with [`World::save`].
+#[derive(Debug, Clone)]
+pub struct World {
+    path: PathBuf,
+    regions: HashMap<RegionKey, Region>,
+    modified: HashSet<RegionKey>,
+}
+
+/// A dimension in a world.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Dimension {
+    /// `minecraft:overworld`
+    Overworld,
+    /// `minecraft:the_nether`
+    Nether,
+    /// `minecraft:the_end`
+    End,
+    /// Any datapack dimension, by its resource location.
+    Custom(ResourceLocation),
+}
+
+/// The kinds of region files a dimension has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RegionKind {
+    /// Chunks with blocks, biomes and block entities.
+    /// `region`
+    Chunks,
+    /// Entities, since 1.17.
+    /// `entities`
+    Entities,
+    /// Points of interest, like beds and workstations.
+    /// `poi`
+    Poi,
+}
+
+type RegionKey = (Dimension, RegionKind, i32, i32);
+
+/// A region that couldn't be written by [`World::save`].
+#[derive(Debug)]
+pub struct RegionSaveError {
+    /// The dimension of the region.
+    pub dimension: Dimension,
+
+    /// The kind of region file.
+    pub kind: RegionKind,
+
+    /// X coordinate of the region.
+    pub x: i32,
+
+    /// Z coordinate of the region.
+    pub z: i32,
+
+    /// Why the region couldn't be written.
+    pub error: SculkParseError,
+}
+
+impl std::fmt::Display for RegionSaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} region ({}, {}): {}",
+            self.dimension.id(),
+            self.kind.folder(),
+            self.x,
+            self.z,
+            self.error
+        )
+    }
+}
+
+impl Dimension {
+    /// The resource location of the dimension.
+    pub fn id(&self) -> ResourceLocation {
+        let path = match self {
+            Dimension::Overworld => "overworld",
+            Dimension::Nether => "the_nether",
+            Dimension::End => "the_end",
+            Dimension::Custom(id) => return id.clone(),
+        };
+
+        // The vanilla ids are known to be valid.
+        ResourceLocation::raw(path)
+    }
+
+    /// The folder of the dimension, relative to the world folder.
+    pub fn path(&self) -> PathBuf {
+        match self {
+            Dimension::Overworld => PathBuf::new(),
+            Dimension::Nether => PathBuf::from("DIM-1"),
+            Dimension::End => PathBuf::from("DIM1"),
+            Dimension::Custom(id) => PathBuf::from("dimensions")
+                .join(id.namespace())
+                .join(id.path()),
+        }
+    }
+}
+
+impl From<ResourceLocation> for Dimension {
+    fn from(id: ResourceLocation) -> Self {
+        if !id.is_minecraft() {
+            return Dimension::Custom(id);
+        }
+
+        match id.path() {
+            "overworld" => Dimension::Overworld,
+            "the_nether" => Dimension::Nether,
+            "the_end" => Dimension::End,
+            _ => Dimension::Custom(id),
+        }
+    }
+}
+
+impl FromStr for Dimension {
+    type Err = SculkParseError;
+
+    /// Parses a dimension id, where the `minecraft:` namespace is optional.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Dimension::from(s.parse::<ResourceLocation>()?))
+    }
+}
+
+impl RegionKind {
+    /// The folder name of the region kind.
+    pub fn folder(&self) -> &'static str {
+        match self {
+            RegionKind::Chunks => "region",
+            RegionKind::Entities => "entities",
+            RegionKind::Poi => "poi",
+        }
+    }
+}
+
+impl World {
+    /// Opens the world save folder at the given path.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SculkParseError> {
+        let path = path.as_ref().to_path_buf();
+        if !path.is_dir() {
+            return Err(SculkParseError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("{} is not a world folder", path.display()),
+            )));
+        }
+
+        Ok(World {
+            path,
+            regions: HashMap::new(),
+            modified: HashSet::new(),
+        })
+    }
+
+    /// The path of the world folder.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The path of the region file at the given region coordinates.
+    pub fn region_path(&self, dimension: &Dimension, kind: RegionKind, x: i32, z: i32) -> PathBuf {
+        self.path
+            .join(dimension.path())
+            .join(kind.folder())
+            .join(Region::file_name(x, z))
+    }
+
+    /// Gets the region at the given region coordinates, loading it from disk if needed.
+    /// Returns `None` if the region file doesn't exist.
+    pub fn region(
+        &mut self,
+        dimension: &Dimension,
+        kind: RegionKind,
+        x: i32,
+        z: i32,
+    ) -> Result<Option<&Region>, SculkParseError> {
+        let key = (dimension.clone(), kind, x, z);
+        if !self.load_region(&key)? {
+            return Ok(None);
+        }
+
+        Ok(self.regions.get(&key))
+    }
+
+    /// Gets the region at the given region coordinates for editing, creating an empty one if the file doesn't exist.
+    /// The region is written to disk on the next [`World::save`].
+    pub fn region_mut(
+        &mut self,
+        dimension: &Dimension,
+        kind: RegionKind,
+        x: i32,
+        z: i32,
+    ) -> Result<&mut Region, SculkParseError> {
+        let key = (dimension.clone(), kind, x, z);
+        self.load_region(&key)?;
+
+        self.modified.insert(key.clone());
+        Ok(self.regions.entry(key).or_insert_with(|| Region::new(x, z)))
+    }
+
+    /// Gets the raw chunk at the given absolute chunk coordinates.
+    pub fn region_chunk(
+        &mut self,
+        dimension: &Dimension,
+        kind: RegionKind,
+        x: i32,
+        z: i32,
+    ) -> Result<Option<&RegionChunk>, SculkParseError> {
+        Ok(self
+            .region(dimension, kind, x >> 5, z >> 5)?
+            .and_then(|region| region.get(x, z)))
+    }
+
+    /// Parses the chunk at the given absolute chunk coordinates.
+    pub fn chunk(
+        &mut self,
+        dimension: &Dimension,
+        x: i32,
+        z: i32,
+    ) -> Result<Option<Chunk>, SculkParseError> {
+        self.region_chunk(dimension, RegionKind::Chunks, x, z)?
+            .map(|chunk| chunk.chunk())
+            .transpose()
+    }
+
+    /// Gets the raw nbt of the chunk at the given absolute chunk coordinates.
+    pub fn chunk_nbt(
+        &mut self,
+        dimension: &Dimension,
+        kind: RegionKind,
+        x: i32,
+        z: i32,
+    ) -> Result<Option<NbtCompound>, SculkParseError> {
+        self.region_chunk(dimension, kind, x, z)?
+            .map(|chunk| chunk.nbt())
+            .transpose()
+    }
+
+    /// Replaces the raw nbt of the chunk at the given absolute chunk coordinates.
+    pub fn set_chunk_nbt(
+        &mut self,
+        dimension: &Dimension,
+        kind: RegionKind,
+        x: i32,
+        z: i32,
+        nbt: &NbtCompound,
+    ) -> Result<(), SculkParseError> {
+        self.region_mut(dimension, kind, x >> 5, z >> 5)?
+            .set_chunk_nbt(x, z, nbt);
+        Ok(())
+    }
+
+    /// Writes all regions changed since the last save to disk.
+    /// Regions left without any chunks have their file deleted.
+    /// A region that fails to write doesn't stop the others, it's returned as an error and stays modified,
+    /// so the next save tries it again.
+    pub fn save(&mut self) -> Result<(), Vec<RegionSaveError>> {
+        let modified: Vec<RegionKey> = self.modified.iter().cloned().collect();
+        let mut errors = vec![];
+
+        for key in modified {
+            if let Err(error) = self.write_region(&key) {
+                let (dimension, kind, x, z) = key;
+                errors.push(RegionSaveError {
+                    dimension,
+                    kind,
+                    x,
+                    z,
+                    error,
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Lists the region coordinates of every region file of the given kind in a dimension.
+    pub fn region_coords(
+        &self,
+        dimension: &Dimension,
+        kind: RegionKind,
+    ) -> Result<Vec<(i32, i32)>, SculkParseError> {
+        let folder = self.path.join(dimension.path()).join(kind.folder());
+        if !folder.is_dir() {
+            return Ok(vec![]);
+        }
+
+        let mut coords = vec![];
+        for entry in fs::read_dir(folder)? {
+            let name = entry?.file_name();
+            let coord = name
+                .to_str()
+                .and_then(|name| name.strip_prefix("r."))
+                .and_then(|name| name.strip_suffix(".mca"))
+                .and_then(|name| name.split_once('.'))
+                .and_then(|(x, z)| Some((x.parse().ok()?, z.parse().ok()?)));
+
+            if let Some(coord) = coord {
+                coords.push(coord);
+            }
+        }
+        coords.sort();
+
+        Ok(coords)
+    }
+
+    /// Parses every chunk in a dimension across threads.
+    /// Region files are read, decompressed and parsed in parallel, one region per task,
+    /// so only a region per thread is kept in memory at once. Regions already loaded are used as is.
+    ///
+    /// Each chunk gets its own result, so one bad chunk doesn't stop the rest.
+    /// A region file that can't be read gives a single error in place of its chunks.
+    #[cfg(feature = "parallel")]
+    pub fn par_chunks(
+        &self,
+        dimension: &Dimension,
+    ) -> Result<
+        impl rayon::iter::ParallelIterator<Item = Result<Chunk, SculkParseError>> + '_,
+        SculkParseError,
+    > {
+        use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+        let dimension = dimension.clone();
+        let coords = self.region_coords(&dimension, RegionKind::Chunks)?;
+
+        Ok(coords.into_par_iter().flat_map_iter(move |(x, z)| {
+            let key = (dimension.clone(), RegionKind::Chunks, x, z);
+
+            let chunks: Box<dyn Iterator<Item = Result<Chunk, SculkParseError>> + Send + '_> =
+                match self.regions.get(&key) {
+                    Some(region) => Box::new(region.chunks()),
+                    None => match self.read_region(&key) {
+                        Ok(region) => Box::new(
+                            region
+                                .chunks
+                                .into_iter()
+                                .flatten()
+                                .map(|chunk| chunk.chunk()),
+                        ),
+                        Err(err) => Box::new(std::iter::once(Err(err))),
+                    },
+                };
+
+            chunks
+        }))
+    }
+
+    /// Writes a single cached region to disk, regions without any chunks have their file deleted.
+    /// Returns true if the file was deleted.
+    fn write_region(&mut self, key: &RegionKey) -> Result<bool, SculkParseError> {
+        let written = self.try_write_region(key);
+        if written.is_err() {
+            self.modified.insert(key.clone());
+        }
+
+        written
+    }
+
+    fn try_write_region(&mut self, key: &RegionKey) -> Result<bool, SculkParseError> {
+        self.modified.remove(key);
+        let region = match self.regions.get(key) {
+            Some(region) => region,
+            None => return Ok(false),
+        };
+
+        let (dimension, kind, x, z) = key;
+        let path = self.region_path(dimension, *kind, *x, *z);
+
+        if region.chunks.iter().all(|chunk| chunk.is_none()) {
+            if path.exists() {
+                fs::remove_file(path)?;
+                return Ok(true);
+            }
+            return Ok(false);
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, region.to_bytes()?)?;
+
+        Ok(false)
+    }
+
+    /// Drops a region from the cache, unless it has unsaved changes.
+    fn unload_region(&mut self, key: &RegionKey) {
+        if !self.modified.contains(key) {
+            self.regions.remove(key);
+        }
+    }
+
+    /// Loads the region into the cache, returns false if the region file doesn't exist.
+    fn load_region(&mut self, key: &RegionKey) -> Result<bool, SculkParseError> {
+        if self.regions.contains_key(key) {
+            return Ok(true);
+        }
+
+        let (dimension, kind, x, z) = key;
+        if !self.region_path(dimension, *kind, *x, *z).exists() {
+            return Ok(false);
+        }
+
+        let region = self.read_region(key)?;
+        self.regions.insert(key.clone(), region);
+        Ok(true)
+    }
+
+    /// Reads a region file from disk, without caching it.
+    fn read_region(&self, key: &RegionKey) -> Result<Region, SculkParseError> {
+        let (dimension, kind, x, z) = key;
+        let path = self.region_path(dimension, *kind, *x, *z);
+
+        Region::from_bytes(*x, *z, &fs::read(path)?)
+    }
+}
+
+#[cfg(all(test, feature = "parallel"))]
+#[test]
+fn par_chunks_with_corrupt_chunk() {
+    use crate::region::ChunkCompression;
+    use rayon::iter::ParallelIterator;
+    use simdnbt::owned::NbtList;
+
+    let path = std::env::temp_dir().join(format!("sculk_par_chunks_{}", std::process::id()));
+    std::fs::create_dir_all(path.join("region")).unwrap();
+
+    let mut chunk = NbtCompound::new();
+    chunk.insert("DataVersion", crate::MC_DATA_VERSION);
+    chunk.insert("xPos", 0);
+    chunk.insert("zPos", 0);
+    chunk.insert("yPos", -4);
+    chunk.insert("Status", "minecraft:full");
+    chunk.insert("LastUpdate", 0i64);
+    chunk.insert("InhabitedTime", 0i64);
+    chunk.insert("Heightmaps", NbtCompound::new());
+    chunk.insert("sections", NbtList::Empty);
+    chunk.insert("block_entities", NbtList::Empty);
+
+    // One good chunk, one in an unsupported compression and one that doesn't decompress.
+    let mut region = Region::new(0, 0);
+    region.set_chunk_nbt(0, 0, &chunk);
+    region.chunks[Region::chunk_index(1, 0)] = Some(RegionChunk {
+        timestamp: 0,
+        compression: ChunkCompression::Unsupported(4),
+        data: vec![1, 2, 3],
+    });
+    region.chunks[Region::chunk_index(2, 0)] = Some(RegionChunk {
+        timestamp: 0,
+        compression: ChunkCompression::Zlib,
+        data: vec![1, 2, 3],
+    });
+    std::fs::write(
+        path.join("region").join(Region::file_name(0, 0)),
+        region.to_bytes().unwrap(),
+    )
+    .unwrap();
+
+    let world = World::open(&path).unwrap();
+    let chunks: Vec<_> = world.par_chunks(&Dimension::Overworld).unwrap().collect();
+    std::fs::remove_dir_all(&path).unwrap();
+
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks.iter().filter(|chunk| chunk.is_ok()).count(), 1);
+    assert!(chunks
+        .iter()
+        .any(|chunk| matches!(chunk, Err(SculkParseError::UnsupportedCompression(4)))));
+}
+
+#[cfg(test)]
+#[test]
+fn dimension_ids() {
+    let nether: Dimension = "the_nether".parse().unwrap();
+    assert_eq!(nether, Dimension::Nether);
+    assert_eq!(nether.id(), "minecraft:the_nether");
+
+    let custom: Dimension = "my_pack:mining".parse().unwrap();
+    assert_eq!(custom, Dimension::Custom("my_pack:mining".parse().unwrap()));
+    assert_eq!(
+        custom.path(),
+        PathBuf::from("dimensions").join("my_pack").join("mining")
+    );
+    assert!("My Pack:mining".parse::<Dimension>().is_err());
+}
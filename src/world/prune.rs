@@ -0,0 +1,418 @@
+use super::{Dimension, RegionKind, World};
+use crate::{
+    block_entities::BlockEntityKind, block_entity::BlockEntity, chunk::status::ChunkStatus,
+    error::SculkParseError, traits::FromCompoundNbt, version::DataVersionScope,
+};
+use std::io::Cursor;
+
+/// Which chunks [`World::prune`] removes.
+/// A chunk is removed if any of the set criteria matches, unless it contains a protected block entity.
+#[derive(Debug, Clone)]
+pub struct PruneOptions {
+    /// Remove chunks with an `InhabitedTime` below this, in ticks.
+    pub min_inhabited_time: Option<i64>,
+
+    /// Remove chunks whose status isn't [`ChunkStatus::Full`].
+    pub remove_unfinished: bool,
+
+    /// Remove chunks whose center is outside of this area.
+    pub keep_area: Option<PruneArea>,
+
+    /// Chunks containing a block entity that this returns true for are always kept.
+    /// Block entities that fail to parse are treated as protected.
+    pub protected: fn(&BlockEntity) -> bool,
+
+    /// Only report what would be removed, without changing any files.
+    pub dry_run: bool,
+}
+
+/// An area of the world, in block coordinates on the x and z axis.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PruneArea {
+    /// A circle around a center point.
+    Radius {
+        /// Center of the circle, `[x, z]`.
+        center: [i32; 2],
+        /// Radius of the circle, in blocks.
+        radius: i32,
+    },
+
+    /// A polygon made up of `[x, z]` corners, in order.
+    Polygon(Vec<[i32; 2]>),
+}
+
+/// What [`World::prune`] did.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PruneReport {
+    /// Amount of region files that were looked through.
+    pub regions_checked: usize,
+
+    /// Amount of chunks that were looked through.
+    pub chunks_checked: usize,
+
+    /// Chunk coordinates `[x, z]` of every removed chunk.
+    pub removed_chunks: Vec<[i32; 2]>,
+
+    /// Amount of region files that were deleted because they had no chunks left.
+    pub regions_deleted: usize,
+
+    /// Chunk coordinates `[x, z]` of chunks that couldn't be decoded, these are always kept.
+    pub skipped_chunks: Vec<[i32; 2]>,
+
+    /// Region coordinates `[x, z]` of regions where a region file couldn't be read, these are left untouched.
+    pub skipped_regions: Vec<[i32; 2]>,
+}
+
+impl Default for PruneOptions {
+    fn default() -> Self {
+        PruneOptions {
+            min_inhabited_time: None,
+            remove_unfinished: false,
+            keep_area: None,
+            protected: is_protected_block_entity,
+            dry_run: false,
+        }
+    }
+}
+
+impl PruneArea {
+    /// Whether the block position `[x, z]` is inside of the area.
+    pub fn contains(&self, pos: [f64; 2]) -> bool {
+        match self {
+            PruneArea::Radius { center, radius } => {
+                let dx = pos[0] - center[0] as f64;
+                let dz = pos[1] - center[1] as f64;
+                dx * dx + dz * dz <= (*radius as f64) * (*radius as f64)
+            }
+            PruneArea::Polygon(corners) => {
+                // Even-odd ray casting along the x axis.
+                let mut inside = false;
+                let mut previous = match corners.last() {
+                    Some(corner) => corner,
+                    None => return false,
+                };
+
+                for corner in corners {
+                    let (x1, z1) = (previous[0] as f64, previous[1] as f64);
+                    let (x2, z2) = (corner[0] as f64, corner[1] as f64);
+
+                    if (z1 > pos[1]) != (z2 > pos[1])
+                        && pos[0] < (x2 - x1) * (pos[1] - z1) / (z2 - z1) + x1
+                    {
+                        inside = !inside;
+                    }
+                    previous = corner;
+                }
+
+                inside
+            }
+        }
+    }
+}
+
+/// The default protection, keeps containers with items and signs with text.
+pub fn is_protected_block_entity(block_entity: &BlockEntity) -> bool {
+    match &block_entity.kind {
+        BlockEntityKind::Chest(chest) | BlockEntityKind::TrappedChest(chest) => {
+            !chest.items.is_empty()
+        }
+        BlockEntityKind::Barrel(barrel) => !barrel.items.is_empty(),
+        BlockEntityKind::ShulkerBox(shulker_box) => !shulker_box.items.is_empty(),
+        BlockEntityKind::Sign(sign) | BlockEntityKind::HangingSign(sign) => sign
+            .front_text
+            .messages
+            .iter()
+            .chain(sign.back_text.messages.iter())
+            .any(|message| !matches!(message.as_str(), "" | "\"\"" | "{\"text\":\"\"}")),
+        _ => false,
+    }
+}
+
+impl World {
+    /// Removes chunks matching the options from every region in a dimension.
+    /// The chunk's entities and points of interest are removed along with it.
+    /// Regions are processed one at a time and written as soon as they're done, region files left empty are deleted.
+    /// Chunks and regions that can't be read are skipped and listed in the report instead of stopping the prune.
+    pub fn prune(
+        &mut self,
+        dimension: &Dimension,
+        options: &PruneOptions,
+    ) -> Result<PruneReport, SculkParseError> {
+        let mut report = PruneReport::default();
+
+        for (region_x, region_z) in self.region_coords(dimension, RegionKind::Chunks)? {
+            // Read all three region files up front, so a bad one is found before anything is changed.
+            let loaded = [RegionKind::Chunks, RegionKind::Entities, RegionKind::Poi]
+                .into_iter()
+                .map(|kind| {
+                    self.region(dimension, kind, region_x, region_z)
+                        .map(|region| region.is_some())
+                })
+                .collect::<Result<Vec<bool>, SculkParseError>>();
+            let loaded = match loaded {
+                Ok(loaded) if loaded[0] => loaded,
+                loaded => {
+                    for kind in [RegionKind::Chunks, RegionKind::Entities, RegionKind::Poi] {
+                        self.unload_region(&(dimension.clone(), kind, region_x, region_z));
+                    }
+                    if loaded.is_err() {
+                        report.skipped_regions.push([region_x, region_z]);
+                    }
+                    continue;
+                }
+            };
+            let region = match self.region(dimension, RegionKind::Chunks, region_x, region_z)? {
+                Some(region) => region,
+                None => continue,
+            };
+            report.regions_checked += 1;
+
+            let mut removed = vec![];
+            for (index, chunk) in region.chunks.iter().enumerate() {
+                let chunk = match chunk {
+                    Some(chunk) => chunk,
+                    None => continue,
+                };
+                report.chunks_checked += 1;
+
+                let pos = [
+                    region_x * 32 + (index % 32) as i32,
+                    region_z * 32 + (index / 32) as i32,
+                ];
+                match chunk
+                    .decompress()
+                    .and_then(|data| should_prune(&data, pos, options))
+                {
+                    Ok(true) => removed.push(pos),
+                    Ok(false) => {}
+                    Err(_) => report.skipped_chunks.push(pos),
+                }
+            }
+
+            for (kind, exists) in [RegionKind::Chunks, RegionKind::Entities, RegionKind::Poi]
+                .into_iter()
+                .zip(loaded)
+            {
+                let key = (dimension.clone(), kind, region_x, region_z);
+
+                if !options.dry_run && !removed.is_empty() && exists {
+                    let region = self.region_mut(dimension, kind, region_x, region_z)?;
+                    for [x, z] in &removed {
+                        region.remove_chunk(*x, *z);
+                    }
+
+                    if self.write_region(&key)? && kind == RegionKind::Chunks {
+                        report.regions_deleted += 1;
+                    }
+                }
+
+                self.unload_region(&key);
+            }
+
+            report.removed_chunks.extend(removed);
+        }
+
+        Ok(report)
+    }
+}
+
+/// Reads the chunk's nbt in place, the block entities are only parsed if the chunk would otherwise be removed.
+fn should_prune(
+    data: &[u8],
+    pos: [i32; 2],
+    options: &PruneOptions,
+) -> Result<bool, SculkParseError> {
+    let nbt = match simdnbt::borrow::read(&mut Cursor::new(data))? {
+        simdnbt::borrow::Nbt::Some(nbt) => nbt,
+        simdnbt::borrow::Nbt::None => return Err(SculkParseError::NoNbt),
+    };
+    let nbt = nbt.as_compound();
+
+    let barely_inhabited = match options.min_inhabited_time {
+        Some(min) => nbt.long("InhabitedTime").unwrap_or(0) < min,
+        None => false,
+    };
+
+    let unfinished = options.remove_unfinished
+        && nbt
+            .string("Status")
+            .map(|status| ChunkStatus::from(status.to_str().as_ref()) != ChunkStatus::Full)
+            .unwrap_or(true);
+
+    let outside = match &options.keep_area {
+        Some(area) => !area.contains([pos[0] as f64 * 16.0 + 8.0, pos[1] as f64 * 16.0 + 8.0]),
+        None => false,
+    };
+
+    if !(barely_inhabited || unfinished || outside) {
+        return Ok(false);
+    }
+
+    let _data_version = nbt.int("DataVersion").map(DataVersionScope::enter);
+    let protected = nbt
+        .list("block_entities")
+        .and_then(|list| list.compounds())
+        .into_iter()
+        .flatten()
+        .any(
+            |block_entity| match BlockEntity::from_compound_nbt(&block_entity) {
+                Ok(block_entity) => (options.protected)(&block_entity),
+                Err(_) => true,
+            },
+        );
+
+    Ok(!protected)
+}
+
+#[cfg(test)]
+#[test]
+fn prune_area() {
+    let square = PruneArea::Polygon(vec![[0, 0], [32, 0], [32, 32], [0, 32]]);
+    assert!(square.contains([8.0, 8.0]));
+    assert!(!square.contains([40.0, 8.0]));
+    assert!(!square.contains([-8.0, 8.0]));
+
+    let circle = PruneArea::Radius {
+        center: [0, 0],
+        radius: 100,
+    };
+    assert!(circle.contains([70.0, 70.0]));
+    assert!(!circle.contains([71.0, 71.0]));
+}
+
+#[cfg(test)]
+fn prune_test_world(name: &str) -> std::path::PathBuf {
+    use crate::{region::Region, region::RegionChunk, MC_DATA_VERSION};
+    use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+
+    let path = std::env::temp_dir().join(format!("sculk_prune_{name}_{}", std::process::id()));
+    std::fs::create_dir_all(path.join("region")).unwrap();
+    std::fs::create_dir_all(path.join("entities")).unwrap();
+
+    let chunk = |x: i32, inhabited_time: i64, block_entities: Vec<NbtCompound>| {
+        let mut chunk = NbtCompound::new();
+        chunk.insert("DataVersion", MC_DATA_VERSION);
+        chunk.insert("xPos", x);
+        chunk.insert("zPos", 0);
+        chunk.insert("Status", "minecraft:full");
+        chunk.insert("InhabitedTime", inhabited_time);
+        chunk.insert("sections", NbtList::Empty);
+        chunk.insert("block_entities", NbtList::Compound(block_entities));
+        chunk
+    };
+
+    let mut item = NbtCompound::new();
+    item.insert("Slot", 0i8);
+    item.insert("id", "minecraft:diamond");
+    item.insert("count", 1);
+    let mut chest = NbtCompound::new();
+    chest.insert("id", "minecraft:chest");
+    chest.insert("x", 16);
+    chest.insert("y", 64);
+    chest.insert("z", 0);
+    chest.insert("Items", NbtTag::List(NbtList::Compound(vec![item])));
+
+    // Unvisited, protected by a chest with items, visited and undecodable.
+    let mut region = Region::new(0, 0);
+    region.set_chunk_nbt(0, 0, &chunk(0, 0, vec![]));
+    region.set_chunk_nbt(1, 0, &chunk(1, 0, vec![chest]));
+    region.set_chunk_nbt(2, 0, &chunk(2, 1000, vec![]));
+    region.chunks[Region::chunk_index(3, 0)] = Some(RegionChunk::new(vec![0xff; 8]));
+    std::fs::write(
+        path.join("region").join(Region::file_name(0, 0)),
+        region.to_bytes().unwrap(),
+    )
+    .unwrap();
+
+    let mut entities = Region::new(0, 0);
+    entities.set_chunk_nbt(0, 0, &NbtCompound::new());
+    entities.set_chunk_nbt(2, 0, &NbtCompound::new());
+    std::fs::write(
+        path.join("entities").join(Region::file_name(0, 0)),
+        entities.to_bytes().unwrap(),
+    )
+    .unwrap();
+
+    // A region with only an unvisited chunk, it's deleted once the chunk is gone.
+    let mut region = Region::new(1, 0);
+    region.set_chunk_nbt(32, 0, &chunk(32, 0, vec![]));
+    std::fs::write(
+        path.join("region").join(Region::file_name(1, 0)),
+        region.to_bytes().unwrap(),
+    )
+    .unwrap();
+
+    path
+}
+
+#[cfg(test)]
+#[test]
+fn prune_world() {
+    let options = PruneOptions {
+        min_inhabited_time: Some(100),
+        dry_run: true,
+        ..Default::default()
+    };
+    let expected = PruneReport {
+        regions_checked: 2,
+        chunks_checked: 5,
+        removed_chunks: vec![[0, 0], [32, 0]],
+        regions_deleted: 0,
+        skipped_chunks: vec![[3, 0]],
+        skipped_regions: vec![],
+    };
+
+    // A dry run reports the same chunks, but leaves the files alone.
+    let path = prune_test_world("dry_run");
+    let region_file = path
+        .join("region")
+        .join(crate::region::Region::file_name(0, 0));
+    let before = std::fs::read(&region_file).unwrap();
+    let report = World::open(&path)
+        .unwrap()
+        .prune(&Dimension::Overworld, &options)
+        .unwrap();
+    let after = std::fs::read(&region_file).unwrap();
+    std::fs::remove_dir_all(&path).unwrap();
+    assert_eq!(report, expected);
+    assert_eq!(before, after);
+
+    let path = prune_test_world("remove");
+    let mut world = World::open(&path).unwrap();
+    let options = PruneOptions {
+        dry_run: false,
+        ..options
+    };
+    let report = world.prune(&Dimension::Overworld, &options).unwrap();
+    assert_eq!(
+        report,
+        PruneReport {
+            regions_deleted: 1,
+            ..expected
+        }
+    );
+
+    let mut world = World::open(&path).unwrap();
+    let dimension = Dimension::Overworld;
+    let region = world
+        .region(&dimension, RegionKind::Chunks, 0, 0)
+        .unwrap()
+        .unwrap();
+    let kept: Vec<bool> = (0..4).map(|x| region.get(x, 0).is_some()).collect();
+    let entities = world
+        .region(&dimension, RegionKind::Entities, 0, 0)
+        .unwrap()
+        .unwrap();
+    let kept_entities = [entities.get(0, 0).is_some(), entities.get(2, 0).is_some()];
+    let deleted = world
+        .region(&dimension, RegionKind::Chunks, 1, 0)
+        .unwrap()
+        .is_none();
+    std::fs::remove_dir_all(&path).unwrap();
+
+    assert_eq!(kept, vec![false, true, true, true]);
+    assert_eq!(kept_entities, [false, true]);
+    assert!(deleted);
+}
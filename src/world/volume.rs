@@ -0,0 +1,698 @@
+use super::{Dimension, RegionKind, World};
+use crate::{
+    block_entity::BlockEntity,
+    chunk::section::{Biomes, BlockStates, Palette},
+    entity::Entity,
+    error::SculkParseError,
+    resource_location::ResourceLocation,
+    traits::{FromCompoundNbt, ToCompoundNbt},
+    util::{from_owned_compound, set_tag, MC_DATA_VERSION},
+};
+use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+use std::collections::HashSet;
+
+/// A box of blocks copied out of a world with [`World::extract`].
+/// Blocks are stored with the index `(y * size_z + z) * size_x + x`, relative to the minimum corner.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockVolume {
+    /// Size of the volume on each axis.
+    pub size: [i32; 3],
+
+    /// The block states used in the volume, the position in the list is the index used in `blocks`.
+    pub palette: Vec<Palette>,
+
+    /// Palette index of every block in the volume.
+    pub blocks: Vec<u32>,
+
+    /// Block entities in the volume, with positions relative to the volume.
+    pub block_entities: Vec<VolumeBlockEntity>,
+
+    /// Entities in the volume, with positions relative to the volume.
+    pub entities: Vec<VolumeEntity>,
+
+    /// Biomes of every block in the volume, if the chunks had any.
+    pub biomes: Option<VolumeBiomes>,
+}
+
+/// A block entity in a [`BlockVolume`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolumeBlockEntity {
+    /// Position relative to the volume.
+    pub pos: [i32; 3],
+
+    /// The raw block entity data, with `x`, `y` and `z` relative to the volume.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub nbt: NbtCompound,
+}
+
+/// An entity in a [`BlockVolume`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolumeEntity {
+    /// Position relative to the volume.
+    pub pos: [f64; 3],
+
+    /// The raw entity data, with `Pos` relative to the volume.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub nbt: NbtCompound,
+}
+
+/// Biomes of a [`BlockVolume`], stored per block with the same index as the blocks.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolumeBiomes {
+    /// The biomes used in the volume, the position in the list is the index used in `data`.
+    pub palette: Vec<ResourceLocation>,
+
+    /// Palette index of the biome at every block in the volume.
+    pub data: Vec<u32>,
+}
+
+/// What to carry over when pasting a [`BlockVolume`] with [`World::paste`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PasteOptions {
+    /// Paste air blocks, replacing what's in the world. If false, air in the volume leaves the world untouched.
+    pub air: bool,
+
+    /// Paste the block entities of the volume.
+    pub block_entities: bool,
+
+    /// Paste the entities of the volume.
+    /// Entities keep their `UUID`, so pasting the same volume twice into one world duplicates them.
+    pub entities: bool,
+
+    /// Paste the biomes of the volume.
+    /// Biomes are stored per 4x4x4 cell in chunks, so a cell only partially covered by the volume is fully replaced.
+    pub biomes: bool,
+}
+
+impl Default for PasteOptions {
+    fn default() -> Self {
+        PasteOptions {
+            air: true,
+            block_entities: true,
+            entities: true,
+            biomes: false,
+        }
+    }
+}
+
+impl VolumeBlockEntity {
+    /// Parses the typed block entity, with its position relative to the volume.
+    pub fn block_entity(&self) -> Result<BlockEntity, SculkParseError> {
+        from_owned_compound(&self.nbt, BlockEntity::from_compound_nbt)
+    }
+}
+
+impl VolumeEntity {
+    /// Parses the typed entity, with its position relative to the volume.
+    pub fn entity(&self) -> Result<Entity, SculkParseError> {
+        from_owned_compound(&self.nbt, Entity::from_compound_nbt)
+    }
+}
+
+impl BlockVolume {
+    /// Creates a volume of the given size filled with air.
+    pub fn new(size: [i32; 3]) -> Self {
+        let volume = size.iter().map(|s| (*s).max(0) as usize).product();
+
+        BlockVolume {
+            size,
+            palette: vec![Palette::air()],
+            blocks: vec![0; volume],
+            block_entities: vec![],
+            entities: vec![],
+            biomes: None,
+        }
+    }
+
+    /// Gets the index into `blocks` for the given volume relative position, `None` if it's outside of the volume.
+    pub fn index(&self, x: i32, y: i32, z: i32) -> Option<usize> {
+        if !self.contains(x, y, z) {
+            return None;
+        }
+
+        Some(self.offset(x, y, z))
+    }
+
+    /// The index of a position that's known to be inside of the volume.
+    fn offset(&self, x: i32, y: i32, z: i32) -> usize {
+        (y as usize * self.size[2] as usize + z as usize) * self.size[0] as usize + x as usize
+    }
+
+    /// Whether the volume relative position is inside of the volume.
+    pub fn contains(&self, x: i32, y: i32, z: i32) -> bool {
+        x >= 0 && y >= 0 && z >= 0 && x < self.size[0] && y < self.size[1] && z < self.size[2]
+    }
+
+    /// Gets the block state at the given volume relative position.
+    pub fn get_block(&self, x: i32, y: i32, z: i32) -> Option<&Palette> {
+        let state = *self.blocks.get(self.index(x, y, z)?)?;
+        self.palette.get(state as usize)
+    }
+
+    /// Sets the block state at the given volume relative position, inserting it in the palette if it's new.  
+    /// Returns `None` without changing anything if the position is outside of the volume.
+    pub fn set_block(&mut self, x: i32, y: i32, z: i32, state: Palette) -> Option<()> {
+        let index = self
+            .index(x, y, z)
+            .filter(|index| *index < self.blocks.len())?;
+        self.blocks[index] = palette_index(&mut self.palette, &state);
+        Some(())
+    }
+}
+
+impl World {
+    /// Copies the blocks, block entities, entities and biomes between the two corners, both inclusive.
+    /// Chunks that haven't been generated are read as air.
+    pub fn extract(
+        &mut self,
+        dimension: &Dimension,
+        min: [i32; 3],
+        max: [i32; 3],
+    ) -> Result<BlockVolume, SculkParseError> {
+        let (min, max) = (
+            [0, 1, 2].map(|axis| min[axis].min(max[axis])),
+            [0, 1, 2].map(|axis| min[axis].max(max[axis])),
+        );
+        let mut volume = BlockVolume::new([0, 1, 2].map(|axis| max[axis] - min[axis] + 1));
+        let mut biome_palette: Vec<ResourceLocation> = vec![];
+        let mut biome_data = vec![0; volume.blocks.len()];
+
+        for chunk_x in (min[0] >> 4)..=(max[0] >> 4) {
+            for chunk_z in (min[2] >> 4)..=(max[2] >> 4) {
+                let nbt = match self.chunk_nbt(dimension, RegionKind::Chunks, chunk_x, chunk_z)? {
+                    Some(nbt) => nbt,
+                    None => continue,
+                };
+
+                let (x_range, z_range) = (
+                    (min[0].max(chunk_x * 16))..=(max[0].min(chunk_x * 16 + 15)),
+                    (min[2].max(chunk_z * 16))..=(max[2].min(chunk_z * 16 + 15)),
+                );
+
+                for section in compounds(&nbt, "sections") {
+                    let section_y = section
+                        .byte("Y")
+                        .ok_or(SculkParseError::MissingField("Y".into()))?
+                        as i32
+                        * 16;
+                    if section_y + 15 < min[1] || section_y > max[1] {
+                        continue;
+                    }
+
+                    let (palette, indices) = read_section_blocks(section)?;
+                    let mut remap: Vec<Option<u32>> = vec![None; palette.len()];
+
+                    let biomes = read_section_biomes(section)?;
+                    let mut biome_remap: Vec<Option<u32>> =
+                        vec![None; biomes.as_ref().map_or(0, |b| b.palette.len())];
+
+                    for y in (min[1].max(section_y))..=(max[1].min(section_y + 15)) {
+                        for z in z_range.clone() {
+                            for x in x_range.clone() {
+                                let (lx, ly, lz) = (x & 15, y & 15, z & 15);
+                                let index = volume.offset(x - min[0], y - min[1], z - min[2]);
+
+                                let state = indices[((ly * 16 + lz) * 16 + lx) as usize] as usize;
+                                volume.blocks[index] =
+                                    remap_index(&mut remap, &palette, state, |state| {
+                                        palette_index(&mut volume.palette, state)
+                                    })?;
+
+                                if let Some(biomes) = &biomes {
+                                    let cell =
+                                        (((ly >> 2) * 4 + (lz >> 2)) * 4 + (lx >> 2)) as usize;
+                                    let biome = biomes.indices[cell] as usize;
+                                    biome_data[index] = remap_index(
+                                        &mut biome_remap,
+                                        &biomes.palette,
+                                        biome,
+                                        |biome| biome_index(&mut biome_palette, biome),
+                                    )?;
+                                }
+                            }
+                        }
+                    }
+                }
+
+                for block_entity in compounds(&nbt, "block_entities") {
+                    let pos = match (
+                        block_entity.int("x"),
+                        block_entity.int("y"),
+                        block_entity.int("z"),
+                    ) {
+                        (Some(x), Some(y), Some(z)) => [x - min[0], y - min[1], z - min[2]],
+                        _ => return Err(SculkParseError::MissingField("x, y, z".into())),
+                    };
+                    if !volume.contains(pos[0], pos[1], pos[2]) {
+                        continue;
+                    }
+
+                    let mut nbt = block_entity.clone();
+                    shift_block_entity(&mut nbt, min.map(|m| -m));
+                    volume.block_entities.push(VolumeBlockEntity { pos, nbt });
+                }
+
+                let entities = self.chunk_nbt(dimension, RegionKind::Entities, chunk_x, chunk_z)?;
+                for entity in entities.iter().flat_map(|nbt| compounds(nbt, "Entities")) {
+                    let pos = match entity.list("Pos").and_then(|pos| pos.doubles()) {
+                        Some(pos) if pos.len() == 3 => {
+                            [0, 1, 2].map(|axis| pos[axis] - min[axis] as f64)
+                        }
+                        _ => continue,
+                    };
+                    let inside =
+                        (0..3).all(|axis| pos[axis] >= 0.0 && pos[axis] < volume.size[axis] as f64);
+                    if !inside {
+                        continue;
+                    }
+
+                    let mut nbt = entity.clone();
+                    shift_entity(&mut nbt, min.map(|m| -m));
+                    volume.entities.push(VolumeEntity { pos, nbt });
+                }
+            }
+        }
+
+        if !biome_palette.is_empty() {
+            volume.biomes = Some(VolumeBiomes {
+                palette: biome_palette,
+                data: biome_data,
+            });
+        }
+
+        Ok(volume)
+    }
+
+    /// Pastes a volume with its minimum corner at `origin`.
+    /// Only chunks that already exist are changed, parts of the volume over ungenerated chunks are skipped.
+    /// Changed chunks get their heightmaps cleared and `isLightOn` unset, so the game recomputes them when they're loaded.
+    /// Changes are kept in memory until [`World::save`] is called.
+    /// Fails if a block or biome of the volume isn't in its palette.
+    pub fn paste(
+        &mut self,
+        dimension: &Dimension,
+        origin: [i32; 3],
+        volume: &BlockVolume,
+        options: &PasteOptions,
+    ) -> Result<(), SculkParseError> {
+        let max = [0, 1, 2].map(|axis| origin[axis] + volume.size[axis] - 1);
+        let biomes = volume.biomes.as_ref().filter(|_| options.biomes);
+
+        for chunk_x in (origin[0] >> 4)..=(max[0] >> 4) {
+            for chunk_z in (origin[2] >> 4)..=(max[2] >> 4) {
+                let mut nbt =
+                    match self.chunk_nbt(dimension, RegionKind::Chunks, chunk_x, chunk_z)? {
+                        Some(nbt) => nbt,
+                        None => continue,
+                    };
+
+                let (x_range, z_range) = (
+                    (origin[0].max(chunk_x * 16))..=(max[0].min(chunk_x * 16 + 15)),
+                    (origin[2].max(chunk_z * 16))..=(max[2].min(chunk_z * 16 + 15)),
+                );
+                let mut written = HashSet::new();
+
+                if let Some(NbtList::Compound(sections)) = nbt.list_mut("sections") {
+                    for section in sections.iter_mut() {
+                        let section_y = section
+                            .byte("Y")
+                            .ok_or(SculkParseError::MissingField("Y".into()))?
+                            as i32
+                            * 16;
+                        if section_y + 15 < origin[1] || section_y > max[1] {
+                            continue;
+                        }
+
+                        let (mut palette, mut indices) = read_section_blocks(section)?;
+                        let mut remap: Vec<Option<u32>> = vec![None; volume.palette.len()];
+                        let mut section_biomes = match biomes {
+                            Some(_) => read_section_biomes(section)?,
+                            None => None,
+                        };
+
+                        for y in (origin[1].max(section_y))..=(max[1].min(section_y + 15)) {
+                            for z in z_range.clone() {
+                                for x in x_range.clone() {
+                                    let (lx, ly, lz) = (x & 15, y & 15, z & 15);
+                                    let index =
+                                        volume.offset(x - origin[0], y - origin[1], z - origin[2]);
+
+                                    let state = *volume.blocks.get(index).ok_or_else(|| {
+                                        SculkParseError::InvalidField(format!(
+                                            "blocks index {index}"
+                                        ))
+                                    })? as usize;
+                                    let is_air = volume
+                                        .palette
+                                        .get(state)
+                                        .is_some_and(|state| state.is_air());
+                                    if !options.air && is_air {
+                                        continue;
+                                    }
+
+                                    let section_state =
+                                        remap_index(&mut remap, &volume.palette, state, |state| {
+                                            palette_index(&mut palette, state)
+                                        })?;
+                                    indices[((ly * 16 + lz) * 16 + lx) as usize] = section_state;
+                                    written.insert([x, y, z]);
+
+                                    if let (Some(biomes), Some(section_biomes)) =
+                                        (biomes, &mut section_biomes)
+                                    {
+                                        let cell =
+                                            (((ly >> 2) * 4 + (lz >> 2)) * 4 + (lx >> 2)) as usize;
+                                        let biome = biomes
+                                            .data
+                                            .get(index)
+                                            .and_then(|biome| biomes.palette.get(*biome as usize))
+                                            .ok_or_else(|| {
+                                                SculkParseError::InvalidField(format!(
+                                                    "biomes index {index}"
+                                                ))
+                                            })?;
+                                        section_biomes.indices[cell] =
+                                            biome_index(&mut section_biomes.palette, biome);
+                                    }
+                                }
+                            }
+                        }
+
+                        let block_states = BlockStates::from_indices(&palette, &indices)?;
+                        set_tag(
+                            section,
+                            "block_states",
+                            NbtTag::Compound(block_states.to_compound_nbt()),
+                        );
+                        if let Some(section_biomes) = section_biomes {
+                            let biomes = Biomes::from_indices(
+                                &section_biomes.palette,
+                                &section_biomes.indices,
+                            )?;
+                            set_tag(
+                                section,
+                                "biomes",
+                                NbtTag::Compound(biomes.to_compound_nbt()),
+                            );
+                        }
+
+                        section.remove("BlockLight");
+                        section.remove("SkyLight");
+                    }
+                }
+
+                // Block entities at replaced blocks are removed, like the game does when a block is set.
+                let mut block_entities: Vec<NbtCompound> = compounds(&nbt, "block_entities")
+                    .iter()
+                    .filter(|block_entity| {
+                        let pos = [
+                            block_entity.int("x").unwrap_or_default(),
+                            block_entity.int("y").unwrap_or_default(),
+                            block_entity.int("z").unwrap_or_default(),
+                        ];
+                        !written.contains(&pos)
+                    })
+                    .cloned()
+                    .collect();
+
+                if options.block_entities {
+                    for block_entity in &volume.block_entities {
+                        let pos = [0, 1, 2].map(|axis| block_entity.pos[axis] + origin[axis]);
+                        if !x_range.contains(&pos[0]) || !z_range.contains(&pos[2]) {
+                            continue;
+                        }
+
+                        let mut nbt = block_entity.nbt.clone();
+                        shift_block_entity(&mut nbt, origin);
+                        block_entities.push(nbt);
+                    }
+                }
+
+                set_tag(
+                    &mut nbt,
+                    "block_entities",
+                    NbtList::Compound(block_entities),
+                );
+                set_tag(&mut nbt, "Heightmaps", NbtTag::Compound(NbtCompound::new()));
+                set_tag(&mut nbt, "isLightOn", 0i8);
+
+                self.set_chunk_nbt(dimension, RegionKind::Chunks, chunk_x, chunk_z, &nbt)?;
+            }
+        }
+
+        if options.entities {
+            for entity in &volume.entities {
+                let pos = [0, 1, 2].map(|axis| entity.pos[axis] + origin[axis] as f64);
+                let (chunk_x, chunk_z) =
+                    ((pos[0].floor() as i32) >> 4, (pos[2].floor() as i32) >> 4);
+
+                let mut nbt =
+                    match self.chunk_nbt(dimension, RegionKind::Entities, chunk_x, chunk_z)? {
+                        Some(nbt) => nbt,
+                        None => {
+                            let mut nbt = NbtCompound::new();
+                            nbt.insert("DataVersion", MC_DATA_VERSION);
+                            nbt.insert("Position", NbtTag::IntArray(vec![chunk_x, chunk_z]));
+                            nbt
+                        }
+                    };
+
+                let mut entity_nbt = entity.nbt.clone();
+                shift_entity(&mut entity_nbt, origin);
+
+                let mut entities = compounds(&nbt, "Entities").to_vec();
+                entities.push(entity_nbt);
+                set_tag(&mut nbt, "Entities", NbtList::Compound(entities));
+
+                self.set_chunk_nbt(dimension, RegionKind::Entities, chunk_x, chunk_z, &nbt)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Gets the compounds of a list, or nothing if it's missing or empty.
+fn compounds<'a>(nbt: &'a NbtCompound, key: &str) -> &'a [NbtCompound] {
+    nbt.list(key)
+        .and_then(|list| list.compounds())
+        .unwrap_or_default()
+}
+
+/// Maps an index into `palette` to an index into another palette, which `insert` finds or adds the value to.
+/// The result is kept in `remap` so every index is only looked up once.
+/// Fails if the index is outside of `palette`.
+fn remap_index<T>(
+    remap: &mut [Option<u32>],
+    palette: &[T],
+    index: usize,
+    insert: impl FnOnce(&T) -> u32,
+) -> Result<u32, SculkParseError> {
+    let (slot, value) = remap
+        .get_mut(index)
+        .zip(palette.get(index))
+        .ok_or_else(|| SculkParseError::InvalidField(format!("palette index {index}")))?;
+
+    Ok(*slot.get_or_insert_with(|| insert(value)))
+}
+
+fn palette_index(palette: &mut Vec<Palette>, state: &Palette) -> u32 {
+    match palette.iter().position(|p| p == state) {
+        Some(index) => index as u32,
+        None => {
+            palette.push(state.clone());
+            (palette.len() - 1) as u32
+        }
+    }
+}
+
+fn biome_index(palette: &mut Vec<ResourceLocation>, biome: &ResourceLocation) -> u32 {
+    match palette.iter().position(|p| p == biome) {
+        Some(index) => index as u32,
+        None => {
+            palette.push(biome.clone());
+            (palette.len() - 1) as u32
+        }
+    }
+}
+
+fn read_section_blocks(section: &NbtCompound) -> Result<(Vec<Palette>, Vec<u32>), SculkParseError> {
+    match section.compound("block_states") {
+        Some(nbt) => {
+            let block_states = from_owned_compound(nbt, BlockStates::from_compound_nbt)?;
+            let indices = block_states.indices();
+            Ok((block_states.palette, indices))
+        }
+        None => Ok((vec![Palette::air()], vec![0; BlockStates::SIZE])),
+    }
+}
+
+/// Unpacked biomes of a chunk section.
+struct SectionBiomes {
+    palette: Vec<ResourceLocation>,
+    indices: Vec<u32>,
+}
+
+fn read_section_biomes(section: &NbtCompound) -> Result<Option<SectionBiomes>, SculkParseError> {
+    match section.compound("biomes") {
+        Some(nbt) => {
+            let biomes = from_owned_compound(nbt, Biomes::from_compound_nbt)?;
+            let indices = biomes.indices();
+            let palette = biomes.palette.into_iter().map(|p| p.name).collect();
+            Ok(Some(SectionBiomes { palette, indices }))
+        }
+        None => Ok(None),
+    }
+}
+
+fn shift_block_entity(nbt: &mut NbtCompound, offset: [i32; 3]) {
+    for (key, offset) in ["x", "y", "z"].into_iter().zip(offset) {
+        if let Some(value) = nbt.int_mut(key) {
+            *value += offset;
+        }
+    }
+}
+
+/// Moves an entity and its passengers, including the block position hanging entities are attached to.
+fn shift_entity(nbt: &mut NbtCompound, offset: [i32; 3]) {
+    if let Some(pos) = nbt.list("Pos").and_then(|pos| pos.doubles()) {
+        if pos.len() == 3 {
+            let pos = [0, 1, 2].map(|axis| pos[axis] + offset[axis] as f64);
+            set_tag(nbt, "Pos", NbtList::Double(pos.to_vec()));
+        }
+    }
+
+    for (key, offset) in ["TileX", "TileY", "TileZ"].into_iter().zip(offset) {
+        if let Some(value) = nbt.int_mut(key) {
+            *value += offset;
+        }
+    }
+
+    if let Some(NbtList::Compound(passengers)) = nbt.list_mut("Passengers") {
+        for passenger in passengers {
+            shift_entity(passenger, offset);
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn extract_and_paste() {
+    use crate::region::Region;
+    use std::str::FromStr;
+
+    let path = std::env::temp_dir().join(format!("sculk_world_{}", std::process::id()));
+    std::fs::create_dir_all(path.join("region")).unwrap();
+
+    // Two flat chunks of stone in section 0, next to each other on the x axis.
+    let mut region = Region::new(0, 0);
+    for chunk_x in 0..2 {
+        let mut section = NbtCompound::new();
+        section.insert("Y", 0i8);
+        let block_states =
+            BlockStates::from_indices(&["minecraft:stone".parse().unwrap()], &[0; 4096]).unwrap();
+        section.insert(
+            "block_states",
+            NbtTag::Compound(block_states.to_compound_nbt()),
+        );
+        let biomes =
+            Biomes::from_indices(&["minecraft:plains".parse().unwrap()], &[0; 64]).unwrap();
+        section.insert("biomes", NbtTag::Compound(biomes.to_compound_nbt()));
+
+        let mut chunk = NbtCompound::new();
+        chunk.insert("DataVersion", MC_DATA_VERSION);
+        chunk.insert("xPos", chunk_x);
+        chunk.insert("zPos", 0);
+        chunk.insert("sections", NbtList::Compound(vec![section]));
+        chunk.insert("block_entities", NbtList::Empty);
+        region.set_chunk_nbt(chunk_x, 0, &chunk);
+    }
+    std::fs::write(
+        path.join("region").join(Region::file_name(0, 0)),
+        region.to_bytes().unwrap(),
+    )
+    .unwrap();
+
+    let mut world = World::open(&path).unwrap();
+    let dimension = Dimension::Overworld;
+
+    let mut volume = BlockVolume::new([3, 2, 2]);
+    volume
+        .set_block(
+            0,
+            0,
+            0,
+            Palette::from_str("minecraft:oak_log[axis=x]").unwrap(),
+        )
+        .unwrap();
+    volume
+        .set_block(2, 1, 1, "minecraft:chest".parse().unwrap())
+        .unwrap();
+    assert!(volume.set_block(3, 0, 0, Palette::air()).is_none());
+    assert_eq!(volume.index(-1, 0, 0), None);
+    assert_eq!(volume.get_block(0, 0, -1), None);
+    let mut chest = NbtCompound::new();
+    chest.insert("id", "minecraft:chest");
+    chest.insert("x", 2);
+    chest.insert("y", 1);
+    chest.insert("z", 1);
+    chest.insert("Items", NbtList::Empty);
+    volume.block_entities.push(VolumeBlockEntity {
+        pos: [2, 1, 1],
+        nbt: chest,
+    });
+    volume.biomes = Some(VolumeBiomes {
+        palette: vec!["minecraft:desert".parse().unwrap()],
+        data: vec![0; volume.blocks.len()],
+    });
+
+    // Crosses the chunk border at x = 16.
+    let options = PasteOptions {
+        biomes: true,
+        ..Default::default()
+    };
+    world
+        .paste(&dimension, [14, 4, 5], &volume, &options)
+        .unwrap();
+
+    // A region that can't be written doesn't stop the others from being saved.
+    world
+        .set_chunk_nbt(&dimension, RegionKind::Chunks, 32, 0, &NbtCompound::new())
+        .unwrap();
+    let blocked = world.region_path(&dimension, RegionKind::Chunks, 1, 0);
+    std::fs::create_dir_all(&blocked).unwrap();
+    let errors = world.save().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!((errors[0].x, errors[0].z), (1, 0));
+    std::fs::remove_dir(&blocked).unwrap();
+
+    let mut world = World::open(&path).unwrap();
+    let extracted = world.extract(&dimension, [14, 4, 5], [16, 5, 6]).unwrap();
+
+    // A block that isn't in the palette is an error rather than a panic.
+    let mut broken = BlockVolume::new([1, 1, 1]);
+    broken.blocks[0] = 5;
+    let err = world
+        .paste(&dimension, [14, 4, 5], &broken, &PasteOptions::default())
+        .unwrap_err();
+    assert!(matches!(err, SculkParseError::InvalidField(_)));
+    std::fs::remove_dir_all(&path).unwrap();
+
+    for (x, y, z) in [(0, 0, 0), (1, 1, 0), (2, 1, 1)] {
+        assert_eq!(extracted.get_block(x, y, z), volume.get_block(x, y, z));
+    }
+    assert_eq!(extracted.block_entities, volume.block_entities);
+    assert_eq!(
+        extracted.block_entities[0].block_entity().unwrap().base.x,
+        2
+    );
+    assert_eq!(
+        extracted.biomes.unwrap().palette,
+        vec!["minecraft:desert".to_string()]
+    );
+}